@@ -60,7 +60,8 @@ mod arbitrary {
                     let parent_id = BlockId(parent_id.into());
                     let votes = Votes(votes);
                     Self {
-                        version: 1,
+                        // current mainnet block version (v5.0 protocol)
+                        version: 3,
                         parent_id,
                         timestamp,
                         n_bits,
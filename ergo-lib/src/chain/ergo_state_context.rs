@@ -1,5 +1,6 @@
 //! Blockchain state
 use ergo_chain_types::{Header, PreHeader};
+use ergotree_interpreter::eval::validation_settings::ValidationSettings;
 
 /// Fixed number of last block headers in descending order (first header is the newest one)
 pub type Headers = [Header; 10];
@@ -12,6 +13,8 @@ pub struct ErgoStateContext {
     pub pre_header: PreHeader,
     /// Fixed number of last block headers in descending order (first header is the newest one)
     pub headers: Headers,
+    /// Validation rules settings (voted by miners) defining the soft-fork conditions for scripts
+    pub validation_settings: ValidationSettings,
 }
 
 impl ErgoStateContext {
@@ -20,6 +23,15 @@ impl ErgoStateContext {
         ErgoStateContext {
             pre_header,
             headers,
+            validation_settings: ValidationSettings::default(),
+        }
+    }
+
+    /// Return a new ErgoStateContext with the given validation rules settings
+    pub fn with_validation_settings(self, validation_settings: ValidationSettings) -> Self {
+        ErgoStateContext {
+            validation_settings,
+            ..self
         }
     }
 }
@@ -0,0 +1,58 @@
+//! Blockchain parameters (adjustable via miners voting)
+
+use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+
+/// Blockchain parameters used in transaction validation, which can be changed by miners voting
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct Parameters {
+    /// Minimum value (in nanoERGs) per byte of the serialized box
+    pub min_value_per_byte: u32,
+    /// Maximum cumulative computational cost of a block (and so of a single transaction)
+    pub max_block_cost: u64,
+    /// Cost of accessing a token in transaction inputs or outputs
+    pub token_access_cost: u64,
+    /// Cost of a transaction input
+    pub input_cost: u64,
+    /// Cost of a transaction data input
+    pub data_input_cost: u64,
+    /// Cost of a transaction output
+    pub output_cost: u64,
+}
+
+impl Parameters {
+    /// Cost of interpreter initialization, charged once per transaction
+    pub const INTERPRETER_INIT_COST: u64 = 10000;
+
+    /// Create new Parameters instance
+    pub fn new(
+        min_value_per_byte: u32,
+        max_block_cost: u64,
+        token_access_cost: u64,
+        input_cost: u64,
+        data_input_cost: u64,
+        output_cost: u64,
+    ) -> Parameters {
+        Parameters {
+            min_value_per_byte,
+            max_block_cost,
+            token_access_cost,
+            input_cost,
+            data_input_cost,
+            output_cost,
+        }
+    }
+}
+
+impl Default for Parameters {
+    /// Parameters at the launch of the mainnet
+    fn default() -> Self {
+        Parameters {
+            min_value_per_byte: BoxValue::MIN_VALUE_PER_BOX_BYTE,
+            max_block_cost: 1000000,
+            token_access_cost: 100,
+            input_cost: 2000,
+            data_input_cost: 100,
+            output_cost: 100,
+        }
+    }
+}
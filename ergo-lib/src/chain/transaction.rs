@@ -4,6 +4,7 @@ mod data_input;
 pub mod input;
 pub mod reduced;
 pub mod unsigned;
+pub mod validation;
 
 use bounded_vec::BoundedVec;
 use ergo_chain_types::blake2b256_hash;
@@ -13,6 +14,7 @@ use ergotree_interpreter::eval::extract_sigma_boolean;
 use ergotree_interpreter::eval::EvalError;
 use ergotree_interpreter::sigma_protocol::verifier::verify_signature;
 use ergotree_interpreter::sigma_protocol::verifier::TestVerifier;
+use ergotree_interpreter::sigma_protocol::verifier::VerificationResult;
 use ergotree_interpreter::sigma_protocol::verifier::Verifier;
 use ergotree_interpreter::sigma_protocol::verifier::VerifierError;
 use ergotree_ir::chain::ergo_box::BoxId;
@@ -355,6 +357,26 @@ pub fn verify_tx_input_proof(
     state_context: &ErgoStateContext,
     input_idx: usize,
 ) -> Result<bool, TxVerifyError> {
+    let message_to_sign = tx_context.spending_tx.bytes_to_sign()?;
+    Ok(verify_tx_input(
+        tx_context,
+        state_context,
+        input_idx,
+        message_to_sign.as_slice(),
+        None,
+    )?
+    .result)
+}
+
+/// Verify transaction input's proof against the given message (serialized transaction bytes to sign)
+/// failing if the script's cost exceeds `cost_limit` (in block cost units)
+pub(crate) fn verify_tx_input(
+    tx_context: &TransactionContext<Transaction>,
+    state_context: &ErgoStateContext,
+    input_idx: usize,
+    message_to_sign: &[u8],
+    cost_limit: Option<u64>,
+) -> Result<VerificationResult, TxVerifyError> {
     let input = tx_context
         .spending_tx
         .inputs
@@ -363,18 +385,19 @@ pub fn verify_tx_input_proof(
     let input_box = tx_context
         .get_input_box(&input.box_id)
         .ok_or(TransactionContextError::InputBoxNotFound(input_idx))?;
-    let ctx = Rc::new(make_context(state_context, tx_context, input_idx)?);
+    let ctx = make_context(state_context, tx_context, input_idx)?;
+    let ctx = Rc::new(match cost_limit {
+        Some(limit) => ctx.with_cost_limit(limit),
+        None => ctx,
+    });
     let verifier = TestVerifier;
-    let message_to_sign = tx_context.spending_tx.bytes_to_sign()?;
-    Ok(verifier
-        .verify(
-            &input_box.ergo_tree,
-            &Env::empty(),
-            ctx,
-            input.spending_proof.proof.clone(),
-            message_to_sign.as_slice(),
-        )?
-        .result)
+    Ok(verifier.verify(
+        &input_box.ergo_tree,
+        &Env::empty(),
+        ctx,
+        input.spending_proof.proof.clone(),
+        message_to_sign,
+    )?)
 }
 
 /// Arbitrary impl
@@ -4,7 +4,7 @@
 use std::rc::Rc;
 
 use ergotree_interpreter::eval::env::Env;
-use ergotree_interpreter::eval::reduce_to_crypto;
+use ergotree_interpreter::eval::reduce_tree_to_crypto;
 use ergotree_interpreter::sigma_protocol::prover::ContextExtension;
 use ergotree_interpreter::sigma_protocol::prover::ProverError;
 use ergotree_ir::serialization::sigma_byte_reader::SigmaByteRead;
@@ -79,12 +79,7 @@ pub fn reduce_tx(
                 .get_input_box(&input.box_id)
                 .ok_or(TransactionContextError::InputBoxNotFound(idx))?;
             let ctx = Rc::new(make_context(state_context, &tx_context, idx)?);
-            let expr = input_box
-                .ergo_tree
-                .proposition()
-                .map_err(ProverError::ErgoTreeError)
-                .map_err(|e| TxSigningError::ProverError(e, idx))?;
-            let reduction_result = reduce_to_crypto(&expr, &Env::empty(), ctx)
+            let reduction_result = reduce_tree_to_crypto(&input_box.ergo_tree, &Env::empty(), ctx)
                 .map_err(ProverError::EvalError)
                 .map_err(|e| TxSigningError::ProverError(e, idx))?;
             Ok(ReducedInput {
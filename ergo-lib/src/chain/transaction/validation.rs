@@ -0,0 +1,693 @@
+//! Stateful transaction validation (as performed by the node before accepting a transaction)
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use ergotree_interpreter::sigma_protocol::verifier::VerifierError;
+use ergotree_ir::chain::ergo_box::box_value::checked_sum;
+use ergotree_ir::chain::ergo_box::box_value::BoxValueError;
+use ergotree_ir::chain::ergo_box::BoxId;
+use ergotree_ir::chain::ergo_box::ErgoBox;
+use ergotree_ir::chain::token::TokenAmount;
+use ergotree_ir::chain::token::TokenAmountError;
+use ergotree_ir::chain::token::TokenId;
+use ergotree_ir::serialization::SigmaSerializable;
+use ergotree_ir::serialization::SigmaSerializationError;
+use thiserror::Error;
+
+use crate::chain::ergo_state_context::ErgoStateContext;
+use crate::chain::parameters::Parameters;
+use crate::wallet::box_selector::sum_tokens_from_boxes;
+use crate::wallet::signing::TransactionContext;
+use crate::wallet::tx_context::TransactionContextError;
+
+use super::verify_tx_input;
+use super::Transaction;
+use super::TxVerifyError;
+
+/// Block version since which the creation height of the outputs should not be less than the
+/// max creation height of the inputs
+const MONOTONIC_HEIGHT_BLOCK_VERSION: u8 = 3;
+
+/// Transaction validation errors, each one names the violated node validation rule
+#[derive(Error, Debug)]
+pub enum TxValidationError {
+    /// Transaction context error
+    #[error("TransactionContextError: {0}")]
+    TransactionContextError(#[from] TransactionContextError),
+    /// Transaction serialization failed
+    #[error("Transaction serialization failed: {0}")]
+    SerializationError(#[from] SigmaSerializationError),
+    /// Input box is spent more than once (txInputsUnique)
+    #[error("txInputsUnique: input box {0:?} is spent more than once")]
+    DuplicateInputs(BoxId),
+    /// Input box is not found in the transaction context (txBoxesToSpend)
+    #[error("txBoxesToSpend: input box {0:?} not found")]
+    InputBoxNotFound(BoxId),
+    /// Data input box is not found in the transaction context (txDataBoxes)
+    #[error("txDataBoxes: data input box {0:?} not found")]
+    DataInputBoxNotFound(BoxId),
+    /// Sum of the input or output values overflows (txInputsSum, txOutputSum)
+    #[error("txInputsSum/txOutputSum: box value sum overflow: {0}")]
+    ValueOverflow(BoxValueError),
+    /// Sum of the token amounts overflows (txAssetsInOneBox)
+    #[error("txAssetsInOneBox: token amount sum overflow: {0}")]
+    TokenAmountOverflow(TokenAmountError),
+    /// Sum of the input values does not equal the sum of the output values (txErgPreservation)
+    #[error("txErgPreservation: inputs value {inputs_value} is not equal to outputs value {outputs_value}")]
+    ErgPreservation {
+        /// Total value of the inputs
+        inputs_value: u64,
+        /// Total value of the outputs
+        outputs_value: u64,
+    },
+    /// Token amount in outputs exceeds the amount in inputs (txAssetsPreservation)
+    #[error("txAssetsPreservation: token {token_id:?} amount in outputs {outputs_amount} exceeds the amount in inputs {inputs_amount}")]
+    TokenPreservation {
+        /// Token id
+        token_id: TokenId,
+        /// Token amount in the inputs
+        inputs_amount: u64,
+        /// Token amount in the outputs
+        outputs_amount: u64,
+    },
+    /// Token which is not present in inputs is minted with id other than the first input box id (txAssetsPreservation)
+    #[error("txAssetsPreservation: token {0:?} is not in the inputs and cannot be minted (only the first input box id can be used as the minted token id)")]
+    InvalidTokenMinting(TokenId),
+    /// Output value is less than the minimum value for the box size (txDust)
+    #[error("txDust: output #{index} value {value} is less than the minimum value {min_value} for its size")]
+    OutputValueTooLow {
+        /// Output index
+        index: usize,
+        /// Output box value
+        value: u64,
+        /// Minimum value for the box size
+        min_value: u64,
+    },
+    /// Output box is too large (txBoxSize)
+    #[error(
+        "txBoxSize: output #{index} size {size} exceeds the limit of {} bytes",
+        ErgoBox::MAX_BOX_SIZE
+    )]
+    OutputBoxTooLarge {
+        /// Output index
+        index: usize,
+        /// Serialized box size
+        size: usize,
+    },
+    /// Output box script is too large (txBoxPropositionSize)
+    #[error(
+        "txBoxPropositionSize: output #{index} script size {size} exceeds the limit of {} bytes",
+        ErgoBox::MAX_SCRIPT_SIZE
+    )]
+    OutputScriptTooLarge {
+        /// Output index
+        index: usize,
+        /// Serialized script size
+        size: usize,
+    },
+    /// Output creation height is greater than the current height (txFuture)
+    #[error("txFuture: output #{index} creation height {creation_height} is greater than the current height {height}")]
+    OutputCreationHeightInFuture {
+        /// Output index
+        index: usize,
+        /// Output box creation height
+        creation_height: u32,
+        /// Current blockchain height
+        height: u32,
+    },
+    /// Output creation height is less than the max creation height of the inputs (txMonotonicHeight)
+    #[error("txMonotonicHeight: output #{index} creation height {creation_height} is less than the max inputs creation height {max_inputs_height}")]
+    NonMonotonicCreationHeight {
+        /// Output index
+        index: usize,
+        /// Output box creation height
+        creation_height: u32,
+        /// Max creation height of the input boxes
+        max_inputs_height: u32,
+    },
+    /// Input script verification error (txScriptValidation)
+    #[error("txScriptValidation: input #{0} verification error: {1}")]
+    InputVerifierError(usize, VerifierError),
+    /// Input proof is invalid (txScriptValidation)
+    #[error("txScriptValidation: input #{0} proof is invalid")]
+    InvalidInputProof(usize),
+    /// Transaction cost exceeds the limit (txCost)
+    #[error("txCost: transaction cost {cost} exceeds the limit {limit}")]
+    CostLimitExceeded {
+        /// Accumulated transaction cost
+        cost: u64,
+        /// Cost limit
+        limit: u64,
+    },
+}
+
+/// Validates the transaction in the given context against the node validation rules:
+/// unique inputs, existence of the input and data input boxes, ERGs and tokens preservation,
+/// token minting, minimal box value (per byte), max box and script size, outputs creation height,
+/// inputs proofs and the aggregate transaction cost limit.
+/// Returns the total transaction cost on success.
+pub fn validate_transaction(
+    tx_context: &TransactionContext<Transaction>,
+    state_context: &ErgoStateContext,
+    params: &Parameters,
+) -> Result<u64, TxValidationError> {
+    let tx = &tx_context.spending_tx;
+
+    let mut seen_inputs = HashSet::new();
+    for input in tx.inputs.iter() {
+        if !seen_inputs.insert(input.box_id) {
+            return Err(TxValidationError::DuplicateInputs(input.box_id));
+        }
+    }
+    let input_boxes = tx
+        .inputs
+        .iter()
+        .map(|input| {
+            tx_context
+                .get_input_box(&input.box_id)
+                .ok_or(TxValidationError::InputBoxNotFound(input.box_id))
+        })
+        .collect::<Result<Vec<ErgoBox>, TxValidationError>>()?;
+    let data_boxes = tx_context.data_boxes.as_ref();
+    for data_input in tx.data_inputs.iter().flatten() {
+        if !data_boxes
+            .into_iter()
+            .flatten()
+            .any(|b| b.box_id() == data_input.box_id)
+        {
+            return Err(TxValidationError::DataInputBoxNotFound(data_input.box_id));
+        }
+    }
+
+    check_erg_preservation(tx, &input_boxes)?;
+    check_assets_preservation(tx, &input_boxes)?;
+    check_outputs(tx, &input_boxes, state_context, params)?;
+
+    let mut cost = initial_tx_cost(tx, &input_boxes, params)?;
+    check_cost(cost, params)?;
+    let message_to_sign = tx.bytes_to_sign()?;
+    for input_idx in 0..tx.inputs.len() {
+        let res = verify_tx_input(
+            tx_context,
+            state_context,
+            input_idx,
+            message_to_sign.as_slice(),
+            Some(params.max_block_cost - cost),
+        )
+        .map_err(|e| match e {
+            TxVerifyError::TransactionContextError(e) => e.into(),
+            TxVerifyError::SerializationError(e) => e.into(),
+            TxVerifyError::VerifierError(e) => TxValidationError::InputVerifierError(input_idx, e),
+        })?;
+        if !res.result {
+            return Err(TxValidationError::InvalidInputProof(input_idx));
+        }
+        cost = cost.saturating_add(res.cost);
+        check_cost(cost, params)?;
+    }
+    Ok(cost)
+}
+
+fn check_erg_preservation(
+    tx: &Transaction,
+    input_boxes: &[ErgoBox],
+) -> Result<(), TxValidationError> {
+    let inputs_value = checked_sum(input_boxes.iter().map(|b| b.value))
+        .map_err(TxValidationError::ValueOverflow)?;
+    let outputs_value = checked_sum(tx.outputs.iter().map(|b| b.value))
+        .map_err(TxValidationError::ValueOverflow)?;
+    if inputs_value != outputs_value {
+        return Err(TxValidationError::ErgPreservation {
+            inputs_value: *inputs_value.as_u64(),
+            outputs_value: *outputs_value.as_u64(),
+        });
+    }
+    Ok(())
+}
+
+fn check_assets_preservation(
+    tx: &Transaction,
+    input_boxes: &[ErgoBox],
+) -> Result<(), TxValidationError> {
+    let input_tokens =
+        sum_tokens_from_boxes(input_boxes).map_err(TxValidationError::TokenAmountOverflow)?;
+    let output_tokens = sum_tokens_from_boxes(tx.outputs.as_slice())
+        .map_err(TxValidationError::TokenAmountOverflow)?;
+    // the only token that can be minted has the id of the first input box, any amount of it
+    // can be issued even if the token is also in the inputs (as in the node)
+    let new_token_id: TokenId = tx.inputs.first().box_id.into();
+    output_tokens
+        .iter()
+        .try_for_each(|(token_id, outputs_amount)| {
+            if *token_id == new_token_id {
+                return Ok(());
+            }
+            match input_tokens.get(token_id) {
+                Some(inputs_amount) if inputs_amount >= outputs_amount => Ok(()),
+                Some(inputs_amount) => Err(TxValidationError::TokenPreservation {
+                    token_id: *token_id,
+                    inputs_amount: *inputs_amount.as_u64(),
+                    outputs_amount: *outputs_amount.as_u64(),
+                }),
+                None => Err(TxValidationError::InvalidTokenMinting(*token_id)),
+            }
+        })
+}
+
+fn check_outputs(
+    tx: &Transaction,
+    input_boxes: &[ErgoBox],
+    state_context: &ErgoStateContext,
+    params: &Parameters,
+) -> Result<(), TxValidationError> {
+    let height = state_context.pre_header.height;
+    let max_inputs_height = input_boxes
+        .iter()
+        .map(|b| b.creation_height)
+        .max()
+        .unwrap_or(0);
+    let check_monotonic_height = state_context.pre_header.version >= MONOTONIC_HEIGHT_BLOCK_VERSION;
+    for (index, output) in tx.outputs.iter().enumerate() {
+        let size = output.sigma_serialize_bytes()?.len();
+        let min_value = size as u64 * params.min_value_per_byte as u64;
+        if *output.value.as_u64() < min_value {
+            return Err(TxValidationError::OutputValueTooLow {
+                index,
+                value: *output.value.as_u64(),
+                min_value,
+            });
+        }
+        // the script is a part of the box, so its (more specific) limit is checked first
+        let script_size = output.ergo_tree.sigma_serialize_bytes()?.len();
+        if script_size > ErgoBox::MAX_SCRIPT_SIZE {
+            return Err(TxValidationError::OutputScriptTooLarge {
+                index,
+                size: script_size,
+            });
+        }
+        if size > ErgoBox::MAX_BOX_SIZE {
+            return Err(TxValidationError::OutputBoxTooLarge { index, size });
+        }
+        if output.creation_height > height {
+            return Err(TxValidationError::OutputCreationHeightInFuture {
+                index,
+                creation_height: output.creation_height,
+                height,
+            });
+        }
+        if check_monotonic_height && output.creation_height < max_inputs_height {
+            return Err(TxValidationError::NonMonotonicCreationHeight {
+                index,
+                creation_height: output.creation_height,
+                max_inputs_height,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Transaction cost before the input scripts are evaluated (interpreter initialization, inputs,
+/// data inputs, outputs and tokens access costs).
+/// As in the node, the interpreter initialization cost is charged once per transaction, not per
+/// input, since the input scripts' costs are accumulated in a single interpreter run.
+fn initial_tx_cost(
+    tx: &Transaction,
+    input_boxes: &[ErgoBox],
+    params: &Parameters,
+) -> Result<u64, TxValidationError> {
+    let inputs_count = tx.inputs.len() as u64;
+    let data_inputs_count = tx.data_inputs.as_ref().map(|d| d.len()).unwrap_or(0) as u64;
+    let outputs_count = tx.outputs.len() as u64;
+    let input_tokens: HashMap<TokenId, TokenAmount> =
+        sum_tokens_from_boxes(input_boxes).map_err(TxValidationError::TokenAmountOverflow)?;
+    let output_tokens: HashMap<TokenId, TokenAmount> = sum_tokens_from_boxes(tx.outputs.as_slice())
+        .map_err(TxValidationError::TokenAmountOverflow)?;
+    let input_tokens_count: u64 = input_boxes
+        .iter()
+        .map(|b| b.tokens.as_ref().map(|t| t.len()).unwrap_or(0) as u64)
+        .sum();
+    let output_tokens_count: u64 = tx
+        .outputs
+        .iter()
+        .map(|b| b.tokens.as_ref().map(|t| t.len()).unwrap_or(0) as u64)
+        .sum();
+    let tokens_access_cost = (input_tokens_count
+        + output_tokens_count
+        + input_tokens.len() as u64
+        + output_tokens.len() as u64)
+        * params.token_access_cost;
+    Ok(Parameters::INTERPRETER_INIT_COST
+        + params.input_cost * inputs_count
+        + params.data_input_cost * data_inputs_count
+        + params.output_cost * outputs_count
+        + tokens_access_cost)
+}
+
+fn check_cost(cost: u64, params: &Parameters) -> Result<(), TxValidationError> {
+    if cost > params.max_block_cost {
+        Err(TxValidationError::CostLimitExceeded {
+            cost,
+            limit: params.max_block_cost,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use ergotree_interpreter::sigma_protocol::prover::ContextExtension;
+    use ergotree_interpreter::sigma_protocol::prover::ProofBytes;
+    use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+    use ergotree_ir::chain::ergo_box::BoxTokens;
+    use ergotree_ir::chain::ergo_box::ErgoBoxCandidate;
+    use ergotree_ir::chain::ergo_box::NonMandatoryRegisters;
+    use ergotree_ir::chain::token::Token;
+    use ergotree_ir::chain::tx_id::TxId;
+    use ergotree_ir::ergo_tree::ErgoTree;
+    use ergotree_ir::mir::bin_op::BinOp;
+    use ergotree_ir::mir::bin_op::RelationOp;
+    use ergotree_ir::mir::constant::Constant;
+    use ergotree_ir::mir::expr::Expr;
+    use sigma_test_util::force_any_val;
+
+    use crate::chain::transaction::input::prover_result::ProverResult;
+    use crate::chain::transaction::DataInput;
+    use crate::chain::transaction::Input;
+
+    use super::*;
+
+    fn true_tree() -> ErgoTree {
+        ErgoTree::try_from(Expr::Const(true.into())).unwrap()
+    }
+
+    fn input_box(value: u64, tokens: Option<BoxTokens>, index: u16) -> ErgoBox {
+        ErgoBox::new(
+            BoxValue::try_from(value).unwrap(),
+            true_tree(),
+            tokens,
+            NonMandatoryRegisters::empty(),
+            0,
+            TxId::zero(),
+            index,
+        )
+        .unwrap()
+    }
+
+    fn output(value: u64, tokens: Option<BoxTokens>) -> ErgoBoxCandidate {
+        ErgoBoxCandidate {
+            value: BoxValue::try_from(value).unwrap(),
+            ergo_tree: true_tree(),
+            tokens,
+            additional_registers: NonMandatoryRegisters::empty(),
+            creation_height: 0,
+        }
+    }
+
+    fn tx(input_boxes: &[ErgoBox], outputs: Vec<ErgoBoxCandidate>) -> Transaction {
+        tx_with_data_inputs(input_boxes, vec![], outputs)
+    }
+
+    fn tx_with_data_inputs(
+        input_boxes: &[ErgoBox],
+        data_inputs: Vec<DataInput>,
+        outputs: Vec<ErgoBoxCandidate>,
+    ) -> Transaction {
+        let inputs = input_boxes
+            .iter()
+            .map(|b| {
+                Input::new(
+                    b.box_id(),
+                    ProverResult {
+                        proof: ProofBytes::Empty,
+                        extension: ContextExtension::empty(),
+                    },
+                )
+            })
+            .collect();
+        Transaction::new_from_vec(inputs, data_inputs, outputs).unwrap()
+    }
+
+    fn validate(
+        input_boxes: Vec<ErgoBox>,
+        outputs: Vec<ErgoBoxCandidate>,
+    ) -> Result<u64, TxValidationError> {
+        validate_with_params(input_boxes, outputs, &Parameters::default())
+    }
+
+    fn validate_with_params(
+        input_boxes: Vec<ErgoBox>,
+        outputs: Vec<ErgoBoxCandidate>,
+        params: &Parameters,
+    ) -> Result<u64, TxValidationError> {
+        let tx = tx(&input_boxes, outputs);
+        let tx_context = TransactionContext::new(tx, input_boxes, vec![]).unwrap();
+        validate_transaction(&tx_context, &state_context(), params)
+    }
+
+    fn state_context() -> ErgoStateContext {
+        let mut state_context = force_any_val::<ErgoStateContext>();
+        state_context.pre_header.height = 100;
+        state_context
+    }
+
+    fn token(token_id: TokenId, amount: u64) -> BoxTokens {
+        BoxTokens::from_vec(vec![Token {
+            token_id,
+            amount: amount.try_into().unwrap(),
+        }])
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_tx() {
+        let cost = validate(
+            vec![input_box(2000000, None, 0), input_box(1000000, None, 1)],
+            vec![output(3000000, None)],
+        )
+        .unwrap();
+        assert!(cost > 0);
+    }
+
+    #[test]
+    fn test_duplicate_inputs() {
+        let b = input_box(1000000, None, 0);
+        assert!(matches!(
+            validate(vec![b.clone(), b], vec![output(2000000, None)]),
+            Err(TxValidationError::DuplicateInputs(_))
+        ));
+    }
+
+    #[test]
+    fn test_erg_preservation() {
+        assert!(matches!(
+            validate(
+                vec![input_box(2000000, None, 0)],
+                vec![output(1000000, None)]
+            ),
+            Err(TxValidationError::ErgPreservation {
+                inputs_value: 2000000,
+                outputs_value: 1000000
+            })
+        ));
+    }
+
+    #[test]
+    fn test_dust_output() {
+        let params = Parameters {
+            min_value_per_byte: 1000000,
+            ..Parameters::default()
+        };
+        assert!(matches!(
+            validate_with_params(
+                vec![input_box(1000000, None, 0)],
+                vec![output(1000000, None)],
+                &params
+            ),
+            Err(TxValidationError::OutputValueTooLow { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_token_minting() {
+        let first_input = input_box(1000000, None, 0);
+        let minted_token_id: TokenId = first_input.box_id().into();
+        assert!(validate(
+            vec![first_input],
+            vec![output(1000000, Some(token(minted_token_id, 100)))]
+        )
+        .is_ok());
+
+        let other_token_id = force_any_val::<TokenId>();
+        assert!(matches!(
+            validate(
+                vec![input_box(1000000, None, 0)],
+                vec![output(1000000, Some(token(other_token_id, 100)))]
+            ),
+            Err(TxValidationError::InvalidTokenMinting(_))
+        ));
+    }
+
+    #[test]
+    fn test_token_minting_with_input_token() {
+        let first_input = input_box(1000000, None, 0);
+        let minted_token_id: TokenId = first_input.box_id().into();
+        // the inputs already hold a smaller amount of the token being minted
+        let second_input = input_box(1000000, Some(token(minted_token_id, 1)), 1);
+        assert!(validate(
+            vec![first_input, second_input],
+            vec![output(2000000, Some(token(minted_token_id, 100)))]
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_token_preservation() {
+        let token_id = force_any_val::<TokenId>();
+        assert!(matches!(
+            validate(
+                vec![input_box(1000000, Some(token(token_id, 10)), 0)],
+                vec![output(1000000, Some(token(token_id, 11)))]
+            ),
+            Err(TxValidationError::TokenPreservation {
+                inputs_amount: 10,
+                outputs_amount: 11,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_cost_limit() {
+        let params = Parameters {
+            max_block_cost: 1,
+            ..Parameters::default()
+        };
+        assert!(matches!(
+            validate_with_params(
+                vec![input_box(1000000, None, 0)],
+                vec![output(1000000, None)],
+                &params
+            ),
+            Err(TxValidationError::CostLimitExceeded { limit: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_interpreter_init_cost_charged_once() {
+        let params = Parameters::default();
+        let initial_cost = |input_boxes: Vec<ErgoBox>| {
+            let tx = tx(&input_boxes, vec![output(1000000, None)]);
+            initial_tx_cost(&tx, &input_boxes, &params).unwrap()
+        };
+        let one_input_cost = initial_cost(vec![input_box(1000000, None, 0)]);
+        let two_inputs_cost =
+            initial_cost(vec![input_box(500000, None, 0), input_box(500000, None, 1)]);
+        assert_eq!(
+            one_input_cost,
+            Parameters::INTERPRETER_INIT_COST + params.input_cost + params.output_cost
+        );
+        // a second input adds its own cost, but not another interpreter initialization
+        assert_eq!(two_inputs_cost - one_input_cost, params.input_cost);
+    }
+
+    #[test]
+    fn test_data_input_not_found() {
+        let input_boxes = vec![input_box(1000000, None, 0)];
+        let data_box = input_box(1000000, None, 1);
+        let tx = tx_with_data_inputs(
+            &input_boxes,
+            vec![data_box.box_id().into()],
+            vec![output(1000000, None)],
+        );
+        // the transaction context constructor checks the data boxes as well
+        let mut tx_context = TransactionContext::new(tx, input_boxes, vec![data_box]).unwrap();
+        tx_context.data_boxes = None;
+        assert!(matches!(
+            validate_transaction(&tx_context, &state_context(), &Parameters::default()),
+            Err(TxValidationError::DataInputBoxNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_box_too_large() {
+        let params = Parameters {
+            min_value_per_byte: 0,
+            ..Parameters::default()
+        };
+        let mut out = output(1000000, None);
+        out.additional_registers =
+            NonMandatoryRegisters::try_from(vec![Constant::from(vec![0u8; ErgoBox::MAX_BOX_SIZE])])
+                .unwrap();
+        assert!(matches!(
+            validate_with_params(vec![input_box(1000000, None, 0)], vec![out], &params),
+            Err(TxValidationError::OutputBoxTooLarge { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_script_too_large() {
+        let params = Parameters {
+            min_value_per_byte: 0,
+            ..Parameters::default()
+        };
+        let bytes: Expr = Constant::from(vec![0u8; ErgoBox::MAX_SCRIPT_SIZE / 2]).into();
+        let expr: Expr = BinOp {
+            kind: RelationOp::Eq.into(),
+            left: bytes.clone().into(),
+            right: bytes.into(),
+        }
+        .into();
+        let mut out = output(1000000, None);
+        out.ergo_tree = ErgoTree::try_from(expr).unwrap();
+        assert!(matches!(
+            validate_with_params(vec![input_box(1000000, None, 0)], vec![out], &params),
+            Err(TxValidationError::OutputScriptTooLarge { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_non_monotonic_creation_height() {
+        let input_boxes = vec![ErgoBox::new(
+            BoxValue::try_from(1000000u64).unwrap(),
+            true_tree(),
+            None,
+            NonMandatoryRegisters::empty(),
+            50,
+            TxId::zero(),
+            0,
+        )
+        .unwrap()];
+        let mut out = output(1000000, None);
+        out.creation_height = 10;
+        let tx = tx(&input_boxes, vec![out]);
+        let tx_context = TransactionContext::new(tx, input_boxes, vec![]).unwrap();
+        let mut state_context = state_context();
+        state_context.pre_header.version = MONOTONIC_HEIGHT_BLOCK_VERSION;
+        assert!(matches!(
+            validate_transaction(&tx_context, &state_context, &Parameters::default()),
+            Err(TxValidationError::NonMonotonicCreationHeight {
+                index: 0,
+                creation_height: 10,
+                max_inputs_height: 50,
+            })
+        ));
+        // the rule is not checked before the activation block version
+        state_context.pre_header.version = MONOTONIC_HEIGHT_BLOCK_VERSION - 1;
+        assert!(validate_transaction(&tx_context, &state_context, &Parameters::default()).is_ok());
+    }
+
+    #[test]
+    fn test_creation_height_in_future() {
+        let mut out = output(1000000, None);
+        out.creation_height = 101;
+        assert!(matches!(
+            validate(vec![input_box(1000000, None, 0)], vec![out]),
+            Err(TxValidationError::OutputCreationHeightInFuture { index: 0, .. })
+        ));
+    }
+}
@@ -1,9 +1,13 @@
 //! Wallet-related features for Ergo
 
+pub mod account;
 pub mod box_selector;
 pub mod derivation_path;
+pub mod ext_key_encoding;
 pub mod ext_pub_key;
 pub mod ext_secret_key;
+#[cfg(feature = "json")]
+pub mod keystore;
 pub mod miner_fee;
 pub mod mnemonic;
 #[cfg(feature = "mnemonic_gen")]
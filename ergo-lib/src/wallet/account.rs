@@ -0,0 +1,550 @@
+//! HD wallet account according to
+//! EIP-3 <https://github.com/ergoplatform/eips/blob/master/eip-0003.md>
+
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+
+use ergotree_ir::chain::address::Address;
+use ergotree_ir::chain::ergo_box::ErgoBox;
+use ergotree_ir::chain::ergo_box::ErgoBoxCandidate;
+use thiserror::Error;
+
+use crate::chain::ergo_state_context::ErgoStateContext;
+use crate::chain::transaction::reduced::reduce_tx;
+use crate::chain::transaction::reduced::ReducedTransaction;
+use crate::chain::transaction::unsigned::UnsignedTransaction;
+
+use super::box_selector::BoxSelector;
+use super::derivation_path::ChildIndex;
+use super::derivation_path::ChildIndexHardened;
+use super::derivation_path::ChildIndexNormal;
+use super::derivation_path::DerivationPath;
+use super::ext_pub_key::ExtPubKey;
+use super::ext_secret_key::ExtSecretKey;
+use super::ext_secret_key::ExtSecretKeyError;
+use super::mnemonic::Mnemonic;
+use super::secret_key::SecretKey;
+use super::signing::TransactionContext;
+use super::signing::TxSigningError;
+use super::tx_builder::TxBuilder;
+use super::tx_builder::TxBuilderError;
+use super::tx_builder::TxFee;
+use super::tx_context::TransactionContextError;
+use super::Wallet;
+
+/// Tells whether the address was used (e.g. appeared on the chain), used for the address discovery
+pub trait AddressUsage {
+    /// Error of the usage check (e.g. a failed request to the node or explorer)
+    type Error;
+
+    /// Returns true if the address was used
+    fn is_used(&self, address: &Address) -> Result<bool, Self::Error>;
+}
+
+impl<F: Fn(&Address) -> bool> AddressUsage for F {
+    type Error = Infallible;
+
+    fn is_used(&self, address: &Address) -> Result<bool, Self::Error> {
+        Ok(self(address))
+    }
+}
+
+/// Address derived for the account along with its derivation path
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct DerivedAddress {
+    /// Derivation path (`m / 44' / 429' / account' / 0 / index`)
+    pub path: DerivationPath,
+    /// P2PK address
+    pub address: Address,
+}
+
+/// Account errors
+#[derive(Error, Debug)]
+pub enum AccountError {
+    /// Secret key derivation error
+    #[error("ExtSecretKeyError: {0}")]
+    ExtSecretKeyError(#[from] ExtSecretKeyError),
+    /// Secret keys are requested from the watch-only account
+    #[error("account is watch-only")]
+    WatchOnly,
+    /// Extended public key is not of the account according to EIP-3
+    #[error("not an EIP-3 account path: {0}")]
+    InvalidDerivationPath(DerivationPath),
+    /// Transaction building error
+    #[error("TxBuilderError: {0}")]
+    TxBuilderError(#[from] TxBuilderError),
+    /// Transaction context error
+    #[error("TransactionContextError: {0}")]
+    TransactionContextError(#[from] TransactionContextError),
+    /// Transaction reduction error
+    #[error("TxSigningError: {0}")]
+    TxSigningError(#[from] TxSigningError),
+}
+
+/// Account of the HD wallet (`m / 44' / 429' / account' / 0`), derives the addresses (P2PK) on
+/// demand, discovers the used ones and keeps track of the next unused address index.
+/// Watch-only account (see [`Account::watch_only`]) has no secret keys, its transactions are
+/// built and reduced (see [`Account::reduce_tx`]) for an external signer.
+pub struct Account {
+    index: ChildIndexHardened,
+    ext_secret_key: Option<ExtSecretKey>,
+    ext_pub_key: ExtPubKey,
+    gap_limit: u32,
+    addresses: BTreeMap<u32, DerivedAddress>,
+    next_unused_index: ChildIndexNormal,
+}
+
+impl Account {
+    /// Number of consecutive unused addresses after which the discovery stops (see BIP-44)
+    pub const DEFAULT_GAP_LIMIT: u32 = 20;
+
+    /// Create account with the given index from the master key (see
+    /// [`ExtSecretKey::derive_master`])
+    pub fn new(master_key: &ExtSecretKey, index: ChildIndexHardened) -> Result<Self, AccountError> {
+        let ext_secret_key = master_key.derive(DerivationPath::new(index, vec![]))?;
+        let ext_pub_key = ext_secret_key.public_key()?;
+        Ok(Account::with_keys(index, Some(ext_secret_key), ext_pub_key))
+    }
+
+    /// Create watch-only account from the extended public key of the account
+    /// (`m / 44' / 429' / account'` or `m / 44' / 429' / account' / 0`), e.g. parsed with
+    /// [`ExtPubKey::from_base58check`]
+    pub fn watch_only(ext_pub_key: ExtPubKey) -> Result<Self, AccountError> {
+        let path = ext_pub_key.derivation_path.clone();
+        let index = path
+            .account_index()
+            .ok_or_else(|| AccountError::InvalidDerivationPath(path.clone()))?;
+        let account_path = DerivationPath::new(index, vec![]);
+        let ext_pub_key = ext_pub_key
+            .derive(account_path.clone())
+            .map_err(|_| AccountError::InvalidDerivationPath(path))?;
+        // the derived key may skip the index (see BIP-32)
+        if ext_pub_key.derivation_path != account_path {
+            return Err(AccountError::InvalidDerivationPath(
+                ext_pub_key.derivation_path,
+            ));
+        }
+        Ok(Account::with_keys(index, None, ext_pub_key))
+    }
+
+    fn with_keys(
+        index: ChildIndexHardened,
+        ext_secret_key: Option<ExtSecretKey>,
+        ext_pub_key: ExtPubKey,
+    ) -> Self {
+        // 0 is a valid normal index
+        #[allow(clippy::unwrap_used)]
+        let next_unused_index = ChildIndexNormal::normal(0).unwrap();
+        Account {
+            index,
+            ext_secret_key,
+            ext_pub_key,
+            gap_limit: Account::DEFAULT_GAP_LIMIT,
+            addresses: BTreeMap::new(),
+            next_unused_index,
+        }
+    }
+
+    /// Create account with the given index from the mnemonic phrase and password
+    pub fn from_mnemonic(
+        mnemonic_phrase: &str,
+        mnemonic_pass: &str,
+        index: ChildIndexHardened,
+    ) -> Result<Self, AccountError> {
+        let seed = Mnemonic::to_seed(mnemonic_phrase, mnemonic_pass);
+        Account::new(&ExtSecretKey::derive_master(seed)?, index)
+    }
+
+    /// Set the number of consecutive unused addresses after which the discovery stops
+    pub fn set_gap_limit(&mut self, gap_limit: u32) {
+        self.gap_limit = gap_limit;
+    }
+
+    /// Account index
+    pub fn index(&self) -> ChildIndexHardened {
+        self.index
+    }
+
+    /// Returns true if the account has no secret keys
+    pub fn is_watch_only(&self) -> bool {
+        self.ext_secret_key.is_none()
+    }
+
+    /// Extended public key of the account (`m / 44' / 429' / account' / 0`)
+    pub fn ext_pub_key(&self) -> ExtPubKey {
+        self.ext_pub_key.clone()
+    }
+
+    /// Index of the first address after the last used (or handed out) one
+    pub fn next_unused_index(&self) -> ChildIndexNormal {
+        self.next_unused_index
+    }
+
+    /// Address with the given index (derived once and remembered)
+    pub fn address(&mut self, index: ChildIndexNormal) -> DerivedAddress {
+        let ext_pub_key = &self.ext_pub_key;
+        self.addresses
+            .entry(ChildIndex::Normal(index).to_bits())
+            .or_insert_with(|| {
+                let child = ext_pub_key.child(index);
+                DerivedAddress {
+                    path: child.derivation_path.clone(),
+                    address: child.into(),
+                }
+            })
+            .clone()
+    }
+
+    /// Hands out the next unused address (the following call returns the next one)
+    pub fn next_address(&mut self) -> DerivedAddress {
+        let index = self.next_unused_index;
+        self.next_unused_index = index.next();
+        self.address(index)
+    }
+
+    /// Every derived address (ordered by index)
+    pub fn addresses(&self) -> Vec<DerivedAddress> {
+        self.addresses.values().cloned().collect()
+    }
+
+    /// Checks the addresses starting from the next unused one until the gap limit of consecutive
+    /// unused addresses is reached, moving the next unused index past the used ones.
+    /// Returns the used addresses found.
+    pub fn discover<U: AddressUsage>(
+        &mut self,
+        usage: &U,
+    ) -> Result<Vec<DerivedAddress>, U::Error> {
+        let mut used = vec![];
+        let mut index = self.next_unused_index;
+        let mut gap = 0;
+        while gap < self.gap_limit {
+            let derived = self.address(index);
+            index = index.next();
+            if usage.is_used(&derived.address)? {
+                used.push(derived);
+                self.next_unused_index = index;
+                gap = 0;
+            } else {
+                gap += 1;
+            }
+        }
+        Ok(used)
+    }
+
+    /// Secret key for the given address index
+    pub fn secret_key(&self, index: ChildIndexNormal) -> Result<SecretKey, AccountError> {
+        let ext_secret_key = self
+            .ext_secret_key
+            .as_ref()
+            .ok_or(AccountError::WatchOnly)?;
+        Ok(ext_secret_key.child(index.into())?.secret_key())
+    }
+
+    /// Wallet with the secret keys of every derived address
+    pub fn wallet(&self) -> Result<Wallet, AccountError> {
+        let ext_secret_key = self
+            .ext_secret_key
+            .as_ref()
+            .ok_or(AccountError::WatchOnly)?;
+        let secrets = self
+            .addresses
+            .values()
+            .map(|derived| Ok(ext_secret_key.derive(derived.path.clone())?.secret_key()))
+            .collect::<Result<Vec<SecretKey>, AccountError>>()?;
+        Ok(Wallet::from_secrets(secrets))
+    }
+
+    /// Selects the inputs (see [`TxBuilder::select_inputs`]) from the account boxes and builds
+    /// the transaction, the change goes to the next unused address
+    pub fn build_tx<B: BoxSelector<ErgoBox>>(
+        &mut self,
+        box_selector: &B,
+        inputs: Vec<ErgoBox>,
+        output_candidates: Vec<ErgoBoxCandidate>,
+        current_height: u32,
+        fee: TxFee,
+    ) -> Result<TransactionContext<UnsignedTransaction>, AccountError> {
+        let change_address = self.address(self.next_unused_index).address;
+        let tx_builder = TxBuilder::select_inputs(
+            box_selector,
+            inputs,
+            output_candidates,
+            current_height,
+            fee,
+            change_address,
+        )?;
+        let box_selection = tx_builder.box_selection();
+        let tx = tx_builder.build()?;
+        if !box_selection.change_boxes.is_empty() {
+            self.next_unused_index = self.next_unused_index.next();
+        }
+        Ok(TransactionContext::new(
+            tx,
+            box_selection.boxes.as_vec().clone(),
+            vec![],
+        )?)
+    }
+
+    /// Reduces the transaction inputs to the sigma propositions for an external signer (e.g. a
+    /// hardware wallet), doesn't need the secret keys
+    pub fn reduce_tx(
+        &self,
+        tx_context: TransactionContext<UnsignedTransaction>,
+        state_context: &ErgoStateContext,
+    ) -> Result<ReducedTransaction, AccountError> {
+        Ok(reduce_tx(tx_context, state_context)?)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+    use std::convert::TryInto;
+
+    use ergotree_ir::chain::address::AddressEncoder;
+    use ergotree_ir::chain::address::NetworkAddress;
+    use ergotree_ir::chain::address::NetworkPrefix;
+    use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+    use ergotree_ir::chain::ergo_box::NonMandatoryRegisters;
+    use ergotree_ir::chain::tx_id::TxId;
+    use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+    use sigma_test_util::force_any_val;
+
+    use crate::chain::ergo_box::box_builder::ErgoBoxCandidateBuilder;
+    use crate::wallet::box_selector::SimpleBoxSelector;
+    use crate::wallet::ext_key_encoding::ExtKeyEncodingError;
+    use crate::wallet::tx_builder::SUGGESTED_TX_FEE;
+
+    use super::*;
+
+    // from https://github.com/ergoplatform/ergo-appkit/blob/b77b6910bb36a26d5d46d41ae3af8ae1167c902c/common/src/test/scala/org/ergoplatform/appkit/AppkitTestingCommon.scala#L4-L21
+    const MNEMONIC: &str = "slow silly start wash bundle suffer bulb ancient height spin express remind today effort helmet";
+    const P2PK0: &str = "9eatpGQdYNjTi5ZZLK7Bo7C3ms6oECPnxbQTRn6sDcBNLMYSCa8";
+    const P2PK1: &str = "9iBhwkjzUAVBkdxWvKmk7ab7nFgZRFbGpXA9gP6TAoakFnLNomk";
+
+    fn account() -> Account {
+        Account::from_mnemonic(MNEMONIC, "", ChildIndexHardened::from_31_bit(0).unwrap()).unwrap()
+    }
+
+    fn account_with_gap(gap_limit: u32) -> Account {
+        let mut account = account();
+        account.set_gap_limit(gap_limit);
+        account
+    }
+
+    fn parse(address: &str) -> Address {
+        AddressEncoder::unchecked_parse_network_address_from_str(address)
+            .unwrap()
+            .address()
+    }
+
+    #[test]
+    fn test_eip3_addresses() {
+        let mut account = account();
+        let a0 = account.next_address();
+        assert_eq!(
+            NetworkAddress::new(NetworkPrefix::Mainnet, &a0.address).to_base58(),
+            P2PK0
+        );
+        assert_eq!(a0.path.to_string(), "m/44'/429'/0'/0/0");
+        let a1 = account.next_address();
+        assert_eq!(
+            NetworkAddress::new(NetworkPrefix::Mainnet, &a1.address).to_base58(),
+            P2PK1
+        );
+        assert_eq!(a1.path.to_string(), "m/44'/429'/0'/0/1");
+        assert_eq!(account.addresses(), vec![a0, a1]);
+        assert_eq!(
+            account.next_unused_index(),
+            ChildIndexNormal::normal(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_discover() {
+        let mut account = account_with_gap(4);
+        let used_address = account
+            .address(ChildIndexNormal::normal(4).unwrap())
+            .address;
+        let first_address = parse(P2PK0);
+        let used = account
+            .discover(&|address: &Address| *address == first_address || *address == used_address)
+            .unwrap();
+        assert_eq!(
+            used.iter().map(|a| a.path.to_string()).collect::<Vec<_>>(),
+            vec!["m/44'/429'/0'/0/0", "m/44'/429'/0'/0/4"]
+        );
+        assert_eq!(
+            account.next_unused_index(),
+            ChildIndexNormal::normal(5).unwrap()
+        );
+        // 0..=4 and the gap of 4 after the last used one
+        assert_eq!(account.addresses().len(), 9);
+    }
+
+    #[test]
+    fn test_discover_stops_at_gap_limit() {
+        let mut account = account_with_gap(4);
+        let far_address = account
+            .address(ChildIndexNormal::normal(10).unwrap())
+            .address;
+        assert!(account
+            .discover(&|address: &Address| *address == far_address)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            account.next_unused_index(),
+            ChildIndexNormal::normal(0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_secret_keys() {
+        let mut account = account();
+        let a0 = account.next_address();
+        let a1 = account.next_address();
+        let sk1 = account
+            .secret_key(ChildIndexNormal::normal(1).unwrap())
+            .unwrap();
+        assert_eq!(sk1.get_address_from_public_image(), a1.address);
+        let root = ExtSecretKey::derive_master(Mnemonic::to_seed(MNEMONIC, "")).unwrap();
+        let sk0 = root.derive(a0.path).unwrap().secret_key();
+        assert_eq!(sk0.get_address_from_public_image(), a0.address);
+        assert!(account.wallet().is_ok());
+    }
+
+    #[test]
+    fn test_watch_only() {
+        let mut account = account();
+        let xpub = account
+            .ext_pub_key()
+            .to_base58check(NetworkPrefix::Mainnet)
+            .unwrap();
+        let ext_pub_key = ExtPubKey::from_base58check(
+            &xpub,
+            NetworkPrefix::Mainnet,
+            "m/44'/429'/0'/0".parse().unwrap(),
+        )
+        .unwrap();
+        let mut watch_only = Account::watch_only(ext_pub_key).unwrap();
+        assert!(watch_only.is_watch_only());
+        assert_eq!(watch_only.next_address(), account.next_address());
+        assert_eq!(watch_only.next_address(), account.next_address());
+        assert!(matches!(
+            watch_only.secret_key(ChildIndexNormal::normal(0).unwrap()),
+            Err(AccountError::WatchOnly)
+        ));
+        assert!(watch_only.wallet().is_err());
+        assert_eq!(
+            ExtPubKey::from_base58check(
+                &xpub,
+                NetworkPrefix::Mainnet,
+                "m/44'/429'/0'".parse().unwrap(),
+            ),
+            Err(ExtKeyEncodingError::DerivationPathMismatch(
+                "m/44'/429'/0'".parse().unwrap(),
+                4,
+                0
+            ))
+        );
+        // account level key (without the change index)
+        let root = ExtSecretKey::derive_master(Mnemonic::to_seed(MNEMONIC, "")).unwrap();
+        let acc_key = root
+            .derive("m/44'/429'/0'".parse().unwrap())
+            .unwrap()
+            .public_key()
+            .unwrap();
+        let mut watch_only = Account::watch_only(acc_key).unwrap();
+        assert_eq!(
+            NetworkAddress::new(NetworkPrefix::Mainnet, &watch_only.next_address().address)
+                .to_base58(),
+            P2PK0
+        );
+        assert!(matches!(
+            Account::watch_only(root.public_key().unwrap()),
+            Err(AccountError::InvalidDerivationPath(_))
+        ));
+        for path in [
+            "m/1'/429'/0'",
+            "m/44'/1'/0'",
+            "m/44'/429'/0'/1",
+            "m/44'/429'/0'/0/0",
+        ] {
+            let ext_pub_key = root
+                .derive(path.parse().unwrap())
+                .unwrap()
+                .public_key()
+                .unwrap();
+            assert!(
+                matches!(
+                    Account::watch_only(ext_pub_key),
+                    Err(AccountError::InvalidDerivationPath(p)) if p == path.parse().unwrap()
+                ),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_watch_only_reduce_tx() {
+        let mut account = account();
+        let mut watch_only = Account::watch_only(account.ext_pub_key()).unwrap();
+        let deposit_address = watch_only.next_address();
+        let input = ErgoBox::new(
+            (BoxValue::SAFE_USER_MIN.as_u64() * 100).try_into().unwrap(),
+            deposit_address.address.script().unwrap(),
+            None,
+            NonMandatoryRegisters::empty(),
+            0,
+            force_any_val::<TxId>(),
+            0,
+        )
+        .unwrap();
+        let output = ErgoBoxCandidateBuilder::new(
+            BoxValue::SAFE_USER_MIN.checked_mul_u32(10).unwrap(),
+            parse(P2PK1).script().unwrap(),
+            0,
+        )
+        .build()
+        .unwrap();
+        let tx_context = watch_only
+            .build_tx(
+                &SimpleBoxSelector::new(),
+                vec![input],
+                vec![output],
+                0,
+                TxFee::Fixed(SUGGESTED_TX_FEE()),
+            )
+            .unwrap();
+        // change went to the next address
+        let change_address = watch_only.addresses()[1].address.clone();
+        assert_eq!(
+            watch_only.next_unused_index(),
+            ChildIndexNormal::normal(2).unwrap()
+        );
+        assert!(tx_context
+            .spending_tx
+            .output_candidates
+            .iter()
+            .any(|b| b.ergo_tree == change_address.script().unwrap()));
+        let reduced_tx = watch_only
+            .reduce_tx(tx_context, &force_any_val::<ErgoStateContext>())
+            .unwrap();
+        let expected_sigma_prop: SigmaBoolean = match deposit_address.address {
+            Address::P2Pk(pk) => pk.into(),
+            _ => panic!("P2PK address expected"),
+        };
+        assert_eq!(
+            reduced_tx.reduced_inputs().first().sigma_prop,
+            expected_sigma_prop
+        );
+        // external signer
+        account.next_address();
+        assert!(account
+            .wallet()
+            .unwrap()
+            .sign_reduced_transaction(reduced_tx, None)
+            .is_ok());
+    }
+}
@@ -1,10 +1,18 @@
 //! Box selection for transaction inputs
 
+mod branch_and_bound;
+mod consolidating;
+mod random;
 mod simple;
+mod token_minimizing;
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::convert::TryInto;
 
 use bounded_vec::BoundedVec;
+pub use branch_and_bound::*;
+pub use consolidating::*;
 use ergotree_ir::chain::ergo_box::box_value::BoxValue;
 use ergotree_ir::chain::ergo_box::BoxId;
 use ergotree_ir::chain::ergo_box::BoxTokens;
@@ -14,7 +22,9 @@ use ergotree_ir::chain::token::Token;
 use ergotree_ir::chain::token::TokenAmount;
 use ergotree_ir::chain::token::TokenAmountError;
 use ergotree_ir::chain::token::TokenId;
+pub use random::*;
 pub use simple::*;
+pub use token_minimizing::*;
 
 use thiserror::Error;
 
@@ -213,6 +223,142 @@ pub fn subtract_tokens(
     Ok(res)
 }
 
+/// Splits the inputs into the boxes (taken in the given order) needed to cover the target tokens
+/// and the rest of the inputs
+pub(crate) fn select_token_boxes<T: ErgoBoxAssets>(
+    inputs: Vec<T>,
+    target_tokens: &HashMap<TokenId, TokenAmount>,
+) -> Result<(Vec<T>, Vec<T>), BoxSelectorError> {
+    let mut tokens_left = target_tokens.clone();
+    let mut selected = Vec::new();
+    let mut rest = Vec::new();
+    for b in inputs {
+        let mut has_target_tokens = false;
+        for t in b.tokens().into_iter().flatten() {
+            if let Some(amount_left) = tokens_left.get(&t.token_id).cloned() {
+                has_target_tokens = true;
+                if amount_left <= t.amount {
+                    tokens_left.remove(&t.token_id);
+                } else {
+                    tokens_left.insert(t.token_id, amount_left.checked_sub(&t.amount)?);
+                }
+            }
+        }
+        if has_target_tokens {
+            selected.push(b);
+        } else {
+            rest.push(b);
+        }
+    }
+    if !tokens_left.is_empty() {
+        return Err(BoxSelectorError::NotEnoughTokens(
+            tokens_left.into_iter().map(Token::from).collect(),
+        ));
+    }
+    Ok((selected, rest))
+}
+
+/// Adds the boxes from `rest` (in the given order) to `selected` until the selection is complete
+/// (see [`is_selection_complete`]), returns the boxes left unused
+pub(crate) fn select_value_boxes<T: ErgoBoxAssets>(
+    selected: &mut Vec<T>,
+    rest: Vec<T>,
+    target_balance: BoxValue,
+    target_tokens: &HashMap<TokenId, TokenAmount>,
+) -> Result<Vec<T>, BoxSelectorError> {
+    let mut rest = rest.into_iter();
+    while !is_selection_complete(selected.as_slice(), target_balance, target_tokens)? {
+        match rest.next() {
+            Some(b) => selected.push(b),
+            None => break,
+        }
+    }
+    Ok(rest.collect())
+}
+
+/// Returns true if the selected boxes cover the target and either there is no change at all, or
+/// the change value is enough for a change box ([`BoxValue::SAFE_USER_MIN`])
+pub(crate) fn is_selection_complete<T: ErgoBoxAssets>(
+    selected: &[T],
+    target_balance: BoxValue,
+    target_tokens: &HashMap<TokenId, TokenAmount>,
+) -> Result<bool, TokenAmountError> {
+    let selected_value = sum_value(selected);
+    let target_value = *target_balance.as_u64();
+    if selected_value < target_value {
+        return Ok(false);
+    }
+    let selected_tokens = sum_tokens_from_boxes(selected)?;
+    let has_target_tokens = target_tokens
+        .iter()
+        .all(|(id, amt)| selected_tokens.get(id).map_or(false, |t_amt| t_amt >= amt));
+    if !has_target_tokens {
+        return Ok(false);
+    }
+    let has_token_change = selected_tokens
+        .iter()
+        .any(|(id, t_amt)| target_tokens.get(id).map_or(true, |amt| t_amt > amt));
+    let change_value = selected_value - target_value;
+    Ok((change_value == 0 && !has_token_change)
+        || change_value >= *BoxValue::SAFE_USER_MIN.as_u64())
+}
+
+/// Makes the selection out of the given boxes, putting their value and tokens over the target
+/// into the change boxes
+pub(crate) fn make_selection<T: ErgoBoxAssets>(
+    selected: Vec<T>,
+    target_balance: BoxValue,
+    target_tokens: &[Token],
+) -> Result<BoxSelection<T>, BoxSelectorError> {
+    let selected_value = sum_value(selected.as_slice());
+    let target_value = *target_balance.as_u64();
+    if selected_value < target_value {
+        return Err(BoxSelectorError::NotEnoughCoins(
+            target_value - selected_value,
+        ));
+    }
+    let selected_tokens = sum_tokens_from_boxes(selected.as_slice())?;
+    let target_tokens_sum = sum_tokens(Some(target_tokens))?;
+    let missing_tokens = target_tokens_sum
+        .iter()
+        .filter_map(|(id, amt)| match selected_tokens.get(id) {
+            Some(t_amt) if t_amt >= amt => None,
+            Some(t_amt) => Some(amt.checked_sub(t_amt).map(|missing| (*id, missing))),
+            None => Some(Ok((*id, *amt))),
+        })
+        .map(|missing| missing.map(Token::from))
+        .collect::<Result<Vec<Token>, TokenAmountError>>()?;
+    if !missing_tokens.is_empty() {
+        return Err(BoxSelectorError::NotEnoughTokens(missing_tokens));
+    }
+    let change_tokens = subtract_tokens(&selected_tokens, &target_tokens_sum)?;
+    let change_value = selected_value - target_value;
+    let change_boxes = if change_value == 0 && change_tokens.is_empty() {
+        vec![]
+    } else {
+        let change_value = BoxValue::try_from(change_value).map_err(|e| {
+            NotEnoughCoinsForChangeBox(format!(
+                "change box value {} is too small, error: {} ",
+                change_value, e
+            ))
+        })?;
+        make_change_boxes(change_value, change_tokens)?
+    };
+    check_input_preservation(
+        selected.as_slice(),
+        change_boxes.as_slice(),
+        target_balance,
+        target_tokens,
+    )?;
+    let selected_len = selected.len();
+    Ok(BoxSelection {
+        boxes: selected
+            .try_into()
+            .map_err(|_| BoxSelectorError::SelectedInputsOutOfBounds(selected_len))?,
+        change_boxes,
+    })
+}
+
 /// Arbitrary impl for ErgoBoxAssetsData
 #[allow(clippy::unwrap_used, clippy::panic)]
 #[cfg(feature = "arbitrary")]
@@ -291,6 +437,131 @@ pub mod arbitrary {
     }
 }
 
+/// Checks shared by the tests of all [`BoxSelector`] implementations
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+pub(crate) mod selector_test_harness {
+
+    use std::convert::TryFrom;
+    use std::convert::TryInto;
+
+    use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+    use ergotree_ir::chain::token::arbitrary::ArbTokenIdParam;
+    use ergotree_ir::chain::token::Token;
+    use proptest::collection::vec;
+    use proptest::prelude::*;
+    use proptest::test_runner::TestRunner;
+
+    use super::arbitrary::ArbErgoBoxAssetsDataParam;
+    use super::arbitrary::ArbTokensParam;
+    use super::sum_tokens;
+    use super::sum_tokens_from_boxes;
+    use super::sum_tokens_from_hashmaps;
+    use super::sum_value;
+    use super::BoxSelector;
+    use super::BoxSelectorError;
+    use super::ErgoBoxAssetsData;
+
+    /// Inputs (with tokens out of a few predefined ids), target balance (up to slightly more than
+    /// the inputs have) and target tokens (up to a half of the amounts the inputs have)
+    fn arb_selection_case() -> impl Strategy<Value = (Vec<ErgoBoxAssetsData>, BoxValue, Vec<Token>)>
+    {
+        vec(
+            any_with::<ErgoBoxAssetsData>(ArbErgoBoxAssetsDataParam {
+                value_range: (BoxValue::MIN_RAW * 1000..BoxValue::MIN_RAW * 10000).into(),
+                tokens_param: ArbTokensParam {
+                    token_id_param: ArbTokenIdParam::Predef,
+                    token_count_range: 0..3,
+                },
+            }),
+            1..30,
+        )
+        .prop_flat_map(|inputs| {
+            let total_value = sum_value(inputs.as_slice());
+            let tokens: Vec<Token> = sum_tokens_from_boxes(inputs.as_slice())
+                .unwrap()
+                .into_iter()
+                .map(|(token_id, amount)| Token {
+                    token_id,
+                    amount: ((u64::from(amount) + 1) / 2).try_into().unwrap(),
+                })
+                .collect();
+            let tokens_count = tokens.len();
+            (
+                Just(inputs),
+                BoxValue::MIN_RAW..total_value + BoxValue::MIN_RAW * 1000,
+                proptest::sample::subsequence(tokens, 0..=tokens_count),
+            )
+        })
+        .prop_map(|(inputs, target_balance, target_tokens)| {
+            (
+                inputs,
+                BoxValue::try_from(target_balance).unwrap(),
+                target_tokens,
+            )
+        })
+    }
+
+    /// Runs the selector on the random inputs and checks that the selected boxes are taken from
+    /// the inputs and hold exactly the target plus the change, or that the error is legit
+    pub(crate) fn check_selector<S: BoxSelector<ErgoBoxAssetsData>>(selector: S) {
+        TestRunner::default()
+            .run(
+                &arb_selection_case(),
+                |(inputs, target_balance, target_tokens)| {
+                    check_selection(&selector, inputs, target_balance, target_tokens.as_slice())
+                },
+            )
+            .unwrap();
+    }
+
+    fn check_selection<S: BoxSelector<ErgoBoxAssetsData>>(
+        selector: &S,
+        inputs: Vec<ErgoBoxAssetsData>,
+        target_balance: BoxValue,
+        target_tokens: &[Token],
+    ) -> Result<(), TestCaseError> {
+        let total_value = sum_value(inputs.as_slice());
+        let target_value = *target_balance.as_u64();
+        let selection = match selector.select(inputs.clone(), target_balance, target_tokens) {
+            Ok(selection) => selection,
+            Err(BoxSelectorError::NotEnoughCoins(_)) => {
+                prop_assert!(total_value < target_value, "inputs have enough coins");
+                return Ok(());
+            }
+            // the coins left over the target are not enough for a change box
+            Err(BoxSelectorError::NotEnoughCoinsForChangeBox(_)) => {
+                prop_assert!(total_value - target_value < *BoxValue::SAFE_USER_MIN.as_u64());
+                return Ok(());
+            }
+            Err(e) => return Err(TestCaseError::fail(format!("unexpected error: {}", e))),
+        };
+        for b in selection.boxes.iter() {
+            prop_assert!(
+                selection.boxes.iter().filter(|s| *s == b).count()
+                    <= inputs.iter().filter(|i| *i == b).count(),
+                "selected box {:?} is not in the inputs",
+                b
+            );
+        }
+        prop_assert_eq!(
+            sum_value(selection.boxes.as_slice()),
+            target_value + sum_value(selection.change_boxes.as_slice()),
+            "total value of the selected boxes should equal target balance + total value in change boxes"
+        );
+        prop_assert_eq!(
+            sum_tokens_from_boxes(selection.boxes.as_slice()).unwrap(),
+            sum_tokens_from_hashmaps(
+                sum_tokens_from_boxes(selection.change_boxes.as_slice()).unwrap(),
+                sum_tokens(Some(target_tokens)).unwrap()
+            )
+            .unwrap(),
+            "all tokens from selected boxes should equal all tokens from the change boxes + target tokens"
+        );
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
@@ -0,0 +1,241 @@
+//! Branch and bound box selector, looks for the inputs that need no change (or the smallest one)
+
+use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+use ergotree_ir::chain::token::Token;
+
+use super::make_selection;
+use super::select_token_boxes;
+use super::subtract_tokens;
+use super::sum_tokens;
+use super::sum_tokens_from_boxes;
+use super::sum_value;
+use super::BoxSelection;
+use super::BoxSelector;
+use super::BoxSelectorError;
+use super::ErgoBoxAssets;
+use super::SimpleBoxSelector;
+
+/// Branch and bound box selector.
+/// Boxes with the target tokens are selected first, then the boxes without tokens are searched
+/// (depth-first, largest first) for a combination that matches the target balance exactly, so that
+/// no change box is needed. If there is none, the combination with the smallest change that is
+/// still enough for a change box ([`BoxValue::SAFE_USER_MIN`]) is chosen.
+/// Falls back to [`SimpleBoxSelector`] if nothing is found in `max_tries` steps.
+pub struct BranchAndBoundBoxSelector {
+    max_tries: usize,
+}
+
+impl BranchAndBoundBoxSelector {
+    /// Default limit of the search steps
+    pub const DEFAULT_MAX_TRIES: usize = 100_000;
+
+    /// Create new instance with the limit of the search steps
+    pub fn new(max_tries: usize) -> Self {
+        BranchAndBoundBoxSelector { max_tries }
+    }
+}
+
+impl Default for BranchAndBoundBoxSelector {
+    fn default() -> Self {
+        BranchAndBoundBoxSelector::new(BranchAndBoundBoxSelector::DEFAULT_MAX_TRIES)
+    }
+}
+
+impl<T: ErgoBoxAssets + Clone> BoxSelector<T> for BranchAndBoundBoxSelector {
+    fn select(
+        &self,
+        inputs: Vec<T>,
+        target_balance: BoxValue,
+        target_tokens: &[Token],
+    ) -> Result<BoxSelection<T>, BoxSelectorError> {
+        let target_tokens_sum = sum_tokens(Some(target_tokens))?;
+        let (mut selected, rest) = select_token_boxes(inputs.clone(), &target_tokens_sum)?;
+        // tokens of other boxes would end up in the change
+        let (mut candidates, _): (Vec<T>, Vec<T>) =
+            rest.into_iter().partition(|b| b.tokens().is_none());
+        candidates.sort_by_key(|b| std::cmp::Reverse(u64::from(b.value())));
+        let has_token_change = !subtract_tokens(
+            &sum_tokens_from_boxes(selected.as_slice())?,
+            &target_tokens_sum,
+        )?
+        .is_empty();
+        let search = Search {
+            values: candidates.iter().map(|b| u64::from(b.value())).collect(),
+            target: *target_balance.as_u64(),
+            min_change: *BoxValue::SAFE_USER_MIN.as_u64(),
+            allow_exact: !has_token_change,
+        };
+        match search.run(sum_value(selected.as_slice()), self.max_tries) {
+            Some(picks) => {
+                selected.extend(
+                    candidates
+                        .into_iter()
+                        .zip(picks)
+                        .filter_map(|(b, picked)| picked.then_some(b)),
+                );
+                make_selection(selected, target_balance, target_tokens)
+            }
+            None => SimpleBoxSelector::new().select(inputs, target_balance, target_tokens),
+        }
+    }
+}
+
+/// Search for the subset of values (sorted in descending order)
+struct Search {
+    values: Vec<u64>,
+    target: u64,
+    min_change: u64,
+    /// whether the sum can match the target exactly (no change at all)
+    allow_exact: bool,
+}
+
+impl Search {
+    /// Returns the picks (a flag for every value up to the last picked one) with the smallest
+    /// excess over the target (starting from `initial` sum)
+    fn run(&self, initial: u64, max_tries: usize) -> Option<Vec<bool>> {
+        // sum of the values starting from the given index
+        let mut remaining: Vec<u64> = self
+            .values
+            .iter()
+            .rev()
+            .scan(0u64, |sum, v| {
+                *sum = sum.saturating_add(*v);
+                Some(*sum)
+            })
+            .collect();
+        remaining.reverse();
+        remaining.push(0);
+
+        let mut best: Option<(u64, Vec<bool>)> = None;
+        let mut picks: Vec<bool> = Vec::new();
+        let mut sum = initial;
+        for _ in 0..max_tries {
+            let mut backtrack = false;
+            if sum >= self.target {
+                let excess = sum - self.target;
+                if best
+                    .as_ref()
+                    .map_or(false, |(best_excess, _)| excess >= *best_excess)
+                {
+                    backtrack = true;
+                } else if (excess == 0 && self.allow_exact) || excess >= self.min_change {
+                    best = Some((excess, picks.clone()));
+                    if excess == 0 {
+                        break;
+                    }
+                    // picking more values only increases the excess
+                    backtrack = true;
+                }
+            }
+            let idx = picks.len();
+            if idx == self.values.len() || sum.saturating_add(remaining[idx]) < self.target {
+                backtrack = true;
+            }
+            if backtrack {
+                // skip the last picked value and try the next ones
+                while picks.last() == Some(&false) {
+                    picks.pop();
+                }
+                match picks.len().checked_sub(1) {
+                    Some(last) => {
+                        picks[last] = false;
+                        sum -= self.values[last];
+                    }
+                    None => break,
+                }
+            } else {
+                picks.push(true);
+                sum += self.values[idx];
+            }
+        }
+        best.map(|(_, picks)| picks)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+
+    use std::convert::TryInto;
+
+    use crate::wallet::box_selector::selector_test_harness::check_selector;
+    use crate::wallet::box_selector::ErgoBoxAssetsData;
+
+    use super::*;
+
+    fn input(value: u64) -> ErgoBoxAssetsData {
+        ErgoBoxAssetsData {
+            value: value.try_into().unwrap(),
+            tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_harness() {
+        check_selector(BranchAndBoundBoxSelector::default());
+    }
+
+    #[test]
+    fn test_exact_match_without_change() {
+        let unit = BoxValue::SAFE_USER_MIN.as_u64() * 10;
+        let inputs = vec![
+            input(unit * 10),
+            input(unit * 6),
+            input(unit * 4),
+            input(unit * 3),
+        ];
+        let s = BranchAndBoundBoxSelector::default();
+        let selection = s
+            .select(inputs, (unit * 7).try_into().unwrap(), vec![].as_slice())
+            .unwrap();
+        assert_eq!(
+            selection.boxes.as_slice(),
+            &[input(unit * 4), input(unit * 3)]
+        );
+        assert!(selection.change_boxes.is_empty());
+    }
+
+    #[test]
+    fn test_smallest_change() {
+        let unit = BoxValue::SAFE_USER_MIN.as_u64() * 10;
+        let inputs = vec![input(unit * 10), input(unit * 7), input(unit * 5)];
+        let s = BranchAndBoundBoxSelector::default();
+        let selection = s
+            .select(inputs, (unit * 6).try_into().unwrap(), vec![].as_slice())
+            .unwrap();
+        assert_eq!(selection.boxes.as_slice(), &[input(unit * 7)]);
+        assert_eq!(selection.change_boxes.len(), 1);
+        assert_eq!(selection.change_boxes[0].value.as_u64(), &unit);
+    }
+
+    #[test]
+    fn test_change_too_small_for_a_box() {
+        let unit = BoxValue::SAFE_USER_MIN.as_u64() * 10;
+        // 6 + 1 leaves the change below BoxValue::SAFE_USER_MIN
+        let inputs = vec![input(unit * 6 + 1), input(unit * 3), input(unit * 4)];
+        let s = BranchAndBoundBoxSelector::default();
+        let selection = s
+            .select(inputs, (unit * 6).try_into().unwrap(), vec![].as_slice())
+            .unwrap();
+        assert_eq!(
+            selection.boxes.as_slice(),
+            &[input(unit * 4), input(unit * 3)]
+        );
+    }
+
+    #[test]
+    fn test_fallback_to_simple() {
+        let unit = BoxValue::SAFE_USER_MIN.as_u64() * 10;
+        let inputs = vec![input(unit * 10), input(unit * 6), input(unit * 4)];
+        let target_balance = (unit * 7).try_into().unwrap();
+        let selection = BranchAndBoundBoxSelector::new(0)
+            .select(inputs.clone(), target_balance, vec![].as_slice())
+            .unwrap();
+        assert_eq!(
+            selection,
+            SimpleBoxSelector::new()
+                .select(inputs, target_balance, vec![].as_slice())
+                .unwrap()
+        );
+    }
+}
@@ -0,0 +1,150 @@
+//! Largest-first box selector that sweeps dust boxes into the change
+
+use std::cmp::Reverse;
+
+use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+use ergotree_ir::chain::token::Token;
+
+use super::is_selection_complete;
+use super::make_selection;
+use super::select_token_boxes;
+use super::select_value_boxes;
+use super::sum_tokens;
+use super::BoxSelection;
+use super::BoxSelector;
+use super::BoxSelectorError;
+use super::ErgoBoxAssets;
+
+/// Consolidating box selector.
+/// Selects the boxes with the target tokens and then the largest boxes until the target balance is
+/// reached, then adds the boxes worth less than `dust_threshold` (smallest first) while the
+/// total number of inputs stays within `max_inputs`, consolidating them into the change box.
+pub struct ConsolidatingBoxSelector {
+    max_inputs: usize,
+    dust_threshold: BoxValue,
+}
+
+impl ConsolidatingBoxSelector {
+    /// Default limit of the inputs count (including the swept dust boxes)
+    pub const DEFAULT_MAX_INPUTS: usize = 100;
+
+    /// Create new instance
+    /// `max_inputs` - limit of the inputs count (dust is not swept beyond it),
+    /// `dust_threshold` - boxes worth less are considered dust
+    pub fn new(max_inputs: usize, dust_threshold: BoxValue) -> Self {
+        ConsolidatingBoxSelector {
+            max_inputs,
+            dust_threshold,
+        }
+    }
+}
+
+impl Default for ConsolidatingBoxSelector {
+    /// Sweeps boxes worth less than [`BoxValue::SAFE_USER_MIN`]
+    fn default() -> Self {
+        ConsolidatingBoxSelector::new(
+            ConsolidatingBoxSelector::DEFAULT_MAX_INPUTS,
+            BoxValue::SAFE_USER_MIN,
+        )
+    }
+}
+
+impl<T: ErgoBoxAssets> BoxSelector<T> for ConsolidatingBoxSelector {
+    fn select(
+        &self,
+        inputs: Vec<T>,
+        target_balance: BoxValue,
+        target_tokens: &[Token],
+    ) -> Result<BoxSelection<T>, BoxSelectorError> {
+        let target_tokens_sum = sum_tokens(Some(target_tokens))?;
+        let mut sorted_inputs = inputs;
+        sorted_inputs.sort_by_key(|b| Reverse(u64::from(b.value())));
+        let (mut selected, rest) = select_token_boxes(sorted_inputs, &target_tokens_sum)?;
+        let mut rest = select_value_boxes(&mut selected, rest, target_balance, &target_tokens_sum)?;
+        if is_selection_complete(selected.as_slice(), target_balance, &target_tokens_sum)? {
+            let selected_len = selected.len();
+            rest.retain(|b| b.value().as_u64() < self.dust_threshold.as_u64());
+            // smallest first
+            rest.reverse();
+            selected.extend(
+                rest.into_iter()
+                    .take(self.max_inputs.saturating_sub(selected_len)),
+            );
+            // dust might leave the change too small for a change box
+            while selected.len() > selected_len
+                && !is_selection_complete(selected.as_slice(), target_balance, &target_tokens_sum)?
+            {
+                selected.pop();
+            }
+        }
+        make_selection(selected, target_balance, target_tokens)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+
+    use std::convert::TryInto;
+
+    use crate::wallet::box_selector::selector_test_harness::check_selector;
+    use crate::wallet::box_selector::ErgoBoxAssetsData;
+
+    use super::*;
+
+    fn input(value: u64) -> ErgoBoxAssetsData {
+        ErgoBoxAssetsData {
+            value: value.try_into().unwrap(),
+            tokens: None,
+        }
+    }
+
+    #[test]
+    fn test_harness() {
+        check_selector(ConsolidatingBoxSelector::default());
+        check_selector(ConsolidatingBoxSelector::new(
+            5,
+            BoxValue::SAFE_USER_MIN.checked_mul_u32(10000).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn test_sweep_dust() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let dust = BoxValue::MIN_RAW;
+        let inputs = vec![
+            input(dust * 3),
+            input(unit * 10),
+            input(dust),
+            input(unit * 50),
+            input(dust * 2),
+        ];
+        let s = ConsolidatingBoxSelector::new(3, BoxValue::SAFE_USER_MIN);
+        let selection = s
+            .select(inputs, (unit * 20).try_into().unwrap(), vec![].as_slice())
+            .unwrap();
+        assert_eq!(
+            selection.boxes.as_slice(),
+            &[input(unit * 50), input(dust), input(dust * 2)]
+        );
+        assert_eq!(selection.change_boxes.len(), 1);
+        assert_eq!(
+            selection.change_boxes[0].value.as_u64(),
+            &(unit * 30 + dust * 3)
+        );
+    }
+
+    #[test]
+    fn test_dust_keeps_change_valid() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let dust = BoxValue::MIN_RAW;
+        let inputs = vec![input(unit * 5), input(dust)];
+        let s = ConsolidatingBoxSelector::default();
+        // exact match, sweeping the dust would make a change box too small
+        let selection = s
+            .select(inputs, (unit * 5).try_into().unwrap(), vec![].as_slice())
+            .unwrap();
+        assert_eq!(selection.boxes.as_slice(), &[input(unit * 5)]);
+        assert!(selection.change_boxes.is_empty());
+    }
+}
@@ -0,0 +1,101 @@
+//! Random box selector, picks the inputs in random order to make the transactions less linkable
+
+use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+use ergotree_ir::chain::token::Token;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+use super::make_selection;
+use super::select_token_boxes;
+use super::select_value_boxes;
+use super::sum_tokens;
+use super::BoxSelection;
+use super::BoxSelector;
+use super::BoxSelectorError;
+use super::ErgoBoxAssets;
+
+/// Random box selector.
+/// Shuffles the inputs and selects the boxes with the target tokens and then the rest of the
+/// boxes (in that random order) until the target balance is reached, so that the selection
+/// doesn't reveal which boxes belong to the wallet beyond those spent.
+#[derive(Default)]
+pub struct RandomBoxSelector {
+    seed: Option<u64>,
+}
+
+impl RandomBoxSelector {
+    /// Create new instance (shuffled with the random seed on every selection)
+    pub fn new() -> Self {
+        RandomBoxSelector { seed: None }
+    }
+
+    /// Create new instance with the fixed seed (the same inputs are always shuffled the same
+    /// way)
+    pub fn with_seed(seed: u64) -> Self {
+        RandomBoxSelector { seed: Some(seed) }
+    }
+}
+
+impl<T: ErgoBoxAssets> BoxSelector<T> for RandomBoxSelector {
+    fn select(
+        &self,
+        inputs: Vec<T>,
+        target_balance: BoxValue,
+        target_tokens: &[Token],
+    ) -> Result<BoxSelection<T>, BoxSelectorError> {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        let target_tokens_sum = sum_tokens(Some(target_tokens))?;
+        let mut shuffled_inputs = inputs;
+        shuffled_inputs.shuffle(&mut rng);
+        let (mut selected, rest) = select_token_boxes(shuffled_inputs, &target_tokens_sum)?;
+        select_value_boxes(&mut selected, rest, target_balance, &target_tokens_sum)?;
+        make_selection(selected, target_balance, target_tokens)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+
+    use std::convert::TryInto;
+
+    use crate::wallet::box_selector::selector_test_harness::check_selector;
+    use crate::wallet::box_selector::ErgoBoxAssetsData;
+
+    use super::*;
+
+    #[test]
+    fn test_harness() {
+        check_selector(RandomBoxSelector::new());
+        check_selector(RandomBoxSelector::with_seed(42));
+    }
+
+    #[test]
+    fn test_seed() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let inputs: Vec<ErgoBoxAssetsData> = (1..50)
+            .map(|i| ErgoBoxAssetsData {
+                value: (unit * i).try_into().unwrap(),
+                tokens: None,
+            })
+            .collect();
+        let target_balance = (unit * 100).try_into().unwrap();
+        let select = |s: RandomBoxSelector| {
+            s.select(inputs.clone(), target_balance, vec![].as_slice())
+                .unwrap()
+        };
+        assert_eq!(
+            select(RandomBoxSelector::with_seed(1)),
+            select(RandomBoxSelector::with_seed(1))
+        );
+        // 49 boxes are ordered in one of 49! ways
+        assert_ne!(
+            select(RandomBoxSelector::with_seed(1)),
+            select(RandomBoxSelector::with_seed(2))
+        );
+    }
+}
@@ -194,7 +194,7 @@ impl From<TokenAmountError> for CheckPreservationError {
 }
 
 /// Check if the selected inputs value and tokens are equal to the target + change
-fn check_input_preservation<T: ErgoBoxAssets>(
+pub(crate) fn check_input_preservation<T: ErgoBoxAssets>(
     selected_inputs: &[T],
     change_boxes: &[ErgoBoxAssetsData],
     target_balance: BoxValue,
@@ -224,10 +224,10 @@ fn check_input_preservation<T: ErgoBoxAssets>(
 /// Not enough coins for change box(es)
 #[derive(Error, PartialEq, Eq, Debug, Clone)]
 #[error("Not enough coins for change box(es)")]
-pub struct NotEnoughCoinsForChangeBox(String);
+pub struct NotEnoughCoinsForChangeBox(pub(crate) String);
 
 /// Split change tokens into a multiple boxes if over ErgoBox::MAX_TOKENS_COUNT distinct tokens
-fn make_change_boxes(
+pub(crate) fn make_change_boxes(
     change_value: BoxValue,
     change_tokens: HashMap<TokenId, TokenAmount>,
 ) -> Result<Vec<ErgoBoxAssetsData>, NotEnoughCoinsForChangeBox> {
@@ -303,6 +303,7 @@ mod tests {
         chain::ergo_box::box_builder::{ErgoBoxCandidateBuilder, ErgoBoxCandidateBuilderError},
         wallet::box_selector::{
             arbitrary::{ArbErgoBoxAssetsDataParam, ArbTokensParam},
+            selector_test_harness::check_selector,
             sum_value,
         },
     };
@@ -317,6 +318,11 @@ mod tests {
         assert!(r.is_err());
     }
 
+    #[test]
+    fn test_harness() {
+        check_selector(SimpleBoxSelector::new());
+    }
+
     proptest! {
 
         #[test]
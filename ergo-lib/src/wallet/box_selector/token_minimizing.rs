@@ -0,0 +1,134 @@
+//! Box selector that keeps the tokens not targeted by the transaction out of the change
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use ergotree_ir::chain::ergo_box::box_value::BoxValue;
+use ergotree_ir::chain::token::Token;
+use ergotree_ir::chain::token::TokenAmount;
+use ergotree_ir::chain::token::TokenId;
+
+use super::make_selection;
+use super::select_token_boxes;
+use super::select_value_boxes;
+use super::sum_tokens;
+use super::BoxSelection;
+use super::BoxSelector;
+use super::BoxSelectorError;
+use super::ErgoBoxAssets;
+
+/// Token minimizing box selector.
+/// Prefers the boxes with fewer non-target tokens (the largest first among the boxes with the
+/// same count), so that as few tokens as possible end up in the change.
+#[derive(Default)]
+pub struct TokenMinimizingBoxSelector {}
+
+impl TokenMinimizingBoxSelector {
+    /// Create new instance
+    pub fn new() -> Self {
+        TokenMinimizingBoxSelector {}
+    }
+}
+
+impl<T: ErgoBoxAssets> BoxSelector<T> for TokenMinimizingBoxSelector {
+    fn select(
+        &self,
+        inputs: Vec<T>,
+        target_balance: BoxValue,
+        target_tokens: &[Token],
+    ) -> Result<BoxSelection<T>, BoxSelectorError> {
+        let target_tokens_sum = sum_tokens(Some(target_tokens))?;
+        let mut sorted_inputs = inputs;
+        sorted_inputs.sort_by_key(|b| {
+            (
+                non_target_tokens_count(b, &target_tokens_sum),
+                Reverse(u64::from(b.value())),
+            )
+        });
+        let (mut selected, rest) = select_token_boxes(sorted_inputs, &target_tokens_sum)?;
+        select_value_boxes(&mut selected, rest, target_balance, &target_tokens_sum)?;
+        make_selection(selected, target_balance, target_tokens)
+    }
+}
+
+/// Number of the box tokens not in the target
+fn non_target_tokens_count<T: ErgoBoxAssets>(
+    b: &T,
+    target_tokens: &HashMap<TokenId, TokenAmount>,
+) -> usize {
+    b.tokens()
+        .into_iter()
+        .flatten()
+        .filter(|t| !target_tokens.contains_key(&t.token_id))
+        .count()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used, clippy::panic)]
+mod tests {
+
+    use std::convert::TryInto;
+
+    use ergo_chain_types::Digest32;
+    use ergotree_ir::chain::ergo_box::BoxTokens;
+
+    use crate::wallet::box_selector::selector_test_harness::check_selector;
+    use crate::wallet::box_selector::sum_tokens_from_boxes;
+    use crate::wallet::box_selector::ErgoBoxAssetsData;
+    use crate::wallet::box_selector::SimpleBoxSelector;
+
+    use super::*;
+
+    fn token(id: u8, amount: u64) -> Token {
+        Token {
+            token_id: TokenId::from(Digest32::from([id; 32])),
+            amount: amount.try_into().unwrap(),
+        }
+    }
+
+    fn input(value: u64, tokens: Vec<Token>) -> ErgoBoxAssetsData {
+        ErgoBoxAssetsData {
+            value: value.try_into().unwrap(),
+            tokens: BoxTokens::from_vec(tokens).ok(),
+        }
+    }
+
+    #[test]
+    fn test_harness() {
+        check_selector(TokenMinimizingBoxSelector::new());
+    }
+
+    #[test]
+    fn test_no_foreign_tokens_in_change() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let inputs = vec![
+            input(unit * 10, vec![token(1, 10), token(2, 5)]),
+            input(unit * 10, vec![token(1, 10)]),
+            input(unit * 50, vec![token(3, 5)]),
+            input(unit * 5, vec![]),
+            input(unit * 5, vec![]),
+        ];
+        let target_balance = (unit * 15).try_into().unwrap();
+        let target_tokens = vec![token(1, 10)];
+        let selection = TokenMinimizingBoxSelector::new()
+            .select(inputs.clone(), target_balance, target_tokens.as_slice())
+            .unwrap();
+        assert_eq!(
+            selection.boxes.as_slice(),
+            &[
+                input(unit * 10, vec![token(1, 10)]),
+                input(unit * 5, vec![])
+            ]
+        );
+        assert!(selection.change_boxes.is_empty());
+        // the simple selector picks the boxes with the most target tokens first
+        let simple_selection = SimpleBoxSelector::new()
+            .select(inputs, target_balance, target_tokens.as_slice())
+            .unwrap();
+        assert!(
+            !sum_tokens_from_boxes(simple_selection.change_boxes.as_slice())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -173,6 +173,16 @@ impl DerivationPath {
         self.0.len()
     }
 
+    /// Returns the account index if the path is of the account according to EIP-3
+    /// (`m / 44' / 429' / acc'` or `m / 44' / 429' / acc' / 0`)
+    pub(crate) fn account_index(&self) -> Option<ChildIndexHardened> {
+        match *self.0 {
+            [PURPOSE, ERG, ChildIndex::Hardened(acc)]
+            | [PURPOSE, ERG, ChildIndex::Hardened(acc), CHANGE] => Some(acc),
+            _ => None,
+        }
+    }
+
     /// Extend the path with the given index.
     /// Returns this derivation path with added index.
     pub fn extend(&self, index: ChildIndex) -> DerivationPath {
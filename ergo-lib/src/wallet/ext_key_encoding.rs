@@ -0,0 +1,169 @@
+//! Serialization of the extended keys in base58check format according to
+//! BIP-32 <https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#serialization-format>
+
+use ergotree_ir::chain::address::NetworkPrefix;
+use ripemd::Ripemd160;
+use sha2::Digest;
+use sha2::Sha256;
+use thiserror::Error;
+
+use super::derivation_path::ChildIndex;
+use super::derivation_path::DerivationPath;
+
+/// Key fingerprint (the first 4 bytes of HASH160 of the serialized public key)
+pub type Fingerprint = [u8; 4];
+
+/// Extended key encoding errors
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum ExtKeyEncodingError {
+    /// Invalid base58 string
+    #[error("base58 decoding error: {0}")]
+    Base58(String),
+    /// Unexpected length of the decoded bytes
+    #[error("invalid length: {0}")]
+    InvalidLength(usize),
+    /// Checksum doesn't match
+    #[error("invalid checksum")]
+    InvalidChecksum,
+    /// Version bytes are not of the expected key kind (public or secret) and network
+    #[error("unexpected version bytes: {0:?}")]
+    UnexpectedVersion([u8; 4]),
+    /// Depth or child number do not match the provided derivation path
+    #[error("derivation path {0} does not match depth {1} and child number {2}")]
+    DerivationPathMismatch(DerivationPath, u8, u32),
+    /// Invalid key bytes
+    #[error("invalid key: {0}")]
+    InvalidKey(String),
+    /// Derivation path is deeper than 255 levels and can't be encoded
+    #[error("derivation path depth {0} exceeds 255")]
+    DepthTooLarge(usize),
+}
+
+/// Kind of the extended key (determines the version bytes)
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub(crate) enum ExtKeyKind {
+    Public,
+    Secret,
+}
+
+impl ExtKeyKind {
+    fn version(self, network: NetworkPrefix) -> [u8; 4] {
+        match (self, network) {
+            // xpub
+            (ExtKeyKind::Public, NetworkPrefix::Mainnet) => [0x04, 0x88, 0xb2, 0x1e],
+            // xprv
+            (ExtKeyKind::Secret, NetworkPrefix::Mainnet) => [0x04, 0x88, 0xad, 0xe4],
+            // tpub
+            (ExtKeyKind::Public, NetworkPrefix::Testnet) => [0x04, 0x35, 0x87, 0xcf],
+            // tprv
+            (ExtKeyKind::Secret, NetworkPrefix::Testnet) => [0x04, 0x35, 0x83, 0x94],
+        }
+    }
+}
+
+/// Fields of the serialized extended key
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub(crate) struct ExtKeyFields {
+    pub(crate) network: NetworkPrefix,
+    pub(crate) kind: ExtKeyKind,
+    pub(crate) parent_fingerprint: Fingerprint,
+    pub(crate) chain_code: [u8; 32],
+    /// Serialized public key, or 0x00 followed by the secret key bytes
+    pub(crate) key: [u8; 33],
+}
+
+const PAYLOAD_LEN: usize = 78;
+const CHECKSUM_LEN: usize = 4;
+
+impl ExtKeyFields {
+    /// Encodes as base58check string, depth and child number are taken from the derivation path
+    pub(crate) fn encode(
+        &self,
+        derivation_path: &DerivationPath,
+    ) -> Result<String, ExtKeyEncodingError> {
+        // paths deeper than 255 levels can't be encoded according to BIP-32
+        let depth = u8::try_from(derivation_path.depth())
+            .map_err(|_| ExtKeyEncodingError::DepthTooLarge(derivation_path.depth()))?;
+        let mut bytes = Vec::with_capacity(PAYLOAD_LEN + CHECKSUM_LEN);
+        bytes.extend_from_slice(&self.kind.version(self.network));
+        bytes.push(depth);
+        bytes.extend_from_slice(&self.parent_fingerprint);
+        bytes.extend_from_slice(&child_number(derivation_path).to_be_bytes());
+        bytes.extend_from_slice(&self.chain_code);
+        bytes.extend_from_slice(&self.key);
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        Ok(bs58::encode(bytes).into_string())
+    }
+
+    /// Decodes base58check string of the given key kind and network, the derivation path should
+    /// match the encoded depth and child number
+    pub(crate) fn decode(
+        s: &str,
+        kind: ExtKeyKind,
+        network: NetworkPrefix,
+        derivation_path: &DerivationPath,
+    ) -> Result<Self, ExtKeyEncodingError> {
+        let bytes = bs58::decode(s)
+            .into_vec()
+            .map_err(|e| ExtKeyEncodingError::Base58(e.to_string()))?;
+        if bytes.len() != PAYLOAD_LEN + CHECKSUM_LEN {
+            return Err(ExtKeyEncodingError::InvalidLength(bytes.len()));
+        }
+        let (payload, checksum_bytes) = bytes.split_at(PAYLOAD_LEN);
+        if checksum(payload) != checksum_bytes {
+            return Err(ExtKeyEncodingError::InvalidChecksum);
+        }
+        let mut version = [0u8; 4];
+        version.copy_from_slice(&payload[..4]);
+        if version != kind.version(network) {
+            return Err(ExtKeyEncodingError::UnexpectedVersion(version));
+        }
+        let depth = payload[4];
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&payload[5..9]);
+        let mut child_number_bytes = [0u8; 4];
+        child_number_bytes.copy_from_slice(&payload[9..13]);
+        let child_number_value = u32::from_be_bytes(child_number_bytes);
+        if derivation_path.depth() != depth as usize
+            || child_number(derivation_path) != child_number_value
+        {
+            return Err(ExtKeyEncodingError::DerivationPathMismatch(
+                derivation_path.clone(),
+                depth,
+                child_number_value,
+            ));
+        }
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&payload[13..45]);
+        let mut key = [0u8; 33];
+        key.copy_from_slice(&payload[45..]);
+        Ok(ExtKeyFields {
+            network,
+            kind,
+            parent_fingerprint,
+            chain_code,
+            key,
+        })
+    }
+}
+
+/// Fingerprint of the serialized public key
+pub(crate) fn fingerprint(pub_key_bytes: &[u8]) -> Fingerprint {
+    let hash = Ripemd160::digest(Sha256::digest(pub_key_bytes));
+    let mut res = [0u8; 4];
+    res.copy_from_slice(&hash[..4]);
+    res
+}
+
+/// Index of the last element of the path (0 for the master key)
+fn child_number(derivation_path: &DerivationPath) -> u32 {
+    derivation_path.0.last().map_or(0, ChildIndex::to_bits)
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LEN] {
+    let hash = Sha256::digest(Sha256::digest(bytes));
+    let mut res = [0u8; CHECKSUM_LEN];
+    res.copy_from_slice(&hash[..CHECKSUM_LEN]);
+    res
+}
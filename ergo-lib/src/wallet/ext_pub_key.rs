@@ -4,6 +4,7 @@ use std::convert::TryInto;
 use ergo_chain_types::EcPoint;
 use ergotree_interpreter::sigma_protocol::private_input::DlogProverInput;
 use ergotree_ir::chain::address::Address;
+use ergotree_ir::chain::address::NetworkPrefix;
 use ergotree_ir::serialization::SigmaParsingError;
 use ergotree_ir::serialization::SigmaSerializable;
 use hmac::{Hmac, Mac};
@@ -13,6 +14,11 @@ use thiserror::Error;
 use super::derivation_path::ChildIndex;
 use super::derivation_path::ChildIndexNormal;
 use super::derivation_path::DerivationPath;
+use super::ext_key_encoding::fingerprint;
+use super::ext_key_encoding::ExtKeyEncodingError;
+use super::ext_key_encoding::ExtKeyFields;
+use super::ext_key_encoding::ExtKeyKind;
+use super::ext_key_encoding::Fingerprint;
 
 /// Public key (serialized EcPoint) bytes
 pub type PubKeyBytes = [u8; EcPoint::GROUP_SIZE];
@@ -30,6 +36,7 @@ pub struct ExtPubKey {
     chain_code: ChainCode,
     /// Derivation path for this extended public key
     pub derivation_path: DerivationPath,
+    pub(super) parent_fingerprint: Fingerprint,
 }
 
 /// Extended secret key errors
@@ -53,6 +60,7 @@ impl ExtPubKey {
             public_key,
             chain_code,
             derivation_path,
+            parent_fingerprint: [0; 4],
         })
     }
 
@@ -74,6 +82,16 @@ impl ExtPubKey {
         self.chain_code
     }
 
+    /// Fingerprint of the `ExtPubKey` (identifies it as a parent in the BIP-32 serialization)
+    pub fn fingerprint(&self) -> Fingerprint {
+        fingerprint(&self.pub_key_bytes())
+    }
+
+    /// Fingerprint of the parent key (zeroes for the master key or if unknown)
+    pub fn parent_fingerprint(&self) -> Fingerprint {
+        self.parent_fingerprint
+    }
+
     /// Soft derivation of the child public key with a given index
     #[allow(clippy::unwrap_used)]
     pub fn child(&self, index: ChildIndexNormal) -> Self {
@@ -96,6 +114,7 @@ impl ExtPubKey {
                     public_key: child_pub_key,
                     chain_code,
                     derivation_path: self.derivation_path.extend(index.into()),
+                    parent_fingerprint: self.fingerprint(),
                 }
             }
         } else {
@@ -128,6 +147,34 @@ impl ExtPubKey {
             )))
         }
     }
+
+    /// Serialize in BIP-32 format (base58check encoded xpub for mainnet, tpub for testnet).
+    /// Fails if the derivation path is deeper than 255 levels.
+    pub fn to_base58check(&self, network: NetworkPrefix) -> Result<String, ExtKeyEncodingError> {
+        ExtKeyFields {
+            network,
+            kind: ExtKeyKind::Public,
+            parent_fingerprint: self.parent_fingerprint,
+            chain_code: self.chain_code,
+            key: self.pub_key_bytes(),
+        }
+        .encode(&self.derivation_path)
+    }
+
+    /// Parse from BIP-32 format (base58check encoded xpub for mainnet, tpub for testnet).
+    /// The serialized key has only the depth and the last index of the derivation path, so the
+    /// full path should be provided.
+    pub fn from_base58check(
+        s: &str,
+        network: NetworkPrefix,
+        derivation_path: DerivationPath,
+    ) -> Result<Self, ExtKeyEncodingError> {
+        let fields = ExtKeyFields::decode(s, ExtKeyKind::Public, network, &derivation_path)?;
+        let mut ext_pub_key = ExtPubKey::new(fields.key, fields.chain_code, derivation_path)
+            .map_err(|e| ExtKeyEncodingError::InvalidKey(e.to_string()))?;
+        ext_pub_key.parent_fingerprint = fields.parent_fingerprint;
+        Ok(ext_pub_key)
+    }
 }
 
 impl From<ExtPubKey> for Address {
@@ -247,6 +294,95 @@ mod tests {
         assert_eq!(child.pub_key_bytes(), expected_child_pub_key_bytes);
     }
 
+    #[test]
+    fn bip32_serialization() {
+        // from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+        let master_secret_key_bytes =
+            base16::decode(b"e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35")
+                .unwrap();
+        let chain_code =
+            base16::decode(b"873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508")
+                .unwrap();
+        let master = ExtSecretKey::new(
+            master_secret_key_bytes.try_into().unwrap(),
+            chain_code.try_into().unwrap(),
+            DerivationPath::master_path(),
+        )
+        .unwrap()
+        .public_key()
+        .unwrap();
+        let master_xpub = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+        assert_eq!(
+            master.to_base58check(NetworkPrefix::Mainnet).unwrap(),
+            master_xpub
+        );
+        assert_eq!(
+            ExtPubKey::from_base58check(
+                master_xpub,
+                NetworkPrefix::Mainnet,
+                DerivationPath::master_path()
+            )
+            .unwrap(),
+            master
+        );
+
+        // Chain m/0'/1
+        let path: DerivationPath = "m/0'/1".parse().unwrap();
+        let parent_xpub = "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw";
+        let parent = ExtPubKey::from_base58check(
+            parent_xpub,
+            NetworkPrefix::Mainnet,
+            "m/0'".parse().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(parent.parent_fingerprint(), master.fingerprint());
+        let child = parent.child(ChildIndexNormal::normal(1).unwrap());
+        let child_xpub = "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ";
+        assert_eq!(
+            child.to_base58check(NetworkPrefix::Mainnet).unwrap(),
+            child_xpub
+        );
+        assert_eq!(
+            ExtPubKey::from_base58check(child_xpub, NetworkPrefix::Mainnet, path.clone()).unwrap(),
+            child
+        );
+
+        assert_eq!(
+            ExtPubKey::from_base58check(child_xpub, NetworkPrefix::Testnet, path),
+            Err(ExtKeyEncodingError::UnexpectedVersion([
+                0x04, 0x88, 0xb2, 0x1e
+            ]))
+        );
+        assert!(matches!(
+            ExtPubKey::from_base58check(
+                child_xpub,
+                NetworkPrefix::Mainnet,
+                "m/0'/2".parse().unwrap()
+            ),
+            Err(ExtKeyEncodingError::DerivationPathMismatch(_, 2, 1))
+        ));
+        let tpub = master.to_base58check(NetworkPrefix::Testnet).unwrap();
+        assert_eq!(tpub, "tpubD6NzVbkrYhZ4XgiXtGrdW5XDAPFCL9h7we1vwNCpn8tGbBcgfVYjXyhWo4E1xkh56hjod1RhGjxbaTLV3X4FyWuejifB9jusQ46QzG87VKp");
+        let mut corrupted = tpub.into_bytes();
+        corrupted[50] = if corrupted[50] == b'a' { b'b' } else { b'a' };
+        assert_eq!(
+            ExtPubKey::from_base58check(
+                &String::from_utf8(corrupted).unwrap(),
+                NetworkPrefix::Testnet,
+                DerivationPath::master_path()
+            ),
+            Err(ExtKeyEncodingError::InvalidChecksum)
+        );
+        let too_deep_path = format!("m{}", "/0".repeat(256));
+        assert_eq!(
+            master
+                .derive(too_deep_path.parse().unwrap())
+                .unwrap()
+                .to_base58check(NetworkPrefix::Mainnet),
+            Err(ExtKeyEncodingError::DepthTooLarge(256))
+        );
+    }
+
     #[test]
     fn ergo_node_key_tree_derivation_from_seed() {
         // Tests against the following ergo node test vector:
@@ -3,6 +3,7 @@ use std::convert::TryInto;
 
 use super::{
     derivation_path::{ChildIndex, ChildIndexError, DerivationPath},
+    ext_key_encoding::{fingerprint, ExtKeyEncodingError, ExtKeyFields, ExtKeyKind, Fingerprint},
     ext_pub_key::ExtPubKey,
     mnemonic::MnemonicSeed,
     secret_key::SecretKey,
@@ -10,6 +11,7 @@ use super::{
 use crate::ArrLength;
 use ergotree_interpreter::sigma_protocol::{private_input::DlogProverInput, wscalar::Wscalar};
 use ergotree_ir::{
+    chain::address::NetworkPrefix,
     serialization::{SigmaParsingError, SigmaSerializable, SigmaSerializationError},
     sigma_protocol::sigma_boolean::ProveDlog,
 };
@@ -17,6 +19,8 @@ use hmac::{Hmac, Mac};
 
 use sha2::Sha512;
 use thiserror::Error;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
 
 /// Private key (serialized Scalar) bytes
 pub type SecretKeyBytes = [u8; 32];
@@ -27,14 +31,39 @@ type HmacSha512 = Hmac<Sha512>;
 
 /// Extended secret key
 /// implemented according to BIP-32
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct ExtSecretKey {
     /// The secret key
     private_input: DlogProverInput,
     chain_code: ChainCode,
     derivation_path: DerivationPath,
+    parent_fingerprint: Fingerprint,
 }
 
+impl std::fmt::Debug for ExtSecretKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // to avoid leaking the secret key and chain code in error messages, logs, etc.
+        f.debug_struct("ExtSecretKey")
+            .field("derivation_path", &self.derivation_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Zeroize for ExtSecretKey {
+    fn zeroize(&mut self) {
+        self.private_input.zeroize();
+        self.chain_code.zeroize();
+    }
+}
+
+impl Drop for ExtSecretKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for ExtSecretKey {}
+
 /// Extended secret key errors
 #[derive(Error, PartialEq, Eq, Debug, Clone)]
 pub enum ExtSecretKeyError {
@@ -71,6 +100,7 @@ impl ExtSecretKey {
             private_input,
             chain_code,
             derivation_path,
+            parent_fingerprint: [0; 4],
         })
     }
 
@@ -102,12 +132,19 @@ impl ExtSecretKey {
     /// The extended public key associated with this secret key
     pub fn public_key(&self) -> Result<ExtPubKey, ExtSecretKeyError> {
         #[allow(clippy::unwrap_used)]
-        Ok(ExtPubKey::new(
+        let mut ext_pub_key = ExtPubKey::new(
             // unwrap is safe as it is used on an Infallible result type
             self.public_image_bytes()?.try_into().unwrap(),
             self.chain_code,
             self.derivation_path.clone(),
-        )?)
+        )?;
+        ext_pub_key.parent_fingerprint = self.parent_fingerprint;
+        Ok(ext_pub_key)
+    }
+
+    /// Fingerprint of the parent key (zeroes for the master key or if unknown)
+    pub fn parent_fingerprint(&self) -> Fingerprint {
+        self.parent_fingerprint
     }
 
     /// Derive a child extended secret key using the provided index
@@ -145,11 +182,13 @@ impl ExtSecretKey {
             } else {
                 let mut chain_code = [0; ChainCode::LEN];
                 chain_code.copy_from_slice(&mac_bytes[32..]);
-                ExtSecretKey::new(
+                let mut child = ExtSecretKey::new(
                     child_secret_key.to_bytes(),
                     chain_code,
                     self.derivation_path.extend(index),
-                )
+                )?;
+                child.parent_fingerprint = fingerprint(&self.public_image_bytes()?);
+                Ok(child)
             }
         } else {
             // not in range [0, modulus), thus repeat with next index value (BIP-32)
@@ -181,12 +220,54 @@ impl ExtSecretKey {
         }
     }
 
+    /// Serialize in BIP-32 format (base58check encoded xprv for mainnet, tprv for testnet).
+    /// Fails if the derivation path is deeper than 255 levels.
+    pub fn to_base58check(&self, network: NetworkPrefix) -> Result<String, ExtKeyEncodingError> {
+        let mut key = [0; 33];
+        key[1..].copy_from_slice(&self.secret_key_bytes());
+        ExtKeyFields {
+            network,
+            kind: ExtKeyKind::Secret,
+            parent_fingerprint: self.parent_fingerprint,
+            chain_code: self.chain_code,
+            key,
+        }
+        .encode(&self.derivation_path)
+    }
+
+    /// Parse from BIP-32 format (base58check encoded xprv for mainnet, tprv for testnet).
+    /// The serialized key has only the depth and the last index of the derivation path, so the
+    /// full path should be provided.
+    pub fn from_base58check(
+        s: &str,
+        network: NetworkPrefix,
+        derivation_path: DerivationPath,
+    ) -> Result<Self, ExtKeyEncodingError> {
+        let fields = ExtKeyFields::decode(s, ExtKeyKind::Secret, network, &derivation_path)?;
+        let (prefix, secret_key_bytes) = fields.key.split_at(1);
+        if prefix != [0] {
+            return Err(ExtKeyEncodingError::InvalidKey(
+                "secret key should be prefixed with 0x00".to_string(),
+            ));
+        }
+        #[allow(clippy::unwrap_used)]
+        // unwrap is safe as the slice has 32 bytes
+        let mut ext_secret_key = ExtSecretKey::new(
+            secret_key_bytes.try_into().unwrap(),
+            fields.chain_code,
+            derivation_path,
+        )
+        .map_err(|e| ExtKeyEncodingError::InvalidKey(e.to_string()))?;
+        ext_secret_key.parent_fingerprint = fields.parent_fingerprint;
+        Ok(ext_secret_key)
+    }
+
     /// Derive a root master key from the provided mnemonic seed
     pub fn derive_master(seed: MnemonicSeed) -> Result<ExtSecretKey, ExtSecretKeyError> {
         // Unwrap is safe, we are using a valid static length slice
         #[allow(clippy::unwrap_used)]
         let mut mac = HmacSha512::new_from_slice(ExtSecretKey::BITCOIN_SEED).unwrap();
-        mac.update(&seed);
+        mac.update(seed.as_ref());
         let hash = mac.finalize().into_bytes();
         let mut secret_key_bytes = [0; SecretKeyBytes::LEN];
         secret_key_bytes.copy_from_slice(&hash[..32]);
@@ -402,6 +483,59 @@ mod tests {
         assert_eq!(expected_p2pk, mainnet_p2pk.to_base58());
     }
 
+    #[test]
+    fn bip32_serialization() {
+        // from https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#test-vector-1
+        let secret_key_bytes =
+            base16::decode(b"e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35")
+                .unwrap();
+        let chain_code =
+            base16::decode(b"873dff81c02f525623fd1fe5167eac3a55a049de3d314bb42ee227ffed37d508")
+                .unwrap();
+        let master = ExtSecretKey::new(
+            secret_key_bytes.try_into().unwrap(),
+            chain_code.try_into().unwrap(),
+            DerivationPath::master_path(),
+        )
+        .unwrap();
+        let master_xprv = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi";
+        assert_eq!(
+            master.to_base58check(NetworkPrefix::Mainnet).unwrap(),
+            master_xprv
+        );
+
+        // Chain m/0'/1
+        let path: DerivationPath = "m/0'/1".parse().unwrap();
+        let child = master.derive(path.clone()).unwrap();
+        let child_xprv = "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs";
+        assert_eq!(
+            child.to_base58check(NetworkPrefix::Mainnet).unwrap(),
+            child_xprv
+        );
+        let parsed =
+            ExtSecretKey::from_base58check(child_xprv, NetworkPrefix::Mainnet, path).unwrap();
+        assert_eq!(parsed, child);
+        assert_eq!(
+            parsed.public_key().unwrap().to_base58check(NetworkPrefix::Mainnet).unwrap(),
+            "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ"
+        );
+        // xpub is not accepted as xprv
+        assert!(ExtSecretKey::from_base58check(
+            &parsed
+                .public_key()
+                .unwrap()
+                .to_base58check(NetworkPrefix::Mainnet)
+                .unwrap(),
+            NetworkPrefix::Mainnet,
+            "m/0'/1".parse().unwrap()
+        )
+        .is_err());
+        // secrets are not leaked via Debug
+        let debug = format!("{:?}", child);
+        assert!(!debug.contains(&base16::encode_lower(&child.secret_key_bytes())));
+        assert!(!debug.contains(&base16::encode_lower(&child.chain_code)));
+    }
+
     #[test]
     fn appkit_test_vector() {
         // from https://github.com/ergoplatform/ergo-appkit/blob/b77b6910bb36a26d5d46d41ae3af8ae1167c902c/common/src/test/scala/org/ergoplatform/appkit/AppkitTestingCommon.scala#L4-L21
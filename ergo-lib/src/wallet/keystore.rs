@@ -0,0 +1,352 @@
+//! Encrypted storage of the wallet secret (mnemonic seed) in the Ergo node's JSON format
+//! (PBKDF2 key derivation and AES-GCM cipher)
+
+use aes_gcm::aead::Aead;
+use aes_gcm::Aes256Gcm;
+use aes_gcm::KeyInit;
+use aes_gcm::Nonce;
+use ergo_chain_types::Base16DecodedBytes;
+use hmac::Hmac;
+use pbkdf2::pbkdf2;
+use rand::RngCore;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Sha256;
+use thiserror::Error;
+use zeroize::Zeroizing;
+
+use super::ext_secret_key::ExtSecretKey;
+use super::ext_secret_key::ExtSecretKeyError;
+use super::mnemonic::Mnemonic;
+use super::mnemonic::MnemonicSeed;
+
+/// Keystore errors
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum KeystoreError {
+    /// The secret is requested from the locked storage
+    #[error("secret storage is locked")]
+    Locked,
+    /// Decryption failed (wrong password or corrupted data)
+    #[error("wrong password or corrupted secret")]
+    WrongPassword,
+    /// Unsupported encryption settings
+    #[error("unsupported encryption settings: {0}")]
+    UnsupportedSettings(String),
+    /// Invalid parameters of the encrypted secret
+    #[error("invalid encrypted secret: {0}")]
+    InvalidSecret(String),
+    /// JSON (de)serialization error
+    #[error("JSON error: {0}")]
+    Json(String),
+    /// Master key derivation error
+    #[error("ExtSecretKeyError: {0}")]
+    ExtSecretKeyError(#[from] ExtSecretKeyError),
+}
+
+/// Parameters of the encryption key derivation (PBKDF2)
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+pub struct EncryptionSettings {
+    /// Pseudo-random function (only `HmacSHA256` is supported)
+    pub prf: String,
+    /// Number of PBKDF2 iterations
+    pub c: u32,
+    /// Length of the derived key in bits (only 256 is supported, i.e. AES-256)
+    #[serde(rename = "dkLen")]
+    pub dk_len: u32,
+}
+
+impl EncryptionSettings {
+    const PRF: &'static str = "HmacSHA256";
+    const DK_LEN: u32 = 256;
+}
+
+impl Default for EncryptionSettings {
+    /// Default settings of the Ergo node
+    fn default() -> Self {
+        EncryptionSettings {
+            prf: EncryptionSettings::PRF.to_string(),
+            c: 128000,
+            dk_len: EncryptionSettings::DK_LEN,
+        }
+    }
+}
+
+/// Encrypted secret in the node's format.
+/// As in the node, the first 16 bytes of the cipher output go to `authTag` and the rest (with the
+/// actual GCM tag at the end) go to `cipherText`.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedSecret {
+    cipher_text: Base16DecodedBytes,
+    salt: Base16DecodedBytes,
+    iv: Base16DecodedBytes,
+    auth_tag: Base16DecodedBytes,
+    cipher_params: EncryptionSettings,
+    #[serde(
+        rename = "usePre1627KeyDerivation",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    use_pre_1627_key_derivation: Option<bool>,
+}
+
+impl EncryptedSecret {
+    const SALT_LEN: usize = 32;
+    const IV_LEN: usize = 12;
+    const AUTH_TAG_LEN: usize = 16;
+
+    /// Encrypts the seed with the given password (random salt and iv)
+    fn encrypt(
+        seed: &MnemonicSeed,
+        password: &str,
+        cipher_params: EncryptionSettings,
+        use_pre_1627_key_derivation: Option<bool>,
+    ) -> Result<Self, KeystoreError> {
+        let mut salt = vec![0u8; EncryptedSecret::SALT_LEN];
+        let mut iv = vec![0u8; EncryptedSecret::IV_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+        rand::thread_rng().fill_bytes(&mut iv);
+        let cipher = cipher(password, &salt, &cipher_params)?;
+        let seed_bytes: &[u8] = seed.as_ref();
+        let mut cipher_text = cipher
+            .encrypt(Nonce::from_slice(&iv), seed_bytes)
+            .map_err(|e| KeystoreError::InvalidSecret(e.to_string()))?;
+        let auth_tag: Vec<u8> = cipher_text.drain(..EncryptedSecret::AUTH_TAG_LEN).collect();
+        Ok(EncryptedSecret {
+            cipher_text: Base16DecodedBytes(cipher_text),
+            salt: Base16DecodedBytes(salt),
+            iv: Base16DecodedBytes(iv),
+            auth_tag: Base16DecodedBytes(auth_tag),
+            cipher_params,
+            use_pre_1627_key_derivation,
+        })
+    }
+
+    fn decrypt(&self, password: &str) -> Result<MnemonicSeed, KeystoreError> {
+        if self.iv.0.len() != EncryptedSecret::IV_LEN {
+            return Err(KeystoreError::InvalidSecret(format!(
+                "iv length is {}, expected {}",
+                self.iv.0.len(),
+                EncryptedSecret::IV_LEN
+            )));
+        }
+        let cipher = cipher(password, &self.salt.0, &self.cipher_params)?;
+        let mut data = self.auth_tag.0.clone();
+        data.extend_from_slice(&self.cipher_text.0);
+        let seed_bytes = Zeroizing::new(
+            cipher
+                .decrypt(Nonce::from_slice(&self.iv.0), data.as_slice())
+                .map_err(|_| KeystoreError::WrongPassword)?,
+        );
+        MnemonicSeed::try_from(seed_bytes.as_slice()).map_err(|_| {
+            KeystoreError::InvalidSecret(format!(
+                "seed length is {}, expected {}",
+                seed_bytes.len(),
+                MnemonicSeed::LEN
+            ))
+        })
+    }
+}
+
+/// AES-GCM cipher with the key derived from the password
+fn cipher(
+    password: &str,
+    salt: &[u8],
+    settings: &EncryptionSettings,
+) -> Result<Aes256Gcm, KeystoreError> {
+    if settings.prf != EncryptionSettings::PRF || settings.dk_len != EncryptionSettings::DK_LEN {
+        return Err(KeystoreError::UnsupportedSettings(format!(
+            "prf: {}, dkLen: {}",
+            settings.prf, settings.dk_len
+        )));
+    }
+    let mut key = Zeroizing::new([0u8; EncryptionSettings::DK_LEN as usize / 8]);
+    pbkdf2::<Hmac<Sha256>>(password.as_bytes(), salt, settings.c, key.as_mut());
+    Aes256Gcm::new_from_slice(key.as_ref())
+        .map_err(|e| KeystoreError::UnsupportedSettings(e.to_string()))
+}
+
+/// Secret storage compatible with the node's keystore (`secret.json`).
+/// The seed is kept encrypted and is only available (decrypted) while the storage is unlocked.
+pub struct SecretStorage {
+    encrypted_secret: EncryptedSecret,
+    unlocked_seed: Option<MnemonicSeed>,
+}
+
+impl SecretStorage {
+    /// Encrypts the seed with the given password (with the node's default settings), the storage is
+    /// locked
+    pub fn init(seed: &MnemonicSeed, encryption_pass: &str) -> Result<Self, KeystoreError> {
+        Ok(SecretStorage {
+            encrypted_secret: EncryptedSecret::encrypt(
+                seed,
+                encryption_pass,
+                EncryptionSettings::default(),
+                Some(false),
+            )?,
+            unlocked_seed: None,
+        })
+    }
+
+    /// Restores the seed from the mnemonic phrase (see [`Mnemonic::to_seed`]) and encrypts it with
+    /// the given password, the storage is locked
+    pub fn restore(
+        mnemonic_phrase: &str,
+        mnemonic_pass: &str,
+        encryption_pass: &str,
+    ) -> Result<Self, KeystoreError> {
+        SecretStorage::init(
+            &Mnemonic::to_seed(mnemonic_phrase, mnemonic_pass),
+            encryption_pass,
+        )
+    }
+
+    /// Parse the node's keystore JSON, the storage is locked
+    pub fn from_json(json: &str) -> Result<Self, KeystoreError> {
+        let encrypted_secret: EncryptedSecret =
+            serde_json::from_str(json).map_err(|e| KeystoreError::Json(e.to_string()))?;
+        Ok(SecretStorage {
+            encrypted_secret,
+            unlocked_seed: None,
+        })
+    }
+
+    /// Encrypted secret in the node's keystore JSON format
+    pub fn to_json(&self) -> Result<String, KeystoreError> {
+        serde_json::to_string(&self.encrypted_secret)
+            .map_err(|e| KeystoreError::Json(e.to_string()))
+    }
+
+    /// Encrypted secret
+    pub fn encrypted_secret(&self) -> &EncryptedSecret {
+        &self.encrypted_secret
+    }
+
+    /// Whether the keys were derived with the node's pre-1627 (incorrect) BIP-32 implementation
+    /// (see <https://github.com/ergoplatform/ergo/issues/1627>), which is not supported by
+    /// [`ExtSecretKey`]
+    pub fn use_pre_1627_key_derivation(&self) -> bool {
+        self.encrypted_secret
+            .use_pre_1627_key_derivation
+            .unwrap_or(false)
+    }
+
+    /// Returns true if the secret is not decrypted
+    pub fn is_locked(&self) -> bool {
+        self.unlocked_seed.is_none()
+    }
+
+    /// Decrypts the secret with the given password
+    pub fn unlock(&mut self, encryption_pass: &str) -> Result<(), KeystoreError> {
+        self.unlocked_seed = Some(self.encrypted_secret.decrypt(encryption_pass)?);
+        Ok(())
+    }
+
+    /// Drops the decrypted secret
+    pub fn lock(&mut self) {
+        self.unlocked_seed = None;
+    }
+
+    /// Decrypted seed (only while unlocked)
+    pub fn seed(&self) -> Result<&MnemonicSeed, KeystoreError> {
+        self.unlocked_seed.as_ref().ok_or(KeystoreError::Locked)
+    }
+
+    /// Master key derived from the decrypted seed (only while unlocked)
+    pub fn master_key(&self) -> Result<ExtSecretKey, KeystoreError> {
+        Ok(ExtSecretKey::derive_master(self.seed()?.clone())?)
+    }
+
+    /// Re-encrypts the secret with the new password (and new salt and iv), keeping the lock state
+    pub fn change_password(
+        &mut self,
+        current_pass: &str,
+        new_pass: &str,
+    ) -> Result<(), KeystoreError> {
+        let seed = self.encrypted_secret.decrypt(current_pass)?;
+        self.encrypted_secret = EncryptedSecret::encrypt(
+            &seed,
+            new_pass,
+            self.encrypted_secret.cipher_params.clone(),
+            self.encrypted_secret.use_pre_1627_key_derivation,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "change me do not use me change me do not use me";
+
+    // `secret.json` in the node keystore format with the default node settings and random salt
+    // and iv, encrypted seed of the MNEMONIC (without mnemonic password) with "encryption pass"
+    const SECRET_JSON: &str = include_str!("./keystore/secret.json");
+
+    #[test]
+    fn test_unlock_node_secret() {
+        let mut storage = SecretStorage::from_json(SECRET_JSON).unwrap();
+        assert!(storage.is_locked());
+        assert_eq!(storage.seed(), Err(KeystoreError::Locked));
+        assert_eq!(
+            storage.unlock("wrong pass"),
+            Err(KeystoreError::WrongPassword)
+        );
+        storage.unlock("encryption pass").unwrap();
+        assert!(!storage.is_locked());
+        assert_eq!(storage.seed().unwrap(), &Mnemonic::to_seed(MNEMONIC, ""));
+        assert!(!storage.use_pre_1627_key_derivation());
+        storage.lock();
+        assert!(storage.is_locked());
+        assert!(storage.master_key().is_err());
+    }
+
+    #[test]
+    fn test_json_roundtrip() {
+        let storage = SecretStorage::from_json(SECRET_JSON).unwrap();
+        let json = storage.to_json().unwrap();
+        let parsed = SecretStorage::from_json(&json).unwrap();
+        assert_eq!(parsed.encrypted_secret(), storage.encrypted_secret());
+        // missing in the files of the older node versions
+        let value: serde_json::Value = serde_json::from_str(SECRET_JSON).unwrap();
+        let mut object = value.as_object().unwrap().clone();
+        object.remove("usePre1627KeyDerivation");
+        let storage =
+            SecretStorage::from_json(&serde_json::Value::Object(object).to_string()).unwrap();
+        assert!(!storage.use_pre_1627_key_derivation());
+    }
+
+    #[test]
+    fn test_restore_and_change_password() {
+        let mut storage = SecretStorage::restore(MNEMONIC, "", "pass1").unwrap();
+        assert_eq!(
+            storage.encrypted_secret().cipher_params,
+            EncryptionSettings::default()
+        );
+        storage.unlock("pass1").unwrap();
+        let master_key = storage.master_key().unwrap();
+        assert_eq!(
+            storage.change_password("wrong pass", "pass2"),
+            Err(KeystoreError::WrongPassword)
+        );
+        storage.change_password("pass1", "pass2").unwrap();
+        // still unlocked
+        assert_eq!(storage.master_key().unwrap(), master_key);
+        let mut restored = SecretStorage::from_json(&storage.to_json().unwrap()).unwrap();
+        assert_eq!(restored.unlock("pass1"), Err(KeystoreError::WrongPassword));
+        restored.unlock("pass2").unwrap();
+        assert_eq!(restored.master_key().unwrap(), master_key);
+    }
+
+    #[test]
+    fn test_unsupported_settings() {
+        let json = SECRET_JSON.replace("HmacSHA256", "HmacSHA1");
+        let mut storage = SecretStorage::from_json(&json).unwrap();
+        assert!(matches!(
+            storage.unlock("encryption pass"),
+            Err(KeystoreError::UnsupportedSettings(_))
+        ));
+    }
+}
@@ -1,16 +1,83 @@
 //! Mnemonic operations according to BIP32/BIP39
 
+use std::array::TryFromSliceError;
+use std::convert::TryFrom;
+use std::fmt::Formatter;
+
 use hmac::Hmac;
 use pbkdf2::pbkdf2;
 use sha2::Sha512;
 extern crate unicode_normalization;
 use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
+use zeroize::Zeroizing;
+
+#[cfg(feature = "mnemonic_gen")]
+use bitvec::prelude::*;
+#[cfg(feature = "mnemonic_gen")]
+use sha2::{Digest, Sha256};
+#[cfg(feature = "mnemonic_gen")]
+use thiserror::Error;
+
+#[cfg(feature = "mnemonic_gen")]
+use super::mnemonic_generator::{Language, MnemonicGenerator, MnemonicGeneratorError, WordList};
 
 /// Length of mnemonic seed in bytes
 const SHA512_OUTPUT_LEN: usize = 512 / 8;
 
-/// Mnemonic seed
-pub type MnemonicSeed = [u8; SHA512_OUTPUT_LEN];
+/// Mnemonic seed (zeroized on drop)
+///
+/// Construct it with `From<[u8; 64]>` or `TryFrom<&[u8]>` and read the bytes with
+/// `AsRef<[u8]>`.
+#[derive(PartialEq, Eq, Clone)]
+pub struct MnemonicSeed([u8; SHA512_OUTPUT_LEN]);
+
+impl MnemonicSeed {
+    /// Length of mnemonic seed in bytes
+    pub const LEN: usize = SHA512_OUTPUT_LEN;
+}
+
+impl From<[u8; SHA512_OUTPUT_LEN]> for MnemonicSeed {
+    fn from(bytes: [u8; SHA512_OUTPUT_LEN]) -> Self {
+        MnemonicSeed(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for MnemonicSeed {
+    type Error = TryFromSliceError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; SHA512_OUTPUT_LEN]>::try_from(bytes).map(MnemonicSeed)
+    }
+}
+
+impl AsRef<[u8]> for MnemonicSeed {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for MnemonicSeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // to avoid leaking it in error messages, logs, etc.
+        f.write_str("MnemonicSeed:***")
+    }
+}
+
+impl Zeroize for MnemonicSeed {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Drop for MnemonicSeed {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for MnemonicSeed {}
 
 /// Mnemonic type
 #[derive(PartialEq, Eq, Debug, Clone)]
@@ -22,22 +89,165 @@ impl Mnemonic {
     /// Convert a mnemonic phrase into a mnemonic seed
     /// mnemonic_pass is optional and is used to salt the seed
     pub fn to_seed(mnemonic_phrase: &str, mnemonic_pass: &str) -> MnemonicSeed {
-        let mut seed: MnemonicSeed = [0u8; SHA512_OUTPUT_LEN];
-        let normalized_phrase = mnemonic_phrase.nfkd().collect::<String>();
-        let normalized_pass = mnemonic_pass.nfkd().collect::<String>();
+        let mut seed = MnemonicSeed([0u8; SHA512_OUTPUT_LEN]);
+        let normalized_phrase = Zeroizing::new(mnemonic_phrase.nfkd().collect::<String>());
+        let mut salt = Zeroizing::new("mnemonic".to_string());
+        salt.extend(mnemonic_pass.nfkd());
         pbkdf2::<Hmac<Sha512>>(
             normalized_phrase.as_bytes(),
-            format!("mnemonic{}", normalized_pass).as_bytes(),
+            salt.as_bytes(),
             Mnemonic::PBKDF2_ITERATIONS,
-            &mut seed,
+            &mut seed.0,
         );
 
         seed
     }
 }
 
+/// Mnemonic validation errors
+#[cfg(feature = "mnemonic_gen")]
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
+pub enum MnemonicError {
+    /// Number of words is not one of [`MnemonicGenerator::ALLOWED_SENTENCE_LENS`]
+    #[error("invalid number of words: {0}")]
+    InvalidWordCount(usize),
+    /// Word is not in the wordlist
+    #[error("unknown word {word:?} at position {index}, did you mean one of {suggestions:?}?")]
+    UnknownWord {
+        /// Position of the word in the phrase (starting from 0)
+        index: usize,
+        /// The unknown word
+        word: String,
+        /// Similar words from the wordlist
+        suggestions: Vec<String>,
+    },
+    /// Checksum of the entropy doesn't match the one encoded in the phrase
+    #[error("invalid checksum")]
+    InvalidChecksum,
+    /// Entropy length is not one of the allowed ones
+    #[error("invalid entropy length: {0}")]
+    InvalidEntropyLen(usize),
+}
+
+#[cfg(feature = "mnemonic_gen")]
+impl Mnemonic {
+    /// Maximum number of suggestions for an unknown word
+    pub const MAX_SUGGESTIONS: usize = 5;
+    /// Maximum edit distance between an unknown word and a suggested one
+    const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+    /// Check that every word of the phrase is in the wordlist and the checksum is valid
+    pub fn validate(mnemonic_phrase: &str, lang: Language) -> Result<(), MnemonicError> {
+        Mnemonic::to_entropy(mnemonic_phrase, lang).map(|_| ())
+    }
+
+    /// Convert the entropy to a mnemonic phrase
+    pub fn from_entropy(entropy: &[u8], lang: Language) -> Result<String, MnemonicError> {
+        MnemonicGenerator::new(lang, (entropy.len() * 8) as u32)
+            .from_entrophy(entropy.to_vec())
+            .map_err(|e| match e {
+                MnemonicGeneratorError::InvalidStrength(_) => {
+                    MnemonicError::InvalidEntropyLen(entropy.len())
+                }
+                MnemonicGeneratorError::InvalidEntrophyLen(len) => {
+                    MnemonicError::InvalidEntropyLen(len)
+                }
+            })
+    }
+
+    /// Convert a mnemonic phrase back to the entropy, verifying the checksum
+    pub fn to_entropy(mnemonic_phrase: &str, lang: Language) -> Result<Vec<u8>, MnemonicError> {
+        let normalized_phrase = mnemonic_phrase.nfkd().collect::<String>();
+        // the phrase delimiters (including the japanese one) are whitespaces
+        let phrase_words = normalized_phrase.split_whitespace().collect::<Vec<_>>();
+        if !MnemonicGenerator::ALLOWED_SENTENCE_LENS.contains(&phrase_words.len()) {
+            return Err(MnemonicError::InvalidWordCount(phrase_words.len()));
+        }
+        let words = normalized_words(lang);
+        let mut bits = BitVec::<u8, Msb0>::new();
+        for (index, word) in phrase_words.into_iter().enumerate() {
+            let word_index =
+                words
+                    .iter()
+                    .position(|w| w == word)
+                    .ok_or_else(|| MnemonicError::UnknownWord {
+                        index,
+                        word: word.to_string(),
+                        suggestions: Mnemonic::suggestions(word, lang)
+                            .into_iter()
+                            .map(String::from)
+                            .collect(),
+                    })?;
+            for i in (0..MnemonicGenerator::BITS_GROUP_SIZE).rev() {
+                bits.push((word_index >> i) & 1 == 1);
+            }
+        }
+        // entropy length is a multiple of 32 bits, with 1 checksum bit per each 32 bits
+        let checksum_len = bits.len() / 33;
+        let (entropy_bits, checksum_bits) = bits.split_at(bits.len() - checksum_len);
+        let entropy = entropy_bits
+            .chunks(8)
+            .map(|byte| byte.load_be::<u8>())
+            .collect::<Vec<_>>();
+        let hash = Sha256::digest(&entropy);
+        if checksum_bits.load_be::<u8>() != hash[0] >> (8 - checksum_len) {
+            return Err(MnemonicError::InvalidChecksum);
+        }
+        Ok(entropy)
+    }
+
+    /// Words from the wordlist that are the closest (by edit distance) to the given (possibly
+    /// mistyped) word, at most [`Mnemonic::MAX_SUGGESTIONS`]
+    pub fn suggestions(word: &str, lang: Language) -> Vec<&'static str> {
+        let normalized_word = word.nfkd().collect::<Vec<char>>();
+        let mut candidates = WordList(lang)
+            .words()
+            .into_iter()
+            .map(|w| {
+                (
+                    edit_distance(&normalized_word, &w.nfkd().collect::<Vec<_>>()),
+                    w,
+                )
+            })
+            .filter(|(distance, _)| *distance <= Mnemonic::MAX_SUGGESTION_DISTANCE)
+            .collect::<Vec<_>>();
+        // stable sort keeps the wordlist order for the same distance
+        candidates.sort_by_key(|(distance, _)| *distance);
+        candidates
+            .into_iter()
+            .take(Mnemonic::MAX_SUGGESTIONS)
+            .map(|(_, w)| w)
+            .collect()
+    }
+}
+
+/// Wordlist in the NFKD form (as the normalized phrase)
+#[cfg(feature = "mnemonic_gen")]
+fn normalized_words(lang: Language) -> Vec<String> {
+    WordList(lang)
+        .words()
+        .into_iter()
+        .map(|w| w.nfkd().collect())
+        .collect()
+}
+
+/// Levenshtein distance
+#[cfg(feature = "mnemonic_gen")]
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut prev_row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.iter().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev_row[j] + usize::from(ca != cb);
+            row[j + 1] = substitution.min(prev_row[j + 1] + 1).min(row[j] + 1);
+        }
+        prev_row = row;
+    }
+    prev_row[b.len()]
+}
+
 #[cfg(test)]
-#[allow(clippy::unwrap_used)]
+#[allow(clippy::unwrap_used, clippy::panic)]
 mod tests {
     use super::*;
 
@@ -60,4 +270,127 @@ mod tests {
 
         assert_eq!(encoded_seed, expected);
     }
+
+    #[test]
+    fn test_mnemonic_seed_debug() {
+        let seed = Mnemonic::to_seed("change me do not use me change me do not use me", "");
+        assert_eq!(format!("{:?}", seed), "MnemonicSeed:***");
+        assert_eq!(
+            MnemonicSeed::try_from(seed.as_ref()).unwrap(),
+            MnemonicSeed::from(seed.0)
+        );
+    }
+
+    #[cfg(feature = "mnemonic_gen")]
+    mod validation {
+        use super::super::*;
+        use rand::RngCore;
+
+        const LANGUAGES: [Language; 8] = [
+            Language::ChineseSimplified,
+            Language::ChineseTraditional,
+            Language::English,
+            Language::French,
+            Language::Italian,
+            Language::Japanese,
+            Language::Korean,
+            Language::Spanish,
+        ];
+
+        #[test]
+        fn test_entropy_roundtrip() {
+            for lang in LANGUAGES {
+                for strength in MnemonicGenerator::ALLOWED_STRENGTHS {
+                    let mut entropy = vec![0u8; strength as usize / 8];
+                    rand::thread_rng().fill_bytes(&mut entropy);
+                    let phrase = Mnemonic::from_entropy(&entropy, lang).unwrap();
+                    assert_eq!(Mnemonic::validate(&phrase, lang), Ok(()));
+                    assert_eq!(Mnemonic::to_entropy(&phrase, lang).unwrap(), entropy);
+                }
+            }
+            assert_eq!(
+                Mnemonic::from_entropy(&[0u8; 15], Language::English),
+                Err(MnemonicError::InvalidEntropyLen(15))
+            );
+        }
+
+        #[test]
+        fn test_to_entropy_vectors() {
+            assert_eq!(
+                Mnemonic::to_entropy(
+                    "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                    Language::English
+                )
+                .unwrap(),
+                vec![0x7f; 16]
+            );
+            assert_eq!(
+                Mnemonic::to_entropy(
+                    "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo when",
+                    Language::English
+                )
+                .unwrap(),
+                vec![0xff; 24]
+            );
+            assert_eq!(
+                Mnemonic::to_entropy(
+                    "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+                    Language::Japanese
+                )
+                .unwrap(),
+                vec![0x7f; 16]
+            );
+        }
+
+        #[test]
+        fn test_validate_errors() {
+            assert_eq!(
+                Mnemonic::validate("abandon abandon abandon", Language::English),
+                Err(MnemonicError::InvalidWordCount(3))
+            );
+            assert_eq!(
+                Mnemonic::validate(
+                    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+                    Language::English
+                ),
+                Err(MnemonicError::InvalidChecksum)
+            );
+            // valid English phrase checked against the French wordlist
+            // ("abandon" is also a French word, "about" is not)
+            assert!(matches!(
+                Mnemonic::validate(
+                    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                    Language::French
+                ),
+                Err(MnemonicError::UnknownWord { index: 11, .. })
+            ));
+            let err = Mnemonic::validate(
+                "legal winner thank year wave sausage worth usefull legal winner thank yellow",
+                Language::English,
+            )
+            .unwrap_err();
+            match err {
+                MnemonicError::UnknownWord {
+                    index,
+                    word,
+                    suggestions,
+                } => {
+                    assert_eq!(index, 7);
+                    assert_eq!(word, "usefull");
+                    assert_eq!(suggestions[0], "useful");
+                    assert!(suggestions.len() <= Mnemonic::MAX_SUGGESTIONS);
+                }
+                _ => panic!("unexpected error {:?}", err),
+            }
+        }
+
+        #[test]
+        fn test_suggestions() {
+            assert_eq!(
+                Mnemonic::suggestions("abandn", Language::English),
+                vec!["abandon"]
+            );
+            assert!(Mnemonic::suggestions("qqqqqqqq", Language::English).is_empty());
+        }
+    }
 }
@@ -135,7 +135,7 @@ impl MnemonicGenerator {
     pub const ALLOWED_SENTENCE_LENS: [usize; 5] = [12, 15, 18, 21, 24];
     /// Allowed entrophy strenght for mnemonic generation
     pub const ALLOWED_STRENGTHS: [u32; 5] = [128, 160, 192, 224, 256];
-    const BITS_GROUP_SIZE: usize = 11;
+    pub(crate) const BITS_GROUP_SIZE: usize = 11;
 
     /// Create new MnemonicGenerator instance
     pub fn new(lang: Language, strength: u32) -> Self {
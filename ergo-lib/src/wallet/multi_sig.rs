@@ -3,7 +3,7 @@ use crate::chain::ergo_state_context::ErgoStateContext;
 use crate::chain::transaction::unsigned::UnsignedTransaction;
 use crate::chain::transaction::Transaction;
 use crate::ergotree_interpreter::eval::env::Env;
-use crate::ergotree_interpreter::eval::reduce_to_crypto;
+use crate::ergotree_interpreter::eval::reduce_tree_to_crypto;
 use crate::ergotree_interpreter::sigma_protocol::dht_protocol::interactive_prover as dht_interactive_prover;
 use crate::ergotree_interpreter::sigma_protocol::dlog_protocol::interactive_prover as dlog_interactive_prover;
 use crate::ergotree_interpreter::sigma_protocol::proof_tree::ProofTreeLeaf;
@@ -67,45 +67,19 @@ impl TransactionHintsBag {
 
     /// Replacing Hints for an input index
     pub fn replace_hints_for_input(&mut self, index: usize, hints_bag: HintsBag) {
-        let public: Vec<Hint> = hints_bag
-            .hints
-            .clone()
-            .into_iter()
-            .filter(|hint| matches!(hint, Hint::CommitmentHint(_)))
-            .collect();
-        let secret: Vec<Hint> = hints_bag
-            .hints
-            .into_iter()
-            .filter(|hint| matches!(hint, Hint::SecretProven(_)))
-            .collect();
-
+        let (public, secret) = split_hints(hints_bag);
         self.secret_hints.insert(index, HintsBag { hints: secret });
         self.public_hints.insert(index, HintsBag { hints: public });
     }
 
     /// Adding hints for a input index
     pub fn add_hints_for_input(&mut self, index: usize, hints_bag: HintsBag) {
-        let mut public: Vec<Hint> = hints_bag
-            .hints
-            .clone()
-            .into_iter()
-            .filter(|hint| matches!(hint, Hint::CommitmentHint(_)))
-            .collect();
-        let mut secret: Vec<Hint> = hints_bag
-            .hints
-            .into_iter()
-            .filter(|hint| matches!(hint, Hint::SecretProven(_)))
-            .collect();
-        let secret_bag = HintsBag::empty();
-        let public_bag = HintsBag::empty();
-        let old_secret: &Vec<Hint> = &self.secret_hints.get(&index).unwrap_or(&secret_bag).hints;
-        for hint in old_secret {
-            secret.push(hint.clone());
+        let (mut public, mut secret) = split_hints(hints_bag);
+        if let Some(old_secret) = self.secret_hints.remove(&index) {
+            secret.extend(old_secret.hints);
         }
-
-        let old_public: &Vec<Hint> = &self.public_hints.get(&index).unwrap_or(&public_bag).hints;
-        for hint in old_public {
-            public.push(hint.clone());
+        if let Some(old_public) = self.public_hints.remove(&index) {
+            public.extend(old_public.hints);
         }
         self.secret_hints.insert(index, HintsBag { hints: secret });
         self.public_hints.insert(index, HintsBag { hints: public });
@@ -129,6 +103,14 @@ impl TransactionHintsBag {
     }
 }
 
+/// Split hints into commitments (public) and proofs (secret) without copying them
+fn split_hints(hints_bag: HintsBag) -> (Vec<Hint>, Vec<Hint>) {
+    hints_bag
+        .hints
+        .into_iter()
+        .partition(|hint| matches!(hint, Hint::CommitmentHint(_)))
+}
+
 /// A method which is extracting partial proofs of secret knowledge for particular secrets with their
 /// respective public images given. Useful for distributed signature applications.
 /// See DistributedSigSpecification for examples of usage.
@@ -251,12 +233,7 @@ pub fn generate_commitments(
             .get_input_box(&input.box_id)
             .ok_or(TransactionContextError::InputBoxNotFound(i))?;
         let ctx = Rc::new(make_context(state_context, &tx_context, i)?);
-        let tree = input_box.ergo_tree.clone();
-        let exp = tree
-            .proposition()
-            .map_err(ProverError::ErgoTreeError)
-            .map_err(|e| TxSigningError::ProverError(e, i))?;
-        let reduction_result = reduce_to_crypto(&exp, &Env::empty(), ctx)
+        let reduction_result = reduce_tree_to_crypto(&input_box.ergo_tree, &Env::empty(), ctx)
             .map_err(ProverError::EvalError)
             .map_err(|e| TxSigningError::ProverError(e, i))?;
 
@@ -279,12 +256,7 @@ pub fn extract_hints(
             .get_input_box(&input.box_id)
             .ok_or(TransactionContextError::InputBoxNotFound(i))?;
         let ctx = Rc::new(make_context(state_context, tx_ctx, i)?);
-        let tree = input_box.ergo_tree.clone();
-        let exp = tree
-            .proposition()
-            .map_err(ProverError::ErgoTreeError)
-            .map_err(|e| TxSigningError::ProverError(e, i))?;
-        let reduction_result = reduce_to_crypto(&exp, &Env::empty(), ctx)
+        let reduction_result = reduce_tree_to_crypto(&input_box.ergo_tree, &Env::empty(), ctx)
             .map_err(ProverError::EvalError)
             .map_err(|e| TxSigningError::ProverError(e, i))?;
         let sigma_tree = reduction_result.sigma_prop;
@@ -598,7 +570,7 @@ mod tests {
         hint = bag.hints[1].clone();
         if let Hint::CommitmentHint(CommitmentHint::OwnCommitment(comm)) = hint {
             assert_eq!(comm.position, NodePosition::crypto_tree_prefix());
-            r = Some(comm.secret_randomness);
+            r = Some(comm.secret_randomness.clone());
         }
         use ergo_chain_types::ec_point::{exponentiate, generator};
         let g_to_r = exponentiate(&generator(), r.unwrap().as_scalar_ref());
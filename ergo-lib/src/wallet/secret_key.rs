@@ -11,6 +11,8 @@ use ergotree_ir::mir::constant::Constant;
 use ergotree_ir::mir::expr::Expr;
 use ergotree_ir::serialization::SigmaSerializable;
 use thiserror::Error;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
 
 /// Types of secrets
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
@@ -111,6 +113,18 @@ impl SecretKey {
     }
 }
 
+impl Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        match self {
+            SecretKey::DlogSecretKey(dlog) => dlog.zeroize(),
+            SecretKey::DhtSecretKey(dht) => dht.zeroize(),
+        }
+    }
+}
+
+// secrets of both variants are zeroized on drop
+impl ZeroizeOnDrop for SecretKey {}
+
 /// Error type for SecretKey parsing
 #[allow(missing_docs)]
 #[derive(Error, Eq, PartialEq, Debug, Clone)]
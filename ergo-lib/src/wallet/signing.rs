@@ -159,6 +159,8 @@ pub fn make_context<T: ErgoTransaction>(
         pre_header: state_ctx.pre_header.clone(),
         extension,
         headers: state_ctx.headers.clone(),
+        cost_limit: None,
+        validation_settings: state_ctx.validation_settings.clone(),
     })
 }
 
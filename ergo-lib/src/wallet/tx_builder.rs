@@ -15,6 +15,8 @@ use ergotree_interpreter::sigma_protocol::prover::ProofBytes;
 use ergotree_ir::chain::address::Address;
 use ergotree_ir::chain::ergo_box::box_value::BoxValue;
 use ergotree_ir::chain::ergo_box::BoxId;
+use ergotree_ir::chain::ergo_box::BoxTokens;
+use ergotree_ir::chain::ergo_box::ErgoBox;
 use ergotree_ir::chain::ergo_box::ErgoBoxCandidate;
 use ergotree_ir::chain::token::Token;
 use ergotree_ir::chain::token::TokenId;
@@ -30,7 +32,10 @@ use super::box_selector::subtract_tokens;
 use super::box_selector::sum_tokens_from_boxes;
 use super::box_selector::sum_value;
 use super::box_selector::BoxSelection;
+use super::box_selector::BoxSelector;
+use super::box_selector::BoxSelectorError;
 use super::box_selector::ErgoBoxAssets;
+use super::box_selector::ErgoBoxAssetsData;
 use super::box_selector::ErgoBoxId;
 use super::miner_fee::MINERS_FEE_BASE16_BYTES;
 
@@ -48,6 +53,10 @@ pub struct TxBuilder<S: ErgoBoxAssets> {
 }
 
 impl<S: ErgoBoxAssets + ErgoBoxId + Clone> TxBuilder<S> {
+    /// Limit of the selection attempts in [`TxBuilder::select_inputs`] (each time the change boxes
+    /// or the fee require more coins)
+    pub const MAX_SELECTION_ATTEMPTS: usize = 10;
+
     /// Creates new TxBuilder
     /// `box_selection` - selected input boxes  (via [`super::box_selector::BoxSelector`])
     /// `output_candidates` - output boxes to be "created" in this transaction,
@@ -74,6 +83,149 @@ impl<S: ErgoBoxAssets + ErgoBoxId + Clone> TxBuilder<S> {
         }
     }
 
+    /// Selects the inputs (via `box_selector`) to cover the outputs and the miner's fee and
+    /// creates the builder.
+    /// Unlike the change boxes of [`BoxSelection`], the change is split into as many boxes as
+    /// needed to hold at most [`ErgoBox::MAX_TOKENS_COUNT`] tokens and fit into
+    /// [`ErgoBox::MAX_BOX_SIZE`] each, with the box value not less than the minimal value for the
+    /// box size (see [`ErgoBoxCandidateBuilder::calc_min_box_value`]). Extra inputs are selected
+    /// to cover these values and the fee for the estimated transaction size (see
+    /// [`TxBuilder::estimate_tx_size_bytes`]) if it's set per byte.
+    /// A token minted in the outputs (with the id of the first of the `inputs`) is not selected,
+    /// and the first of the `inputs` is put first in the selected boxes.
+    pub fn select_inputs<B: BoxSelector<S>>(
+        box_selector: &B,
+        inputs: Vec<S>,
+        output_candidates: Vec<ErgoBoxCandidate>,
+        current_height: u32,
+        fee: TxFee,
+        change_address: Address,
+    ) -> Result<TxBuilder<S>, TxBuilderError> {
+        let change_address_ergo_tree = Contract::pay_to_address(&change_address)?.ergo_tree();
+        let outputs_value = sum_value(output_candidates.as_slice());
+        let output_tokens = sum_tokens_from_boxes(output_candidates.as_slice())
+            .map_err(TxBuilderError::TooManyTokensInOutputCandidates)?;
+        // the id of a minted token is the id of the first input box
+        let minting_input = inputs
+            .first()
+            .filter(|b| output_tokens.contains_key(&TokenId::from(b.box_id())));
+        let target_tokens: Vec<Token> = output_tokens
+            .into_iter()
+            .filter(|(id, _)| minting_input.map_or(true, |b| TokenId::from(b.box_id()) != *id))
+            .map(Token::from)
+            .collect();
+        let mut fee_amount = match fee {
+            TxFee::Fixed(fee_amount) => fee_amount,
+            TxFee::PerByte(_) => BoxValue::SAFE_USER_MIN,
+        };
+        // value added to the target to cover the minimal values of the change boxes
+        let mut change_extra: u64 = 0;
+        for _ in 0..Self::MAX_SELECTION_ATTEMPTS {
+            let target_balance =
+                BoxValue::try_from(outputs_value + fee_amount.as_u64() + change_extra)
+                    .map_err(|e| TxBuilderError::InvalidArgs(format!("target balance: {}", e)))?;
+            let selection =
+                match box_selector.select(inputs.clone(), target_balance, target_tokens.as_slice())
+                {
+                    Ok(selection) => selection,
+                    // the change boxes are re-made below, ask the selector for more coins
+                    Err(BoxSelectorError::NotEnoughCoinsForChangeBox(_)) => {
+                        change_extra += max_min_change_value(
+                            inputs.as_slice(),
+                            target_tokens.as_slice(),
+                            &change_address_ergo_tree,
+                            current_height,
+                        )?;
+                        continue;
+                    }
+                    Err(e) => return Err(e.into()),
+                };
+            let mut boxes = selection.boxes.as_vec().clone();
+            let mut selection_change = selection.change_boxes;
+            if let Some(minting_input) = minting_input {
+                match boxes
+                    .iter()
+                    .position(|b| b.box_id() == minting_input.box_id())
+                {
+                    Some(idx) => boxes[..=idx].rotate_right(1),
+                    None => {
+                        boxes.insert(0, minting_input.clone());
+                        selection_change.push(ErgoBoxAssetsData {
+                            value: minting_input.value(),
+                            tokens: minting_input.tokens(),
+                        });
+                    }
+                }
+            }
+            let change_value = sum_value(selection_change.as_slice()) + change_extra;
+            let change_tokens: Vec<Token> = sum_tokens_from_boxes(selection_change.as_slice())
+                .map_err(TxBuilderError::TooManyTokensInInputBoxes)?
+                .into_iter()
+                .map(Token::from)
+                .collect();
+            let change_boxes = if change_value == 0 && change_tokens.is_empty() {
+                vec![]
+            } else {
+                let change_box_tokens =
+                    split_change_tokens(change_tokens, &change_address_ergo_tree, current_height)?;
+                let min_change_value: u64 = change_box_tokens
+                    .iter()
+                    .map(|(_, min_value)| *min_value.as_u64())
+                    .sum();
+                if change_value < min_change_value {
+                    change_extra += min_change_value - change_value;
+                    continue;
+                }
+                // the last box gets the rest of the change
+                let last_idx = change_box_tokens.len() - 1;
+                change_box_tokens
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, (tokens, min_value))| {
+                        let value = if idx == last_idx {
+                            BoxValue::try_from(min_value.as_u64() + change_value - min_change_value)
+                                .map_err(|e| {
+                                    TxBuilderError::InvalidArgs(format!("change box value: {}", e))
+                                })?
+                        } else {
+                            min_value
+                        };
+                        Ok(ErgoBoxAssetsData {
+                            value,
+                            tokens: BoxTokens::from_vec(tokens).ok(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, TxBuilderError>>()?
+            };
+            let boxes_len = boxes.len();
+            let tx_builder = TxBuilder::new(
+                BoxSelection {
+                    boxes: boxes
+                        .try_into()
+                        .map_err(|_| BoxSelectorError::SelectedInputsOutOfBounds(boxes_len))?,
+                    change_boxes,
+                },
+                output_candidates.clone(),
+                current_height,
+                fee_amount,
+                change_address.clone(),
+            );
+            if let TxFee::PerByte(fee_per_byte) = fee {
+                let tx_size = tx_builder.estimate_tx_size_bytes()? as u64;
+                let required_fee = fee_per_byte.saturating_mul(tx_size);
+                if required_fee > *fee_amount.as_u64() {
+                    fee_amount = BoxValue::try_from(required_fee)
+                        .map_err(|e| TxBuilderError::InvalidArgs(format!("fee: {}", e)))?;
+                    continue;
+                }
+            }
+            return Ok(tx_builder);
+        }
+        Err(TxBuilderError::SelectionAttemptsExceeded(
+            Self::MAX_SELECTION_ATTEMPTS,
+        ))
+    }
+
     /// Get inputs
     pub fn box_selection(&self) -> BoxSelection<S> {
         self.box_selection.clone()
@@ -266,6 +418,82 @@ pub fn SUGGESTED_TX_FEE() -> BoxValue {
     BoxValue::new(1100000u64).unwrap()
 }
 
+/// Miner's fee for [`TxBuilder::select_inputs`]
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TxFee {
+    /// Fixed amount
+    Fixed(BoxValue),
+    /// nanoERGs per byte of the estimated signed transaction size, but not less than
+    /// [`BoxValue::SAFE_USER_MIN`]
+    PerByte(u64),
+}
+
+/// Splits the change tokens into boxes (guarded by the given tree) of at most
+/// [`ErgoBox::MAX_TOKENS_COUNT`] tokens that fit into [`ErgoBox::MAX_BOX_SIZE`], along with the
+/// minimal value for each box size. Returns a single box without tokens if there are none.
+fn split_change_tokens(
+    tokens: Vec<Token>,
+    ergo_tree: &ErgoTree,
+    creation_height: u32,
+) -> Result<Vec<(Vec<Token>, BoxValue)>, TxBuilderError> {
+    let box_builder = |tokens: &[Token]| {
+        // the largest value to get the upper bound of the box size
+        #[allow(clippy::unwrap_used)]
+        let value = BoxValue::new(BoxValue::MAX_RAW).unwrap();
+        let mut box_builder =
+            ErgoBoxCandidateBuilder::new(value, ergo_tree.clone(), creation_height);
+        tokens
+            .iter()
+            .for_each(|token| box_builder.add_token(token.clone()));
+        box_builder
+    };
+    let mut boxes_tokens: Vec<Vec<Token>> = vec![];
+    let mut box_tokens: Vec<Token> = vec![];
+    for token in tokens {
+        box_tokens.push(token);
+        let fits = box_tokens.len() <= ErgoBox::MAX_TOKENS_COUNT
+            && box_builder(box_tokens.as_slice()).calc_box_size_bytes()? <= ErgoBox::MAX_BOX_SIZE;
+        if !fits && box_tokens.len() > 1 {
+            if let Some(token) = box_tokens.pop() {
+                boxes_tokens.push(std::mem::replace(&mut box_tokens, vec![token]));
+            }
+        }
+    }
+    boxes_tokens.push(box_tokens);
+    boxes_tokens
+        .into_iter()
+        .map(|tokens| {
+            let min_value = box_builder(tokens.as_slice()).calc_min_box_value()?;
+            Ok((tokens, min_value))
+        })
+        .collect()
+}
+
+/// The minimal value of the change boxes for all the tokens of the `inputs` not in the
+/// `target_tokens`, i.e. the upper bound for any selection of the `inputs`
+fn max_min_change_value<S: ErgoBoxAssets>(
+    inputs: &[S],
+    target_tokens: &[Token],
+    ergo_tree: &ErgoTree,
+    creation_height: u32,
+) -> Result<u64, TxBuilderError> {
+    let input_tokens =
+        sum_tokens_from_boxes(inputs).map_err(TxBuilderError::TooManyTokensInInputBoxes)?;
+    let target_tokens = vec_tokens_to_map(target_tokens.to_vec())
+        .map_err(TxBuilderError::TooManyTokensInOutputCandidates)?;
+    let change_tokens = subtract_tokens(&input_tokens, &target_tokens)
+        .map_err(TxBuilderError::TokensInOutputsExceedInputs)?
+        .into_iter()
+        .map(Token::from)
+        .collect();
+    Ok(
+        split_change_tokens(change_tokens, ergo_tree, creation_height)?
+            .iter()
+            .map(|(_, min_value)| *min_value.as_u64())
+            .sum(),
+    )
+}
+
 /// Create a box with miner's contract and a given value
 #[allow(clippy::unwrap_used)]
 pub fn new_miner_fee_box(
@@ -314,6 +542,10 @@ pub enum TxBuilderError {
     TokensInOutputsExceedInputs(TokenAmountError),
     #[error("Coins in outputs are less than coins in inputs for {0} nanoERGs")]
     NotEnoughCoinsInOutputs(u64),
+    #[error("Box selection error: {0}")]
+    BoxSelectorError(#[from] BoxSelectorError),
+    #[error("Change boxes and fee are not covered after {0} selection attempts")]
+    SelectionAttemptsExceeded(usize),
 }
 
 /// Sums up the tokens into a hash map
@@ -381,6 +613,7 @@ mod tests {
 
     use std::convert::TryInto;
 
+    use ergo_chain_types::Digest32;
     use ergotree_ir::chain::ergo_box::box_value::checked_sum;
     use ergotree_ir::chain::ergo_box::ErgoBox;
     use ergotree_ir::chain::ergo_box::NonMandatoryRegisters;
@@ -822,6 +1055,170 @@ mod tests {
         assert!(tx_builder.estimate_tx_size_bytes().unwrap() > 0);
     }
 
+    /// Boxes with 100 distinct tokens each (and 1 token of each box in the output) plus a box
+    /// without tokens
+    fn many_tokens_inputs_and_outputs(
+        token_box_value: u64,
+        output_value: u64,
+    ) -> (Vec<ErgoBox>, Vec<ErgoBoxCandidate>) {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let token = |box_idx: u8, token_idx: u8, amount: u64| {
+            let mut id_bytes = [0u8; 32];
+            id_bytes[0] = box_idx;
+            id_bytes[1] = token_idx;
+            Token {
+                token_id: TokenId::from(Digest32::from(id_bytes)),
+                amount: amount.try_into().unwrap(),
+            }
+        };
+        let new_box = |value: u64, tokens: Vec<Token>| {
+            ErgoBox::new(
+                value.try_into().unwrap(),
+                force_any_val::<ErgoTree>(),
+                BoxTokens::from_vec(tokens).ok(),
+                NonMandatoryRegisters::empty(),
+                1,
+                force_any_val::<TxId>(),
+                0,
+            )
+            .unwrap()
+        };
+        let mut inputs: Vec<ErgoBox> = (0..3u8)
+            .map(|box_idx| {
+                new_box(
+                    token_box_value,
+                    (0..100u8)
+                        .map(|token_idx| token(box_idx, token_idx, 10))
+                        .collect(),
+                )
+            })
+            .collect();
+        inputs.push(new_box(unit * 100, vec![]));
+        let mut box_builder = ErgoBoxCandidateBuilder::new(
+            output_value.try_into().unwrap(),
+            force_any_val::<ErgoTree>(),
+            0,
+        );
+        (0..3u8).for_each(|box_idx| box_builder.add_token(token(box_idx, 0, 1)));
+        (inputs, vec![box_builder.build().unwrap()])
+    }
+
+    #[test]
+    fn test_select_inputs_split_change() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        // the change of the token boxes is not enough for the change boxes with 300 tokens
+        let (inputs, outputs) = many_tokens_inputs_and_outputs(unit * 10, unit * 28);
+        let tx_builder = TxBuilder::select_inputs(
+            &SimpleBoxSelector::new(),
+            inputs.clone(),
+            outputs,
+            1,
+            TxFee::Fixed(SUGGESTED_TX_FEE()),
+            force_any_val::<Address>(),
+        )
+        .unwrap();
+        let selected_boxes = tx_builder.box_selection().boxes;
+        assert_eq!(selected_boxes.len(), 4);
+        let change_boxes = tx_builder.box_selection().change_boxes;
+        assert!(change_boxes.len() > 1);
+        assert_eq!(
+            sum_tokens_from_boxes(change_boxes.as_slice()).unwrap(),
+            subtract_tokens(
+                &sum_tokens_from_boxes(inputs.as_slice()).unwrap(),
+                &sum_tokens_from_boxes(tx_builder.output_candidates().as_slice()).unwrap()
+            )
+            .unwrap()
+        );
+        let change_ergo_tree = Contract::pay_to_address(&tx_builder.change_address())
+            .unwrap()
+            .ergo_tree();
+        let tx = tx_builder.build().unwrap();
+        let tx_change_boxes: Vec<&ErgoBoxCandidate> = tx
+            .output_candidates
+            .iter()
+            .filter(|b| b.ergo_tree == change_ergo_tree && b.tokens.is_some())
+            .collect();
+        assert_eq!(tx_change_boxes.len(), change_boxes.len());
+        assert!(tx_change_boxes.iter().all(|b| {
+            b.tokens.as_ref().unwrap().len() <= ErgoBox::MAX_TOKENS_COUNT
+                && b.sigma_serialize_bytes().unwrap().len() <= ErgoBox::MAX_BOX_SIZE
+        }));
+    }
+
+    #[test]
+    fn test_select_inputs_mint_token() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let new_box = || {
+            ErgoBox::new(
+                (unit * 10).try_into().unwrap(),
+                force_any_val::<ErgoTree>(),
+                None,
+                NonMandatoryRegisters::empty(),
+                1,
+                force_any_val::<TxId>(),
+                0,
+            )
+            .unwrap()
+        };
+        let inputs = vec![new_box(), new_box()];
+        let mint_output = |input_box: &ErgoBox| {
+            let mut box_builder = ErgoBoxCandidateBuilder::new(
+                BoxValue::SAFE_USER_MIN,
+                force_any_val::<ErgoTree>(),
+                0,
+            );
+            box_builder.mint_token(
+                Token {
+                    token_id: TokenId::from(input_box.box_id()),
+                    amount: 1.try_into().unwrap(),
+                },
+                "TKN".to_string(),
+                "token desc".to_string(),
+                2,
+            );
+            box_builder.build().unwrap()
+        };
+        let select_inputs = |outputs| {
+            TxBuilder::select_inputs(
+                &SimpleBoxSelector::new(),
+                inputs.clone(),
+                outputs,
+                1,
+                TxFee::Fixed(SUGGESTED_TX_FEE()),
+                force_any_val::<Address>(),
+            )
+        };
+        let tx_builder = select_inputs(vec![mint_output(&inputs[0])]).unwrap();
+        assert!(tx_builder.build().is_ok());
+        // only the first input box id can be used for a new token
+        assert!(matches!(
+            select_inputs(vec![mint_output(&inputs[1])]),
+            Err(TxBuilderError::BoxSelectorError(
+                BoxSelectorError::NotEnoughTokens(_)
+            ))
+        ));
+    }
+
+    #[test]
+    fn test_select_inputs_fee_per_byte() {
+        let unit = *BoxValue::SAFE_USER_MIN.as_u64();
+        let (inputs, outputs) = many_tokens_inputs_and_outputs(unit * 10, unit * 10);
+        let fee_per_byte = 1000;
+        let tx_builder = TxBuilder::select_inputs(
+            &SimpleBoxSelector::new(),
+            inputs,
+            outputs,
+            1,
+            TxFee::PerByte(fee_per_byte),
+            force_any_val::<Address>(),
+        )
+        .unwrap();
+        let tx_size = tx_builder.estimate_tx_size_bytes().unwrap() as u64;
+        assert!(*tx_builder.fee_amount().as_u64() >= fee_per_byte * tx_size);
+        assert!(*tx_builder.fee_amount().as_u64() > *BoxValue::SAFE_USER_MIN.as_u64());
+        assert!(tx_builder.build().is_ok());
+    }
+
     proptest! {
 
         #![proptest_config(ProptestConfig::with_cases(16))]
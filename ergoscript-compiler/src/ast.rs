@@ -31,8 +31,104 @@ impl Root {
         }
     }
 
-    pub fn children(&self) -> impl Iterator<Item = Expr> {
-        self.0.children().filter_map(Expr::cast)
+    pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+        self.0.children().filter_map(Stmt::cast)
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub enum Stmt {
+    VariableDef(VariableDef),
+    FunctionDef(FunctionDef),
+    Expr(Expr),
+}
+
+impl Stmt {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        match node.kind() {
+            SyntaxKind::VariableDef => Some(Self::VariableDef(VariableDef(node))),
+            SyntaxKind::FunctionDef => Some(Self::FunctionDef(FunctionDef(node))),
+            _ => Expr::cast(node).map(Self::Expr),
+        }
+    }
+}
+
+/// `val name: Type = rhs` (type is optional)
+#[derive(Debug)]
+pub struct VariableDef(SyntaxNode);
+
+impl VariableDef {
+    pub fn name(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+            .ok_or_else(|| {
+                AstError::new(
+                    format!("Empty VariableDef.name in: {:?}", self.0),
+                    self.span(),
+                )
+            })
+    }
+
+    pub fn tpe(&self) -> Option<Type> {
+        self.0.children().find_map(Type::cast)
+    }
+
+    pub fn rhs(&self) -> Result<Expr, AstError> {
+        self.0
+            .children()
+            .find_map(Expr::cast)
+            .ok_or_else(|| AstError::new(format!("Cannot find value of {:?}", self.0), self.span()))
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+/// `def name(param: Type, ...): Type = body` (result type is optional)
+#[derive(Debug)]
+pub struct FunctionDef(SyntaxNode);
+
+impl FunctionDef {
+    pub fn name(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+            .ok_or_else(|| {
+                AstError::new(
+                    format!("Empty FunctionDef.name in: {:?}", self.0),
+                    self.span(),
+                )
+            })
+    }
+
+    pub fn params(&self) -> impl Iterator<Item = Param> {
+        self.0
+            .children()
+            .filter(|node| node.kind() == SyntaxKind::ParamList)
+            .flat_map(|params| params.children())
+            .filter_map(Param::cast)
+    }
+
+    /// Declared result type
+    pub fn tpe(&self) -> Option<Type> {
+        self.0.children().find_map(Type::cast)
+    }
+
+    pub fn body(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(
+                format!("Cannot find function body in {:?}", self.0),
+                self.span(),
+            )
+        })
     }
 
     pub fn span(&self) -> TextRange {
@@ -63,8 +159,15 @@ pub enum Expr {
     Ident(Ident),
     BinaryExpr(BinaryExpr),
     Literal(Literal),
-    // ParenExpr(ParenExpr),
-    // UnaryExpr(UnaryExpr),
+    ParenExpr(ParenExpr),
+    UnaryExpr(UnaryExpr),
+    IfExpr(IfExpr),
+    SelectExpr(SelectExpr),
+    CallExpr(CallExpr),
+    ApplyTypesExpr(ApplyTypesExpr),
+    LambdaExpr(LambdaExpr),
+    TupleExpr(TupleExpr),
+    BlockExpr(BlockExpr),
 }
 
 impl Expr {
@@ -74,8 +177,18 @@ impl Expr {
             SyntaxKind::InfixExpr => Self::BinaryExpr(BinaryExpr(node)),
             SyntaxKind::IntNumber => Self::Literal(Literal(node)),
             SyntaxKind::LongNumber => Self::Literal(Literal(node)),
-            // SyntaxKind::ParenExpr => Self::ParenExpr(ParenExpr(node)),
-            // SyntaxKind::PrefixExpr => Self::UnaryExpr(UnaryExpr(node)),
+            SyntaxKind::StringLiteral => Self::Literal(Literal(node)),
+            SyntaxKind::TrueKw => Self::Literal(Literal(node)),
+            SyntaxKind::FalseKw => Self::Literal(Literal(node)),
+            SyntaxKind::ParenExpr => Self::ParenExpr(ParenExpr(node)),
+            SyntaxKind::PrefixExpr => Self::UnaryExpr(UnaryExpr(node)),
+            SyntaxKind::IfExpr => Self::IfExpr(IfExpr(node)),
+            SyntaxKind::SelectExpr => Self::SelectExpr(SelectExpr(node)),
+            SyntaxKind::CallExpr => Self::CallExpr(CallExpr(node)),
+            SyntaxKind::ApplyTypesExpr => Self::ApplyTypesExpr(ApplyTypesExpr(node)),
+            SyntaxKind::LambdaExpr => Self::LambdaExpr(LambdaExpr(node)),
+            SyntaxKind::TupleExpr => Self::TupleExpr(TupleExpr(node)),
+            SyntaxKind::BlockExpr => Self::BlockExpr(BlockExpr(node)),
             _ => return None,
         };
 
@@ -127,7 +240,16 @@ impl BinaryExpr {
                         | SyntaxKind::Minus
                         | SyntaxKind::Star
                         | SyntaxKind::Slash
-                        | SyntaxKind::And,
+                        | SyntaxKind::Percent
+                        | SyntaxKind::EqualsEquals
+                        | SyntaxKind::NotEquals
+                        | SyntaxKind::Less
+                        | SyntaxKind::LessEquals
+                        | SyntaxKind::Greater
+                        | SyntaxKind::GreaterEquals
+                        | SyntaxKind::And
+                        | SyntaxKind::Or
+                        | SyntaxKind::Caret,
                 )
             })
             .ok_or_else(|| {
@@ -147,6 +269,8 @@ impl BinaryExpr {
 pub enum LiteralValue {
     Int(i32),
     Long(i64),
+    Bool(bool),
+    Str(String),
 }
 
 #[derive(Debug)]
@@ -155,14 +279,20 @@ pub struct Literal(SyntaxNode);
 impl Literal {
     pub fn parse(&self) -> Result<LiteralValue, AstError> {
         let text = self.0.first_token().unwrap().text().to_string();
-        if text.ends_with('L') {
-            text.strip_suffix('L')
+        match self.0.kind() {
+            SyntaxKind::TrueKw => Some(LiteralValue::Bool(true)),
+            SyntaxKind::FalseKw => Some(LiteralValue::Bool(false)),
+            SyntaxKind::StringLiteral => text
+                .strip_prefix('"')
+                .and_then(|t| t.strip_suffix('"'))
+                .map(|t| LiteralValue::Str(t.to_string())),
+            _ if text.ends_with('L') => text
+                .strip_suffix('L')
                 .unwrap()
                 .parse()
                 .ok()
-                .map(LiteralValue::Long)
-        } else {
-            text.parse().ok().map(LiteralValue::Int)
+                .map(LiteralValue::Long),
+            _ => text.parse().ok().map(LiteralValue::Int),
         }
         .ok_or_else(|| {
             AstError::new(
@@ -177,27 +307,283 @@ impl Literal {
     }
 }
 
-// #[derive(Debug)]
-// pub struct ParenExpr(SyntaxNode);
+#[derive(Debug)]
+pub struct ParenExpr(SyntaxNode);
+
+impl ParenExpr {
+    pub fn expr(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(
+                format!("Empty parenthesized expression: {:?}", self.0),
+                self.span(),
+            )
+        })
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct TupleExpr(SyntaxNode);
+
+impl TupleExpr {
+    pub fn items(&self) -> impl Iterator<Item = Expr> {
+        self.0.children().filter_map(Expr::cast)
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+/// `{ stmt; ...; expr }`
+#[derive(Debug)]
+pub struct BlockExpr(SyntaxNode);
+
+impl BlockExpr {
+    pub fn stmts(&self) -> impl Iterator<Item = Stmt> {
+        self.0.children().filter_map(Stmt::cast)
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct UnaryExpr(SyntaxNode);
+
+impl UnaryExpr {
+    pub fn expr(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(format!("Cannot find operand in {:?}", self.0), self.span())
+        })
+    }
+
+    pub fn op(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| matches!(token.kind(), SyntaxKind::Minus | SyntaxKind::Bang))
+            .ok_or_else(|| {
+                AstError::new(format!("Cannot find unary op in {:?}", self.0), self.span())
+            })
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct IfExpr(SyntaxNode);
+
+impl IfExpr {
+    pub fn condition(&self) -> Result<Expr, AstError> {
+        self.nth_expr(0, "condition")
+    }
+
+    pub fn then_branch(&self) -> Result<Expr, AstError> {
+        self.nth_expr(1, "then branch")
+    }
+
+    pub fn else_branch(&self) -> Result<Expr, AstError> {
+        self.nth_expr(2, "else branch")
+    }
+
+    fn nth_expr(&self, n: usize, what: &str) -> Result<Expr, AstError> {
+        self.0
+            .children()
+            .filter_map(Expr::cast)
+            .nth(n)
+            .ok_or_else(|| {
+                AstError::new(format!("Cannot find {} in {:?}", what, self.0), self.span())
+            })
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct SelectExpr(SyntaxNode);
+
+impl SelectExpr {
+    pub fn obj(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(
+                format!("Cannot find select receiver in {:?}", self.0),
+                self.span(),
+            )
+        })
+    }
+
+    pub fn field(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+            .ok_or_else(|| {
+                AstError::new(
+                    format!("Cannot find field name in {:?}", self.0),
+                    self.span(),
+                )
+            })
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct CallExpr(SyntaxNode);
+
+impl CallExpr {
+    pub fn func(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(format!("Cannot find callee in {:?}", self.0), self.span())
+        })
+    }
+
+    pub fn args(&self) -> impl Iterator<Item = Expr> {
+        self.0
+            .children()
+            .filter(|node| node.kind() == SyntaxKind::ArgList)
+            .flat_map(|args| args.children())
+            .filter_map(Expr::cast)
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct ApplyTypesExpr(SyntaxNode);
 
-// impl ParenExpr {
-//     pub fn expr(&self) -> Option<Expr> {
-//         self.0.children().find_map(Expr::cast)
-//     }
-// }
+impl ApplyTypesExpr {
+    pub fn expr(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(
+                format!("Cannot find expression in {:?}", self.0),
+                self.span(),
+            )
+        })
+    }
+
+    pub fn type_args(&self) -> impl Iterator<Item = Type> {
+        type_args(&self.0)
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
 
-// #[derive(Debug)]
-// pub struct UnaryExpr(SyntaxNode);
+#[derive(Debug)]
+pub struct LambdaExpr(SyntaxNode);
 
-// impl UnaryExpr {
-//     pub fn expr(&self) -> Option<Expr> {
-//         self.0.children().find_map(Expr::cast)
-//     }
+impl LambdaExpr {
+    pub fn params(&self) -> impl Iterator<Item = Param> {
+        self.0
+            .children()
+            .filter(|node| node.kind() == SyntaxKind::ParamList)
+            .flat_map(|params| params.children())
+            .filter_map(Param::cast)
+    }
 
-//     pub fn op(&self) -> Option<SyntaxToken> {
-//         self.0
-//             .children_with_tokens()
-//             .filter_map(SyntaxElement::into_token)
-//             .find(|token| token.kind() == SyntaxKind::Minus)
-//     }
-// }
+    pub fn body(&self) -> Result<Expr, AstError> {
+        self.0.children().find_map(Expr::cast).ok_or_else(|| {
+            AstError::new(
+                format!("Cannot find lambda body in {:?}", self.0),
+                self.span(),
+            )
+        })
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct Param(SyntaxNode);
+
+impl Param {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if node.kind() == SyntaxKind::Param {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+            .ok_or_else(|| AstError::new(format!("Empty Param.name in: {:?}", self.0), self.span()))
+    }
+
+    pub fn tpe(&self) -> Result<Type, AstError> {
+        self.0
+            .children()
+            .find_map(Type::cast)
+            .ok_or_else(|| AstError::new(format!("Cannot find type of {:?}", self.0), self.span()))
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+#[derive(Debug)]
+pub struct Type(SyntaxNode);
+
+impl Type {
+    pub fn cast(node: SyntaxNode) -> Option<Self> {
+        if matches!(node.kind(), SyntaxKind::Type | SyntaxKind::TupleType) {
+            Some(Self(node))
+        } else {
+            None
+        }
+    }
+
+    pub fn name(&self) -> Result<SyntaxToken, AstError> {
+        self.0
+            .children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .find(|token| token.kind() == SyntaxKind::Ident)
+            .ok_or_else(|| AstError::new(format!("Empty Type.name in: {:?}", self.0), self.span()))
+    }
+
+    pub fn type_args(&self) -> impl Iterator<Item = Type> {
+        type_args(&self.0)
+    }
+
+    /// Item types if this is a tuple type, e.g. `(Int, Long)`
+    pub fn tuple_items(&self) -> Option<impl Iterator<Item = Type>> {
+        if self.0.kind() == SyntaxKind::TupleType {
+            Some(self.0.children().filter_map(Type::cast))
+        } else {
+            None
+        }
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.0.text_range()
+    }
+}
+
+/// Types in the `TypeArgList` child of the given node
+fn type_args(node: &SyntaxNode) -> impl Iterator<Item = Type> {
+    node.children()
+        .filter(|node| node.kind() == SyntaxKind::TypeArgList)
+        .flat_map(|args| args.children())
+        .filter_map(Type::cast)
+}
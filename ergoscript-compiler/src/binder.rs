@@ -1,10 +1,26 @@
+use ergotree_ir::chain::address::Address;
+use ergotree_ir::chain::address::AddressEncoder;
+use ergotree_ir::mir::constant::Constant;
+use ergotree_ir::mir::constant::ConstantPlaceholder;
+use ergotree_ir::serialization::SigmaSerializable;
+use ergotree_ir::types::stype::SType;
 use rowan::TextRange;
+use std::cell::RefCell;
 
 use crate::error::pretty_error_desc;
 use crate::hir;
+use crate::hir::map_children_infallible;
+use crate::hir::Apply;
+use crate::hir::ApplyTypes;
+use crate::hir::Block;
 use crate::hir::Expr;
 use crate::hir::ExprKind;
 use crate::hir::GlobalVars;
+use crate::hir::Lambda;
+use crate::hir::Literal;
+use crate::hir::PredefFunc;
+use crate::hir::PredefFuncCall;
+use crate::hir::Val;
 use crate::script_env::ScriptEnv;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -21,41 +37,310 @@ impl BinderError {
     pub fn pretty_desc(&self, source: &str) -> String {
         pretty_error_desc(source, self.span, &self.msg)
     }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.span
+    }
 }
 
 pub struct Binder {
     env: ScriptEnv,
+    /// Env values bound as placeholders, the index in the list is the placeholder id
+    placeholders: Option<RefCell<Vec<(String, Constant)>>>,
 }
 
 impl Binder {
     pub fn new(env: ScriptEnv) -> Self {
-        Binder { env }
+        Binder {
+            env,
+            placeholders: None,
+        }
+    }
+
+    /// Binds env values as [`ConstantPlaceholder`]s numbered in the order of the first use
+    pub fn with_placeholders(env: ScriptEnv) -> Self {
+        Binder {
+            env,
+            placeholders: Some(RefCell::new(Vec::new())),
+        }
     }
 
     pub fn bind(&self, expr: Expr) -> Result<Expr, BinderError> {
-        rewrite(expr, &self.env)
-    }
-}
-
-fn rewrite(expr: Expr, env: &ScriptEnv) -> Result<Expr, BinderError> {
-    hir::rewrite(expr, |e| {
-        Ok(match &e.kind {
-            ExprKind::Ident(ident) => match env.get(ident) {
-                Some(_) => todo!(),
-                None => match ident.as_ref() {
-                    "HEIGHT" => {
-                        let v = GlobalVars::Height;
-                        let tpe = v.tpe();
-                        Some(Expr {
-                            kind: v.into(),
-                            span: e.span,
-                            tpe: tpe.into(),
-                        })
-                    }
-                    _ => None,
-                },
+        let (bound, errors) = self.bind_partial(expr);
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(bound),
+        }
+    }
+
+    /// Binds the tree collecting all the errors, erroneous expressions are left unbound
+    pub fn bind_partial(&self, expr: Expr) -> (Expr, Vec<BinderError>) {
+        let mut errors = Vec::new();
+        let bound = self.bind_in(expr, &mut Vec::new(), &mut errors);
+        (bound, errors)
+    }
+
+    /// Binds the tree top-down, `locals` are the lambda arguments and values in scope (innermost
+    /// last) along with the flag whether the value is already defined at this point.
+    /// Locals shadow the env values, global variables and predefined functions.
+    fn bind_in(
+        &self,
+        e: Expr,
+        locals: &mut Vec<(String, bool)>,
+        errors: &mut Vec<BinderError>,
+    ) -> Expr {
+        match &e.kind {
+            ExprKind::Ident(ident) => match locals.iter().rev().find(|(name, _)| name == ident) {
+                Some((_, true)) => e,
+                Some((_, false)) => {
+                    errors.push(BinderError::new(
+                        format!(
+                            "{0} is used before its definition (recursive definitions are not supported)",
+                            ident
+                        ),
+                        e.span,
+                    ));
+                    e
+                }
+                None => self.bind_ident(ident, e.span).unwrap_or(e),
             },
+            ExprKind::Apply(apply) => {
+                let calls_local = func_name(&apply.func)
+                    .map_or(false, |func| locals.iter().any(|(name, _)| name == func));
+                let e = map_children_infallible(e, |child| self.bind_in(child, locals, errors));
+                match &e.kind {
+                    ExprKind::Apply(apply) if !calls_local => match bind_apply(&e, apply) {
+                        Ok(Some(bound)) => bound,
+                        Ok(None) => e,
+                        Err(error) => {
+                            errors.push(error);
+                            e
+                        }
+                    },
+                    _ => e,
+                }
+            }
+            ExprKind::Lambda(lambda) => {
+                let lambda = lambda.clone();
+                let scope_len = locals.len();
+                locals.extend(lambda.args.iter().map(|(name, _)| (name.clone(), true)));
+                let body = self.bind_in(*lambda.body, locals, errors);
+                locals.truncate(scope_len);
+                let body = lambda
+                    .args
+                    .iter()
+                    .fold(body, |body, (name, tpe)| hir::bind_refs(body, name, tpe));
+                Expr {
+                    kind: Lambda {
+                        args: lambda.args,
+                        body: body.into(),
+                    }
+                    .into(),
+                    ..e
+                }
+            }
+            ExprKind::Block(block) => {
+                let block = block.clone();
+                if let Some(dup) = block
+                    .vals
+                    .iter()
+                    .enumerate()
+                    .find(|(i, val)| block.vals[..*i].iter().any(|v| v.name == val.name))
+                    .map(|(_, val)| val)
+                {
+                    errors.push(BinderError::new(
+                        format!("{0} is already defined in this block", dup.name),
+                        dup.span,
+                    ));
+                }
+                let scope_len = locals.len();
+                locals.extend(block.vals.iter().map(|val| (val.name.clone(), false)));
+                let block = self.bind_block(block, locals, scope_len, errors);
+                locals.truncate(scope_len);
+                Expr {
+                    kind: block.into(),
+                    ..e
+                }
+            }
+            _ => map_children_infallible(e, |child| self.bind_in(child, locals, errors)),
+        }
+    }
+
+    /// Values are in `locals` starting from `first`, each one is marked as defined after its
+    /// right-hand side is bound
+    fn bind_block(
+        &self,
+        block: Block,
+        locals: &mut Vec<(String, bool)>,
+        first: usize,
+        errors: &mut Vec<BinderError>,
+    ) -> Block {
+        let mut vals = Vec::with_capacity(block.vals.len());
+        for (i, val) in block.vals.into_iter().enumerate() {
+            let rhs = self.bind_in(*val.rhs, locals, errors);
+            locals[first + i].1 = true;
+            vals.push(Val {
+                rhs: rhs.into(),
+                ..val
+            });
+        }
+        Block {
+            vals,
+            body: self.bind_in(*block.body, locals, errors).into(),
+        }
+    }
+
+    fn bind_ident(&self, ident: &str, span: TextRange) -> Option<Expr> {
+        match self.env.get(ident) {
+            Some(c) => Some(Expr {
+                kind: self.env_value(ident, c).into(),
+                span,
+                tpe: Some(c.tpe.clone()),
+            }),
+            None => global_var(ident).map(|v| {
+                let tpe = v.tpe();
+                Expr {
+                    kind: v.into(),
+                    span,
+                    tpe: tpe.into(),
+                }
+            }),
+        }
+    }
+
+    /// Env values bound as placeholders so far (placeholder id is the index in the list)
+    pub fn into_placeholders(self) -> Vec<(String, Constant)> {
+        self.placeholders
+            .map(RefCell::into_inner)
+            .unwrap_or_default()
+    }
+
+    fn env_value(&self, ident: &str, c: &Constant) -> Literal {
+        match &self.placeholders {
+            Some(placeholders) => {
+                let mut placeholders = placeholders.borrow_mut();
+                let id = match placeholders.iter().position(|(name, _)| name == ident) {
+                    Some(id) => id,
+                    None => {
+                        placeholders.push((ident.to_string(), c.clone()));
+                        placeholders.len() - 1
+                    }
+                };
+                Literal::ConstPlaceholder(ConstantPlaceholder {
+                    id: id as u32,
+                    tpe: c.tpe.clone(),
+                })
+            }
+            None => Literal::Constant(c.clone()),
+        }
+    }
+}
+
+/// Resolves the application of a predefined function. Functions with string literal arguments
+/// (e.g. `PK("...")`, `fromBase16("...")`) are evaluated at compile time.
+fn bind_apply(e: &Expr, apply: &Apply) -> Result<Option<Expr>, BinderError> {
+    let (name, tpe_args) = match (&apply.func.kind, func_name(&apply.func)) {
+        (ExprKind::ApplyTypes(ApplyTypes { tpes, .. }), Some(name)) => (name, tpes.clone()),
+        (_, Some(name)) => (name, Vec::new()),
+        _ => return Ok(None),
+    };
+    if let Some(c) = eval_const_func(name, &tpe_args, &apply.args, e.span)? {
+        return Ok(Some(Expr {
+            tpe: Some(c.tpe.clone()),
+            kind: Literal::Constant(c).into(),
+            span: e.span,
+        }));
+    }
+    Ok(PredefFunc::from_name(name).map(|func| Expr {
+        kind: PredefFuncCall {
+            func,
+            tpe_args,
+            args: apply.args.clone(),
+        }
+        .into(),
+        ..e.clone()
+    }))
+}
+
+fn eval_const_func(
+    name: &str,
+    tpe_args: &[SType],
+    args: &[Expr],
+    span: TextRange,
+) -> Result<Option<Constant>, BinderError> {
+    if !matches!(
+        name,
+        "PK" | "fromBase16" | "fromBase58" | "fromBase64" | "deserialize"
+    ) {
+        return Ok(None);
+    }
+    let err = |msg: String| BinderError::new(format!("{0}: {1}", name, msg), span);
+    let arg = match args {
+        [Expr {
+            kind: ExprKind::Literal(Literal::Str(s)),
+            ..
+        }] => s.as_str(),
+        _ => return Err(err("expected a single string literal argument".to_string())),
+    };
+    let c: Constant = match (name, tpe_args) {
+        ("PK", []) => {
+            match AddressEncoder::unchecked_parse_address_from_str(arg)
+                .map_err(|e| err(e.to_string()))?
+            {
+                Address::P2Pk(prove_dlog) => prove_dlog.into(),
+                _ => return Err(err("expected P2PK address".to_string())),
+            }
+        }
+        ("fromBase16", []) => base16::decode(arg).map_err(|e| err(e.to_string()))?.into(),
+        ("fromBase58", []) => bs58::decode(arg)
+            .into_vec()
+            .map_err(|e| err(e.to_string()))?
+            .into(),
+        ("fromBase64", []) => base64::decode(arg).map_err(|e| err(e.to_string()))?.into(),
+        ("deserialize", [tpe]) => {
+            let bytes = bs58::decode(arg)
+                .into_vec()
+                .map_err(|e| err(e.to_string()))?;
+            let c = Constant::sigma_parse_bytes(&bytes).map_err(|e| err(e.to_string()))?;
+            if c.tpe != *tpe {
+                return Err(err(format!(
+                    "expected value of type {0:?}, got {1:?}",
+                    tpe, c.tpe
+                )));
+            }
+            c
+        }
+        _ => return Err(err(format!("unexpected type arguments {0:?}", tpe_args))),
+    };
+    Ok(Some(c))
+}
+
+/// Name of the applied function, e.g. `f` in `f(x)` or `f[Int](x)`
+fn func_name(func: &Expr) -> Option<&str> {
+    match &func.kind {
+        ExprKind::Ident(name) => Some(name),
+        ExprKind::ApplyTypes(ApplyTypes { expr, .. }) => match &expr.kind {
+            ExprKind::Ident(name) => Some(name),
             _ => None,
-        })
-    })
+        },
+        _ => None,
+    }
+}
+
+fn global_var(ident: &str) -> Option<GlobalVars> {
+    match ident {
+        "HEIGHT" => Some(GlobalVars::Height),
+        "SELF" => Some(GlobalVars::SelfBox),
+        "INPUTS" => Some(GlobalVars::Inputs),
+        "OUTPUTS" => Some(GlobalVars::Outputs),
+        "CONTEXT" => Some(GlobalVars::Context),
+        "minerPubKey" => Some(GlobalVars::MinerPubKey),
+        "groupGenerator" => Some(GlobalVars::GroupGenerator),
+        "LastBlockUtxoRootHash" => Some(GlobalVars::LastBlockUtxoRootHash),
+        _ => None,
+    }
 }
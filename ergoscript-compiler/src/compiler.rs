@@ -10,12 +10,14 @@ use crate::parser::parse_error::ParseError;
 use crate::script_env::ScriptEnv;
 use crate::type_infer::assign_type;
 use crate::type_infer::TypeInferenceError;
+use std::collections::HashMap;
 use std::convert::TryInto;
 
 extern crate derive_more;
 use derive_more::From;
 use ergotree_ir::ergo_tree::ErgoTree;
 use ergotree_ir::ergo_tree::ErgoTreeError;
+use ergotree_ir::ergo_tree::ErgoTreeVersion;
 use ergotree_ir::type_check::TypeCheckError;
 use mir::lower::MirLoweringError;
 
@@ -60,13 +62,7 @@ pub fn compile_expr(
     source: &str,
     env: ScriptEnv,
 ) -> Result<ergotree_ir::mir::expr::Expr, CompileError> {
-    let hir = compile_hir(source)?;
-    let binder = Binder::new(env);
-    let bind = binder.bind(hir)?;
-    let typed = assign_type(bind)?;
-    let mir = mir::lower::lower(typed)?;
-    let res = ergotree_ir::type_check::type_check(mir)?;
-    Ok(res)
+    compile_expr_with(source, &Binder::new(env))
 }
 
 /// Compiles given source code to [`ErgoTree`], or returns an error
@@ -75,6 +71,39 @@ pub fn compile(source: &str, env: ScriptEnv) -> Result<ErgoTree, CompileError> {
     Ok(expr.try_into()?)
 }
 
+/// Compiles given source code to a constant-segregated [`ErgoTree`] where the values from the
+/// `env` are referred by
+/// [`ergotree_ir::mir::constant::ConstantPlaceholder`]s, so that they can be replaced later
+/// with [`ErgoTree::with_constant`] without recompiling.
+/// Returns the tree along with the constant index for every env identifier used in the script.
+pub fn compile_with_placeholders(
+    source: &str,
+    env: ScriptEnv,
+) -> Result<(ErgoTree, HashMap<String, usize>), CompileError> {
+    let binder = Binder::with_placeholders(env);
+    let expr = compile_expr_with(source, &binder)?;
+    let (names, constants): (Vec<String>, Vec<_>) = binder.into_placeholders().into_iter().unzip();
+    let tree = ErgoTree::with_segregated_constants(ErgoTreeVersion::V0, &expr, constants)?;
+    let indices = names
+        .into_iter()
+        .enumerate()
+        .map(|(idx, name)| (name, idx))
+        .collect();
+    Ok((tree, indices))
+}
+
+fn compile_expr_with(
+    source: &str,
+    binder: &Binder,
+) -> Result<ergotree_ir::mir::expr::Expr, CompileError> {
+    let hir = compile_hir(source)?;
+    let bind = binder.bind(hir)?;
+    let typed = assign_type(bind)?;
+    let mir = mir::lower::lower(typed)?;
+    let res = ergotree_ir::type_check::type_check(mir)?;
+    Ok(res)
+}
+
 pub(crate) fn compile_hir(source: &str) -> Result<hir::Expr, CompileError> {
     let parse = super::parser::parse(source);
     if !parse.errors.is_empty() {
@@ -99,6 +128,7 @@ fn check(input: &str, expected_tree: expect_test::Expect) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ergotree_ir::mir::constant::Constant;
     use expect_test::expect;
 
     #[test]
@@ -115,12 +145,234 @@ mod tests {
     #[test]
     fn test_parser_error() {
         check(
-            "HSB.HEIGHT",
+            "HSB@HEIGHT",
             expect![[r#"
-                error: expected ‘+’, ‘-’, ‘*’, ‘/’, ‘val’, number, number, identifier, ‘-’ or ‘(’, but found an unrecognized token
+                error: expected ‘.’, ‘(’, ‘[’, ‘{’, ‘+’, ‘-’, ‘*’, ‘/’, ‘%’, ‘==’, ‘!=’, ‘<’, ‘<=’, ‘>’, ‘>=’, ‘&&’, ‘||’, ‘^’, ‘;’, ‘val’, ‘def’, number, number, string, identifier, ‘true’, ‘false’, ‘-’, ‘!’, ‘(’, ‘if’ or ‘{’, but found an unrecognized token
                 line: 1
-                HSB.HEIGHT
-                  ^^"#]],
+                HSB@HEIGHT
+                   ^"#]],
+        );
+    }
+
+    #[test]
+    fn test_error_at_start() {
+        check(
+            "foo + 1",
+            expect![[r#"
+            unknown identifier `foo`
+            line: 1
+            foo + 1
+            ^^^"#]],
+        );
+        check(
+            "1 + true",
+            expect![[r#"
+            Invalid operand types for Plus: Int and Boolean
+            line: 1
+            1 + true
+            ^^^^^^^^"#]],
+        );
+        check(
+            "5 ^ 3 == 6",
+            expect![[r#"
+            Invalid operand types for Xor: Int and Boolean
+            line: 1
+            5 ^ 3 == 6
+            ^^^^^^^^^^"#]],
+        );
+        check(
+            r#"deserialize[Int]("AQ") > 0"#,
+            expect![[r#"
+            deserialize: expected value of type SInt, got SByte
+            line: 1
+            deserialize[Int]("AQ") > 0
+            ^^^^^^^^^^^^^^^^^^^^^^"#]],
+        );
+    }
+
+    #[test]
+    fn test_duplicate_val_error() {
+        check(
+            "{ val a = 1; val a = 2; a }",
+            expect![[r#"
+            a is already defined in this block
+            line: 1
+            { val a = 1; val a = 2; a }
+                             ^"#]],
+        );
+    }
+
+    #[test]
+    fn test_type_error() {
+        assert!(matches!(
+            compile_expr("HEIGHT + true", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+        assert!(matches!(
+            compile_expr("if (HEIGHT) 1 else 2", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+        assert!(matches!(
+            compile_expr("OUTPUTS.exists { (b: Box) => b.value }", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+        assert!(matches!(
+            compile_expr("val x: Long = 1\nx", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+        assert!(matches!(
+            compile_expr("SELF.R4[Int].getOrElse(0L)", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+        assert!(matches!(
+            compile_expr("Coll(1, 2L)", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+    }
+
+    #[test]
+    fn test_sigma_prop_conjectures() {
+        let tree = compile(
+            r#"PK("9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE") && sigmaProp(HEIGHT > 5)"#,
+            ScriptEnv::new(),
+        )
+        .unwrap();
+        assert!(matches!(
+            tree.proposition().unwrap(),
+            ergotree_ir::mir::expr::Expr::SigmaAnd(_)
+        ));
+        let tree = compile(
+            r#"PK("9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE") || HEIGHT > 5"#,
+            ScriptEnv::new(),
+        )
+        .unwrap();
+        assert!(matches!(
+            tree.proposition().unwrap(),
+            ergotree_ir::mir::expr::Expr::SigmaOr(_)
+        ));
+    }
+
+    #[test]
+    fn test_scope_error() {
+        assert!(matches!(
+            compile_expr("def f(x: Int): Int = f(x) + 1\nf(1)", ScriptEnv::new()),
+            Err(CompileError::BinderError(_))
+        ));
+        assert!(matches!(
+            compile_expr("{ val a = b; val b = 1; a }", ScriptEnv::new()),
+            Err(CompileError::BinderError(_))
+        ));
+        assert!(matches!(
+            compile_expr("{ val a = 1; val a = 2; a }", ScriptEnv::new()),
+            Err(CompileError::BinderError(_))
+        ));
+        // local definition shadows the predefined function
+        assert!(matches!(
+            compile_expr("def max(x: Int, y: Int) = x\nmax(1L, 2L)", ScriptEnv::new()),
+            Err(CompileError::TypeInferenceError(_))
+        ));
+    }
+
+    #[test]
+    fn test_env_constants() {
+        let env = ScriptEnv::new()
+            .with("deadline", 100i32)
+            .with("unused", true);
+        assert_eq!(
+            compile_expr("HEIGHT > deadline", env).unwrap(),
+            compile_expr("HEIGHT > 100", ScriptEnv::new()).unwrap()
+        );
+        // local value shadows the env value
+        let env = ScriptEnv::new().with("deadline", 100i32);
+        assert_eq!(
+            compile_expr("val deadline = 5\nHEIGHT > deadline", env).unwrap(),
+            compile_expr("val deadline = 5\nHEIGHT > deadline", ScriptEnv::new()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compile_with_placeholders() {
+        let source = "sigmaProp(HEIGHT > deadline && HEIGHT < deadline + 1000)";
+        let env = ScriptEnv::new()
+            .with("deadline", 100i32)
+            .with("unused", true);
+        let (tree, indices) = compile_with_placeholders(source, env).unwrap();
+        assert_eq!(indices, HashMap::from([("deadline".to_string(), 0)]));
+        assert_eq!(
+            tree.get_constants().unwrap(),
+            vec![Constant::from(100i32), Constant::from(1000i32)]
+        );
+        let expected = compile(source, ScriptEnv::new().with("deadline", 500i32)).unwrap();
+        let tree = tree.with_constant(0, 500i32.into()).unwrap();
+        assert_eq!(tree.proposition().unwrap(), expected.proposition().unwrap());
+    }
+
+    #[test]
+    fn test_spending_condition() {
+        check(
+            "HEIGHT > 100 || !(HEIGHT <= 5)",
+            expect![[r#"
+                BinOp(
+                    Spanned {
+                        source_span: SourceSpan {
+                            offset: 0,
+                            length: 0,
+                        },
+                        expr: BinOp {
+                            kind: Logical(
+                                Or,
+                            ),
+                            left: BinOp(
+                                Spanned {
+                                    source_span: SourceSpan {
+                                        offset: 0,
+                                        length: 0,
+                                    },
+                                    expr: BinOp {
+                                        kind: Relation(
+                                            Gt,
+                                        ),
+                                        left: GlobalVars(
+                                            Height,
+                                        ),
+                                        right: Const(
+                                            "100: SInt",
+                                        ),
+                                    },
+                                },
+                            ),
+                            right: LogicalNot(
+                                Spanned {
+                                    source_span: SourceSpan {
+                                        offset: 0,
+                                        length: 0,
+                                    },
+                                    expr: LogicalNot {
+                                        input: BinOp(
+                                            Spanned {
+                                                source_span: SourceSpan {
+                                                    offset: 0,
+                                                    length: 0,
+                                                },
+                                                expr: BinOp {
+                                                    kind: Relation(
+                                                        Le,
+                                                    ),
+                                                    left: GlobalVars(
+                                                        Height,
+                                                    ),
+                                                    right: Const(
+                                                        "5: SInt",
+                                                    ),
+                                                },
+                                            },
+                                        ),
+                                    },
+                                },
+                            ),
+                        },
+                    },
+                )"#]],
         );
     }
 }
@@ -0,0 +1,889 @@
+//! ErgoTree decompiler to ErgoScript source
+
+use std::collections::HashMap;
+
+extern crate derive_more;
+use derive_more::From;
+use ergotree_ir::chain::address::Address;
+use ergotree_ir::chain::address::AddressEncoder;
+use ergotree_ir::chain::address::NetworkPrefix;
+use ergotree_ir::ergo_tree::ErgoTree;
+use ergotree_ir::ergo_tree::ErgoTreeError;
+use ergotree_ir::mir::bin_op::ArithOp;
+use ergotree_ir::mir::bin_op::BinOp;
+use ergotree_ir::mir::bin_op::BinOpKind;
+use ergotree_ir::mir::bin_op::BitOp;
+use ergotree_ir::mir::bin_op::LogicalOp;
+use ergotree_ir::mir::bin_op::RelationOp;
+use ergotree_ir::mir::block::BlockValue;
+use ergotree_ir::mir::collection::Collection;
+use ergotree_ir::mir::constant::Constant;
+use ergotree_ir::mir::constant::ConstantPlaceholder;
+use ergotree_ir::mir::constant::Literal;
+use ergotree_ir::mir::constant::TryExtractInto;
+use ergotree_ir::mir::expr::Expr;
+use ergotree_ir::mir::func_value::FuncArg;
+use ergotree_ir::mir::func_value::FuncValue;
+use ergotree_ir::mir::global_vars::GlobalVars;
+use ergotree_ir::mir::method_call::MethodCall;
+use ergotree_ir::mir::property_call::PropertyCall;
+use ergotree_ir::mir::select_field::SelectField;
+use ergotree_ir::mir::val_def::ValId;
+use ergotree_ir::serialization::SigmaSerializable;
+use ergotree_ir::serialization::SigmaSerializationError;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProofOfKnowledgeTree;
+use ergotree_ir::types::stype::SType;
+
+use crate::type_infer::numeric_cast_tpe;
+use crate::type_infer::specialized_method;
+use crate::type_infer::COLL_METHODS;
+
+/// Decompilation errors
+#[derive(Debug, PartialEq, Eq, From)]
+pub enum DecompileError {
+    /// ErgoTree error
+    ErgoTreeError(ErgoTreeError),
+    /// Error on constant serialization
+    SerializationError(SigmaSerializationError),
+    /// Constant placeholder without a constant value
+    UnresolvedPlaceholder(ConstantPlaceholder),
+}
+
+/// Decompiled ErgoScript source
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Decompiled {
+    /// ErgoScript source
+    pub source: String,
+    /// Whether compiling the source with [`crate::compiler::compile`] yields an equivalent tree.
+    /// If not, the source is a best-effort rendering of the expressions the compiler does not
+    /// support (e.g. bitwise operations or AVL tree lookups).
+    pub recompilable: bool,
+}
+
+/// Decompiles the given [`ErgoTree`] to ErgoScript source. Segregated constants are substituted,
+/// `ProveDlog` constants are rendered as `PK("...")` with the address encoded for the given
+/// network. Compiling the source with [`crate::compiler::compile`] yields an equivalent tree
+/// unless [`Decompiled::recompilable`] is false.
+pub fn decompile(
+    tree: &ErgoTree,
+    network_prefix: NetworkPrefix,
+) -> Result<Decompiled, DecompileError> {
+    decompile_expr(&tree.proposition()?, network_prefix)
+}
+
+/// Decompiles the given [`Expr`] to ErgoScript source (see [`decompile`])
+pub fn decompile_expr(
+    expr: &Expr,
+    network_prefix: NetworkPrefix,
+) -> Result<Decompiled, DecompileError> {
+    let mut decompiler = Decompiler {
+        network_prefix,
+        fold_args: HashMap::new(),
+        recompilable: true,
+    };
+    let source = match expr {
+        // top-level block statements are not wrapped in braces
+        Expr::BlockValue(block) => decompiler.block(block.expr())?,
+        _ => decompiler.expr(expr)?.text,
+    };
+    Ok(Decompiled {
+        source,
+        recompilable: decompiler.recompilable,
+    })
+}
+
+// Binding powers of the operators, see `parser::grammar::expr`
+const IF_PREC: u8 = 0;
+const OR_PREC: u8 = 1;
+const XOR_PREC: u8 = 3;
+const AND_PREC: u8 = 5;
+const EQ_PREC: u8 = 7;
+const CMP_PREC: u8 = 9;
+const ADD_PREC: u8 = 11;
+const MUL_PREC: u8 = 13;
+const PREFIX_PREC: u8 = 15;
+const ATOM_PREC: u8 = 16;
+
+/// Rendered expression along with the binding power of its outermost operator
+struct Code {
+    text: String,
+    prec: u8,
+}
+
+impl Code {
+    fn new(text: String, prec: u8) -> Self {
+        Self { text, prec }
+    }
+
+    fn atom<S: Into<String>>(text: S) -> Self {
+        Self::new(text.into(), ATOM_PREC)
+    }
+}
+
+struct Decompiler {
+    network_prefix: NetworkPrefix,
+    /// Names of the accumulator and the item for the tuple argument of the `fold` operation
+    fold_args: HashMap<ValId, (String, String)>,
+    /// Whether all the rendered expressions are compiled back to the same nodes
+    recompilable: bool,
+}
+
+impl Decompiler {
+    fn expr(&mut self, e: &Expr) -> Result<Code, DecompileError> {
+        if !has_source_form(e) {
+            self.recompilable = false;
+        }
+        Ok(match e {
+            Expr::Const(c) => self.constant(c)?,
+            Expr::ConstPlaceholder(cp) => {
+                return Err(DecompileError::UnresolvedPlaceholder(cp.clone()))
+            }
+            Expr::GlobalVars(v) => Code::atom(match v {
+                GlobalVars::Height => "HEIGHT",
+                GlobalVars::SelfBox => "SELF",
+                GlobalVars::Inputs => "INPUTS",
+                GlobalVars::Outputs => "OUTPUTS",
+                GlobalVars::MinerPubKey => "minerPubKey",
+                GlobalVars::GroupGenerator => "groupGenerator",
+                GlobalVars::LastBlockUtxoRootHash => "LastBlockUtxoRootHash",
+            }),
+            Expr::Context => Code::atom("CONTEXT"),
+            Expr::Global => Code::atom("Global"),
+            Expr::ValUse(v) => match self.fold_args.get(&v.val_id) {
+                Some((acc, item)) => Code::atom(format!("({0}, {1})", acc, item)),
+                None => Code::atom(val_name(v.val_id, &v.tpe)),
+            },
+            Expr::ValDef(v) => Code::new(self.val_def(v.expr().id, &v.expr().rhs)?, IF_PREC),
+            Expr::FunDef(v) => Code::new(self.val_def(v.expr().id, &v.expr().rhs)?, IF_PREC),
+            Expr::BlockValue(v) => {
+                Code::atom(format!("{{\n{0}\n}}", indent(&self.block(v.expr())?)))
+            }
+            Expr::FuncValue(f) => Code::atom(self.lambda(f)?),
+            Expr::Apply(v) => Code::atom(format!(
+                "{0}({1})",
+                self.atom(&v.func)?,
+                self.args(v.args.iter())?
+            )),
+            Expr::If(v) => Code::new(
+                format!(
+                    "if ({0}) {1} else {2}",
+                    self.expr(&v.condition)?.text,
+                    self.expr(&v.true_branch)?.text,
+                    self.expr(&v.false_branch)?.text
+                ),
+                IF_PREC,
+            ),
+            Expr::BinOp(v) => self.bin_op(v.expr())?,
+            Expr::And(v) => self.call("allOf", &[&v.expr().input])?,
+            Expr::Or(v) => self.call("anyOf", &[&v.expr().input])?,
+            Expr::XorOf(v) => self.call("xorOf", &[&v.input])?,
+            Expr::Xor(v) => self.call("xor", &[&v.left, &v.right])?,
+            Expr::Atleast(v) => self.call("atLeast", &[&v.bound, &v.input])?,
+            // `&&` and `||` with SigmaProp operands are compiled to the two-item conjectures
+            Expr::SigmaAnd(v) => match v.items.as_slice() {
+                [l, r] => self.infix(l, "&&", AND_PREC, r)?,
+                items => self.sigma_conjecture("allZK", items)?,
+            },
+            Expr::SigmaOr(v) => match v.items.as_slice() {
+                [l, r] => self.infix(l, "||", OR_PREC, r)?,
+                items => self.sigma_conjecture("anyZK", items)?,
+            },
+            Expr::LogicalNot(v) => self.prefix("!", &v.expr().input)?,
+            Expr::Negation(v) => self.prefix("-", &v.expr().input)?,
+            Expr::BitInversion(v) => self.prefix("~", &v.input)?,
+            Expr::OptionGet(v) => self.select(&v.expr().input, "get")?,
+            Expr::OptionIsDefined(v) => self.select(&v.expr().input, "isDefined")?,
+            Expr::OptionGetOrElse(v) => {
+                self.method(&v.expr().input, "getOrElse", &[&v.expr().default])?
+            }
+            Expr::SomeValue(v) => self.call("Some", &[&v.input])?,
+            Expr::NoneValue(_) => Code::atom("None"),
+            Expr::ExtractAmount(v) => self.select(&v.input, "value")?,
+            Expr::ExtractScriptBytes(v) => self.select(&v.input, "propositionBytes")?,
+            Expr::ExtractId(v) => self.select(&v.input, "id")?,
+            Expr::ExtractBytes(v) => self.select(&v.input, "bytes")?,
+            Expr::ExtractBytesWithNoRef(v) => self.select(&v.input, "bytesWithoutRef")?,
+            Expr::ExtractCreationInfo(v) => self.select(&v.input, "creationInfo")?,
+            Expr::ExtractRegisterAs(v) => {
+                let v = v.expr();
+                let register = format!("R{0}[{1}]", v.register_id, v.elem_tpe);
+                self.select(&v.input, &register)?
+            }
+            Expr::ByIndex(v) => {
+                let v = v.expr();
+                match &v.default {
+                    Some(default) => self.method(&v.input, "getOrElse", &[&v.index, default])?,
+                    None => Code::atom(format!(
+                        "{0}({1})",
+                        self.atom(&v.input)?,
+                        self.expr(&v.index)?.text
+                    )),
+                }
+            }
+            Expr::SizeOf(v) => self.select(&v.input, "size")?,
+            Expr::Slice(v) => {
+                let v = v.expr();
+                self.method(&v.input, "slice", &[&v.from, &v.until])?
+            }
+            Expr::Append(v) => self.method(&v.expr().input, "append", &[&v.expr().col_2])?,
+            Expr::CollShift(v) => {
+                let v = v.expr();
+                self.method(&v.input, &v.op.to_string(), &[&v.n])?
+            }
+            Expr::Map(v) => self.coll_method(&v.expr().input, "map", &v.expr().mapper)?,
+            Expr::Filter(v) => self.coll_method(&v.expr().input, "filter", &v.expr().condition)?,
+            Expr::Exists(v) => self.coll_method(&v.expr().input, "exists", &v.expr().condition)?,
+            Expr::ForAll(v) => self.coll_method(&v.expr().input, "forall", &v.expr().condition)?,
+            Expr::Fold(v) => {
+                let v = v.expr();
+                Code::atom(format!(
+                    "{0}.fold({1}, {2})",
+                    self.atom(&v.input)?,
+                    self.expr(&v.zero)?.text,
+                    self.fold_op(&v.fold_op)?
+                ))
+            }
+            Expr::SelectField(v) => self.select_field(v.expr())?,
+            Expr::SelectFieldShort(v) => self.select_field(v)?,
+            Expr::Tuple(v) => Code::atom(format!("({0})", self.args(v.items.iter())?)),
+            Expr::Collection(Collection::Exprs { elem_tpe, items }) if items.is_empty() => {
+                Code::atom(format!("Coll[{0}]()", elem_tpe))
+            }
+            Expr::Collection(Collection::Exprs { items, .. }) => {
+                Code::atom(format!("Coll({0})", self.args(items.iter())?))
+            }
+            Expr::Collection(Collection::BoolConstants(bools)) if bools.is_empty() => {
+                Code::atom("Coll[Boolean]()")
+            }
+            Expr::Collection(Collection::BoolConstants(bools)) => Code::atom(format!(
+                "Coll({0})",
+                bools
+                    .iter()
+                    .map(bool::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+            Expr::BoolToSigmaProp(v) => self.call("sigmaProp", &[&v.input])?,
+            Expr::CreateProveDlog(v) => self.call("proveDlog", &[&v.input])?,
+            Expr::CreateProveDhTuple(v) => self.call("proveDHTuple", &[&v.g, &v.h, &v.u, &v.v])?,
+            Expr::DecodePoint(v) => self.call("decodePoint", &[&v.input])?,
+            Expr::CalcBlake2b256(v) => self.call("blake2b256", &[&v.input])?,
+            Expr::CalcSha256(v) => self.call("sha256", &[&v.input])?,
+            Expr::LongToByteArray(v) => self.call("longToByteArray", &[&v.input])?,
+            Expr::ByteArrayToBigInt(v) => self.call("byteArrayToBigInt", &[&v.expr().input])?,
+            Expr::ByteArrayToLong(v) => self.call("byteArrayToLong", &[&v.expr().input])?,
+            Expr::SigmaPropBytes(v) => self.select(&v.input, "propBytes")?,
+            Expr::SigmaPropIsProven(v) => self.select(&v.input, "isProven")?,
+            Expr::Upcast(v) => self.select(&v.input, &cast_method(&v.tpe))?,
+            Expr::Downcast(v) => self.select(&v.input, &cast_method(&v.tpe))?,
+            Expr::GetVar(v) => Code::atom(format!(
+                "getVar[{0}]({1})",
+                v.expr().var_tpe,
+                v.expr().var_id
+            )),
+            Expr::DeserializeContext(v) => {
+                Code::atom(format!("executeFromVar[{0}]({1})", v.tpe, v.id))
+            }
+            Expr::DeserializeRegister(v) => match &v.default {
+                Some(default) => Code::atom(format!(
+                    "executeFromSelfReg[{0}]({1}, {2})",
+                    v.tpe,
+                    v.reg,
+                    self.expr(default)?.text
+                )),
+                None => Code::atom(format!("executeFromSelfReg[{0}]({1})", v.tpe, v.reg)),
+            },
+            Expr::MultiplyGroup(v) => self.method(&v.left, "multiply", &[&v.right])?,
+            Expr::Exponentiate(v) => self.method(&v.left, "exp", &[&v.right])?,
+            Expr::ModQ(v) => self.select(&v.input, "modQ")?,
+            Expr::PlusModQ(v) => self.method(&v.left, "plusModQ", &[&v.right])?,
+            Expr::MinusModQ(v) => self.method(&v.left, "minusModQ", &[&v.right])?,
+            Expr::MethodCall(v) => {
+                let v = v.expr();
+                let args: Vec<&Expr> = v.args.iter().collect();
+                self.method(&v.obj, v.method.name(), &args)?
+            }
+            Expr::PropertyCall(v) => self.select(&v.expr().obj, v.expr().method.name())?,
+            Expr::TreeLookup(v) => {
+                let v = v.expr();
+                self.method(&v.tree, "get", &[&v.key, &v.proof])?
+            }
+            Expr::CreateAvlTree(v) => match &v.value_length {
+                Some(value_length) => self.call(
+                    "avlTree",
+                    &[&v.flags, &v.digest, &v.key_length, value_length],
+                )?,
+                None => self.call("avlTree", &[&v.flags, &v.digest, &v.key_length])?,
+            },
+            Expr::SubstConstants(v) => {
+                let v = v.expr();
+                self.call(
+                    "substConstants",
+                    &[&v.script_bytes, &v.positions, &v.new_values],
+                )?
+            }
+        })
+    }
+
+    /// Renders the expression, wrapping it in parentheses if it binds weaker than `min_prec`
+    fn operand(&mut self, e: &Expr, min_prec: u8) -> Result<String, DecompileError> {
+        let code = self.expr(e)?;
+        Ok(if code.prec < min_prec {
+            format!("({0})", code.text)
+        } else {
+            code.text
+        })
+    }
+
+    /// Renders the expression to be used as a receiver of a method call or a property access
+    fn atom(&mut self, e: &Expr) -> Result<String, DecompileError> {
+        self.operand(e, ATOM_PREC)
+    }
+
+    fn args<'a, I: Iterator<Item = &'a Expr>>(
+        &mut self,
+        args: I,
+    ) -> Result<String, DecompileError> {
+        Ok(args
+            .map(|arg| Ok(self.expr(arg)?.text))
+            .collect::<Result<Vec<_>, DecompileError>>()?
+            .join(", "))
+    }
+
+    fn call(&mut self, func: &str, args: &[&Expr]) -> Result<Code, DecompileError> {
+        Ok(Code::atom(format!(
+            "{0}({1})",
+            func,
+            self.args(args.iter().copied())?
+        )))
+    }
+
+    fn select(&mut self, obj: &Expr, field: &str) -> Result<Code, DecompileError> {
+        Ok(Code::atom(format!("{0}.{1}", self.atom(obj)?, field)))
+    }
+
+    fn method(&mut self, obj: &Expr, name: &str, args: &[&Expr]) -> Result<Code, DecompileError> {
+        Ok(Code::atom(format!(
+            "{0}.{1}({2})",
+            self.atom(obj)?,
+            name,
+            self.args(args.iter().copied())?
+        )))
+    }
+
+    /// Collection method with a function argument, e.g. `xs.map { (x: Int) => x + 1 }`
+    fn coll_method(&mut self, coll: &Expr, name: &str, f: &Expr) -> Result<Code, DecompileError> {
+        let coll = self.atom(coll)?;
+        Ok(Code::atom(match f {
+            Expr::FuncValue(f) => format!("{0}.{1} {2}", coll, name, self.lambda(f)?),
+            _ => format!("{0}.{1}({2})", coll, name, self.expr(f)?.text),
+        }))
+    }
+
+    fn prefix(&mut self, op: &str, input: &Expr) -> Result<Code, DecompileError> {
+        Ok(Code::new(
+            format!("{0}{1}", op, self.atom(input)?),
+            PREFIX_PREC,
+        ))
+    }
+
+    /// Left-associative infix operator
+    fn infix(&mut self, l: &Expr, op: &str, prec: u8, r: &Expr) -> Result<Code, DecompileError> {
+        Ok(Code::new(
+            format!(
+                "{0} {1} {2}",
+                self.operand(l, prec)?,
+                op,
+                self.operand(r, prec + 1)?
+            ),
+            prec,
+        ))
+    }
+
+    /// Sigma conjecture of other than two items, e.g. `allZK(Coll(a, b, c))`
+    fn sigma_conjecture(&mut self, func: &str, items: &[Expr]) -> Result<Code, DecompileError> {
+        Ok(Code::atom(format!(
+            "{0}(Coll({1}))",
+            func,
+            self.args(items.iter())?
+        )))
+    }
+
+    fn bin_op(&mut self, bin_op: &BinOp) -> Result<Code, DecompileError> {
+        let (l, r) = (&*bin_op.left, &*bin_op.right);
+        let (op, prec) = match bin_op.kind {
+            BinOpKind::Arith(ArithOp::Plus) => ("+", ADD_PREC),
+            BinOpKind::Arith(ArithOp::Minus) => ("-", ADD_PREC),
+            BinOpKind::Arith(ArithOp::Multiply) => ("*", MUL_PREC),
+            BinOpKind::Arith(ArithOp::Divide) => ("/", MUL_PREC),
+            BinOpKind::Arith(ArithOp::Modulo) => ("%", MUL_PREC),
+            BinOpKind::Arith(ArithOp::Min) => return self.call("min", &[l, r]),
+            BinOpKind::Arith(ArithOp::Max) => return self.call("max", &[l, r]),
+            BinOpKind::Relation(RelationOp::Eq) => ("==", EQ_PREC),
+            BinOpKind::Relation(RelationOp::NEq) => ("!=", EQ_PREC),
+            BinOpKind::Relation(RelationOp::Gt) => (">", CMP_PREC),
+            BinOpKind::Relation(RelationOp::Ge) => (">=", CMP_PREC),
+            BinOpKind::Relation(RelationOp::Lt) => ("<", CMP_PREC),
+            BinOpKind::Relation(RelationOp::Le) => ("<=", CMP_PREC),
+            BinOpKind::Logical(LogicalOp::And) => ("&&", AND_PREC),
+            BinOpKind::Logical(LogicalOp::Or) => ("||", OR_PREC),
+            BinOpKind::Logical(LogicalOp::Xor) | BinOpKind::Bit(BitOp::BitXor) => ("^", XOR_PREC),
+            BinOpKind::Bit(BitOp::BitOr) => return self.method(l, "bitwiseOr", &[r]),
+            BinOpKind::Bit(BitOp::BitAnd) => return self.method(l, "bitwiseAnd", &[r]),
+            BinOpKind::Bit(BitOp::BitShiftLeft) => return self.method(l, "shiftLeft", &[r]),
+            BinOpKind::Bit(BitOp::BitShiftRight) => return self.method(l, "shiftRight", &[r]),
+            BinOpKind::Bit(BitOp::BitShiftRightZeroed) => {
+                return self.method(l, "shiftRightZeroed", &[r])
+            }
+        };
+        self.infix(l, op, prec, r)
+    }
+
+    fn select_field(&mut self, select: &SelectField) -> Result<Code, DecompileError> {
+        let index = select.field_index.zero_based_index();
+        if let Expr::ValUse(v) = &*select.input {
+            if let Some((acc, item)) = self.fold_args.get(&v.val_id) {
+                match index {
+                    0 => return Ok(Code::atom(acc.clone())),
+                    1 => return Ok(Code::atom(item.clone())),
+                    _ => (),
+                }
+            }
+        }
+        self.select(&select.input, &format!("_{0}", index + 1))
+    }
+
+    fn constant(&mut self, c: &Constant) -> Result<Code, DecompileError> {
+        match (&c.tpe, &c.v) {
+            (_, Literal::Boolean(v)) => return Ok(Code::atom(v.to_string())),
+            // i32::MIN is out of the range of the literal
+            (_, Literal::Int(v)) if *v >= 0 => return Ok(Code::atom(v.to_string())),
+            (_, Literal::Int(v)) if *v > i32::MIN => {
+                return Ok(Code::new(v.to_string(), PREFIX_PREC))
+            }
+            (_, Literal::Long(v)) if *v >= 0 => return Ok(Code::atom(format!("{0}L", v))),
+            (_, Literal::Long(v)) if *v > i64::MIN => {
+                return Ok(Code::new(format!("{0}L", v), PREFIX_PREC))
+            }
+            (_, Literal::SigmaProp(sp)) => {
+                if let SigmaBoolean::ProofOfKnowledge(SigmaProofOfKnowledgeTree::ProveDlog(pd)) =
+                    sp.value()
+                {
+                    let address = AddressEncoder::encode_address_as_string(
+                        self.network_prefix,
+                        &Address::P2Pk(pd.clone()),
+                    );
+                    return Ok(Code::atom(format!("PK(\"{0}\")", address)));
+                }
+            }
+            (SType::SColl(elem_tpe), _) if **elem_tpe == SType::SByte => {
+                if let Ok(bytes) = c.v.clone().try_extract_into::<Vec<u8>>() {
+                    return Ok(Code::atom(format!(
+                        "fromBase16(\"{0}\")",
+                        base16::encode_lower(&bytes)
+                    )));
+                }
+            }
+            _ => (),
+        }
+        // any other constant is embedded in its serialized form
+        let bytes = bs58::encode(c.sigma_serialize_bytes()?).into_string();
+        Ok(Code::atom(format!(
+            "deserialize[{0}](\"{1}\")",
+            c.tpe, bytes
+        )))
+    }
+
+    /// Statements of the block, one per line
+    fn block(&mut self, block: &BlockValue) -> Result<String, DecompileError> {
+        let mut stmts = block
+            .items
+            .iter()
+            .map(|item| match item {
+                Expr::ValDef(v) => self.val_def(v.expr().id, &v.expr().rhs),
+                Expr::FunDef(v) => self.val_def(v.expr().id, &v.expr().rhs),
+                _ => Ok(self.expr(item)?.text),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        stmts.push(self.expr(&block.result)?.text);
+        Ok(join_stmts(stmts))
+    }
+
+    fn val_def(&mut self, id: ValId, rhs: &Expr) -> Result<String, DecompileError> {
+        Ok(match rhs {
+            Expr::FuncValue(f) => format!(
+                "def {0}({1}) = {2}",
+                val_name(id, &rhs.tpe()),
+                params(f.args()),
+                self.expr(f.body())?.text
+            ),
+            _ => format!(
+                "val {0} = {1}",
+                val_name(id, &rhs.tpe()),
+                self.expr(rhs)?.text
+            ),
+        })
+    }
+
+    fn lambda(&mut self, f: &FuncValue) -> Result<String, DecompileError> {
+        Ok(format!(
+            "{{ ({0}) => {1} }}",
+            params(f.args()),
+            self.expr(f.body())?.text
+        ))
+    }
+
+    /// `fold` operation takes a single (accumulator, item) tuple argument, which is rendered as
+    /// a lambda with two arguments
+    fn fold_op(&mut self, fold_op: &Expr) -> Result<String, DecompileError> {
+        if let Expr::FuncValue(f) = fold_op {
+            if let [FuncArg {
+                idx,
+                tpe: SType::STuple(tuple),
+            }] = f.args()
+            {
+                if let [acc_tpe, item_tpe] = tuple.items.iter().collect::<Vec<_>>().as_slice() {
+                    let acc = format!("acc{0}", idx);
+                    let item = val_name(*idx, item_tpe);
+                    let params = format!("{0}: {1}, {2}: {3}", acc, acc_tpe, item, item_tpe);
+                    self.fold_args.insert(*idx, (acc, item));
+                    let body = self.expr(f.body());
+                    self.fold_args.remove(idx);
+                    return Ok(format!("{{ ({0}) => {1} }}", params, body?.text));
+                }
+            }
+        }
+        Ok(self.expr(fold_op)?.text)
+    }
+}
+
+/// Whether compiling the rendered node yields the same node (children are checked separately)
+fn has_source_form(e: &Expr) -> bool {
+    match e {
+        Expr::Global
+        | Expr::Xor(_)
+        | Expr::BitInversion(_)
+        | Expr::SomeValue(_)
+        | Expr::NoneValue(_)
+        | Expr::CollShift(_)
+        | Expr::SigmaPropIsProven(_)
+        | Expr::DeserializeContext(_)
+        | Expr::DeserializeRegister(_)
+        | Expr::ModQ(_)
+        | Expr::PlusModQ(_)
+        | Expr::MinusModQ(_)
+        | Expr::TreeLookup(_)
+        | Expr::CreateAvlTree(_) => false,
+        // `^` on numeric operands is compiled to the bitwise xor
+        Expr::BinOp(v) => !matches!(
+            v.expr().kind,
+            BinOpKind::Bit(
+                BitOp::BitOr
+                    | BitOp::BitAnd
+                    | BitOp::BitShiftLeft
+                    | BitOp::BitShiftRight
+                    | BitOp::BitShiftRightZeroed
+            )
+        ),
+        Expr::MethodCall(v) => compiles_to_method(v.expr()),
+        Expr::PropertyCall(v) => compiles_to_property(v.expr()),
+        _ => true,
+    }
+}
+
+/// Method call is compiled back to the same node only if the compiler resolves the method name
+/// to the same method, i.e. the name is not lowered to a dedicated node (e.g. `box.value`)
+fn compiles_to_method(call: &MethodCall) -> bool {
+    let obj_tpe = call.obj.tpe();
+    let name = call.method.name();
+    let lowered_to_node = match &obj_tpe {
+        SType::SColl(_) => COLL_METHODS.contains(&name),
+        SType::SOption(_) => name == "getOrElse",
+        SType::SGroupElement => matches!(name, "exp" | "multiply"),
+        _ => false,
+    };
+    let arg_tpes: Vec<SType> = call.args.iter().map(Expr::tpe).collect();
+    !lowered_to_node && specialized_method(&obj_tpe, name, &arg_tpes).as_ref() == Some(&call.method)
+}
+
+/// Property access is compiled back to the same node only if the compiler resolves the property
+/// name to the same method (see [`compiles_to_method`])
+fn compiles_to_property(call: &PropertyCall) -> bool {
+    let obj_tpe = call.obj.tpe();
+    let name = call.method.name();
+    let lowered_to_node = match &obj_tpe {
+        SType::SColl(_) => name == "size",
+        SType::SBox => matches!(
+            name,
+            "value" | "propositionBytes" | "id" | "bytes" | "bytesWithoutRef" | "creationInfo"
+        ),
+        SType::SSigmaProp => name == "propBytes",
+        SType::SOption(_) => matches!(name, "get" | "isDefined"),
+        tpe => tpe.is_numeric() && numeric_cast_tpe(name).is_some(),
+    };
+    !lowered_to_node && specialized_method(&obj_tpe, name, &[]).as_ref() == Some(&call.method)
+}
+
+/// Variable name made of the type-specific prefix and the id
+fn val_name(id: ValId, tpe: &SType) -> String {
+    let prefix = match tpe {
+        SType::SBoolean => "cond",
+        SType::SByte | SType::SShort | SType::SInt | SType::SLong | SType::SBigInt => "n",
+        SType::SGroupElement => "point",
+        SType::SSigmaProp => "prop",
+        SType::SBox => "box",
+        SType::SAvlTree => "tree",
+        SType::SColl(elem_tpe) if **elem_tpe == SType::SByte => "bytes",
+        SType::SColl(elem_tpe) if **elem_tpe == SType::SBox => "boxes",
+        SType::SColl(_) => "coll",
+        SType::SOption(_) => "opt",
+        SType::STuple(_) => "tuple",
+        SType::SFunc(_) => "f",
+        _ => "v",
+    };
+    format!("{0}{1}", prefix, id)
+}
+
+fn params(args: &[FuncArg]) -> String {
+    args.iter()
+        .map(|arg| format!("{0}: {1}", val_name(arg.idx, &arg.tpe), arg.tpe))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn cast_method(tpe: &SType) -> String {
+    format!("to{0}", tpe)
+}
+
+/// Newlines are not statement separators if the next statement can continue the previous
+/// one (e.g. `(a, b)` would be parsed as a call), so `;` is put in between
+fn join_stmts(stmts: Vec<String>) -> String {
+    let mut res = String::new();
+    for stmt in stmts {
+        if !res.is_empty() {
+            if stmt.starts_with(['(', '{', '-']) {
+                res.push(';');
+            }
+            res.push('\n');
+        }
+        res.push_str(&stmt);
+    }
+    res
+}
+
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| format!("  {0}", line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compiler::compile;
+    use crate::compiler::compile_with_placeholders;
+    use crate::script_env::ScriptEnv;
+    use expect_test::expect;
+
+    fn check_round_trip(source: &str) {
+        let tree = compile(source, ScriptEnv::new()).unwrap();
+        let decompiled = decompile(&tree, NetworkPrefix::Mainnet).unwrap();
+        assert!(decompiled.recompilable, "{0}", decompiled.source);
+        let decompiled = decompiled.source;
+        let recompiled = compile(&decompiled, ScriptEnv::new())
+            .unwrap_or_else(|e| panic!("{0}\n{1}", decompiled, e.pretty_desc(&decompiled)));
+        assert_eq!(
+            recompiled.proposition().unwrap(),
+            tree.proposition().unwrap(),
+            "{0}",
+            decompiled
+        );
+    }
+
+    fn check(source: &str, expected: expect_test::Expect) {
+        let tree = compile(source, ScriptEnv::new()).unwrap();
+        expected.assert_eq(&decompile(&tree, NetworkPrefix::Mainnet).unwrap().source);
+    }
+
+    #[test]
+    fn round_trip() {
+        check_round_trip("sigmaProp(HEIGHT > 100 && OUTPUTS.size == 2 || !(HEIGHT <= 5))");
+        check_round_trip("sigmaProp((HEIGHT + 1) * 2 - -HEIGHT > (HEIGHT, 2L)._1 % 3)");
+        check_round_trip("sigmaProp(HEIGHT - (HEIGHT - 1) == -1 ^ HEIGHT > 5)");
+        check_round_trip("sigmaProp(if (HEIGHT > 5) true else HEIGHT < -5)");
+        check_round_trip("sigmaProp(SELF.R4[Long].getOrElse(-1L) < HEIGHT.toLong + 1L)");
+        check_round_trip("sigmaProp(getVar[Int](1).get > 0 && SELF.R5[Coll[Byte]].isDefined)");
+        check_round_trip("sigmaProp(SELF.R4[Option[Int]].get.isDefined)");
+        check_round_trip(r#"sigmaProp(blake2b256(SELF.propositionBytes) == fromBase16("01ff"))"#);
+        check_round_trip("sigmaProp(OUTPUTS.exists { (b: Box) => b.value >= SELF.value - 1000L })");
+        check_round_trip(
+            "sigmaProp(OUTPUTS.fold(0L, { (acc: Long, b: Box) => acc + b.value }) > 10L)",
+        );
+        check_round_trip("sigmaProp(allOf(Coll(HEIGHT > 1, INPUTS.size > 1)))");
+        check_round_trip("sigmaProp(Coll[Int]().size == OUTPUTS.slice(0, 1).size)");
+        check_round_trip("val x = 1\nval y = { val z = x + 1; z * 2 }\nsigmaProp(y > HEIGHT)");
+        check_round_trip(
+            "def inc(x: Int) = x + 1\nval limit = inc(HEIGHT)\nsigmaProp(limit > inc(1))",
+        );
+        check_round_trip("sigmaProp({ (x: Int) => x > 1 }(HEIGHT))");
+        // the lambda argument has the same id as the value it is assigned to
+        check_round_trip("val y = { (x: Int) => x + 1 }(HEIGHT)\nsigmaProp(y > 1)");
+        check_round_trip(
+            r#"if (HEIGHT > 5) PK("9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE") else sigmaProp(false)"#,
+        );
+    }
+
+    #[test]
+    fn round_trip_box_and_coll_ops() {
+        check_round_trip("sigmaProp(SELF.creationInfo._1 + 720 <= HEIGHT)");
+        check_round_trip("sigmaProp(SELF.bytes.size > SELF.bytesWithoutRef.size)");
+        check_round_trip("sigmaProp(SELF.propositionBytes.append(SELF.id).size > 32)");
+        check_round_trip("sigmaProp(OUTPUTS.getOrElse(1, SELF).value > 0L)");
+        check_round_trip("sigmaProp(blake2b256(minerPubKey).size == 32)");
+        check_round_trip("sigmaProp(byteArrayToLong(SELF.id) > 0L)");
+        check_round_trip("sigmaProp(SELF.R4[SigmaProp].get.propBytes == SELF.propositionBytes)");
+        check_round_trip(
+            "sigmaProp(substConstants(SELF.propositionBytes, Coll(0), Coll(1)) == SELF.propositionBytes)",
+        );
+    }
+
+    #[test]
+    fn round_trip_sigma_conjectures() {
+        check_round_trip(
+            "allZK(Coll(sigmaProp(HEIGHT > 1), sigmaProp(HEIGHT > 2), sigmaProp(HEIGHT > 3)))",
+        );
+        check_round_trip(
+            "anyZK(Coll(sigmaProp(HEIGHT > 1), sigmaProp(HEIGHT > 2), sigmaProp(HEIGHT > 3)))",
+        );
+        check_round_trip("sigmaProp(HEIGHT > 1) && sigmaProp(HEIGHT > 2) && sigmaProp(HEIGHT > 3)");
+    }
+
+    #[test]
+    fn constants() {
+        let env = ScriptEnv::new()
+            .with("b", 5i8)
+            .with("lowest", i32::MIN)
+            .with("bools", vec![true, false]);
+        let tree = compile("sigmaProp(b.toInt != lowest && bools.size > 1)", env).unwrap();
+        let decompiled = decompile(&tree, NetworkPrefix::Mainnet).unwrap().source;
+        assert!(decompiled.contains("deserialize[Byte]"), "{0}", decompiled);
+        assert!(decompiled.contains("deserialize[Int]"), "{0}", decompiled);
+        assert_eq!(
+            compile(&decompiled, ScriptEnv::new())
+                .unwrap()
+                .proposition()
+                .unwrap(),
+            tree.proposition().unwrap()
+        );
+    }
+
+    #[test]
+    fn segregated_constants() {
+        let env = ScriptEnv::new().with("deadline", 100i32);
+        let (tree, _) = compile_with_placeholders("sigmaProp(HEIGHT > deadline)", env).unwrap();
+        assert_eq!(
+            decompile(&tree, NetworkPrefix::Mainnet).unwrap().source,
+            "sigmaProp(HEIGHT > 100)"
+        );
+        let placeholder = Expr::ConstPlaceholder(ConstantPlaceholder {
+            id: 0,
+            tpe: SType::SInt,
+        });
+        assert!(matches!(
+            decompile_expr(&placeholder, NetworkPrefix::Mainnet),
+            Err(DecompileError::UnresolvedPlaceholder(_))
+        ));
+    }
+
+    #[test]
+    fn readable_source() {
+        check(
+            r#"
+            val pk = PK("9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE")
+            def paid(b: Box) = b.propositionBytes == SELF.propositionBytes
+            val total = OUTPUTS.filter { (b: Box) => paid(b) }.fold(0L, { (acc: Long, b: Box) => acc + b.value })
+            if (total >= SELF.value) pk else sigmaProp(HEIGHT > SELF.R4[Int].get)
+            "#,
+            expect![[r#"
+                val prop1 = PK("9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE")
+                def f2(box2: Box) = box2.propositionBytes == SELF.propositionBytes
+                val n3 = OUTPUTS.filter { (box3: Box) => f2(box3) }.fold(0L, { (acc3: Long, box3: Box) => acc3 + box3.value })
+                if (n3 >= SELF.value) prop1 else sigmaProp(HEIGHT > SELF.R4[Int].get)"#]],
+        );
+    }
+
+    fn check_real_tree(tree_hex: &str) {
+        let tree = ErgoTree::sigma_parse_bytes(&base16::decode(tree_hex).unwrap()).unwrap();
+        let decompiled = decompile(&tree, NetworkPrefix::Mainnet).unwrap();
+        assert!(decompiled.recompilable, "{0}", decompiled.source);
+        let decompiled = decompiled.source;
+        let recompiled = compile(&decompiled, ScriptEnv::new())
+            .unwrap_or_else(|e| panic!("{0}\n{1}", decompiled, e.pretty_desc(&decompiled)));
+        assert_eq!(
+            recompiled.proposition().unwrap(),
+            tree.proposition().unwrap(),
+            "{0}",
+            decompiled
+        );
+    }
+
+    #[test]
+    fn real_trees_round_trip() {
+        // P2PK
+        let address = AddressEncoder::unchecked_parse_network_address_from_str(
+            "9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE",
+        )
+        .unwrap();
+        let tree_bytes = address
+            .address()
+            .script()
+            .unwrap()
+            .sigma_serialize_bytes()
+            .unwrap();
+        check_real_tree(&base16::encode_lower(&tree_bytes));
+        // miner fee contract (`ergo_lib::wallet::miner_fee::MINERS_FEE_BASE16_BYTES`)
+        check_real_tree("1005040004000e36100204a00b08cd0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ea02d192a39a8cc7a701730073011001020402d19683030193a38cc7b2a57300000193c2b2a57301007473027303830108cdeeac93b1a57304");
+        // mainnet contracts (see `ergotree_ir::pretty_printer` tests)
+        check_real_tree("10070580dac409040204020400040204000e206251655468576d5a7134743777217a25432a462d4a404e635266556a586e3272d803d601e4c6a70407d602b2a5e4e3000400d603c672020407eb02cd7201d1edededede6720393c27202c2a793db63087202db6308a792c172027300ededededed91b1a4730191b1db6308b2a47302007303938cb2db6308b2a473040073050001730693e47203720192c17202c1a7efe6c672020561");
+        check_real_tree("100a040004000580dac409040004000e20472b4b6250655368566d597133743677397a24432646294a404d635166546a570402040204020402d804d601b2a5e4e3000400d602db63087201d603db6308a7d604e4c6a70407ea02d1ededed93b27202730000b2720373010093c27201c2a7e6c67201040792c172017302eb02cd7204d1ededededed938cb2db6308b2a4730300730400017305938cb27202730600018cb2720373070001918cb27202730800028cb272037309000293e4c672010407720492c17201c1a7efe6c672010561");
+        // use `SELF.creationInfo`
+        for tree_hex in [
+            "1016043c040004000e202a472d4a614e645267556b58703273357638792f423f4528482b4d625065536801000502010105000400040004020402040204080400040a05c8010e20472b4b6250655368566d597133743677397a24432646294a404d635166546a570400040404020408d80ed60199a37300d602b2a4730100d603b5a4d901036395e6c672030605eded928cc77203017201938cb2db6308720373020001730393e4c672030504e4c6720205047304d604b17203d605b0720386027305860273067307d901053c413d0563d803d607e4c68c7205020605d6088c720501d6098c720802860272078602ed8c720901908c72080172079a8c7209027207d6068c720502d6078c720501d608db63087202d609b27208730800d60ab2a5730900d60bdb6308720ad60cb2720b730a00d60db27208730b00d60eb2a5730c00ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02ea02cde4c6b27203e4e30004000407d18f8cc77202017201d1927204730dd18c720601d190997207e4c6b27203730e0006059d9c72077e730f057310d1938c7209017311d193b2720b7312007209d1938c720c018c720d01d1928c720c02998c720d027e9c7204731305d193b1720bb17208d193e4c6720a04059d8c7206027e720405d193e4c6720a05049ae4c6720205047314d193c2720ac27202d192c1720ac17202d1928cc7720a0199a37315d193db6308720edb6308a7d193c2720ec2a7d192c1720ec1a7",
+            "100f0400040004000402040204020e20472b4b6250655368566d597133743677397a24432646294a404d635166546a570400040004000e203f4428472d4b6150645367566b5970337336763979244226452948404d625165010005000400040cd80ad601b2a4730000d602db63087201d603b27202730100d604b2a5730200d605db63087204d606b2a5730300d607b27205730400d6088c720701d6098c720702d60ab27202730500d1ededed938c7203017306edededed937203b2720573070093c17201c1720493c672010405c67204040593c672010504c672040504efe6c672040661edededed93db63087206db6308a793c27206c2a792c17206c1a7918cc77206018cc7a701efe6c67206046192b0b5a4d9010b63d801d60ddb6308720b9591b1720d7308d801d60ec6720b070eededed938cb2720d73090001730a93e4c6720b05048cc7a70193e4c6720b060ecbc2720495ede6720ee6c6720b0805ed93e4720e720893e4c6720b08057209ed938c720a017208938c720a027209730b730cd9010b41639a8c720b018cb2db63088c720b02730d00027e730e05",
+        ] {
+            check_real_tree(tree_hex);
+        }
+    }
+
+    #[test]
+    fn best_effort() {
+        let exprs: Vec<Expr> = vec![
+            Expr::Global,
+            BinOp {
+                kind: BitOp::BitOr.into(),
+                left: Box::new(1i32.into()),
+                right: Box::new(2i32.into()),
+            }
+            .into(),
+        ];
+        for expr in exprs {
+            let decompiled = decompile_expr(&expr, NetworkPrefix::Mainnet).unwrap();
+            assert!(!decompiled.recompilable, "{0}", decompiled.source);
+        }
+        let bit_or: Expr = BinOp {
+            kind: BitOp::BitOr.into(),
+            left: Box::new(1i32.into()),
+            right: Box::new(2i32.into()),
+        }
+        .into();
+        assert_eq!(
+            decompile_expr(&bit_or, NetworkPrefix::Mainnet).unwrap(),
+            Decompiled {
+                source: "1.bitwiseOr(2)".to_string(),
+                recompilable: false,
+            }
+        );
+    }
+}
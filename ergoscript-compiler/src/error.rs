@@ -3,14 +3,18 @@ use rowan::TextRange;
 
 pub fn pretty_error_desc(source: &str, span: TextRange, error_msg: &str) -> String {
     let line_col_lookup = LineColLookup::new(source);
-    let start_zero_based: usize = usize::from(span.start()) - 1;
-    let end_zero_based: usize = usize::from(span.end()) - 1;
-    let (line_start, col_start) = line_col_lookup.get(start_zero_based);
-    let (line_end, col_end) = line_col_lookup.get(end_zero_based);
+    // spans are 0-based with an exclusive end and may include trailing whitespace,
+    // highlight at least one char
+    let start: usize = usize::from(span.start()).min(source.len());
+    let end: usize = usize::from(span.end()).min(source.len());
+    let trimmed_len = source.get(start..end).map_or(0, |s| s.trim_end().len());
+    let end_inclusive: usize = (start + trimmed_len).saturating_sub(1).max(start);
+    let (line_start, col_start) = line_col_lookup.get(start);
+    let (line_end, col_end) = line_col_lookup.get(end_inclusive);
     if line_end != line_start {
         return "Multiline error spans are not yet supported".to_string();
     }
-    let source_line = source.lines().nth(line_start - 1).unwrap();
+    let source_line = source.lines().nth(line_start - 1).unwrap_or("");
     let highlight = format!("{0:^>span$}", "^", span = col_end - col_start + 1);
     format!(
         "{0}\nline: {1}\n{2}\n{3:>ident$}",
@@ -18,6 +22,6 @@ pub fn pretty_error_desc(source: &str, span: TextRange, error_msg: &str) -> Stri
         line_start,
         source_line,
         highlight,
-        ident = col_start + 1,
+        ident = col_end,
     )
 }
@@ -3,8 +3,15 @@
 
 mod rewrite;
 
+use ergotree_ir::mir::constant::Constant;
+use ergotree_ir::mir::constant::ConstantPlaceholder;
+use ergotree_ir::types::sfunc::SFunc;
+use ergotree_ir::types::stuple::STuple;
 use ergotree_ir::types::stype::SType;
+pub use rewrite::children;
+pub use rewrite::map_children_infallible;
 pub use rewrite::rewrite;
+use std::convert::TryFrom;
 
 use super::ast;
 use crate::ast::AstError;
@@ -16,17 +23,59 @@ extern crate derive_more;
 use derive_more::From;
 
 pub fn lower(ast: ast::Root) -> Result<Expr, HirLoweringError> {
-    let exprs: Vec<ast::Expr> = ast.children().collect();
+    lower_block(ast.stmts(), ast.span())
+}
+
+/// Definitions followed by the single result expression (either the root or `{ ... }` block)
+fn lower_block(
+    stmts: impl Iterator<Item = ast::Stmt>,
+    span: TextRange,
+) -> Result<Expr, HirLoweringError> {
+    let mut vals = Vec::new();
+    let mut exprs = Vec::new();
+    for stmt in stmts {
+        match stmt {
+            ast::Stmt::VariableDef(def) if exprs.is_empty() => vals.push(Val::lower(&def)?),
+            ast::Stmt::FunctionDef(def) if exprs.is_empty() => {
+                vals.push(Val::lower_function(&def)?)
+            }
+            ast::Stmt::VariableDef(def) => {
+                return Err(HirLoweringError::new(
+                    "value definition after the result expression".to_string(),
+                    def.span(),
+                ))
+            }
+            ast::Stmt::FunctionDef(def) => {
+                return Err(HirLoweringError::new(
+                    "function definition after the result expression".to_string(),
+                    def.span(),
+                ))
+            }
+            ast::Stmt::Expr(expr) => exprs.push(expr),
+        }
+    }
     if exprs.len() > 1 {
         return Err(HirLoweringError::new(
-            format!("More than one root expr found: {:?}", exprs),
-            ast.span(),
+            format!("More than one result expr found: {:?}", exprs),
+            span,
         ));
     }
     let first_expr = exprs
         .first()
-        .ok_or_else(|| AstError::new(format!("Cannot parse empty root: {:?}", ast), ast.span()))?;
-    Expr::lower(first_expr)
+        .ok_or_else(|| HirLoweringError::new("Cannot find the result expr".to_string(), span))?;
+    let body = Expr::lower(first_expr)?;
+    if vals.is_empty() {
+        return Ok(body);
+    }
+    Ok(Expr {
+        kind: Block {
+            vals,
+            body: body.into(),
+        }
+        .into(),
+        span,
+        tpe: None,
+    })
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -50,6 +99,14 @@ impl HirLoweringError {
     pub fn pretty_desc(&self, source: &str) -> String {
         pretty_error_desc(source, self.span, &self.msg)
     }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.span
+    }
 }
 
 impl From<AstError> for HirLoweringError {
@@ -87,9 +144,92 @@ impl Expr {
                         span: ast.span(),
                         tpe: Some(SType::SLong),
                     },
+                    ast::LiteralValue::Bool(v) => Expr {
+                        kind: Literal::Bool(v).into(),
+                        span: ast.span(),
+                        tpe: Some(SType::SBoolean),
+                    },
+                    ast::LiteralValue::Str(v) => Expr {
+                        kind: Literal::Str(v).into(),
+                        span: ast.span(),
+                        tpe: None,
+                    },
                 };
                 Ok(expr)
             }
+            ast::Expr::ParenExpr(ast) => Ok(Expr {
+                span: ast.span(),
+                ..Expr::lower(&ast.expr()?)?
+            }),
+            ast::Expr::UnaryExpr(ast) => Ok(Expr {
+                kind: Unary::lower(ast)?.into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::IfExpr(ast) => Ok(Expr {
+                kind: If {
+                    cond: Expr::lower(&ast.condition()?)?.into(),
+                    then_branch: Expr::lower(&ast.then_branch()?)?.into(),
+                    else_branch: Expr::lower(&ast.else_branch()?)?.into(),
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::SelectExpr(ast) => Ok(Expr {
+                kind: Select {
+                    obj: Expr::lower(&ast.obj()?)?.into(),
+                    field: ast.field()?.text().to_string(),
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::CallExpr(ast) => Ok(Expr {
+                kind: Apply {
+                    func: Expr::lower(&ast.func()?)?.into(),
+                    args: ast
+                        .args()
+                        .map(|arg| Expr::lower(&arg))
+                        .collect::<Result<Vec<_>, _>>()?,
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::LambdaExpr(ast) => Ok(Expr {
+                kind: Lambda {
+                    args: lower_params(ast.params())?,
+                    body: Expr::lower(&ast.body()?)?.into(),
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::BlockExpr(ast) => lower_block(ast.stmts(), ast.span()),
+            ast::Expr::TupleExpr(ast) => Ok(Expr {
+                kind: Tuple {
+                    items: ast
+                        .items()
+                        .map(|item| Expr::lower(&item))
+                        .collect::<Result<Vec<_>, _>>()?,
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            ast::Expr::ApplyTypesExpr(ast) => Ok(Expr {
+                kind: ApplyTypes {
+                    expr: Expr::lower(&ast.expr()?)?.into(),
+                    tpes: ast
+                        .type_args()
+                        .map(|t| lower_type(&t))
+                        .collect::<Result<Vec<_>, _>>()?,
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
         }
     }
 
@@ -121,6 +261,16 @@ impl Binary {
             SyntaxKind::Minus => BinaryOp::Minus,
             SyntaxKind::Star => BinaryOp::Multiply,
             SyntaxKind::Slash => BinaryOp::Divide,
+            SyntaxKind::Percent => BinaryOp::Modulo,
+            SyntaxKind::EqualsEquals => BinaryOp::Eq,
+            SyntaxKind::NotEquals => BinaryOp::NEq,
+            SyntaxKind::Less => BinaryOp::Lt,
+            SyntaxKind::LessEquals => BinaryOp::Le,
+            SyntaxKind::Greater => BinaryOp::Gt,
+            SyntaxKind::GreaterEquals => BinaryOp::Ge,
+            SyntaxKind::And => BinaryOp::And,
+            SyntaxKind::Or => BinaryOp::Or,
+            SyntaxKind::Caret => BinaryOp::Xor,
             _ => {
                 return Err(HirLoweringError::new(
                     format!("unknown binary operator: {:?}", ast.op()),
@@ -143,19 +293,248 @@ impl Binary {
     }
 }
 
+#[derive(Debug, PartialEq, Clone)]
+pub struct Unary {
+    pub op: Spanned<UnaryOp>,
+    pub expr: Box<Expr>,
+}
+
+impl Unary {
+    fn lower(ast: &ast::UnaryExpr) -> Result<Unary, HirLoweringError> {
+        let syntax_token = ast.op()?;
+        let op = match syntax_token.kind() {
+            SyntaxKind::Minus => UnaryOp::Neg,
+            SyntaxKind::Bang => UnaryOp::Not,
+            _ => {
+                return Err(HirLoweringError::new(
+                    format!("unknown unary operator: {:?}", ast.op()),
+                    syntax_token.text_range(),
+                ))
+            }
+        };
+        Ok(Unary {
+            op: Spanned {
+                node: op,
+                span: syntax_token.text_range(),
+            },
+            expr: Box::new(Expr::lower(&ast.expr()?)?),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct If {
+    pub cond: Box<Expr>,
+    pub then_branch: Box<Expr>,
+    pub else_branch: Box<Expr>,
+}
+
+fn lower_params(
+    params: impl Iterator<Item = ast::Param>,
+) -> Result<Vec<(String, SType)>, HirLoweringError> {
+    params
+        .map(|param| {
+            let name = param.name()?.text().to_string();
+            let tpe = lower_type(&param.tpe()?)?;
+            Ok((name, tpe))
+        })
+        .collect()
+}
+
+/// Converts type syntax (e.g. `Coll[Byte]`) to [`SType`]
+pub fn lower_type(ast: &ast::Type) -> Result<SType, HirLoweringError> {
+    if let Some(items) = ast.tuple_items() {
+        let items = items
+            .map(|t| lower_type(&t))
+            .collect::<Result<Vec<_>, _>>()?;
+        return STuple::try_from(items).map(SType::STuple).map_err(|_| {
+            HirLoweringError::new(
+                "tuple type should have from 2 to 255 items".to_string(),
+                ast.span(),
+            )
+        });
+    }
+    let name = ast.name()?;
+    let args = ast
+        .type_args()
+        .map(|t| lower_type(&t))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(match (name.text(), args.as_slice()) {
+        ("Unit", []) => SType::SUnit,
+        ("Any", []) => SType::SAny,
+        ("Boolean", []) => SType::SBoolean,
+        ("Byte", []) => SType::SByte,
+        ("Short", []) => SType::SShort,
+        ("Int", []) => SType::SInt,
+        ("Long", []) => SType::SLong,
+        ("BigInt", []) => SType::SBigInt,
+        ("GroupElement", []) => SType::SGroupElement,
+        ("SigmaProp", []) => SType::SSigmaProp,
+        ("Box", []) => SType::SBox,
+        ("AvlTree", []) => SType::SAvlTree,
+        ("Context", []) => SType::SContext,
+        ("Header", []) => SType::SHeader,
+        ("PreHeader", []) => SType::SPreHeader,
+        ("Coll", [elem]) => SType::SColl(elem.clone().into()),
+        ("Option", [elem]) => SType::SOption(elem.clone().into()),
+        (name, _) => {
+            return Err(HirLoweringError::new(
+                format!("unknown type: {0}", name),
+                ast.span(),
+            ))
+        }
+    })
+}
+
+/// Property (`obj.field`) or method (when applied to arguments) selection
+#[derive(Debug, PartialEq, Clone)]
+pub struct Select {
+    pub obj: Box<Expr>,
+    pub field: String,
+}
+
+impl Select {
+    /// Register index if the field is a register (`R0` - `R9`)
+    pub fn register_id(&self) -> Option<i8> {
+        match self.field.strip_prefix('R')?.parse::<i8>() {
+            Ok(id) if (0..=9).contains(&id) && self.field.len() == 2 => Some(id),
+            _ => None,
+        }
+    }
+}
+
+/// Application of a function or a collection (indexing) to the arguments
+#[derive(Debug, PartialEq, Clone)]
+pub struct Apply {
+    pub func: Box<Expr>,
+    pub args: Vec<Expr>,
+}
+
+/// Explicit type arguments, e.g. `SELF.R4[Int]`
+#[derive(Debug, PartialEq, Clone)]
+pub struct ApplyTypes {
+    pub expr: Box<Expr>,
+    pub tpes: Vec<SType>,
+}
+
+/// Anonymous function, e.g. `{ (b: Box) => b.value }`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lambda {
+    pub args: Vec<(String, SType)>,
+    pub body: Box<Expr>,
+}
+
+impl Lambda {
+    /// Type of the argument with the given name (if any)
+    pub fn arg_tpe(&self, name: &str) -> Option<&SType> {
+        self.args
+            .iter()
+            .find(|(arg_name, _)| arg_name == name)
+            .map(|(_, tpe)| tpe)
+    }
+}
+
+/// Sets the type of the (not yet typed) references to the given name, skipping the nested
+/// lambdas and blocks that shadow it
+pub fn bind_refs(expr: Expr, name: &str, tpe: &SType) -> Expr {
+    match &expr.kind {
+        ExprKind::Ident(ident) if ident == name && expr.tpe.is_none() => Expr {
+            tpe: Some(tpe.clone()),
+            ..expr
+        },
+        ExprKind::Lambda(lambda) if lambda.arg_tpe(name).is_some() => expr,
+        ExprKind::Block(block) if block.defines(name) => expr,
+        _ => map_children_infallible(expr, |child| bind_refs(child, name, tpe)),
+    }
+}
+
+/// Tuple literal, e.g. `(1, HEIGHT)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Tuple {
+    pub items: Vec<Expr>,
+}
+
+/// Value definitions followed by the result expression
+#[derive(Debug, PartialEq, Clone)]
+pub struct Block {
+    pub vals: Vec<Val>,
+    pub body: Box<Expr>,
+}
+
+impl Block {
+    /// Whether the value with the given name is defined in this block
+    pub fn defines(&self, name: &str) -> bool {
+        self.vals.iter().any(|val| val.name == name)
+    }
+}
+
+/// Value (or function) definition, e.g. `val limit: Long = 100L`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Val {
+    pub name: String,
+    /// Declared type (if any)
+    pub tpe: Option<SType>,
+    pub rhs: Box<Expr>,
+    /// Span of the name
+    pub span: TextRange,
+}
+
+impl Val {
+    fn lower(ast: &ast::VariableDef) -> Result<Val, HirLoweringError> {
+        let name = ast.name()?;
+        Ok(Val {
+            name: name.text().to_string(),
+            tpe: ast.tpe().map(|t| lower_type(&t)).transpose()?,
+            rhs: Expr::lower(&ast.rhs()?)?.into(),
+            span: name.text_range(),
+        })
+    }
+
+    /// Function definition is a value with the lambda on the right-hand side
+    fn lower_function(ast: &ast::FunctionDef) -> Result<Val, HirLoweringError> {
+        let args = lower_params(ast.params())?;
+        let tpe = match ast.tpe() {
+            Some(t) => Some(SType::SFunc(SFunc::new(
+                args.iter().map(|(_, tpe)| tpe.clone()).collect(),
+                lower_type(&t)?,
+            ))),
+            None => None,
+        };
+        let name = ast.name()?;
+        Ok(Val {
+            name: name.text().to_string(),
+            tpe,
+            rhs: Box::new(Expr {
+                kind: Lambda {
+                    args,
+                    body: Expr::lower(&ast.body()?)?.into(),
+                }
+                .into(),
+                span: ast.span(),
+                tpe: None,
+            }),
+            span: name.text_range(),
+        })
+    }
+}
+
 #[derive(Debug, PartialEq, From, Clone)]
 pub enum ExprKind {
     Ident(String),
     Binary(Binary),
+    Unary(Unary),
+    If(If),
+    Select(Select),
+    Apply(Apply),
+    ApplyTypes(ApplyTypes),
+    Lambda(Lambda),
+    PredefFuncCall(PredefFuncCall),
+    Tuple(Tuple),
+    Block(Block),
     GlobalVars(GlobalVars),
     Literal(Literal),
     // ...
-    // Block
-    // ValNode
-    // Select
-    // ApplyTypes
     // MethodCallLike
-    // Lambda
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -164,16 +543,34 @@ pub enum BinaryOp {
     Minus,
     Multiply,
     Divide,
+    Modulo,
+    Eq,
+    NEq,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    And,
+    Or,
+    Xor,
 }
 
-// #[derive(Debug, PartialEq, Clone)]
-// pub enum UnaryOp {
-//     Neg,
-// }
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum UnaryOp {
+    Neg,
+    Not,
+}
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum GlobalVars {
     Height,
+    SelfBox,
+    Inputs,
+    Outputs,
+    Context,
+    MinerPubKey,
+    GroupGenerator,
+    LastBlockUtxoRootHash,
 }
 
 impl GlobalVars {
@@ -181,14 +578,93 @@ impl GlobalVars {
     pub fn tpe(&self) -> SType {
         match self {
             GlobalVars::Height => SType::SInt,
+            GlobalVars::SelfBox => SType::SBox,
+            GlobalVars::Inputs => SType::SColl(SType::SBox.into()),
+            GlobalVars::Outputs => SType::SColl(SType::SBox.into()),
+            GlobalVars::Context => SType::SContext,
+            GlobalVars::MinerPubKey => SType::SColl(SType::SByte.into()),
+            GlobalVars::GroupGenerator => SType::SGroupElement,
+            GlobalVars::LastBlockUtxoRootHash => SType::SAvlTree,
         }
     }
 }
 
+/// Predefined global function
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PredefFunc {
+    SigmaProp,
+    ProveDlog,
+    ProveDhTuple,
+    AtLeast,
+    AllOf,
+    AnyOf,
+    XorOf,
+    /// Conjunction of the items of the SigmaProp collection literal
+    AllZk,
+    /// Disjunction of the items of the SigmaProp collection literal
+    AnyZk,
+    Blake2b256,
+    Sha256,
+    LongToByteArray,
+    ByteArrayToBigInt,
+    ByteArrayToLong,
+    DecodePoint,
+    SubstConstants,
+    GetVar,
+    Min,
+    Max,
+    /// Collection literal, e.g. `Coll(1, 2)` or `Coll[Int]()`
+    Coll,
+}
+
+impl PredefFunc {
+    /// Function by its name in ErgoScript
+    pub fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sigmaProp" => PredefFunc::SigmaProp,
+            "proveDlog" => PredefFunc::ProveDlog,
+            "proveDHTuple" => PredefFunc::ProveDhTuple,
+            "atLeast" => PredefFunc::AtLeast,
+            "allOf" => PredefFunc::AllOf,
+            "anyOf" => PredefFunc::AnyOf,
+            "xorOf" => PredefFunc::XorOf,
+            "allZK" => PredefFunc::AllZk,
+            "anyZK" => PredefFunc::AnyZk,
+            "blake2b256" => PredefFunc::Blake2b256,
+            "sha256" => PredefFunc::Sha256,
+            "longToByteArray" => PredefFunc::LongToByteArray,
+            "byteArrayToBigInt" => PredefFunc::ByteArrayToBigInt,
+            "byteArrayToLong" => PredefFunc::ByteArrayToLong,
+            "decodePoint" => PredefFunc::DecodePoint,
+            "substConstants" => PredefFunc::SubstConstants,
+            "getVar" => PredefFunc::GetVar,
+            "min" => PredefFunc::Min,
+            "max" => PredefFunc::Max,
+            "Coll" => PredefFunc::Coll,
+            _ => return None,
+        })
+    }
+}
+
+/// Application of the predefined function, e.g. `getVar[Int](1)`
+#[derive(Debug, PartialEq, Clone)]
+pub struct PredefFuncCall {
+    pub func: PredefFunc,
+    pub tpe_args: Vec<SType>,
+    pub args: Vec<Expr>,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum Literal {
     Int(i32),
     Long(i64),
+    Bool(bool),
+    /// Only allowed as an argument of the functions evaluated at compile time, e.g. `PK("...")`
+    Str(String),
+    /// Result of the compile time evaluation
+    Constant(Constant),
+    /// Value from the script env to be stored in the segregated constants of the tree
+    ConstPlaceholder(ConstantPlaceholder),
 }
 
 #[cfg(test)]
@@ -1,29 +1,179 @@
+use std::convert::Infallible;
+
+use super::Apply;
+use super::ApplyTypes;
 use super::Binary;
+use super::Block;
 use super::Expr;
 use super::ExprKind;
+use super::If;
+use super::Lambda;
+use super::PredefFuncCall;
+use super::Select;
+use super::Tuple;
+use super::Unary;
+use super::Val;
 
 // pub fn hir_rewrite_safe<F: Fn(&Expr) -> Option<Expr>>(e: Expr, f: F) -> Expr {
 //     let f_wrap = |e| Result::<Option<Expr>, BinderError>::Ok(f(e));
 //     hir_rewrite(e, f_wrap).unwrap()
 // }
 
+/// Rewrites the tree bottom-up, i.e. `f` is called on the node after its children are rewritten
 pub fn rewrite<E, F: Fn(&Expr) -> Result<Option<Expr>, E>>(e: Expr, f: F) -> Result<Expr, E> {
-    let e = f(&e)?.unwrap_or(e);
-    Ok(match &e.kind {
-        ExprKind::Binary(binary) => match (f(&binary.lhs)?, f(&binary.rhs)?) {
-            (None, None) => e,
-            (l, r) => Expr {
-                kind: Binary {
-                    op: binary.op.clone(),
-                    lhs: Box::new(l.unwrap_or(*binary.lhs.clone())),
-                    rhs: Box::new(r.unwrap_or(*binary.rhs.clone())),
-                }
-                .into(),
-                ..e
-            },
-        },
-        ExprKind::Ident(_) => f(&e)?.unwrap_or(e), // TODO: duplicate call to f?
-        ExprKind::GlobalVars(_) => f(&e)?.unwrap_or(e),
-        ExprKind::Literal(_) => f(&e)?.unwrap_or(e),
+    rewrite_with(e, &f)
+}
+
+fn rewrite_with<E, F: Fn(&Expr) -> Result<Option<Expr>, E>>(e: Expr, f: &F) -> Result<Expr, E> {
+    let e = map_children(e, |child| rewrite_with(child, f))?;
+    Ok(f(&e)?.unwrap_or(e))
+}
+
+/// Applies `f` to the direct children of the node
+pub fn map_children<E, F: FnMut(Expr) -> Result<Expr, E>>(e: Expr, mut f: F) -> Result<Expr, E> {
+    Ok(match e.kind {
+        ExprKind::Binary(Binary { op, lhs, rhs }) => Expr {
+            kind: Binary {
+                op,
+                lhs: Box::new(f(*lhs)?),
+                rhs: Box::new(f(*rhs)?),
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Unary(Unary { op, expr }) => Expr {
+            kind: Unary {
+                op,
+                expr: Box::new(f(*expr)?),
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::If(If {
+            cond,
+            then_branch,
+            else_branch,
+        }) => Expr {
+            kind: If {
+                cond: Box::new(f(*cond)?),
+                then_branch: Box::new(f(*then_branch)?),
+                else_branch: Box::new(f(*else_branch)?),
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Select(Select { obj, field }) => Expr {
+            kind: Select {
+                obj: Box::new(f(*obj)?),
+                field,
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Apply(Apply { func, args }) => Expr {
+            kind: Apply {
+                func: Box::new(f(*func)?),
+                args: args
+                    .into_iter()
+                    .map(&mut f)
+                    .collect::<Result<Vec<_>, _>>()?,
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::ApplyTypes(ApplyTypes { expr, tpes }) => Expr {
+            kind: ApplyTypes {
+                expr: Box::new(f(*expr)?),
+                tpes,
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Lambda(Lambda { args, body }) => Expr {
+            kind: Lambda {
+                args,
+                body: Box::new(f(*body)?),
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::PredefFuncCall(PredefFuncCall {
+            func,
+            tpe_args,
+            args,
+        }) => Expr {
+            kind: PredefFuncCall {
+                func,
+                tpe_args,
+                args: args
+                    .into_iter()
+                    .map(&mut f)
+                    .collect::<Result<Vec<_>, _>>()?,
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Tuple(Tuple { items }) => Expr {
+            kind: Tuple {
+                items: items
+                    .into_iter()
+                    .map(&mut f)
+                    .collect::<Result<Vec<_>, _>>()?,
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Block(Block { vals, body }) => Expr {
+            kind: Block {
+                vals: vals
+                    .into_iter()
+                    .map(|val| {
+                        Ok::<_, E>(Val {
+                            rhs: Box::new(f(*val.rhs)?),
+                            ..val
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+                body: Box::new(f(*body)?),
+            }
+            .into(),
+            ..e
+        },
+        ExprKind::Ident(_) | ExprKind::GlobalVars(_) | ExprKind::Literal(_) => e,
     })
 }
+
+/// Applies the infallible `f` to the direct children of the node
+pub fn map_children_infallible<F: FnMut(Expr) -> Expr>(e: Expr, mut f: F) -> Expr {
+    match map_children(e, |child| Ok::<_, Infallible>(f(child))) {
+        Ok(e) => e,
+        Err(never) => match never {},
+    }
+}
+
+/// Direct children of the node
+pub fn children(e: &Expr) -> Vec<&Expr> {
+    match &e.kind {
+        ExprKind::Binary(Binary { lhs, rhs, .. }) => vec![&**lhs, &**rhs],
+        ExprKind::Unary(Unary { expr, .. }) => vec![&**expr],
+        ExprKind::If(If {
+            cond,
+            then_branch,
+            else_branch,
+        }) => vec![&**cond, &**then_branch, &**else_branch],
+        ExprKind::Select(Select { obj, .. }) => vec![&**obj],
+        ExprKind::Apply(Apply { func, args }) => {
+            std::iter::once(&**func).chain(args.iter()).collect()
+        }
+        ExprKind::ApplyTypes(ApplyTypes { expr, .. }) => vec![&**expr],
+        ExprKind::Lambda(Lambda { body, .. }) => vec![&**body],
+        ExprKind::PredefFuncCall(PredefFuncCall { args, .. }) => args.iter().collect(),
+        ExprKind::Tuple(Tuple { items }) => items.iter().collect(),
+        ExprKind::Block(Block { vals, body }) => vals
+            .iter()
+            .map(|val| &*val.rhs)
+            .chain(std::iter::once(&**body))
+            .collect(),
+        ExprKind::Ident(_) | ExprKind::GlobalVars(_) | ExprKind::Literal(_) => Vec::new(),
+    }
+}
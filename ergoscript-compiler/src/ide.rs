@@ -0,0 +1,549 @@
+//! Source code analysis for the editor tooling (diagnostics, hover, go-to-definition and
+//! completion). Positions are byte offsets in the source.
+
+use std::convert::TryFrom;
+use std::ops::Range;
+
+use ergotree_ir::type_check::type_check;
+use ergotree_ir::types::stype::SType;
+use rowan::TextRange;
+use rowan::TextSize;
+
+use crate::ast;
+use crate::binder::Binder;
+use crate::compiler::compile_hir;
+use crate::compiler::CompileError;
+use crate::hir;
+use crate::mir::lower::lower;
+use crate::parser::parse;
+use crate::script_env::ScriptEnv;
+use crate::syntax::SyntaxKind;
+use crate::syntax::SyntaxNode;
+use crate::syntax::SyntaxToken;
+use crate::type_infer::assign_type_partial;
+use crate::type_infer::member_names;
+use crate::type_infer::property_tpe;
+
+/// Error found in the source code
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Diagnostic {
+    /// Byte range of the erroneous code
+    pub range: Range<usize>,
+    /// Error description
+    pub message: String,
+}
+
+/// Inferred type of the expression under the cursor
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HoverInfo {
+    /// Byte range of the expression
+    pub range: Range<usize>,
+    /// Expression type
+    pub tpe: SType,
+}
+
+/// Kind of the completion item
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CompletionKind {
+    /// Predefined global function
+    Function,
+    /// Global variable or the value defined in the script
+    Variable,
+    /// Property or method of the receiver
+    Property,
+}
+
+/// Completion suggestion
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CompletionItem {
+    /// Inserted text
+    pub label: String,
+    /// Item kind
+    pub kind: CompletionKind,
+    /// Signature or type
+    pub detail: String,
+}
+
+const GLOBAL_VARS: [(&str, &str); 8] = [
+    ("HEIGHT", "Int"),
+    ("SELF", "Box"),
+    ("INPUTS", "Coll[Box]"),
+    ("OUTPUTS", "Coll[Box]"),
+    ("CONTEXT", "Context"),
+    ("minerPubKey", "Coll[Byte]"),
+    ("groupGenerator", "GroupElement"),
+    ("LastBlockUtxoRootHash", "AvlTree"),
+];
+
+const GLOBAL_FUNCTIONS: [(&str, &str); 25] = [
+    ("sigmaProp", "(Boolean) => SigmaProp"),
+    ("proveDlog", "(GroupElement) => SigmaProp"),
+    (
+        "proveDHTuple",
+        "(GroupElement, GroupElement, GroupElement, GroupElement) => SigmaProp",
+    ),
+    ("atLeast", "(Int, Coll[SigmaProp]) => SigmaProp"),
+    ("allOf", "(Coll[Boolean]) => Boolean"),
+    ("anyOf", "(Coll[Boolean]) => Boolean"),
+    ("xorOf", "(Coll[Boolean]) => Boolean"),
+    ("allZK", "(Coll[SigmaProp]) => SigmaProp"),
+    ("anyZK", "(Coll[SigmaProp]) => SigmaProp"),
+    ("blake2b256", "(Coll[Byte]) => Coll[Byte]"),
+    ("sha256", "(Coll[Byte]) => Coll[Byte]"),
+    ("longToByteArray", "(Long) => Coll[Byte]"),
+    ("byteArrayToBigInt", "(Coll[Byte]) => BigInt"),
+    ("byteArrayToLong", "(Coll[Byte]) => Long"),
+    ("decodePoint", "(Coll[Byte]) => GroupElement"),
+    (
+        "substConstants",
+        "[T](Coll[Byte], Coll[Int], Coll[T]) => Coll[Byte]",
+    ),
+    ("getVar", "[T](Byte) => Option[T]"),
+    ("min", "[T](T, T) => T"),
+    ("max", "[T](T, T) => T"),
+    ("Coll", "[T](T*) => Coll[T]"),
+    ("PK", "(String) => SigmaProp"),
+    ("fromBase16", "(String) => Coll[Byte]"),
+    ("fromBase58", "(String) => Coll[Byte]"),
+    ("fromBase64", "(String) => Coll[Byte]"),
+    ("deserialize", "[T](String) => T"),
+];
+
+const BOX_PROPERTIES: [(&str, &str); 13] = [
+    ("value", "Long"),
+    ("propositionBytes", "Coll[Byte]"),
+    ("bytes", "Coll[Byte]"),
+    ("bytesWithoutRef", "Coll[Byte]"),
+    ("id", "Coll[Byte]"),
+    ("creationInfo", "(Int, Coll[Byte])"),
+    ("tokens", "Coll[(Coll[Byte], Long)]"),
+    ("R4", "[T] => Option[T]"),
+    ("R5", "[T] => Option[T]"),
+    ("R6", "[T] => Option[T]"),
+    ("R7", "[T] => Option[T]"),
+    ("R8", "[T] => Option[T]"),
+    ("R9", "[T] => Option[T]"),
+];
+
+/// Parse, binding and type errors of the script, all the errors of the first failed pass are
+/// reported (binding and type errors together)
+pub fn diagnostics(source: &str) -> Vec<Diagnostic> {
+    let hir = match compile_hir(source) {
+        Ok(hir) => hir,
+        Err(e) => return error_diagnostics(e, source),
+    };
+    let (typed, errors) = typed_hir(hir);
+    if !errors.is_empty() {
+        return errors;
+    }
+    match lower(typed)
+        .map_err(CompileError::from)
+        .and_then(|mir| Ok(type_check(mir)?))
+    {
+        Ok(_) => Vec::new(),
+        Err(e) => error_diagnostics(e, source),
+    }
+}
+
+/// Bound and typed tree along with the binding and type errors, erroneous expressions are left
+/// untyped
+fn typed_hir(hir: hir::Expr) -> (hir::Expr, Vec<Diagnostic>) {
+    let (bound, binder_errors) = Binder::new(ScriptEnv::new()).bind_partial(hir);
+    let (typed, type_errors) = assign_type_partial(bound);
+    let mut errors: Vec<Diagnostic> = binder_errors
+        .iter()
+        .map(|e| diagnostic(e.span(), e.msg()))
+        .chain(type_errors.iter().map(|e| diagnostic(e.span(), e.msg())))
+        .collect();
+    unknown_idents(&typed, &mut Vec::new(), &mut errors);
+    errors.sort_by_key(|d| d.range.start);
+    (typed, errors)
+}
+
+/// Identifiers left unbound and not defined in their scope (otherwise reported on MIR lowering
+/// only after the binding and type errors are fixed)
+fn unknown_idents(e: &hir::Expr, scope: &mut Vec<String>, errors: &mut Vec<Diagnostic>) {
+    let scope_len = scope.len();
+    match &e.kind {
+        hir::ExprKind::Ident(name) if !scope.contains(name) => {
+            errors.push(diagnostic(
+                e.span,
+                &format!("unknown identifier `{0}`", name),
+            ));
+        }
+        // use before the definition is reported by the binder
+        hir::ExprKind::Block(block) => scope.extend(block.vals.iter().map(|val| val.name.clone())),
+        hir::ExprKind::Lambda(lambda) => {
+            scope.extend(lambda.args.iter().map(|(name, _)| name.clone()))
+        }
+        _ => (),
+    }
+    for child in hir::children(e) {
+        unknown_idents(child, scope, errors);
+    }
+    scope.truncate(scope_len);
+}
+
+/// Tree of the script with parse errors that can be lowered to HIR (e.g. missing closing
+/// parenthesis) is still bound and typed
+fn partial_typed_hir(source: &str) -> Option<hir::Expr> {
+    let root = ast::Root::cast(parse(source).syntax())?;
+    let hir = hir::lower(root).ok()?;
+    Some(typed_hir(hir).0)
+}
+
+fn diagnostic(span: TextRange, message: &str) -> Diagnostic {
+    Diagnostic {
+        range: range(span),
+        message: message.to_string(),
+    }
+}
+
+fn error_diagnostics(error: CompileError, source: &str) -> Vec<Diagnostic> {
+    match error {
+        CompileError::ParseError(errors) => errors
+            .iter()
+            .map(|e| diagnostic(e.span, &e.to_string()))
+            .collect(),
+        CompileError::HirLoweringError(e) => vec![diagnostic(e.span(), e.msg())],
+        CompileError::BinderError(e) => vec![diagnostic(e.span(), e.msg())],
+        CompileError::TypeInferenceError(e) => vec![diagnostic(e.span(), e.msg())],
+        CompileError::MirLoweringError(e) => vec![diagnostic(e.span(), e.msg())],
+        // errors without the source position are reported for the whole script
+        CompileError::TypeCheckError(e) => vec![Diagnostic {
+            range: 0..source.len(),
+            message: e.pretty_desc(),
+        }],
+        CompileError::ErgoTreeError(e) => vec![Diagnostic {
+            range: 0..source.len(),
+            message: format!("{:?}", e),
+        }],
+    }
+}
+
+/// Type of the innermost typed expression at the given offset (errors elsewhere in the script
+/// leave only the erroneous expressions untyped)
+pub fn hover(source: &str, offset: usize) -> Option<HoverInfo> {
+    let typed = partial_typed_hir(source)?;
+    find_type(&typed, TextSize::try_from(offset).ok()?)
+}
+
+fn find_type(e: &hir::Expr, offset: TextSize) -> Option<HoverInfo> {
+    if !e.span.contains(offset) {
+        return None;
+    }
+    if let hir::ExprKind::Block(block) = &e.kind {
+        // the name of the value has the type of its right-hand side
+        if let Some(val) = block.vals.iter().find(|val| val.span.contains(offset)) {
+            return find_type(&val.rhs, offset).or_else(|| {
+                val.rhs.tpe.clone().map(|tpe| HoverInfo {
+                    range: range(val.span),
+                    tpe,
+                })
+            });
+        }
+    }
+    hir::children(e)
+        .into_iter()
+        .find_map(|child| find_type(child, offset))
+        .or_else(|| {
+            e.tpe.clone().map(|tpe| HoverInfo {
+                range: range(e.span),
+                tpe,
+            })
+        })
+}
+
+/// Range of the name in the definition of the value, function or lambda argument referred by
+/// the identifier at the given offset
+pub fn goto_definition(source: &str, offset: usize) -> Option<Range<usize>> {
+    let root = parse(source).syntax();
+    let token = token_at(&root, offset)?
+        .find(|token| token.kind() == SyntaxKind::Ident)
+        .filter(|token| {
+            token
+                .ancestors()
+                .next()
+                .map_or(false, |node| node.kind() == SyntaxKind::Ident)
+        })?;
+    let usage = token.text_range().start();
+    token
+        .ancestors()
+        .find_map(|scope| {
+            scope_defs(scope, usage)
+                .into_iter()
+                .rev()
+                .find(|def| def.text() == token.text())
+        })
+        .map(|def| range(def.text_range()))
+}
+
+/// Completion suggestions at the given offset: properties and methods of the receiver type after
+/// `.`, otherwise the values in scope, global variables and functions
+pub fn completions(source: &str, offset: usize) -> Vec<CompletionItem> {
+    let root = parse(source).syntax();
+    let token = token_at(&root, offset).and_then(|tokens| tokens.left_biased());
+    // skip the identifier being typed
+    let prev = match &token {
+        Some(token) if token.kind() == SyntaxKind::Ident => prev_non_trivia(token),
+        Some(token) if is_trivia(token) => prev_non_trivia(token),
+        Some(token) => Some(token.clone()),
+        None => None,
+    };
+    let item = |kind: CompletionKind| {
+        move |(label, detail): &(&str, &str)| CompletionItem {
+            label: label.to_string(),
+            kind,
+            detail: detail.to_string(),
+        }
+    };
+    if let Some(dot) = prev.filter(|prev| prev.kind() == SyntaxKind::Dot) {
+        return match receiver_tpe(source, &dot) {
+            Some(SType::SBox) => BOX_PROPERTIES
+                .iter()
+                .map(item(CompletionKind::Property))
+                .collect(),
+            Some(tpe) => member_names(&tpe)
+                .into_iter()
+                .map(|name| CompletionItem {
+                    detail: property_tpe(&tpe, &name)
+                        .map(|tpe| tpe.to_string())
+                        .unwrap_or_default(),
+                    label: name,
+                    kind: CompletionKind::Property,
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+    }
+    let usage = TextSize::try_from(offset).unwrap_or_default();
+    let locals = match &token {
+        Some(token) => token
+            .ancestors()
+            .flat_map(|scope| scope_defs(scope, usage))
+            .collect(),
+        None => scope_defs(root, usage),
+    };
+    let mut items: Vec<CompletionItem> = Vec::new();
+    for def in locals {
+        if !items.iter().any(|item| item.label == def.text()) {
+            items.push(CompletionItem {
+                label: def.text().to_string(),
+                kind: CompletionKind::Variable,
+                detail: String::new(),
+            });
+        }
+    }
+    items.extend(GLOBAL_VARS.iter().map(item(CompletionKind::Variable)));
+    items.extend(GLOBAL_FUNCTIONS.iter().map(item(CompletionKind::Function)));
+    items
+}
+
+/// Type of the receiver of the property access with the given dot
+fn receiver_tpe(source: &str, dot: &SyntaxToken) -> Option<SType> {
+    let dot_range = dot.text_range();
+    let typed = match dot.next_token() {
+        Some(token) if token.kind() == SyntaxKind::Ident => partial_typed_hir(source)?,
+        // the property name is not typed yet
+        _ => {
+            let dot_end = usize::from(dot_range.end());
+            partial_typed_hir(&format!("{0}x{1}", &source[..dot_end], &source[dot_end..]))?
+        }
+    };
+    find_receiver(&typed, dot_range.start())?.tpe.clone()
+}
+
+/// Innermost object of the property access with the dot at the given offset
+fn find_receiver(e: &hir::Expr, dot: TextSize) -> Option<&hir::Expr> {
+    if !e.span.contains(dot) {
+        return None;
+    }
+    hir::children(e)
+        .into_iter()
+        .find_map(|child| find_receiver(child, dot))
+        .or(match &e.kind {
+            hir::ExprKind::Select(select) if select.obj.span.end() <= dot => Some(&*select.obj),
+            _ => None,
+        })
+}
+
+fn token_at(root: &SyntaxNode, offset: usize) -> Option<rowan::TokenAtOffset<SyntaxToken>> {
+    let offset = TextSize::try_from(offset).ok()?;
+    if offset > root.text_range().end() {
+        return None;
+    }
+    Some(root.token_at_offset(offset))
+}
+
+fn is_trivia(token: &SyntaxToken) -> bool {
+    matches!(token.kind(), SyntaxKind::Whitespace | SyntaxKind::Comment)
+}
+
+fn prev_non_trivia(token: &SyntaxToken) -> Option<SyntaxToken> {
+    let mut prev = token.prev_token();
+    while let Some(token) = prev.as_ref().filter(|token| is_trivia(token)) {
+        prev = token.prev_token();
+    }
+    prev
+}
+
+/// Names defined by the node that are visible at `usage` (innermost last): lambda and function
+/// arguments, or the values and functions defined in the block before `usage`
+fn scope_defs(scope: SyntaxNode, usage: TextSize) -> Vec<SyntaxToken> {
+    let stmts: Vec<ast::Stmt> = match scope.kind() {
+        SyntaxKind::Root => match ast::Root::cast(scope) {
+            Some(root) => root.stmts().collect(),
+            None => Vec::new(),
+        },
+        SyntaxKind::FunctionDef => match ast::Stmt::cast(scope) {
+            Some(ast::Stmt::FunctionDef(def)) => {
+                return def.params().filter_map(|p| p.name().ok()).collect()
+            }
+            _ => Vec::new(),
+        },
+        _ => match ast::Expr::cast(scope) {
+            Some(ast::Expr::LambdaExpr(lambda)) => {
+                return lambda.params().filter_map(|p| p.name().ok()).collect()
+            }
+            Some(ast::Expr::BlockExpr(block)) => block.stmts().collect(),
+            _ => Vec::new(),
+        },
+    };
+    stmts
+        .into_iter()
+        .filter_map(|stmt| match stmt {
+            ast::Stmt::VariableDef(def) if def.span().end() <= usage => def.name().ok(),
+            ast::Stmt::FunctionDef(def) if def.span().end() <= usage => def.name().ok(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn range(span: TextRange) -> Range<usize> {
+    usize::from(span.start())..usize::from(span.end())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Offset of the first occurrence of `pattern` in `source`
+    fn at(source: &str, pattern: &str) -> usize {
+        source.find(pattern).unwrap()
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        assert_eq!(diagnostics("sigmaProp(HEIGHT > 1)"), Vec::new());
+        let source = "val a = 1\nHEIGHT > b";
+        let res = diagnostics(source);
+        assert_eq!(res.len(), 1);
+        assert_eq!(&source[res[0].range.clone()], "b");
+        let source = "sigmaProp(HEIGHT + true)";
+        let res = diagnostics(source);
+        assert_eq!(res.len(), 1);
+        assert!(source[res[0].range.clone()].starts_with("HEIGHT + true"));
+        assert!(!diagnostics("HEIGHT +").is_empty());
+        let source = "val a = b\nval c = HEIGHT + true\nsigmaProp(a > SELF.foo)";
+        let res: Vec<&str> = diagnostics(source)
+            .into_iter()
+            .map(|d| source[d.range].trim_end())
+            .collect();
+        assert_eq!(res, vec!["b", "HEIGHT + true", "SELF.foo"]);
+    }
+
+    #[test]
+    fn test_hover() {
+        let source = "val limit = SELF.value\nsigmaProp(limit > 1L && OUTPUTS.size > 1)";
+        let res = hover(source, at(source, "SELF")).unwrap();
+        assert_eq!(res.tpe, SType::SBox);
+        assert_eq!(&source[res.range], "SELF");
+        assert_eq!(
+            hover(source, at(source, "value")).unwrap().tpe,
+            SType::SLong
+        );
+        assert_eq!(
+            hover(source, at(source, "limit")).unwrap().tpe,
+            SType::SLong
+        );
+        assert_eq!(
+            hover(source, at(source, "limit >")).unwrap().tpe,
+            SType::SLong
+        );
+        assert_eq!(hover(source, at(source, "size")).unwrap().tpe, SType::SInt);
+        // type errors elsewhere in the script
+        let source = "val a = HEIGHT + true\nval b = SELF.value\nsigmaProp(b > c)";
+        assert_eq!(hover(source, 0), None);
+        assert_eq!(
+            hover(source, at(source, "HEIGHT")).unwrap().tpe,
+            SType::SInt
+        );
+        assert_eq!(hover(source, at(source, "b >")).unwrap().tpe, SType::SLong);
+        assert_eq!(hover(source, at(source, "+")), None);
+        // missing closing parenthesis
+        let source = "sigmaProp(SELF.value > 1L";
+        assert_eq!(
+            hover(source, at(source, "value")).unwrap().tpe,
+            SType::SLong
+        );
+    }
+
+    #[test]
+    fn test_goto_definition() {
+        let source = "val x = 1\ndef f(x: Int) = x + 1\nval y = { (x: Int) => x }(x)\nf(x) + y";
+        let def = |pattern: &str| {
+            let offset = source.rfind(pattern).unwrap();
+            goto_definition(source, offset).map(|range| range.start)
+        };
+        // global value
+        assert_eq!(def("x) + y"), Some(at(source, "x = 1")));
+        // function argument
+        assert_eq!(def("x + 1"), Some(at(source, "x: Int)")));
+        // lambda argument
+        assert_eq!(def("x }"), Some(at(source, "x: Int) =>")));
+        // lambda application argument refers to the global value
+        assert_eq!(def("x)\n"), Some(at(source, "x = 1")));
+        assert_eq!(def("f(x)"), Some(at(source, "f(x: Int)")));
+        assert_eq!(def("y"), Some(at(source, "y = {")));
+        assert_eq!(goto_definition("HEIGHT", 0), None);
+    }
+
+    #[test]
+    fn test_completions() {
+        let labels = |source: &str, offset: usize| -> Vec<String> {
+            completions(source, offset)
+                .into_iter()
+                .map(|item| item.label)
+                .collect()
+        };
+        let source = "SELF.";
+        let res = labels(source, source.len());
+        assert!(res.contains(&"value".to_string()));
+        assert!(res.contains(&"R4".to_string()));
+        assert!(!res.contains(&"HEIGHT".to_string()));
+        let source = "SELF.va";
+        assert!(labels(source, source.len()).contains(&"propositionBytes".to_string()));
+        let source = "sigmaProp(OUTPUTS.";
+        let res = labels(source, source.len());
+        assert!(res.contains(&"size".to_string()));
+        assert!(res.contains(&"exists".to_string()));
+        assert!(!res.contains(&"value".to_string()));
+        let source = "val v = SELF.value\nsigmaProp(v.to";
+        let res = labels(source, source.len());
+        assert!(res.contains(&"toBigInt".to_string()));
+        assert!(!res.contains(&"R4".to_string()));
+        let source = "sigmaProp(SELF.R4[Int].";
+        let res = labels(source, source.len());
+        assert!(res.contains(&"isDefined".to_string()));
+        assert!(!res.contains(&"value".to_string()));
+        assert!(labels("x.", 2).is_empty());
+        let source = "val limit = 1\ndef f(x: Int) = x\nsigmaProp(HEIGHT > l";
+        let res = labels(source, source.len());
+        assert!(res.contains(&"limit".to_string()));
+        assert!(res.contains(&"f".to_string()));
+        assert!(!res.contains(&"x".to_string()));
+        assert!(res.contains(&"HEIGHT".to_string()));
+        assert!(res.contains(&"sigmaProp".to_string()));
+        assert!(labels("", 0).contains(&"PK".to_string()));
+    }
+}
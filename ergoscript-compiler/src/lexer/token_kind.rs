@@ -14,7 +14,19 @@ pub enum TokenKind {
     #[token("val")]
     ValKw,
 
-    #[regex("[A-Za-z][A-Za-z0-9]*")]
+    #[token("if")]
+    IfKw,
+
+    #[token("else")]
+    ElseKw,
+
+    #[token("true")]
+    TrueKw,
+
+    #[token("false")]
+    FalseKw,
+
+    #[regex("[A-Za-z_][A-Za-z0-9_]*")]
     Ident,
 
     #[regex("[0-9]+")]
@@ -23,6 +35,9 @@ pub enum TokenKind {
     #[regex("[0-9]+L")]
     LongNumber,
 
+    #[regex(r#""[^"]*""#)]
+    StringLiteral,
+
     #[token("+")]
     Plus,
 
@@ -35,12 +50,42 @@ pub enum TokenKind {
     #[token("/")]
     Slash,
 
+    #[token("%")]
+    Percent,
+
     #[token("&&")]
     And,
 
+    #[token("||")]
+    Or,
+
+    #[token("^")]
+    Caret,
+
+    #[token("!")]
+    Bang,
+
     #[token("=")]
     Equals,
 
+    #[token("==")]
+    EqualsEquals,
+
+    #[token("!=")]
+    NotEquals,
+
+    #[token("<")]
+    Less,
+
+    #[token("<=")]
+    LessEquals,
+
+    #[token(">")]
+    Greater,
+
+    #[token(">=")]
+    GreaterEquals,
+
     #[token("(")]
     LParen,
 
@@ -53,6 +98,27 @@ pub enum TokenKind {
     #[token("}")]
     RBrace,
 
+    #[token("[")]
+    LBracket,
+
+    #[token("]")]
+    RBracket,
+
+    #[token(".")]
+    Dot,
+
+    #[token(",")]
+    Comma,
+
+    #[token(":")]
+    Colon,
+
+    #[token(";")]
+    Semicolon,
+
+    #[token("=>")]
+    FatArrow,
+
     #[regex("//.*")]
     Comment,
 
@@ -72,19 +138,41 @@ impl fmt::Display for TokenKind {
             Self::Whitespace => "whitespace",
             Self::FnKw => "‘def’",
             Self::ValKw => "‘val’",
+            Self::IfKw => "‘if’",
+            Self::ElseKw => "‘else’",
+            Self::TrueKw => "‘true’",
+            Self::FalseKw => "‘false’",
             Self::Ident => "identifier",
             Self::IntNumber => "number",
             Self::LongNumber => "number",
+            Self::StringLiteral => "string",
             Self::Plus => "‘+’",
             Self::Minus => "‘-’",
             Self::Star => "‘*’",
             Self::Slash => "‘/’",
+            Self::Percent => "‘%’",
             Self::And => "‘&&’",
+            Self::Or => "‘||’",
+            Self::Caret => "‘^’",
+            Self::Bang => "‘!’",
             Self::Equals => "‘=’",
+            Self::EqualsEquals => "‘==’",
+            Self::NotEquals => "‘!=’",
+            Self::Less => "‘<’",
+            Self::LessEquals => "‘<=’",
+            Self::Greater => "‘>’",
+            Self::GreaterEquals => "‘>=’",
             Self::LParen => "‘(’",
             Self::RParen => "‘)’",
             Self::LBrace => "‘{’",
             Self::RBrace => "‘}’",
+            Self::LBracket => "‘[’",
+            Self::RBracket => "‘]’",
+            Self::Dot => "‘.’",
+            Self::Comma => "‘,’",
+            Self::Colon => "‘:’",
+            Self::Semicolon => "‘;’",
+            Self::FatArrow => "‘=>’",
             Self::Comment => "comment",
             Self::Error => "an unrecognized token",
         })
@@ -119,6 +207,31 @@ mod tests {
         check("val", TokenKind::ValKw);
     }
 
+    #[test]
+    fn lex_if_keyword() {
+        check("if", TokenKind::IfKw);
+    }
+
+    #[test]
+    fn lex_else_keyword() {
+        check("else", TokenKind::ElseKw);
+    }
+
+    #[test]
+    fn lex_true_keyword() {
+        check("true", TokenKind::TrueKw);
+    }
+
+    #[test]
+    fn lex_false_keyword() {
+        check("false", TokenKind::FalseKw);
+    }
+
+    #[test]
+    fn lex_keyword_prefixed_identifier() {
+        check("iffy", TokenKind::Ident);
+    }
+
     #[test]
     fn lex_alphabetic_identifier() {
         check("abcd", TokenKind::Ident);
@@ -139,11 +252,25 @@ mod tests {
         check("x", TokenKind::Ident);
     }
 
+    #[test]
+    fn lex_underscored_identifier() {
+        check("_1", TokenKind::Ident);
+        check("min_value", TokenKind::Ident);
+    }
+
     #[test]
     fn lex_number() {
         check("123456", TokenKind::IntNumber);
     }
 
+    #[test]
+    fn lex_string_literal() {
+        check(
+            r#""9fRusAarL1KkrWQVsxSRVYnvWxaAT2A96cKtNn9tvPh5XUyCisr""#,
+            TokenKind::StringLiteral,
+        );
+    }
+
     #[test]
     fn lex_plus() {
         check("+", TokenKind::Plus);
@@ -164,11 +291,66 @@ mod tests {
         check("/", TokenKind::Slash);
     }
 
+    #[test]
+    fn lex_percent() {
+        check("%", TokenKind::Percent);
+    }
+
+    #[test]
+    fn lex_and() {
+        check("&&", TokenKind::And);
+    }
+
+    #[test]
+    fn lex_or() {
+        check("||", TokenKind::Or);
+    }
+
+    #[test]
+    fn lex_caret() {
+        check("^", TokenKind::Caret);
+    }
+
+    #[test]
+    fn lex_bang() {
+        check("!", TokenKind::Bang);
+    }
+
     #[test]
     fn lex_equals() {
         check("=", TokenKind::Equals);
     }
 
+    #[test]
+    fn lex_equals_equals() {
+        check("==", TokenKind::EqualsEquals);
+    }
+
+    #[test]
+    fn lex_not_equals() {
+        check("!=", TokenKind::NotEquals);
+    }
+
+    #[test]
+    fn lex_less() {
+        check("<", TokenKind::Less);
+    }
+
+    #[test]
+    fn lex_less_equals() {
+        check("<=", TokenKind::LessEquals);
+    }
+
+    #[test]
+    fn lex_greater() {
+        check(">", TokenKind::Greater);
+    }
+
+    #[test]
+    fn lex_greater_equals() {
+        check(">=", TokenKind::GreaterEquals);
+    }
+
     #[test]
     fn lex_left_parenthesis() {
         check("(", TokenKind::LParen);
@@ -189,6 +371,41 @@ mod tests {
         check("}", TokenKind::RBrace);
     }
 
+    #[test]
+    fn lex_left_bracket() {
+        check("[", TokenKind::LBracket);
+    }
+
+    #[test]
+    fn lex_right_bracket() {
+        check("]", TokenKind::RBracket);
+    }
+
+    #[test]
+    fn lex_dot() {
+        check(".", TokenKind::Dot);
+    }
+
+    #[test]
+    fn lex_comma() {
+        check(",", TokenKind::Comma);
+    }
+
+    #[test]
+    fn lex_colon() {
+        check(":", TokenKind::Colon);
+    }
+
+    #[test]
+    fn lex_semicolon() {
+        check(";", TokenKind::Semicolon);
+    }
+
+    #[test]
+    fn lex_fat_arrow() {
+        check("=>", TokenKind::FatArrow);
+    }
+
     #[test]
     fn lex_comment() {
         check("// foo", TokenKind::Comment);
@@ -24,4 +24,6 @@ pub(crate) mod syntax;
 pub(crate) mod type_infer;
 
 pub mod compiler;
+pub mod decompiler;
+pub mod ide;
 pub mod script_env;
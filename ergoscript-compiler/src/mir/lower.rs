@@ -1,14 +1,88 @@
+use std::convert::TryFrom;
+
+use ergotree_ir::mir::and::And;
+use ergotree_ir::mir::apply::Apply;
+use ergotree_ir::mir::atleast::Atleast;
 use ergotree_ir::mir::bin_op::ArithOp;
 use ergotree_ir::mir::bin_op::BinOp;
 use ergotree_ir::mir::bin_op::BinOpKind;
-use ergotree_ir::mir::constant::Constant;
+use ergotree_ir::mir::bin_op::BitOp;
+use ergotree_ir::mir::bin_op::LogicalOp;
+use ergotree_ir::mir::bin_op::RelationOp;
+use ergotree_ir::mir::block::BlockValue;
+use ergotree_ir::mir::bool_to_sigma::BoolToSigmaProp;
+use ergotree_ir::mir::byte_array_to_bigint::ByteArrayToBigInt;
+use ergotree_ir::mir::byte_array_to_long::ByteArrayToLong;
+use ergotree_ir::mir::calc_blake2b256::CalcBlake2b256;
+use ergotree_ir::mir::calc_sha256::CalcSha256;
+use ergotree_ir::mir::coll_append::Append;
+use ergotree_ir::mir::coll_by_index::ByIndex;
+use ergotree_ir::mir::coll_exists::Exists;
+use ergotree_ir::mir::coll_filter::Filter;
+use ergotree_ir::mir::coll_fold::Fold;
+use ergotree_ir::mir::coll_forall::ForAll;
+use ergotree_ir::mir::coll_map::Map;
+use ergotree_ir::mir::coll_size::SizeOf;
+use ergotree_ir::mir::coll_slice::Slice;
+use ergotree_ir::mir::collection::Collection;
+use ergotree_ir::mir::create_prove_dh_tuple::CreateProveDhTuple;
+use ergotree_ir::mir::create_provedlog::CreateProveDlog;
+use ergotree_ir::mir::decode_point::DecodePoint;
+use ergotree_ir::mir::downcast::Downcast;
+use ergotree_ir::mir::exponentiate::Exponentiate;
 use ergotree_ir::mir::expr::Expr;
+use ergotree_ir::mir::expr::InvalidArgumentError;
+use ergotree_ir::mir::extract_amount::ExtractAmount;
+use ergotree_ir::mir::extract_bytes::ExtractBytes;
+use ergotree_ir::mir::extract_bytes_with_no_ref::ExtractBytesWithNoRef;
+use ergotree_ir::mir::extract_creation_info::ExtractCreationInfo;
+use ergotree_ir::mir::extract_id::ExtractId;
+use ergotree_ir::mir::extract_reg_as::ExtractRegisterAs;
+use ergotree_ir::mir::extract_script_bytes::ExtractScriptBytes;
+use ergotree_ir::mir::func_value::FuncArg;
+use ergotree_ir::mir::func_value::FuncValue;
+use ergotree_ir::mir::get_var::GetVar;
 use ergotree_ir::mir::global_vars::GlobalVars;
+use ergotree_ir::mir::if_op::If;
+use ergotree_ir::mir::logical_not::LogicalNot;
+use ergotree_ir::mir::long_to_byte_array::LongToByteArray;
+use ergotree_ir::mir::method_call::MethodCall;
+use ergotree_ir::mir::multiply_group::MultiplyGroup;
+use ergotree_ir::mir::negation::Negation;
+use ergotree_ir::mir::option_get::OptionGet;
+use ergotree_ir::mir::option_get_or_else::OptionGetOrElse;
+use ergotree_ir::mir::option_is_defined::OptionIsDefined;
+use ergotree_ir::mir::or::Or;
+use ergotree_ir::mir::property_call::PropertyCall;
+use ergotree_ir::mir::select_field::SelectField;
+use ergotree_ir::mir::select_field::TupleFieldIndex;
+use ergotree_ir::mir::sigma_and::SigmaAnd;
+use ergotree_ir::mir::sigma_or::SigmaOr;
+use ergotree_ir::mir::sigma_prop_bytes::SigmaPropBytes;
+use ergotree_ir::mir::subst_const::SubstConstants;
+use ergotree_ir::mir::tuple::Tuple;
+use ergotree_ir::mir::unary_op::OneArgOpTryBuild;
+use ergotree_ir::mir::upcast::Upcast;
+use ergotree_ir::mir::val_def::ValDef;
+use ergotree_ir::mir::val_def::ValId;
+use ergotree_ir::mir::val_use::ValUse;
+use ergotree_ir::mir::xor_of::XorOf;
+use ergotree_ir::types::sbox;
+use ergotree_ir::types::scontext;
+use ergotree_ir::types::stuple::STuple;
+use ergotree_ir::types::stype::SType;
 use hir::BinaryOp;
+use hir::PredefFunc;
 use rowan::TextRange;
 
 use crate::error::pretty_error_desc;
 use crate::hir;
+use crate::type_infer::numeric_cast_tpe;
+use crate::type_infer::numeric_rank;
+use crate::type_infer::numeric_upcast_tpe;
+use crate::type_infer::specialized_method;
+use crate::type_infer::tuple_field_index;
+use crate::type_infer::COLL_METHODS;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct MirLoweringError {
@@ -24,37 +98,374 @@ impl MirLoweringError {
     pub fn pretty_desc(&self, source: &str) -> String {
         pretty_error_desc(source, self.span, &self.msg)
     }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.span
+    }
+}
+
+/// Lambda arguments and values in scope (innermost last) and the next unused [`ValId`]
+struct Scope {
+    vars: Vec<(String, Expr)>,
+    next_val_id: u32,
+}
+
+impl Default for Scope {
+    fn default() -> Self {
+        // ids start from 1 as in the trees produced by the reference compiler
+        Scope {
+            vars: Vec::new(),
+            next_val_id: 1,
+        }
+    }
+}
+
+/// State of the [`Scope`] to be restored on leaving the nested scope
+struct ScopeMark {
+    vars_len: usize,
+    next_val_id: u32,
+}
+
+impl Scope {
+    fn fresh_val_id(&mut self) -> ValId {
+        let id = ValId(self.next_val_id);
+        self.next_val_id += 1;
+        id
+    }
+
+    /// Ids of the lambda body start after a gap of one id following the argument ids, as in the
+    /// reference compiler
+    fn skip_val_id(&mut self) {
+        self.next_val_id += 1;
+    }
+
+    fn mark(&self) -> ScopeMark {
+        ScopeMark {
+            vars_len: self.vars.len(),
+            next_val_id: self.next_val_id,
+        }
+    }
+
+    /// Ids are reused after leaving the nested scope (numbered by the nesting depth as in the
+    /// reference compiler), so variables of the sibling scopes may share the same id
+    fn reset(&mut self, mark: ScopeMark) {
+        self.vars.truncate(mark.vars_len);
+        self.next_val_id = mark.next_val_id;
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Expr> {
+        self.vars
+            .iter()
+            .rev()
+            .find(|(var_name, _)| var_name == name)
+            .map(|(_, expr)| expr)
+    }
 }
 
 pub fn lower(hir_expr: hir::Expr) -> Result<Expr, MirLoweringError> {
+    lower_in(hir_expr, &mut Scope::default())
+}
+
+fn lower_in(hir_expr: hir::Expr, scope: &mut Scope) -> Result<Expr, MirLoweringError> {
+    let invalid_arg = |e: InvalidArgumentError| {
+        MirLoweringError::new(format!("MIR error: {0}", e), hir_expr.span)
+    };
     let mir: Expr = match &hir_expr.kind {
         hir::ExprKind::GlobalVars(hir) => match hir {
             hir::GlobalVars::Height => GlobalVars::Height.into(),
+            hir::GlobalVars::SelfBox => GlobalVars::SelfBox.into(),
+            hir::GlobalVars::Inputs => GlobalVars::Inputs.into(),
+            hir::GlobalVars::Outputs => GlobalVars::Outputs.into(),
+            hir::GlobalVars::Context => Expr::Context,
+            hir::GlobalVars::MinerPubKey => GlobalVars::MinerPubKey.into(),
+            hir::GlobalVars::GroupGenerator => GlobalVars::GroupGenerator.into(),
+            hir::GlobalVars::LastBlockUtxoRootHash => GlobalVars::LastBlockUtxoRootHash.into(),
+        },
+        hir::ExprKind::Ident(name) => match scope.lookup(name) {
+            Some(arg) => arg.clone(),
+            None => {
+                return Err(MirLoweringError::new(
+                    format!("unknown identifier `{0}`", name),
+                    hir_expr.span,
+                ))
+            }
         },
-        hir::ExprKind::Ident(_) => {
-            return Err(MirLoweringError::new(
-                format!("MIR error: Unresolved Ident {0:?}", hir_expr),
-                hir_expr.span,
-            ))
-        }
         hir::ExprKind::Binary(hir) => {
-            let l = lower(*hir.lhs.clone())?;
-            let r = lower(*hir.rhs.clone())?;
-            BinOp {
-                kind: hir.op.node.clone().into(),
-                left: l.into(),
-                right: r.into(),
+            let l = lower_in(*hir.lhs.clone(), scope)?;
+            let r = lower_in(*hir.rhs.clone(), scope)?;
+            // numeric operands of different types are upcast to the widest type
+            let (l, r) = match numeric_upcast_tpe(&l.tpe(), &r.tpe()) {
+                Some(tpe) if l.tpe() != r.tpe() => (
+                    lower_numeric_cast(l, tpe.clone()).map_err(invalid_arg)?,
+                    lower_numeric_cast(r, tpe).map_err(invalid_arg)?,
+                ),
+                _ => (l, r),
+            };
+            // `&&` and `||` with a SigmaProp operand are sigma conjectures, a Boolean operand is
+            // converted with `sigmaProp`
+            let sigma_operands = l.tpe() == SType::SSigmaProp || r.tpe() == SType::SSigmaProp;
+            match hir.op.node {
+                BinaryOp::And if sigma_operands => SigmaAnd::new(vec![
+                    lower_to_sigma_prop(l).map_err(invalid_arg)?,
+                    lower_to_sigma_prop(r).map_err(invalid_arg)?,
+                ])
+                .map_err(invalid_arg)?
+                .into(),
+                BinaryOp::Or if sigma_operands => SigmaOr::new(vec![
+                    lower_to_sigma_prop(l).map_err(invalid_arg)?,
+                    lower_to_sigma_prop(r).map_err(invalid_arg)?,
+                ])
+                .map_err(invalid_arg)?
+                .into(),
+                _ => {
+                    let kind = match hir.op.node {
+                        // `^` on numeric operands is a bitwise xor
+                        BinaryOp::Xor if l.tpe() != SType::SBoolean => BitOp::BitXor.into(),
+                        _ => hir.op.node.clone().into(),
+                    };
+                    BinOp {
+                        kind,
+                        left: l.into(),
+                        right: r.into(),
+                    }
+                    .into()
+                }
             }
-            .into()
         }
-        hir::ExprKind::Literal(hir) => {
-            let constant: Constant = match *hir {
-                hir::Literal::Int(v) => v.into(),
-                hir::Literal::Long(v) => v.into(),
-            };
-            constant.into()
+        hir::ExprKind::Unary(hir) => match (&hir.op.node, &hir.expr.kind) {
+            // negative numeric literals are constants
+            (hir::UnaryOp::Neg, hir::ExprKind::Literal(hir::Literal::Int(v))) => (-v).into(),
+            (hir::UnaryOp::Neg, hir::ExprKind::Literal(hir::Literal::Long(v))) => (-v).into(),
+            (op, _) => {
+                let input = lower_in(*hir.expr.clone(), scope)?.into();
+                match op {
+                    hir::UnaryOp::Neg => Negation { input }.into(),
+                    hir::UnaryOp::Not => LogicalNot { input }.into(),
+                }
+            }
+        },
+        hir::ExprKind::If(hir) => If {
+            condition: lower_in(*hir.cond.clone(), scope)?.into(),
+            true_branch: lower_in(*hir.then_branch.clone(), scope)?.into(),
+            false_branch: lower_in(*hir.else_branch.clone(), scope)?.into(),
         }
+        .into(),
+        hir::ExprKind::Select(hir) => {
+            let obj = lower_in(*hir.obj.clone(), scope)?;
+            match (obj.tpe(), hir.field.as_str()) {
+                (SType::SColl(_), "size") => SizeOf::try_build(obj).map_err(invalid_arg)?.into(),
+                (SType::SBox, "value") => {
+                    ExtractAmount::try_build(obj).map_err(invalid_arg)?.into()
+                }
+                (SType::SBox, "propositionBytes") => ExtractScriptBytes::try_build(obj)
+                    .map_err(invalid_arg)?
+                    .into(),
+                (SType::SBox, "id") => ExtractId::try_build(obj).map_err(invalid_arg)?.into(),
+                (SType::SBox, "bytes") => ExtractBytes::try_build(obj).map_err(invalid_arg)?.into(),
+                (SType::SBox, "bytesWithoutRef") => ExtractBytesWithNoRef::try_build(obj)
+                    .map_err(invalid_arg)?
+                    .into(),
+                (SType::SBox, "creationInfo") => ExtractCreationInfo::try_build(obj)
+                    .map_err(invalid_arg)?
+                    .into(),
+                (SType::SSigmaProp, "propBytes") => {
+                    SigmaPropBytes::try_build(obj).map_err(invalid_arg)?.into()
+                }
+                (SType::SBox, "tokens") => PropertyCall::new(obj, sbox::TOKENS_METHOD.clone())
+                    .map_err(invalid_arg)?
+                    .into(),
+                (SType::SContext, "dataInputs") => {
+                    PropertyCall::new(obj, scontext::DATA_INPUTS_PROPERTY.clone())
+                        .map_err(invalid_arg)?
+                        .into()
+                }
+                (SType::SOption(_), "get") => {
+                    OptionGet::try_build(obj).map_err(invalid_arg)?.into()
+                }
+                (SType::SOption(_), "isDefined") => {
+                    OptionIsDefined::try_build(obj).map_err(invalid_arg)?.into()
+                }
+                (SType::STuple(_), field) if tuple_field_index(field).is_some() => {
+                    let field_index = tuple_field_index(field)
+                        .and_then(|index| TupleFieldIndex::try_from(index).ok())
+                        .ok_or_else(|| {
+                            MirLoweringError::new(
+                                format!("MIR error: invalid tuple field {0}", field),
+                                hir_expr.span,
+                            )
+                        })?;
+                    SelectField::new(obj, field_index)
+                        .map_err(invalid_arg)?
+                        .into()
+                }
+                (tpe, field) if tpe.is_numeric() && numeric_cast_tpe(field).is_some() => {
+                    match numeric_cast_tpe(field) {
+                        Some(target) => lower_numeric_cast(obj, target).map_err(invalid_arg)?,
+                        None => obj,
+                    }
+                }
+                (SType::SBox, _) if hir.register_id().is_some() => {
+                    return Err(MirLoweringError::new(
+                        format!(
+                            "MIR error: register access requires a type argument, e.g. {0}[Int]",
+                            hir.field
+                        ),
+                        hir_expr.span,
+                    ))
+                }
+                (tpe, field) => match specialized_method(&tpe, field, &[]) {
+                    Some(method) => PropertyCall::new(obj, method).map_err(invalid_arg)?.into(),
+                    None => {
+                        return Err(MirLoweringError::new(
+                            format!("MIR error: unknown property {0} of {1}", field, tpe),
+                            hir_expr.span,
+                        ))
+                    }
+                },
+            }
+        }
+        hir::ExprKind::Apply(hir) => {
+            if let hir::ExprKind::Select(select) = &hir.func.kind {
+                // methods are typed only when applied to the arguments
+                if hir.func.tpe.is_none() {
+                    let obj = lower_in(*select.obj.clone(), scope)?;
+                    let mir = match obj.tpe() {
+                        SType::SColl(_) if COLL_METHODS.contains(&select.field.as_str()) => {
+                            lower_coll_method(obj, &select.field, &hir.args, hir_expr.span, scope)?
+                        }
+                        _ => {
+                            lower_method_call(obj, &select.field, &hir.args, hir_expr.span, scope)?
+                        }
+                    };
+                    return check_tpe(mir, &hir_expr);
+                }
+            }
+            let func = lower_in(*hir.func.clone(), scope)?;
+            let args = hir
+                .args
+                .iter()
+                .cloned()
+                .map(|arg| lower_in(arg, scope))
+                .collect::<Result<Vec<_>, _>>()?;
+            match (func.tpe(), args.as_slice()) {
+                (SType::SColl(_), [index]) => ByIndex::new(func, index.clone(), None)
+                    .map_err(invalid_arg)?
+                    .into(),
+                (SType::SFunc(_), _) => Apply::new(func, args).map_err(invalid_arg)?.into(),
+                (tpe, _) => {
+                    return Err(MirLoweringError::new(
+                        format!("MIR error: cannot apply {0:?} to {1:?}", tpe, args),
+                        hir_expr.span,
+                    ))
+                }
+            }
+        }
+        hir::ExprKind::ApplyTypes(hir) => match (&hir.expr.kind, hir.tpes.as_slice()) {
+            (hir::ExprKind::Select(select), [tpe]) => match select.register_id() {
+                Some(register_id) => {
+                    let obj = lower_in(*select.obj.clone(), scope)?;
+                    ExtractRegisterAs::new(obj, register_id, SType::SOption(tpe.clone().into()))
+                        .map_err(invalid_arg)?
+                        .into()
+                }
+                None => {
+                    return Err(MirLoweringError::new(
+                        format!("MIR error: unexpected type arguments {0:?}", hir.tpes),
+                        hir_expr.span,
+                    ))
+                }
+            },
+            _ => {
+                return Err(MirLoweringError::new(
+                    format!("MIR error: unexpected type arguments {0:?}", hir.tpes),
+                    hir_expr.span,
+                ))
+            }
+        },
+        hir::ExprKind::Lambda(hir) => {
+            let mark = scope.mark();
+            let args: Vec<FuncArg> = hir
+                .args
+                .iter()
+                .map(|(name, tpe)| {
+                    let arg = FuncArg {
+                        idx: scope.fresh_val_id(),
+                        tpe: tpe.clone(),
+                    };
+                    let arg_use = ValUse {
+                        val_id: arg.idx,
+                        tpe: arg.tpe.clone(),
+                    };
+                    scope.vars.push((name.clone(), arg_use.into()));
+                    arg
+                })
+                .collect();
+            scope.skip_val_id();
+            let body = lower_in(*hir.body.clone(), scope)?;
+            scope.reset(mark);
+            FuncValue::new(args, body).into()
+        }
+        hir::ExprKind::PredefFuncCall(hir) => lower_predef_func_call(hir, hir_expr.span, scope)?,
+        hir::ExprKind::Tuple(hir) => {
+            let items = hir
+                .items
+                .iter()
+                .cloned()
+                .map(|item| lower_in(item, scope))
+                .collect::<Result<Vec<_>, _>>()?;
+            Tuple::new(items).map_err(invalid_arg)?.into()
+        }
+        hir::ExprKind::Block(hir) => {
+            let mark = scope.mark();
+            let mut items: Vec<Expr> = Vec::with_capacity(hir.vals.len());
+            for val in &hir.vals {
+                let rhs = lower_in(*val.rhs.clone(), scope)?;
+                let id = scope.fresh_val_id();
+                let val_use = ValUse {
+                    val_id: id,
+                    tpe: rhs.tpe(),
+                };
+                scope.vars.push((val.name.clone(), val_use.into()));
+                items.push(
+                    ValDef {
+                        id,
+                        rhs: rhs.into(),
+                    }
+                    .into(),
+                );
+            }
+            let result = lower_in(*hir.body.clone(), scope)?;
+            scope.reset(mark);
+            BlockValue {
+                items,
+                result: result.into(),
+            }
+            .into()
+        }
+        hir::ExprKind::Literal(hir) => match hir {
+            hir::Literal::Int(v) => (*v).into(),
+            hir::Literal::Long(v) => (*v).into(),
+            hir::Literal::Bool(v) => (*v).into(),
+            hir::Literal::Constant(c) => c.clone().into(),
+            hir::Literal::ConstPlaceholder(cp) => cp.clone().into(),
+            hir::Literal::Str(_) => {
+                return Err(MirLoweringError::new(
+                    "MIR error: string literals are only supported as arguments of PK, \
+                     fromBase16, fromBase58, fromBase64 and deserialize"
+                        .to_string(),
+                    hir_expr.span,
+                ))
+            }
+        },
     };
+    check_tpe(mir, &hir_expr)
+}
+
+fn check_tpe(mir: Expr, hir_expr: &hir::Expr) -> Result<Expr, MirLoweringError> {
     let hir_tpe = hir_expr.tpe.clone().ok_or_else(|| {
         MirLoweringError::new(
             format!("MIR error: missing tpe for HIR: {0:?}", hir_expr),
@@ -75,6 +486,286 @@ pub fn lower(hir_expr: hir::Expr) -> Result<Expr, MirLoweringError> {
     }
 }
 
+fn lower_predef_func_call(
+    call: &hir::PredefFuncCall,
+    span: TextRange,
+    scope: &mut Scope,
+) -> Result<Expr, MirLoweringError> {
+    let invalid_arg =
+        |e: InvalidArgumentError| MirLoweringError::new(format!("MIR error: {0}", e), span);
+    if let (PredefFunc::GetVar, [var_tpe], [var_id]) =
+        (call.func, call.tpe_args.as_slice(), call.args.as_slice())
+    {
+        // variable id is encoded in the opcode and should be known at compile time
+        let id = match var_id.kind {
+            hir::ExprKind::Literal(hir::Literal::Int(id)) => u8::try_from(id).ok(),
+            _ => None,
+        };
+        return match id {
+            Some(id) => Ok(GetVar {
+                var_id: id,
+                var_tpe: var_tpe.clone(),
+            }
+            .into()),
+            None => Err(MirLoweringError::new(
+                "MIR error: getVar expects a variable id literal (0-255)".to_string(),
+                var_id.span,
+            )),
+        };
+    }
+    let args = call
+        .args
+        .iter()
+        .cloned()
+        .map(|arg| lower_in(arg, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+    let res: Result<Expr, InvalidArgumentError> = match (call.func, args.as_slice()) {
+        (PredefFunc::SigmaProp, [input]) => {
+            BoolToSigmaProp::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::ProveDlog, [input]) => {
+            CreateProveDlog::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::ProveDhTuple, [g, h, u, v]) => {
+            CreateProveDhTuple::new(g.clone(), h.clone(), u.clone(), v.clone()).map(Expr::from)
+        }
+        (PredefFunc::AtLeast, [bound, input]) => {
+            Atleast::new(bound.clone(), input.clone()).map(Expr::from)
+        }
+        (PredefFunc::AllOf, [input]) => Ok(And {
+            input: input.clone().into(),
+        }
+        .into()),
+        (PredefFunc::AnyOf, [input]) => Ok(Or {
+            input: input.clone().into(),
+        }
+        .into()),
+        (PredefFunc::XorOf, [input]) => Ok(XorOf {
+            input: input.clone().into(),
+        }
+        .into()),
+        // conjecture items are stored in the node, so only a collection literal is accepted
+        (
+            PredefFunc::AllZk | PredefFunc::AnyZk,
+            [Expr::Collection(Collection::Exprs { items, .. })],
+        ) => {
+            if call.func == PredefFunc::AllZk {
+                SigmaAnd::new(items.clone()).map(Expr::from)
+            } else {
+                SigmaOr::new(items.clone()).map(Expr::from)
+            }
+        }
+        (PredefFunc::AllZk | PredefFunc::AnyZk, _) => {
+            return Err(MirLoweringError::new(
+                format!(
+                    "MIR error: {0:?} expects a collection literal, e.g. allZK(Coll(a, b, c))",
+                    call.func
+                ),
+                span,
+            ))
+        }
+        (PredefFunc::Blake2b256, [input]) => {
+            CalcBlake2b256::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::Sha256, [input]) => CalcSha256::try_build(input.clone()).map(Expr::from),
+        (PredefFunc::LongToByteArray, [input]) => {
+            LongToByteArray::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::ByteArrayToBigInt, [input]) => {
+            ByteArrayToBigInt::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::ByteArrayToLong, [input]) => {
+            ByteArrayToLong::try_build(input.clone()).map(Expr::from)
+        }
+        (PredefFunc::DecodePoint, [input]) => DecodePoint::try_build(input.clone()).map(Expr::from),
+        (PredefFunc::SubstConstants, [script_bytes, positions, new_values]) => {
+            SubstConstants::new(script_bytes.clone(), positions.clone(), new_values.clone())
+                .map(Expr::from)
+        }
+        (PredefFunc::Min, [l, r]) => Ok(BinOp {
+            kind: ArithOp::Min.into(),
+            left: l.clone().into(),
+            right: r.clone().into(),
+        }
+        .into()),
+        (PredefFunc::Max, [l, r]) => Ok(BinOp {
+            kind: ArithOp::Max.into(),
+            left: l.clone().into(),
+            right: r.clone().into(),
+        }
+        .into()),
+        (PredefFunc::Coll, items) => {
+            let elem_tpe = match (call.tpe_args.as_slice(), items) {
+                ([elem_tpe], _) => elem_tpe.clone(),
+                (_, [first, ..]) => first.tpe(),
+                _ => {
+                    return Err(MirLoweringError::new(
+                        "MIR error: element type of an empty collection should be specified, \
+                         e.g. Coll[Int]()"
+                            .to_string(),
+                        span,
+                    ))
+                }
+            };
+            Collection::new(elem_tpe, items.to_vec()).map(Expr::from)
+        }
+        (func, _) => {
+            return Err(MirLoweringError::new(
+                format!("MIR error: cannot apply {0:?} to {1:?}", func, args),
+                span,
+            ))
+        }
+    };
+    res.map_err(invalid_arg)
+}
+
+/// Boolean value is converted to SigmaProp with `BoolToSigmaProp`, SigmaProp is returned as is
+fn lower_to_sigma_prop(input: Expr) -> Result<Expr, InvalidArgumentError> {
+    if input.tpe() == SType::SBoolean {
+        BoolToSigmaProp::try_build(input).map(Expr::from)
+    } else {
+        Ok(input)
+    }
+}
+
+/// `Upcast` or `Downcast` of the numeric value to the given numeric type
+fn lower_numeric_cast(input: Expr, target: SType) -> Result<Expr, InvalidArgumentError> {
+    match (numeric_rank(&input.tpe()), numeric_rank(&target)) {
+        (Some(from), Some(to)) if from < to => Upcast::new(input, target).map(Expr::from),
+        (Some(from), Some(to)) if from > to => Downcast::new(input, target).map(Expr::from),
+        _ => Ok(input),
+    }
+}
+
+fn lower_method_call(
+    obj: Expr,
+    method: &str,
+    args: &[hir::Expr],
+    span: TextRange,
+    scope: &mut Scope,
+) -> Result<Expr, MirLoweringError> {
+    let invalid_arg =
+        |e: InvalidArgumentError| MirLoweringError::new(format!("MIR error: {0}", e), span);
+    let args = args
+        .iter()
+        .cloned()
+        .map(|arg| lower_in(arg, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+    let obj_tpe = obj.tpe();
+    let res = match (&obj_tpe, method, args.as_slice()) {
+        (SType::SOption(_), "getOrElse", [default]) => {
+            OptionGetOrElse::new(obj, default.clone()).map(Expr::from)
+        }
+        (SType::SGroupElement, "exp", [exponent]) => {
+            Exponentiate::new(obj, exponent.clone()).map(Expr::from)
+        }
+        (SType::SGroupElement, "multiply", [other]) => {
+            MultiplyGroup::new(obj, other.clone()).map(Expr::from)
+        }
+        _ => {
+            let arg_tpes: Vec<SType> = args.iter().map(Expr::tpe).collect();
+            match specialized_method(&obj_tpe, method, &arg_tpes) {
+                Some(smethod) => MethodCall::new(obj, smethod, args.clone()).map(Expr::from),
+                None => {
+                    return Err(MirLoweringError::new(
+                        format!(
+                            "MIR error: cannot apply {0} of {1} to {2:?}",
+                            method, obj_tpe, args
+                        ),
+                        span,
+                    ))
+                }
+            }
+        }
+    };
+    res.map_err(invalid_arg)
+}
+
+fn lower_coll_method(
+    coll: Expr,
+    method: &str,
+    args: &[hir::Expr],
+    span: TextRange,
+    scope: &mut Scope,
+) -> Result<Expr, MirLoweringError> {
+    let invalid_arg =
+        |e: InvalidArgumentError| MirLoweringError::new(format!("MIR error: {0}", e), span);
+    if let ("fold", [zero, fold_op]) = (method, args) {
+        let zero = lower_in(zero.clone(), scope)?;
+        let fold_op = lower_fold_op(fold_op, scope)?;
+        return Ok(Fold::new(coll, zero, fold_op).map_err(invalid_arg)?.into());
+    }
+    let args = args
+        .iter()
+        .cloned()
+        .map(|arg| lower_in(arg, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+    let res = match (method, args.as_slice()) {
+        ("map", [mapper]) => Map::new(coll, mapper.clone()).map(Expr::from),
+        ("filter", [condition]) => Filter::new(coll, condition.clone()).map(Expr::from),
+        ("exists", [condition]) => Exists::new(coll, condition.clone()).map(Expr::from),
+        ("forall", [condition]) => ForAll::new(coll, condition.clone()).map(Expr::from),
+        ("slice", [from, until]) => Slice::new(coll, from.clone(), until.clone()).map(Expr::from),
+        ("append", [other]) => Append::new(coll, other.clone()).map(Expr::from),
+        ("getOrElse", [index, default]) => {
+            ByIndex::new(coll, index.clone(), Some(default.clone().into())).map(Expr::from)
+        }
+        _ => {
+            return Err(MirLoweringError::new(
+                format!(
+                    "MIR error: cannot apply {0} of {1:?} to {2:?}",
+                    method,
+                    coll.tpe(),
+                    args
+                ),
+                span,
+            ))
+        }
+    };
+    res.map_err(invalid_arg)
+}
+
+/// `Fold` expects a function of a single (accumulator, item) tuple argument, so the two
+/// arguments of the lambda are lowered to the tuple fields
+fn lower_fold_op(fold_op: &hir::Expr, scope: &mut Scope) -> Result<Expr, MirLoweringError> {
+    let lambda = match &fold_op.kind {
+        hir::ExprKind::Lambda(lambda) if lambda.args.len() == 2 => lambda,
+        _ => {
+            return Err(MirLoweringError::new(
+                "MIR error: fold expects a lambda with two arguments (accumulator and item)"
+                    .to_string(),
+                fold_op.span,
+            ))
+        }
+    };
+    let arg_tpe: SType = STuple::pair(lambda.args[0].1.clone(), lambda.args[1].1.clone()).into();
+    let mark = scope.mark();
+    let arg = FuncArg {
+        idx: scope.fresh_val_id(),
+        tpe: arg_tpe.clone(),
+    };
+    let arg_use: Expr = ValUse {
+        val_id: arg.idx,
+        tpe: arg_tpe,
+    }
+    .into();
+    scope.skip_val_id();
+    for (index, (name, _)) in (1u8..).zip(lambda.args.iter()) {
+        let field_index = TupleFieldIndex::try_from(index).map_err(|_| {
+            MirLoweringError::new(
+                format!("MIR error: invalid tuple field index {0}", index),
+                fold_op.span,
+            )
+        })?;
+        let field = SelectField::new(arg_use.clone(), field_index)
+            .map_err(|e| MirLoweringError::new(format!("MIR error: {0}", e), fold_op.span))?;
+        scope.vars.push((name.clone(), field.into()));
+    }
+    let body = lower_in(*lambda.body.clone(), scope)?;
+    scope.reset(mark);
+    Ok(FuncValue::new(vec![arg], body).into())
+}
+
 impl From<hir::BinaryOp> for BinOpKind {
     fn from(op: hir::BinaryOp) -> Self {
         match op {
@@ -82,6 +773,16 @@ impl From<hir::BinaryOp> for BinOpKind {
             BinaryOp::Minus => ArithOp::Minus.into(),
             BinaryOp::Multiply => ArithOp::Multiply.into(),
             BinaryOp::Divide => ArithOp::Divide.into(),
+            BinaryOp::Modulo => ArithOp::Modulo.into(),
+            BinaryOp::Eq => RelationOp::Eq.into(),
+            BinaryOp::NEq => RelationOp::NEq.into(),
+            BinaryOp::Gt => RelationOp::Gt.into(),
+            BinaryOp::Ge => RelationOp::Ge.into(),
+            BinaryOp::Lt => RelationOp::Lt.into(),
+            BinaryOp::Le => RelationOp::Le.into(),
+            BinaryOp::And => LogicalOp::And.into(),
+            BinaryOp::Or => LogicalOp::Or.into(),
+            BinaryOp::Xor => LogicalOp::Xor.into(),
         }
     }
 }
@@ -101,6 +802,10 @@ pub fn check(input: &str, expected_tree: expect_test::Expect) {
 
 #[cfg(test)]
 mod tests {
+    use ergotree_ir::chain::address::Address;
+    use ergotree_ir::chain::address::AddressEncoder;
+    use ergotree_ir::mir::constant::Constant;
+    use ergotree_ir::serialization::SigmaSerializable;
     use expect_test::expect;
 
     use super::*;
@@ -207,4 +912,618 @@ mod tests {
                 )"#]],
         );
     }
+
+    #[test]
+    fn if_with_comparison() {
+        check(
+            "if (HEIGHT > 100) 1 else 2",
+            expect![[r#"
+                If(
+                    If {
+                        condition: BinOp(
+                            Spanned {
+                                source_span: SourceSpan {
+                                    offset: 0,
+                                    length: 0,
+                                },
+                                expr: BinOp {
+                                    kind: Relation(
+                                        Gt,
+                                    ),
+                                    left: GlobalVars(
+                                        Height,
+                                    ),
+                                    right: Const(
+                                        "100: SInt",
+                                    ),
+                                },
+                            },
+                        ),
+                        true_branch: Const(
+                            "1: SInt",
+                        ),
+                        false_branch: Const(
+                            "2: SInt",
+                        ),
+                    },
+                )"#]],
+        );
+    }
+
+    #[test]
+    fn logical_not() {
+        check(
+            "!(HEIGHT == 1)",
+            expect![[r#"
+                LogicalNot(
+                    Spanned {
+                        source_span: SourceSpan {
+                            offset: 0,
+                            length: 0,
+                        },
+                        expr: LogicalNot {
+                            input: BinOp(
+                                Spanned {
+                                    source_span: SourceSpan {
+                                        offset: 0,
+                                        length: 0,
+                                    },
+                                    expr: BinOp {
+                                        kind: Relation(
+                                            Eq,
+                                        ),
+                                        left: GlobalVars(
+                                            Height,
+                                        ),
+                                        right: Const(
+                                            "1: SInt",
+                                        ),
+                                    },
+                                },
+                            ),
+                        },
+                    },
+                )"#]],
+        );
+    }
+
+    #[test]
+    fn bool_and_xor() {
+        check(
+            "true && false ^ true",
+            expect![[r#"
+                BinOp(
+                    Spanned {
+                        source_span: SourceSpan {
+                            offset: 0,
+                            length: 0,
+                        },
+                        expr: BinOp {
+                            kind: Logical(
+                                Xor,
+                            ),
+                            left: BinOp(
+                                Spanned {
+                                    source_span: SourceSpan {
+                                        offset: 0,
+                                        length: 0,
+                                    },
+                                    expr: BinOp {
+                                        kind: Logical(
+                                            And,
+                                        ),
+                                        left: Const(
+                                            "true: SBoolean",
+                                        ),
+                                        right: Const(
+                                            "false: SBoolean",
+                                        ),
+                                    },
+                                },
+                            ),
+                            right: Const(
+                                "true: SBoolean",
+                            ),
+                        },
+                    },
+                )"#]],
+        );
+    }
+
+    #[test]
+    fn numeric_xor_and_modulo() {
+        check(
+            "4 ^ 7 % 3",
+            expect![[r#"
+                BinOp(
+                    Spanned {
+                        source_span: SourceSpan {
+                            offset: 0,
+                            length: 0,
+                        },
+                        expr: BinOp {
+                            kind: Bit(
+                                BitXor,
+                            ),
+                            left: Const(
+                                "4: SInt",
+                            ),
+                            right: BinOp(
+                                Spanned {
+                                    source_span: SourceSpan {
+                                        offset: 0,
+                                        length: 0,
+                                    },
+                                    expr: BinOp {
+                                        kind: Arith(
+                                            Modulo,
+                                        ),
+                                        left: Const(
+                                            "7: SInt",
+                                        ),
+                                        right: Const(
+                                            "3: SInt",
+                                        ),
+                                    },
+                                },
+                            ),
+                        },
+                    },
+                )"#]],
+        );
+    }
+
+    #[test]
+    fn output_value() {
+        check(
+            "OUTPUTS(0).value",
+            expect![[r#"
+                ExtractAmount(
+                    ExtractAmount {
+                        input: ByIndex(
+                            Spanned {
+                                source_span: SourceSpan {
+                                    offset: 0,
+                                    length: 0,
+                                },
+                                expr: ByIndex {
+                                    input: GlobalVars(
+                                        Outputs,
+                                    ),
+                                    index: Const(
+                                        "0: SInt",
+                                    ),
+                                    default: None,
+                                    input_elem_tpe: SBox,
+                                },
+                            },
+                        ),
+                    },
+                )"#]],
+        );
+    }
+
+    #[test]
+    fn register_access() {
+        check(
+            "SELF.R4[Long]",
+            expect![[r#"
+                ExtractRegisterAs(
+                    Spanned {
+                        source_span: SourceSpan {
+                            offset: 0,
+                            length: 0,
+                        },
+                        expr: ExtractRegisterAs {
+                            input: GlobalVars(
+                                SelfBox,
+                            ),
+                            register_id: 4,
+                            elem_tpe: SLong,
+                        },
+                    },
+                )"#]],
+        );
+    }
+
+    fn lower_str(input: &str) -> Result<Expr, crate::compiler::CompileError> {
+        let root = crate::ast::Root::cast(crate::parser::parse(input).syntax()).unwrap();
+        let binder = crate::binder::Binder::new(crate::script_env::ScriptEnv::new());
+        let bind = binder.bind(hir::lower(root)?)?;
+        Ok(lower(crate::type_infer::assign_type(bind)?)?)
+    }
+
+    #[test]
+    fn context_and_box_properties() {
+        assert!(matches!(
+            lower_str("CONTEXT.dataInputs").unwrap(),
+            Expr::PropertyCall(_)
+        ));
+        assert!(matches!(
+            lower_str("SELF.tokens").unwrap(),
+            Expr::PropertyCall(_)
+        ));
+        assert!(matches!(
+            lower_str("SELF.propositionBytes").unwrap(),
+            Expr::ExtractScriptBytes(_)
+        ));
+        assert!(lower_str("SELF.R4").is_err());
+    }
+
+    #[test]
+    fn lambda_in_exists() {
+        let b: Expr = ValUse {
+            val_id: ValId(1),
+            tpe: SType::SBox,
+        }
+        .into();
+        let condition = FuncValue::new(
+            vec![FuncArg {
+                idx: ValId(1),
+                tpe: SType::SBox,
+            }],
+            BinOp {
+                kind: RelationOp::Gt.into(),
+                left: Box::new(ExtractAmount::try_build(b).unwrap().into()),
+                right: Box::new(0i64.into()),
+            }
+            .into(),
+        );
+        let expected: Expr = Exists::new(GlobalVars::Outputs.into(), condition.into())
+            .unwrap()
+            .into();
+        assert_eq!(
+            lower_str("OUTPUTS.exists { (b: Box) => b.value > 0L }").unwrap(),
+            expected
+        );
+        assert_eq!(
+            lower_str("OUTPUTS.exists({ (b: Box) => b.value > 0L })").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn fold_with_two_arg_lambda() {
+        let arg_tpe: SType = STuple::pair(SType::SLong, SType::SBox).into();
+        let arg: Expr = ValUse {
+            val_id: ValId(1),
+            tpe: arg_tpe.clone(),
+        }
+        .into();
+        let acc = SelectField::new(arg.clone(), TupleFieldIndex::try_from(1).unwrap()).unwrap();
+        let b = SelectField::new(arg, TupleFieldIndex::try_from(2).unwrap()).unwrap();
+        let fold_op = FuncValue::new(
+            vec![FuncArg {
+                idx: ValId(1),
+                tpe: arg_tpe,
+            }],
+            BinOp {
+                kind: ArithOp::Plus.into(),
+                left: Box::new(acc.into()),
+                right: Box::new(ExtractAmount::try_build(b.into()).unwrap().into()),
+            }
+            .into(),
+        );
+        let expected: Expr = Fold::new(GlobalVars::Outputs.into(), 0i64.into(), fold_op.into())
+            .unwrap()
+            .into();
+        assert_eq!(
+            lower_str("OUTPUTS.fold(0L, { (acc: Long, b: Box) => acc + b.value })").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn coll_methods() {
+        assert!(matches!(
+            lower_str("INPUTS.map { (b: Box) => b.value }").unwrap(),
+            Expr::Map(_)
+        ));
+        assert!(matches!(
+            lower_str("INPUTS.filter { (b: Box) => b.value > 1L }").unwrap(),
+            Expr::Filter(_)
+        ));
+        assert!(matches!(
+            lower_str("INPUTS.forall { (b: Box) => b.value > 1L }").unwrap(),
+            Expr::ForAll(_)
+        ));
+        assert!(matches!(
+            lower_str("OUTPUTS.slice(1, 2)").unwrap(),
+            Expr::Slice(_)
+        ));
+        assert!(matches!(
+            lower_str("OUTPUTS.size").unwrap(),
+            Expr::SizeOf(_)
+        ));
+    }
+
+    #[test]
+    fn nested_lambdas() {
+        let res = lower_str(
+            "INPUTS.exists { (i: Box) => OUTPUTS.exists { (o: Box) => o.value == i.value } }",
+        )
+        .unwrap();
+        assert_eq!(res.tpe(), SType::SBoolean);
+    }
+
+    #[test]
+    fn lambda_application() {
+        assert!(matches!(
+            lower_str("{ (x: Int) => x + 1 }(2)").unwrap(),
+            Expr::Apply(_)
+        ));
+    }
+
+    #[test]
+    fn predef_funcs() {
+        assert!(matches!(
+            lower_str("sigmaProp(HEIGHT > 100)").unwrap(),
+            Expr::BoolToSigmaProp(_)
+        ));
+        assert!(matches!(
+            lower_str("atLeast(1, OUTPUTS.map { (b: Box) => sigmaProp(b.value > 1L) })").unwrap(),
+            Expr::Atleast(_)
+        ));
+        assert!(matches!(
+            lower_str("allOf(OUTPUTS.map { (b: Box) => b.value > 1L })").unwrap(),
+            Expr::And(_)
+        ));
+        assert!(matches!(
+            lower_str("max(HEIGHT, 100)").unwrap(),
+            Expr::BinOp(_)
+        ));
+        assert!(matches!(
+            lower_str("getVar[Coll[Byte]](1)").unwrap(),
+            Expr::GetVar(_)
+        ));
+        assert!(lower_str("getVar[Int](HEIGHT)").is_err());
+        assert!(lower_str(r#"sigmaProp("abc")"#).is_err());
+    }
+
+    #[test]
+    fn const_folding() {
+        let expected: Expr = CalcBlake2b256::try_build(Expr::Const(vec![1u8, 255].into()))
+            .unwrap()
+            .into();
+        assert_eq!(
+            lower_str(r#"blake2b256(fromBase16("01ff"))"#).unwrap(),
+            expected
+        );
+        assert_eq!(
+            lower_str(r#"fromBase64("Af8=")"#).unwrap(),
+            Expr::Const(vec![1u8, 255].into())
+        );
+        assert_eq!(
+            lower_str(r#"fromBase58("9Ti")"#).unwrap(),
+            Expr::Const(bs58::decode("9Ti").into_vec().unwrap().into())
+        );
+        let addr = "9gmNsqrqdSppLUBqg2UzREmmivgqh1r3jmNcLAc53hk3YCvAGWE";
+        let prove_dlog = match AddressEncoder::unchecked_parse_address_from_str(addr).unwrap() {
+            Address::P2Pk(prove_dlog) => prove_dlog,
+            _ => unreachable!(),
+        };
+        assert_eq!(
+            lower_str(&format!(r#"PK("{}")"#, addr)).unwrap(),
+            Expr::Const(prove_dlog.into())
+        );
+        let serialized =
+            bs58::encode(Constant::from(42i64).sigma_serialize_bytes().unwrap()).into_string();
+        assert_eq!(
+            lower_str(&format!(r#"deserialize[Long]("{}")"#, serialized)).unwrap(),
+            Expr::Const(42i64.into())
+        );
+    }
+
+    #[test]
+    fn negative_literals() {
+        assert_eq!(lower_str("-5").unwrap(), Expr::Const((-5i32).into()));
+        assert_eq!(lower_str("-5L").unwrap(), Expr::Const((-5i64).into()));
+        assert!(matches!(lower_str("-HEIGHT").unwrap(), Expr::Negation(_)));
+    }
+
+    #[test]
+    fn tuples() {
+        let tuple: Expr = Tuple::new(vec![1i32.into(), 2i64.into()]).unwrap().into();
+        assert_eq!(lower_str("(1, 2L)").unwrap(), tuple);
+        let expected: Expr = SelectField::new(tuple, TupleFieldIndex::try_from(2).unwrap())
+            .unwrap()
+            .into();
+        assert_eq!(lower_str("(1, 2L)._2").unwrap(), expected);
+        assert!(matches!(
+            lower_str("(1, 2L)._3"),
+            Err(crate::compiler::CompileError::TypeInferenceError(_))
+        ));
+    }
+
+    #[test]
+    fn sigma_prop_and_or() {
+        let gt: Expr = BoolToSigmaProp::try_build(lower_str("HEIGHT > 1").unwrap())
+            .unwrap()
+            .into();
+        let lt: Expr = BoolToSigmaProp::try_build(lower_str("HEIGHT < 5").unwrap())
+            .unwrap()
+            .into();
+        let expected: Expr = SigmaAnd::new(vec![gt.clone(), lt.clone()]).unwrap().into();
+        assert_eq!(
+            lower_str("sigmaProp(HEIGHT > 1) && sigmaProp(HEIGHT < 5)").unwrap(),
+            expected
+        );
+        // Boolean operand is converted to SigmaProp
+        let expected: Expr = SigmaOr::new(vec![gt.clone(), lt.clone()]).unwrap().into();
+        assert_eq!(
+            lower_str("sigmaProp(HEIGHT > 1) || HEIGHT < 5").unwrap(),
+            expected
+        );
+        let expected: Expr = SigmaAnd::new(vec![gt, lt]).unwrap().into();
+        assert_eq!(
+            lower_str("HEIGHT > 1 && sigmaProp(HEIGHT < 5)").unwrap(),
+            expected
+        );
+        assert!(matches!(
+            lower_str("HEIGHT > 1 && HEIGHT < 5").unwrap(),
+            Expr::BinOp(_)
+        ));
+        assert!(matches!(
+            lower_str("sigmaProp(HEIGHT > 1) && HEIGHT"),
+            Err(crate::compiler::CompileError::TypeInferenceError(_))
+        ));
+    }
+
+    #[test]
+    fn unknown_identifier() {
+        let source = "HEIGHT + foo";
+        match lower_str(source) {
+            Err(crate::compiler::CompileError::MirLoweringError(e)) => {
+                assert_eq!(e.msg(), "unknown identifier `foo`");
+                assert_eq!(&source[e.span()], "foo");
+            }
+            res => panic!("unexpected result: {0:?}", res),
+        }
+    }
+
+    #[test]
+    fn option_methods() {
+        assert!(matches!(
+            lower_str("SELF.R4[Int].get").unwrap(),
+            Expr::OptionGet(_)
+        ));
+        assert!(matches!(
+            lower_str("SELF.R4[Int].isDefined").unwrap(),
+            Expr::OptionIsDefined(_)
+        ));
+        assert!(matches!(
+            lower_str("SELF.R4[Int].getOrElse(0)").unwrap(),
+            Expr::OptionGetOrElse(_)
+        ));
+    }
+
+    #[test]
+    fn numeric_casts() {
+        let height: Expr = GlobalVars::Height.into();
+        let expected: Expr = BinOp {
+            kind: ArithOp::Plus.into(),
+            left: Box::new(Upcast::new(height.clone(), SType::SLong).unwrap().into()),
+            right: Box::new(1i64.into()),
+        }
+        .into();
+        assert_eq!(lower_str("HEIGHT + 1L").unwrap(), expected);
+        assert_eq!(
+            lower_str("HEIGHT.toLong").unwrap(),
+            Upcast::new(height.clone(), SType::SLong).unwrap().into()
+        );
+        assert_eq!(
+            lower_str("HEIGHT.toByte").unwrap(),
+            Downcast::new(height.clone(), SType::SByte).unwrap().into()
+        );
+        assert_eq!(lower_str("HEIGHT.toInt").unwrap(), height);
+    }
+
+    #[test]
+    fn vals() {
+        let expected: Expr = BlockValue {
+            items: vec![ValDef {
+                id: ValId(1),
+                rhs: Box::new(Expr::Const(1i64.into())),
+            }
+            .into()],
+            result: Box::new(
+                BinOp {
+                    kind: RelationOp::Gt.into(),
+                    left: Box::new(
+                        ValUse {
+                            val_id: ValId(1),
+                            tpe: SType::SLong,
+                        }
+                        .into(),
+                    ),
+                    right: Box::new(0i64.into()),
+                }
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(lower_str("val x: Long = 1L\nx > 0L").unwrap(), expected);
+        assert_eq!(
+            lower_str("val p: (Int, Long) = (1, 2L)\np._1")
+                .unwrap()
+                .tpe(),
+            SType::SInt
+        );
+    }
+
+    #[test]
+    fn coll_literal() {
+        let expected: Expr = Collection::new(SType::SInt, vec![1i32.into(), 2i32.into()])
+            .unwrap()
+            .into();
+        assert_eq!(lower_str("Coll(1, 2)").unwrap(), expected);
+        assert_eq!(
+            lower_str("Coll[Long]()").unwrap().tpe(),
+            SType::SColl(SType::SLong.into())
+        );
+    }
+
+    #[test]
+    fn function_def() {
+        let x: Expr = ValUse {
+            val_id: ValId(1),
+            tpe: SType::SInt,
+        }
+        .into();
+        let inc = FuncValue::new(
+            vec![FuncArg {
+                idx: ValId(1),
+                tpe: SType::SInt,
+            }],
+            BinOp {
+                kind: ArithOp::Plus.into(),
+                left: x.into(),
+                right: Box::new(1i32.into()),
+            }
+            .into(),
+        );
+        // the function and its argument share the id (see `Scope::reset`)
+        let inc_use: Expr = ValUse {
+            val_id: ValId(1),
+            tpe: inc.tpe(),
+        }
+        .into();
+        let expected: Expr = BlockValue {
+            items: vec![ValDef {
+                id: ValId(1),
+                rhs: Box::new(inc.into()),
+            }
+            .into()],
+            result: Box::new(
+                Apply::new(inc_use, vec![GlobalVars::Height.into()])
+                    .unwrap()
+                    .into(),
+            ),
+        }
+        .into();
+        assert_eq!(
+            lower_str("{ def inc(x: Int): Int = x + 1; inc(HEIGHT) }").unwrap(),
+            expected
+        );
+        assert_eq!(
+            lower_str("def inc(x: Int) = x + 1\ninc(HEIGHT)").unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn shadowing() {
+        assert_eq!(
+            lower_str("val x = 1L\n{ val x = 1; x + HEIGHT }")
+                .unwrap()
+                .tpe(),
+            SType::SInt
+        );
+        assert_eq!(
+            lower_str("val x = 1L; { (x: Int) => x }(HEIGHT)")
+                .unwrap()
+                .tpe(),
+            SType::SInt
+        );
+    }
 }
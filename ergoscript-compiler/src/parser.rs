@@ -105,6 +105,11 @@ impl<'t, 'input> Parser<'t, 'input> {
     fn peek(&mut self) -> Option<TokenKind> {
         self.source.peek_kind()
     }
+
+    /// Kind of the n-th token ahead, does not affect the expected kinds reported on error
+    fn nth(&mut self, n: usize) -> Option<TokenKind> {
+        self.source.lookahead_kind(n)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 mod expr;
 mod stmt;
+mod types;
 
 use crate::lexer::TokenKind;
 use crate::parser::Parser;
@@ -12,6 +13,9 @@ pub fn root(p: &mut Parser) -> CompletedMarker {
 
     while !p.at_end() {
         stmt::stmt(p);
+        if p.at(TokenKind::Semicolon) {
+            p.bump();
+        }
     }
 
     m.complete(p, SyntaxKind::Root)
@@ -63,6 +63,21 @@ fn expr_binding_power(p: &mut Parser, minimum_binding_power: u8) -> Option<Compl
     let mut lhs = lhs(p)?;
 
     loop {
+        // Postfix operators bind tighter than any prefix or infix operator
+        if p.at(TokenKind::Dot) {
+            lhs = select_expr(p, lhs);
+            continue;
+        } else if p.at(TokenKind::LParen) {
+            lhs = call_expr(p, lhs);
+            continue;
+        } else if p.at(TokenKind::LBracket) {
+            lhs = apply_types_expr(p, lhs);
+            continue;
+        } else if p.at(TokenKind::LBrace) && at_lambda(p) {
+            lhs = call_expr(p, lhs);
+            continue;
+        }
+
         let op = if p.at(TokenKind::Plus) {
             BinaryOp::Add
         } else if p.at(TokenKind::Minus) {
@@ -71,6 +86,26 @@ fn expr_binding_power(p: &mut Parser, minimum_binding_power: u8) -> Option<Compl
             BinaryOp::Mul
         } else if p.at(TokenKind::Slash) {
             BinaryOp::Div
+        } else if p.at(TokenKind::Percent) {
+            BinaryOp::Mod
+        } else if p.at(TokenKind::EqualsEquals) {
+            BinaryOp::Eq
+        } else if p.at(TokenKind::NotEquals) {
+            BinaryOp::NotEq
+        } else if p.at(TokenKind::Less) {
+            BinaryOp::Lt
+        } else if p.at(TokenKind::LessEquals) {
+            BinaryOp::LtEq
+        } else if p.at(TokenKind::Greater) {
+            BinaryOp::Gt
+        } else if p.at(TokenKind::GreaterEquals) {
+            BinaryOp::GtEq
+        } else if p.at(TokenKind::And) {
+            BinaryOp::And
+        } else if p.at(TokenKind::Or) {
+            BinaryOp::Or
+        } else if p.at(TokenKind::Caret) {
+            BinaryOp::Xor
         } else {
             // We’re not at an operator; we don’t know what to do next, so we return and let the
             // caller decide.
@@ -108,15 +143,31 @@ fn lhs(p: &mut Parser) -> Option<CompletedMarker> {
         int_number(p)
     } else if p.at(TokenKind::LongNumber) {
         long_number(p)
+    } else if p.at(TokenKind::StringLiteral) {
+        string_literal(p)
     } else if p.at(TokenKind::Ident) {
         ident(p)
         // variable_ref(p)
         // } else if p.at(TokenKind::ValKw) {
         //     variable_ref(p)
+    } else if p.at(TokenKind::TrueKw) {
+        bool_literal(p, TokenKind::TrueKw)
+    } else if p.at(TokenKind::FalseKw) {
+        bool_literal(p, TokenKind::FalseKw)
     } else if p.at(TokenKind::Minus) {
-        prefix_expr(p)
+        prefix_expr(p, UnaryOp::Neg)
+    } else if p.at(TokenKind::Bang) {
+        prefix_expr(p, UnaryOp::Not)
     } else if p.at(TokenKind::LParen) {
         paren_expr(p)
+    } else if p.at(TokenKind::IfKw) {
+        if_expr(p)
+    } else if p.at(TokenKind::LBrace) {
+        if at_lambda(p) {
+            lambda_expr(p)
+        } else {
+            block_expr(p)
+        }
     } else {
         p.error();
         return None;
@@ -130,25 +181,42 @@ enum BinaryOp {
     Sub,
     Mul,
     Div,
+    Mod,
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+    Xor,
 }
 
 impl BinaryOp {
+    // precedence follows Scala rules (by the first character of the operator)
     fn binding_power(&self) -> (u8, u8) {
         match self {
-            Self::Add | Self::Sub => (1, 2),
-            Self::Mul | Self::Div => (3, 4),
+            Self::Or => (1, 2),
+            Self::Xor => (3, 4),
+            Self::And => (5, 6),
+            Self::Eq | Self::NotEq => (7, 8),
+            Self::Lt | Self::LtEq | Self::Gt | Self::GtEq => (9, 10),
+            Self::Add | Self::Sub => (11, 12),
+            Self::Mul | Self::Div | Self::Mod => (13, 14),
         }
     }
 }
 
 enum UnaryOp {
     Neg,
+    Not,
 }
 
 impl UnaryOp {
     fn binding_power(&self) -> ((), u8) {
         match self {
-            Self::Neg => ((), 5),
+            Self::Neg | Self::Not => ((), 15),
         }
     }
 }
@@ -167,6 +235,20 @@ fn long_number(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::LongNumber)
 }
 
+fn string_literal(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::StringLiteral));
+    let m = p.start();
+    p.bump();
+    m.complete(p, SyntaxKind::StringLiteral)
+}
+
+fn bool_literal(p: &mut Parser, kind: TokenKind) -> CompletedMarker {
+    assert!(p.at(kind));
+    let m = p.start();
+    p.bump();
+    m.complete(p, kind.into())
+}
+
 // fn variable_ref(p: &mut Parser) -> CompletedMarker {
 //     assert!(p.at(TokenKind::Ident));
 
@@ -183,12 +265,11 @@ fn ident(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::Ident)
 }
 
-fn prefix_expr(p: &mut Parser) -> CompletedMarker {
-    assert!(p.at(TokenKind::Minus));
+fn prefix_expr(p: &mut Parser, op: UnaryOp) -> CompletedMarker {
+    assert!(p.at(TokenKind::Minus) || p.at(TokenKind::Bang));
 
     let m = p.start();
 
-    let op = UnaryOp::Neg;
     let ((), right_binding_power) = op.binding_power();
 
     // Eat the operator’s token.
@@ -199,15 +280,165 @@ fn prefix_expr(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::PrefixExpr)
 }
 
+// `(a)` is a parenthesized expression, `(a, b)` is a tuple
 fn paren_expr(p: &mut Parser) -> CompletedMarker {
     assert!(p.at(TokenKind::LParen));
 
     let m = p.start();
     p.bump();
     expr_binding_power(p, 0);
+    let mut is_tuple = false;
+    while p.at(TokenKind::Comma) {
+        p.bump();
+        is_tuple = true;
+        expr_binding_power(p, 0);
+    }
+    p.expect(TokenKind::RParen);
+
+    if is_tuple {
+        m.complete(p, SyntaxKind::TupleExpr)
+    } else {
+        m.complete(p, SyntaxKind::ParenExpr)
+    }
+}
+
+fn select_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(TokenKind::Dot));
+
+    let m = lhs.precede(p);
+    p.bump();
+    p.expect(TokenKind::Ident);
+
+    m.complete(p, SyntaxKind::SelectExpr)
+}
+
+fn call_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen) || p.at(TokenKind::LBrace));
+
+    let m = lhs.precede(p);
+    if p.at(TokenKind::LBrace) {
+        lambda_arg(p);
+    } else {
+        arg_list(p);
+    }
+
+    m.complete(p, SyntaxKind::CallExpr)
+}
+
+// `xs.map { (x: Int) => x + 1 }` is the same as `xs.map({ (x: Int) => x + 1 })`
+fn lambda_arg(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBrace));
+
+    let m = p.start();
+    lambda_expr(p);
+
+    m.complete(p, SyntaxKind::ArgList)
+}
+
+fn arg_list(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen));
+
+    let m = p.start();
+    p.bump();
+    while !p.at(TokenKind::RParen) && !p.at_end() {
+        expr_binding_power(p, 0);
+        if !p.at(TokenKind::Comma) {
+            break;
+        }
+        p.bump();
+    }
+    p.expect(TokenKind::RParen);
+
+    m.complete(p, SyntaxKind::ArgList)
+}
+
+fn apply_types_expr(p: &mut Parser, lhs: CompletedMarker) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBracket));
+
+    let m = lhs.precede(p);
+    types::type_arg_list(p);
+
+    m.complete(p, SyntaxKind::ApplyTypesExpr)
+}
+
+// `{ (` followed by `)` or `name:` starts a lambda, any other `{` starts a block
+fn at_lambda(p: &mut Parser) -> bool {
+    p.nth(0) == Some(TokenKind::LBrace)
+        && p.nth(1) == Some(TokenKind::LParen)
+        && match p.nth(2) {
+            Some(TokenKind::RParen) => true,
+            Some(TokenKind::Ident) => p.nth(3) == Some(TokenKind::Colon),
+            _ => false,
+        }
+}
+
+// `{ val a = 1; def f(x: Int) = x + a; f(2) }`, statements are separated by `;` or a newline
+fn block_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBrace));
+
+    let m = p.start();
+    p.bump();
+    while !p.at(TokenKind::RBrace) && !p.at_end() {
+        stmt::stmt(p);
+        if p.at(TokenKind::Semicolon) {
+            p.bump();
+        }
+    }
+    p.expect(TokenKind::RBrace);
+
+    m.complete(p, SyntaxKind::BlockExpr)
+}
+
+fn lambda_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBrace));
+
+    let m = p.start();
+    p.bump();
+    param_list(p);
+    p.expect(TokenKind::FatArrow);
+    expr_binding_power(p, 0);
+    p.expect(TokenKind::RBrace);
+
+    m.complete(p, SyntaxKind::LambdaExpr)
+}
+
+pub(super) fn param_list(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.expect(TokenKind::LParen);
+    while !p.at(TokenKind::RParen) && !p.at_end() {
+        param(p);
+        if !p.at(TokenKind::Comma) {
+            break;
+        }
+        p.bump();
+    }
+    p.expect(TokenKind::RParen);
+
+    m.complete(p, SyntaxKind::ParamList)
+}
+
+fn param(p: &mut Parser) -> CompletedMarker {
+    let m = p.start();
+    p.expect(TokenKind::Ident);
+    p.expect(TokenKind::Colon);
+    types::tpe(p);
+
+    m.complete(p, SyntaxKind::Param)
+}
+
+fn if_expr(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::IfKw));
+
+    let m = p.start();
+    p.bump();
+    p.expect(TokenKind::LParen);
+    expr_binding_power(p, 0);
     p.expect(TokenKind::RParen);
+    expr_binding_power(p, 0);
+    p.expect(TokenKind::ElseKw);
+    expr_binding_power(p, 0);
 
-    m.complete(p, SyntaxKind::ParenExpr)
+    m.complete(p, SyntaxKind::IfExpr)
 }
 
 #[cfg(test)]
@@ -387,8 +618,8 @@ mod tests {
                       IntNumber@1..2
                         IntNumber@1..2 "2"
                       Plus@2..3 "+"
-                error: expected number, number, identifier, ‘-’ or ‘(’
-                error: expected ‘)’"#]],
+                error: expected number, number, string, identifier, ‘true’, ‘false’, ‘-’, ‘!’, ‘(’, ‘if’ or ‘{’
+                error: expected ‘,’ or ‘)’"#]],
         );
     }
 
@@ -472,4 +703,166 @@ mod tests {
                       RParen@6..7 ")""#]],
         );
     }
+
+    #[test]
+    fn comparison_has_lower_binding_power_than_arithmetic() {
+        check(
+            "1+2<3",
+            expect![[r#"
+                Root@0..5
+                  InfixExpr@0..5
+                    InfixExpr@0..3
+                      IntNumber@0..1
+                        IntNumber@0..1 "1"
+                      Plus@1..2 "+"
+                      IntNumber@2..3
+                        IntNumber@2..3 "2"
+                    Less@3..4 "<"
+                    IntNumber@4..5
+                      IntNumber@4..5 "3""#]],
+        );
+    }
+
+    #[test]
+    fn or_has_lower_binding_power_than_and() {
+        check(
+            "true||false&&true",
+            expect![[r#"
+                Root@0..17
+                  InfixExpr@0..17
+                    TrueKw@0..4
+                      TrueKw@0..4 "true"
+                    Or@4..6 "||"
+                    InfixExpr@6..17
+                      FalseKw@6..11
+                        FalseKw@6..11 "false"
+                      And@11..13 "&&"
+                      TrueKw@13..17
+                        TrueKw@13..17 "true""#]],
+        );
+    }
+
+    #[test]
+    fn parse_logical_not() {
+        check(
+            "!x",
+            expect![[r#"
+                Root@0..2
+                  PrefixExpr@0..2
+                    Bang@0..1 "!"
+                    Ident@1..2
+                      Ident@1..2 "x""#]],
+        );
+    }
+
+    #[test]
+    fn parse_if_else() {
+        check(
+            "if (x) 1 else 2",
+            expect![[r#"
+                Root@0..15
+                  IfExpr@0..15
+                    IfKw@0..2 "if"
+                    Whitespace@2..3 " "
+                    LParen@3..4 "("
+                    Ident@4..5
+                      Ident@4..5 "x"
+                    RParen@5..6 ")"
+                    Whitespace@6..7 " "
+                    IntNumber@7..9
+                      IntNumber@7..8 "1"
+                      Whitespace@8..9 " "
+                    ElseKw@9..13 "else"
+                    Whitespace@13..14 " "
+                    IntNumber@14..15
+                      IntNumber@14..15 "2""#]],
+        );
+    }
+
+    #[test]
+    fn parse_select_after_call() {
+        check(
+            "OUTPUTS(0).value",
+            expect![[r#"
+                Root@0..16
+                  SelectExpr@0..16
+                    CallExpr@0..10
+                      Ident@0..7
+                        Ident@0..7 "OUTPUTS"
+                      ArgList@7..10
+                        LParen@7..8 "("
+                        IntNumber@8..9
+                          IntNumber@8..9 "0"
+                        RParen@9..10 ")"
+                    Dot@10..11 "."
+                    Ident@11..16 "value""#]],
+        );
+    }
+
+    #[test]
+    fn parse_lambda_arg_in_braces() {
+        check(
+            "xs.exists{(x:Int)=>x}",
+            expect![[r#"
+                Root@0..21
+                  CallExpr@0..21
+                    SelectExpr@0..9
+                      Ident@0..2
+                        Ident@0..2 "xs"
+                      Dot@2..3 "."
+                      Ident@3..9 "exists"
+                    ArgList@9..21
+                      LambdaExpr@9..21
+                        LBrace@9..10 "{"
+                        ParamList@10..17
+                          LParen@10..11 "("
+                          Param@11..16
+                            Ident@11..12 "x"
+                            Colon@12..13 ":"
+                            Type@13..16
+                              Ident@13..16 "Int"
+                          RParen@16..17 ")"
+                        FatArrow@17..19 "=>"
+                        Ident@19..20
+                          Ident@19..20 "x"
+                        RBrace@20..21 "}""#]],
+        );
+    }
+
+    #[test]
+    fn parse_string_literal_arg() {
+        check(
+            r#"PK("abc")"#,
+            expect![[r#"
+                Root@0..9
+                  CallExpr@0..9
+                    Ident@0..2
+                      Ident@0..2 "PK"
+                    ArgList@2..9
+                      LParen@2..3 "("
+                      StringLiteral@3..8
+                        StringLiteral@3..8 "\"abc\""
+                      RParen@8..9 ")""#]],
+        );
+    }
+
+    #[test]
+    fn parse_tuple_field_select() {
+        check(
+            "(1,x)._1",
+            expect![[r#"
+                Root@0..8
+                  SelectExpr@0..8
+                    TupleExpr@0..5
+                      LParen@0..1 "("
+                      IntNumber@1..2
+                        IntNumber@1..2 "1"
+                      Comma@2..3 ","
+                      Ident@3..4
+                        Ident@3..4 "x"
+                      RParen@4..5 ")"
+                    Dot@5..6 "."
+                    Ident@6..8 "_1""#]],
+        );
+    }
 }
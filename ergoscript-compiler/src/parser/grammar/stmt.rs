@@ -3,6 +3,8 @@ use super::*;
 pub(super) fn stmt(p: &mut Parser) -> Option<CompletedMarker> {
     if p.at(TokenKind::ValKw) {
         Some(variable_def(p))
+    } else if p.at(TokenKind::FnKw) {
+        Some(function_def(p))
     } else {
         expr::expr(p)
     }
@@ -14,6 +16,10 @@ fn variable_def(p: &mut Parser) -> CompletedMarker {
     p.bump();
 
     p.expect(TokenKind::Ident);
+    if p.at(TokenKind::Colon) {
+        p.bump();
+        types::tpe(p);
+    }
     p.expect(TokenKind::Equals);
 
     expr::expr(p);
@@ -21,6 +27,25 @@ fn variable_def(p: &mut Parser) -> CompletedMarker {
     m.complete(p, SyntaxKind::VariableDef)
 }
 
+// `def name(param: Type, ...): Type = body` (result type is optional)
+fn function_def(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::FnKw));
+    let m = p.start();
+    p.bump();
+
+    p.expect(TokenKind::Ident);
+    expr::param_list(p);
+    if p.at(TokenKind::Colon) {
+        p.bump();
+        types::tpe(p);
+    }
+    p.expect(TokenKind::Equals);
+
+    expr::expr(p);
+
+    m.complete(p, SyntaxKind::FunctionDef)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::check;
@@ -43,4 +68,78 @@ mod tests {
                   Ident@10..13 "bar""#]],
         );
     }
+
+    #[test]
+    fn parse_variable_definition_with_type() {
+        check(
+            "val a:(Int,Long)=b",
+            expect![[r#"
+            Root@0..18
+              VariableDef@0..18
+                ValKw@0..3 "val"
+                Whitespace@3..4 " "
+                Ident@4..5 "a"
+                Colon@5..6 ":"
+                TupleType@6..16
+                  LParen@6..7 "("
+                  Type@7..10
+                    Ident@7..10 "Int"
+                  Comma@10..11 ","
+                  Type@11..15
+                    Ident@11..15 "Long"
+                  RParen@15..16 ")"
+                Equals@16..17 "="
+                Ident@17..18
+                  Ident@17..18 "b""#]],
+        );
+    }
+
+    #[test]
+    fn parse_function_definition() {
+        check(
+            "def f(x:Int):Int=x",
+            expect![[r#"
+            Root@0..18
+              FunctionDef@0..18
+                FnKw@0..3 "def"
+                Whitespace@3..4 " "
+                Ident@4..5 "f"
+                ParamList@5..12
+                  LParen@5..6 "("
+                  Param@6..11
+                    Ident@6..7 "x"
+                    Colon@7..8 ":"
+                    Type@8..11
+                      Ident@8..11 "Int"
+                  RParen@11..12 ")"
+                Colon@12..13 ":"
+                Type@13..16
+                  Ident@13..16 "Int"
+                Equals@16..17 "="
+                Ident@17..18
+                  Ident@17..18 "x""#]],
+        );
+    }
+
+    #[test]
+    fn parse_block() {
+        check(
+            "{val a=1;a}",
+            expect![[r#"
+            Root@0..11
+              BlockExpr@0..11
+                LBrace@0..1 "{"
+                VariableDef@1..8
+                  ValKw@1..4 "val"
+                  Whitespace@4..5 " "
+                  Ident@5..6 "a"
+                  Equals@6..7 "="
+                  IntNumber@7..8
+                    IntNumber@7..8 "1"
+                Semicolon@8..9 ";"
+                Ident@9..10
+                  Ident@9..10 "a"
+                RBrace@10..11 "}""#]],
+        );
+    }
 }
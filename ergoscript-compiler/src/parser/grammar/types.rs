@@ -0,0 +1,80 @@
+use super::*;
+
+pub(super) fn type_arg_list(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LBracket));
+
+    let m = p.start();
+    p.bump();
+    loop {
+        tpe(p);
+        if !p.at(TokenKind::Comma) {
+            break;
+        }
+        p.bump();
+    }
+    p.expect(TokenKind::RBracket);
+
+    m.complete(p, SyntaxKind::TypeArgList)
+}
+
+pub(super) fn tpe(p: &mut Parser) -> CompletedMarker {
+    if p.at(TokenKind::LParen) {
+        return tuple_type(p);
+    }
+    let m = p.start();
+    p.expect(TokenKind::Ident);
+    if p.at(TokenKind::LBracket) {
+        type_arg_list(p);
+    }
+
+    m.complete(p, SyntaxKind::Type)
+}
+
+// `(Int, Coll[Byte])`
+fn tuple_type(p: &mut Parser) -> CompletedMarker {
+    assert!(p.at(TokenKind::LParen));
+
+    let m = p.start();
+    p.bump();
+    loop {
+        tpe(p);
+        if !p.at(TokenKind::Comma) {
+            break;
+        }
+        p.bump();
+    }
+    p.expect(TokenKind::RParen);
+
+    m.complete(p, SyntaxKind::TupleType)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::check;
+    use expect_test::expect;
+
+    #[test]
+    fn parse_nested_type_args() {
+        check(
+            "x[Coll[Byte],Long]",
+            expect![[r#"
+                Root@0..18
+                  ApplyTypesExpr@0..18
+                    Ident@0..1
+                      Ident@0..1 "x"
+                    TypeArgList@1..18
+                      LBracket@1..2 "["
+                      Type@2..12
+                        Ident@2..6 "Coll"
+                        TypeArgList@6..12
+                          LBracket@6..7 "["
+                          Type@7..11
+                            Ident@7..11 "Byte"
+                          RBracket@11..12 "]"
+                      Comma@12..13 ","
+                      Type@13..17
+                        Ident@13..17 "Long"
+                      RBracket@17..18 "]""#]],
+        );
+    }
+}
@@ -42,6 +42,15 @@ impl<'t, 'input> Source<'t, 'input> {
         self.peek_kind_raw().map_or(false, TokenKind::is_trivia)
     }
 
+    /// Kind of the n-th non-trivia token ahead (0 is the next token)
+    pub fn lookahead_kind(&self, n: usize) -> Option<TokenKind> {
+        self.tokens[self.cursor..]
+            .iter()
+            .map(|Token { kind, .. }| *kind)
+            .filter(|kind| !kind.is_trivia())
+            .nth(n)
+    }
+
     pub fn last_token_range(&self) -> Option<TextRange> {
         self.tokens.last().map(|Token { range, .. }| *range)
     }
@@ -19,6 +19,21 @@ impl ScriptEnv {
         ScriptEnv(HashMap::new())
     }
 
+    /// Adds the value for the given identifier, replacing the previous one (if any)
+    pub fn with(mut self, ident: impl Into<String>, value: impl Into<Constant>) -> Self {
+        self.insert(ident, value);
+        self
+    }
+
+    /// Sets the value for the given identifier, returns the previous value (if any)
+    pub fn insert(
+        &mut self,
+        ident: impl Into<String>,
+        value: impl Into<Constant>,
+    ) -> Option<Constant> {
+        self.0.insert(ident.into(), value.into())
+    }
+
     /// Returns the value([`Constant`]) for the given identifier (if any)
     pub fn get(&self, ident: &str) -> Option<&Constant> {
         self.0.get(ident)
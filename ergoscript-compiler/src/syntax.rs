@@ -9,26 +9,62 @@ pub enum SyntaxKind {
     Whitespace,
     FnKw,
     ValKw,
+    IfKw,
+    ElseKw,
+    TrueKw,
+    FalseKw,
     Ident,
     IntNumber,
     LongNumber,
+    StringLiteral,
     Plus,
     Minus,
     Star,
     Slash,
+    Percent,
     And,
+    Or,
+    Caret,
+    Bang,
     Equals,
+    EqualsEquals,
+    NotEquals,
+    Less,
+    LessEquals,
+    Greater,
+    GreaterEquals,
     LParen,
     RParen,
     LBrace,
     RBrace,
+    LBracket,
+    RBracket,
+    Dot,
+    Comma,
+    Colon,
+    Semicolon,
+    FatArrow,
     Comment,
     Error,
     Root,
     InfixExpr,
     ParenExpr,
     PrefixExpr,
+    IfExpr,
+    SelectExpr,
+    CallExpr,
+    ArgList,
+    ApplyTypesExpr,
+    TypeArgList,
+    Type,
+    LambdaExpr,
+    ParamList,
+    Param,
     VariableDef,
+    TupleExpr,
+    TupleType,
+    BlockExpr,
+    FunctionDef,
 }
 
 impl From<TokenKind> for SyntaxKind {
@@ -37,21 +73,43 @@ impl From<TokenKind> for SyntaxKind {
             TokenKind::Whitespace => Self::Whitespace,
             TokenKind::FnKw => Self::FnKw,
             TokenKind::ValKw => Self::ValKw,
+            TokenKind::IfKw => Self::IfKw,
+            TokenKind::ElseKw => Self::ElseKw,
+            TokenKind::TrueKw => Self::TrueKw,
+            TokenKind::FalseKw => Self::FalseKw,
             TokenKind::Ident => Self::Ident,
             TokenKind::IntNumber => Self::IntNumber,
             TokenKind::LongNumber => Self::LongNumber,
+            TokenKind::StringLiteral => Self::StringLiteral,
             TokenKind::Plus => Self::Plus,
             TokenKind::Minus => Self::Minus,
             TokenKind::Star => Self::Star,
             TokenKind::Slash => Self::Slash,
+            TokenKind::Percent => Self::Percent,
             TokenKind::Equals => Self::Equals,
+            TokenKind::EqualsEquals => Self::EqualsEquals,
+            TokenKind::NotEquals => Self::NotEquals,
+            TokenKind::Less => Self::Less,
+            TokenKind::LessEquals => Self::LessEquals,
+            TokenKind::Greater => Self::Greater,
+            TokenKind::GreaterEquals => Self::GreaterEquals,
             TokenKind::LParen => Self::LParen,
             TokenKind::RParen => Self::RParen,
             TokenKind::LBrace => Self::LBrace,
             TokenKind::RBrace => Self::RBrace,
+            TokenKind::LBracket => Self::LBracket,
+            TokenKind::RBracket => Self::RBracket,
+            TokenKind::Dot => Self::Dot,
+            TokenKind::Comma => Self::Comma,
+            TokenKind::Colon => Self::Colon,
+            TokenKind::Semicolon => Self::Semicolon,
+            TokenKind::FatArrow => Self::FatArrow,
             TokenKind::Comment => Self::Comment,
             TokenKind::Error => Self::Error,
             TokenKind::And => Self::And,
+            TokenKind::Or => Self::Or,
+            TokenKind::Caret => Self::Caret,
+            TokenKind::Bang => Self::Bang,
         }
     }
 }
@@ -2,9 +2,32 @@ use rowan::TextRange;
 
 use crate::error::pretty_error_desc;
 use crate::hir;
+use crate::hir::Apply;
+use crate::hir::ApplyTypes;
 use crate::hir::Binary;
+use crate::hir::BinaryOp;
+use crate::hir::Block;
 use crate::hir::Expr;
 use crate::hir::ExprKind;
+use crate::hir::If;
+use crate::hir::Lambda;
+use crate::hir::PredefFunc;
+use crate::hir::PredefFuncCall;
+use crate::hir::Select;
+use crate::hir::Tuple;
+use crate::hir::Unary;
+use crate::hir::UnaryOp;
+use crate::hir::Val;
+use ergotree_ir::types::sbox;
+use ergotree_ir::types::scontext;
+use ergotree_ir::types::sfunc::SFunc;
+use ergotree_ir::types::smethod::SMethod;
+use ergotree_ir::types::stuple::STuple;
+use ergotree_ir::types::stype::SType;
+use ergotree_ir::types::stype_companion::STypeCompanion;
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::convert::TryFrom;
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct TypeInferenceError {
@@ -20,32 +43,591 @@ impl TypeInferenceError {
     pub fn pretty_desc(&self, source: &str) -> String {
         pretty_error_desc(source, self.span, &self.msg)
     }
+
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    pub fn span(&self) -> TextRange {
+        self.span
+    }
 }
 
 pub fn assign_type(expr: Expr) -> Result<Expr, TypeInferenceError> {
-    hir::rewrite(expr, |e| {
-        Ok(match &e.kind {
-            ExprKind::Binary(Binary { op, lhs, rhs }) => match op.node {
-                hir::BinaryOp::Plus => {
-                    let l = assign_type(*lhs.clone())?;
-                    let r = assign_type(*rhs.clone())?;
-                    let tpe = l.tpe.clone();
-                    Some(Expr {
-                        kind: Binary {
-                            op: op.clone(),
-                            lhs: l.into(),
-                            rhs: r.into(),
-                        }
-                        .into(),
-                        span: e.span,
-                        tpe,
-                    })
+    let (typed, errors) = assign_type_partial(expr);
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(typed),
+    }
+}
+
+/// Assigns types collecting all the errors, erroneous expressions are left untyped
+pub fn assign_type_partial(expr: Expr) -> (Expr, Vec<TypeInferenceError>) {
+    let errors = RefCell::new(Vec::new());
+    let typed = assign_type_in(expr, &errors);
+    (typed, errors.into_inner())
+}
+
+fn assign_type_in(expr: Expr, errors: &RefCell<Vec<TypeInferenceError>>) -> Expr {
+    let res = hir::rewrite(expr, |e| {
+        Ok::<_, Infallible>(expr_tpe(e, errors).unwrap_or_else(|error| {
+            push_error(errors, error);
+            None
+        }))
+    });
+    match res {
+        Ok(typed) => typed,
+        Err(never) => match never {},
+    }
+}
+
+/// Block values and body are typed once more with the value references bound (see
+/// [`assign_block_type`]), so the same error can be found twice
+fn push_error(errors: &RefCell<Vec<TypeInferenceError>>, error: TypeInferenceError) {
+    let mut errors = errors.borrow_mut();
+    if !errors.contains(&error) {
+        errors.push(error);
+    }
+}
+
+/// Typed expression if its type is known from the (already typed) children
+fn expr_tpe(
+    e: &Expr,
+    errors: &RefCell<Vec<TypeInferenceError>>,
+) -> Result<Option<Expr>, TypeInferenceError> {
+    let tpe = match &e.kind {
+        ExprKind::Binary(Binary { op, lhs, rhs }) => match (&lhs.tpe, &rhs.tpe) {
+            (Some(l), Some(r)) => binary_op_tpe(&op.node, l, r, e.span)?,
+            // unresolved operands are reported on MIR lowering
+            _ => return Ok(None),
+        },
+        ExprKind::Unary(Unary { op, expr }) => match &expr.tpe {
+            Some(t) => unary_op_tpe(&op.node, t, e.span)?,
+            _ => return Ok(None),
+        },
+        ExprKind::If(If {
+            cond,
+            then_branch,
+            else_branch,
+        }) => match (&cond.tpe, &then_branch.tpe, &else_branch.tpe) {
+            (Some(c), Some(t), Some(f)) => {
+                if *c != SType::SBoolean {
+                    return Err(TypeInferenceError::new(
+                        format!("if condition should be Boolean, got {0}", c),
+                        cond.span,
+                    ));
+                }
+                if t != f {
+                    return Err(TypeInferenceError::new(
+                        format!(
+                            "if branches should have the same type, got {0} and {1}",
+                            t, f
+                        ),
+                        e.span,
+                    ));
+                }
+                t.clone()
+            }
+            _ => return Ok(None),
+        },
+        ExprKind::Select(select) => match &select.obj.tpe {
+            // register type is set by the type arguments
+            Some(SType::SBox) if select.register_id().is_some() => return Ok(None),
+            Some(obj_tpe) => match property_tpe(obj_tpe, &select.field) {
+                Some(t) => t,
+                // method type is known only after application to the arguments
+                None if matches!(obj_tpe, SType::SColl(_))
+                    && COLL_METHODS.contains(&select.field.as_str()) =>
+                {
+                    return Ok(None)
+                }
+                None if matches!(obj_tpe, SType::SOption(_)) && select.field == "getOrElse" => {
+                    return Ok(None)
+                }
+                None if method_by_name(obj_tpe, &select.field).is_some() => return Ok(None),
+                None => {
+                    return Err(TypeInferenceError::new(
+                        format!("unknown property {0} of {1}", select.field, obj_tpe),
+                        e.span,
+                    ))
                 }
-                _ => todo!(),
             },
-            _ => None,
-        })
-    })
+            _ => return Ok(None),
+        },
+        ExprKind::Apply(Apply { func, args }) => {
+            let arg_tpes = match args
+                .iter()
+                .map(|arg| arg.tpe.clone())
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(arg_tpes) => arg_tpes,
+                None => return Ok(None),
+            };
+            match (&func.kind, &func.tpe) {
+                (ExprKind::Select(Select { obj, field }), None) => {
+                    let obj_tpe = match &obj.tpe {
+                        Some(obj_tpe) => obj_tpe,
+                        None => return Ok(None),
+                    };
+                    let res = match obj_tpe {
+                        SType::SColl(elem_tpe) if COLL_METHODS.contains(&field.as_str()) => {
+                            coll_method_tpe(elem_tpe, field, &arg_tpes)
+                        }
+                        _ => method_tpe(obj_tpe, field, &arg_tpes),
+                    };
+                    res.ok_or_else(|| {
+                        TypeInferenceError::new(
+                            format!(
+                                "cannot apply {0} of {1} to {2}",
+                                field,
+                                obj_tpe,
+                                tpes_desc(&arg_tpes)
+                            ),
+                            e.span,
+                        )
+                    })?
+                }
+                (_, Some(SType::SColl(elem_tpe))) if arg_tpes == [SType::SInt] => *elem_tpe.clone(),
+                (_, Some(SType::SFunc(sfunc))) if sfunc.t_dom == arg_tpes => *sfunc.t_range.clone(),
+                (_, Some(func_tpe)) => {
+                    return Err(TypeInferenceError::new(
+                        format!("cannot apply {0} to {1}", func_tpe, tpes_desc(&arg_tpes)),
+                        e.span,
+                    ))
+                }
+                _ => return Ok(None),
+            }
+        }
+        ExprKind::PredefFuncCall(PredefFuncCall {
+            func,
+            tpe_args,
+            args,
+        }) => {
+            let arg_tpes = match args
+                .iter()
+                .map(|arg| arg.tpe.clone())
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(arg_tpes) => arg_tpes,
+                None => return Ok(None),
+            };
+            predef_func_tpe(func, tpe_args, &arg_tpes).ok_or_else(|| {
+                TypeInferenceError::new(
+                    format!(
+                        "cannot apply {0:?} with type arguments [{1}] to {2}",
+                        func,
+                        tpe_args
+                            .iter()
+                            .map(SType::to_string)
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        tpes_desc(&arg_tpes)
+                    ),
+                    e.span,
+                )
+            })?
+        }
+        ExprKind::Lambda(Lambda { args, body }) => match &body.tpe {
+            Some(body_tpe) => SType::SFunc(SFunc::new(
+                args.iter().map(|(_, tpe)| tpe.clone()).collect(),
+                body_tpe.clone(),
+            )),
+            _ => return Ok(None),
+        },
+        ExprKind::ApplyTypes(ApplyTypes { expr, tpes }) => match (&expr.kind, tpes.as_slice()) {
+            (ExprKind::Select(select), _) if select.obj.tpe.is_none() => return Ok(None),
+            (ExprKind::Select(select), [tpe])
+                if select.obj.tpe == Some(SType::SBox) && select.register_id().is_some() =>
+            {
+                SType::SOption(tpe.clone().into())
+            }
+            _ => {
+                return Err(TypeInferenceError::new(
+                    format!("unexpected type arguments {0}", tpes_desc(tpes)),
+                    e.span,
+                ))
+            }
+        },
+        ExprKind::Tuple(Tuple { items }) => {
+            let item_tpes = match items
+                .iter()
+                .map(|item| item.tpe.clone())
+                .collect::<Option<Vec<_>>>()
+            {
+                Some(item_tpes) => item_tpes,
+                None => return Ok(None),
+            };
+            STuple::try_from(item_tpes)
+                .map(SType::STuple)
+                .map_err(|_| {
+                    TypeInferenceError::new(
+                        "tuple should have from 2 to 255 items".to_string(),
+                        e.span,
+                    )
+                })?
+        }
+        ExprKind::Block(block) => return Ok(Some(assign_block_type(block, e, errors))),
+        _ => return Ok(None),
+    };
+    Ok(Some(Expr {
+        tpe: Some(tpe),
+        ..e.clone()
+    }))
+}
+
+/// Values are typed in the order of definition, the references to the value are typed
+/// before the rest of the block is typed (the binder ensures the names are unique in the block)
+fn assign_block_type(block: &Block, e: &Expr, errors: &RefCell<Vec<TypeInferenceError>>) -> Expr {
+    let mut defined: Vec<(String, SType)> = Vec::new();
+    let mut vals = Vec::with_capacity(block.vals.len());
+    for val in &block.vals {
+        let rhs = assign_type_in(bind_val_refs(*val.rhs.clone(), &defined), errors);
+        let tpe = match (&val.tpe, &rhs.tpe) {
+            (Some(declared), Some(actual)) if declared != actual => {
+                push_error(
+                    errors,
+                    TypeInferenceError::new(
+                        format!(
+                            "value {0} is declared as {1}, but has type {2}",
+                            val.name, declared, actual
+                        ),
+                        rhs.span,
+                    ),
+                );
+                Some(declared.clone())
+            }
+            (declared, actual) => actual.clone().or_else(|| declared.clone()),
+        };
+        if let Some(tpe) = tpe {
+            defined.push((val.name.clone(), tpe));
+        }
+        vals.push(Val {
+            rhs: rhs.into(),
+            ..val.clone()
+        });
+    }
+    let body = assign_type_in(bind_val_refs(*block.body.clone(), &defined), errors);
+    Expr {
+        tpe: body.tpe.clone(),
+        kind: Block {
+            vals,
+            body: body.into(),
+        }
+        .into(),
+        span: e.span,
+    }
+}
+
+/// Sets the type of the (not yet typed) references to the given values
+fn bind_val_refs(expr: Expr, vals: &[(String, SType)]) -> Expr {
+    vals.iter()
+        .fold(expr, |expr, (name, tpe)| hir::bind_refs(expr, name, tpe))
+}
+
+fn tpes_desc(tpes: &[SType]) -> String {
+    format!(
+        "({0})",
+        tpes.iter()
+            .map(SType::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn predef_func_tpe(func: &PredefFunc, tpe_args: &[SType], arg_tpes: &[SType]) -> Option<SType> {
+    let coll_byte = SType::SColl(SType::SByte.into());
+    match (func, tpe_args, arg_tpes) {
+        (PredefFunc::SigmaProp, [], [SType::SBoolean]) => Some(SType::SSigmaProp),
+        (PredefFunc::ProveDlog, [], [SType::SGroupElement]) => Some(SType::SSigmaProp),
+        (
+            PredefFunc::ProveDhTuple,
+            [],
+            [SType::SGroupElement, SType::SGroupElement, SType::SGroupElement, SType::SGroupElement],
+        ) => Some(SType::SSigmaProp),
+        (PredefFunc::AtLeast, [], [SType::SInt, SType::SColl(elem_tpe)])
+            if **elem_tpe == SType::SSigmaProp =>
+        {
+            Some(SType::SSigmaProp)
+        }
+        (
+            PredefFunc::AllOf | PredefFunc::AnyOf | PredefFunc::XorOf,
+            [],
+            [SType::SColl(elem_tpe)],
+        ) if **elem_tpe == SType::SBoolean => Some(SType::SBoolean),
+        (PredefFunc::AllZk | PredefFunc::AnyZk, [], [SType::SColl(elem_tpe)])
+            if **elem_tpe == SType::SSigmaProp =>
+        {
+            Some(SType::SSigmaProp)
+        }
+        (PredefFunc::Blake2b256 | PredefFunc::Sha256, [], [input]) if *input == coll_byte => {
+            Some(coll_byte)
+        }
+        (PredefFunc::LongToByteArray, [], [SType::SLong]) => Some(coll_byte),
+        (PredefFunc::ByteArrayToBigInt, [], [input]) if *input == coll_byte => Some(SType::SBigInt),
+        (PredefFunc::ByteArrayToLong, [], [input]) if *input == coll_byte => Some(SType::SLong),
+        (PredefFunc::DecodePoint, [], [input]) if *input == coll_byte => Some(SType::SGroupElement),
+        (PredefFunc::GetVar, [tpe], [SType::SInt]) => Some(SType::SOption(tpe.clone().into())),
+        // type argument (if any) is the element type of the new values
+        (
+            PredefFunc::SubstConstants,
+            tpe_args,
+            [script_bytes, SType::SColl(positions_tpe), SType::SColl(values_tpe)],
+        ) if *script_bytes == coll_byte
+            && **positions_tpe == SType::SInt
+            && (tpe_args.is_empty() || tpe_args == [*values_tpe.clone()]) =>
+        {
+            Some(coll_byte)
+        }
+        (PredefFunc::Min | PredefFunc::Max, [], [l, r]) if l == r && l.is_numeric() => {
+            Some(l.clone())
+        }
+        (PredefFunc::Coll, [elem_tpe], items) if items.iter().all(|t| t == elem_tpe) => {
+            Some(SType::SColl(elem_tpe.clone().into()))
+        }
+        (PredefFunc::Coll, [], [first, rest @ ..]) if rest.iter().all(|t| t == first) => {
+            Some(SType::SColl(first.clone().into()))
+        }
+        _ => None,
+    }
+}
+
+/// Collection methods that take arguments
+pub(crate) const COLL_METHODS: [&str; 8] = [
+    "map",
+    "filter",
+    "exists",
+    "forall",
+    "fold",
+    "slice",
+    "append",
+    "getOrElse",
+];
+
+fn coll_method_tpe(elem_tpe: &SType, method: &str, arg_tpes: &[SType]) -> Option<SType> {
+    match (method, arg_tpes) {
+        ("map", [SType::SFunc(f)]) if f.t_dom == [elem_tpe.clone()] => {
+            Some(SType::SColl(f.t_range.clone()))
+        }
+        ("filter", [SType::SFunc(f)])
+            if f.t_dom == [elem_tpe.clone()] && *f.t_range == SType::SBoolean =>
+        {
+            Some(SType::SColl(elem_tpe.clone().into()))
+        }
+        ("exists", [SType::SFunc(f)]) | ("forall", [SType::SFunc(f)])
+            if f.t_dom == [elem_tpe.clone()] && *f.t_range == SType::SBoolean =>
+        {
+            Some(SType::SBoolean)
+        }
+        ("fold", [zero, SType::SFunc(f)])
+            if f.t_dom == [zero.clone(), elem_tpe.clone()] && *f.t_range == *zero =>
+        {
+            Some(zero.clone())
+        }
+        ("slice", [SType::SInt, SType::SInt]) => Some(SType::SColl(elem_tpe.clone().into())),
+        ("append", [SType::SColl(other_elem_tpe)]) if **other_elem_tpe == *elem_tpe => {
+            Some(SType::SColl(elem_tpe.clone().into()))
+        }
+        ("getOrElse", [SType::SInt, default]) if default == elem_tpe => Some(default.clone()),
+        _ => None,
+    }
+}
+
+/// Type of the method application (other than collection methods above)
+fn method_tpe(obj_tpe: &SType, method: &str, arg_tpes: &[SType]) -> Option<SType> {
+    match (obj_tpe, method, arg_tpes) {
+        (SType::SOption(elem_tpe), "getOrElse", [default]) if **elem_tpe == *default => {
+            Some(default.clone())
+        }
+        _ => {
+            let method = specialized_method(obj_tpe, method, arg_tpes)?;
+            Some(*method.tpe().t_range.clone())
+        }
+    }
+}
+
+pub(crate) fn property_tpe(obj_tpe: &SType, field: &str) -> Option<SType> {
+    match (obj_tpe, field) {
+        (SType::SColl(_), "size") => Some(SType::SInt),
+        (SType::SBox, "value") => Some(SType::SLong),
+        (SType::SBox, "propositionBytes") | (SType::SBox, "id") => {
+            Some(SType::SColl(SType::SByte.into()))
+        }
+        (SType::SBox, "tokens") => Some(*sbox::TOKENS_METHOD.tpe().t_range.clone()),
+        (SType::SContext, "dataInputs") => {
+            Some(*scontext::DATA_INPUTS_PROPERTY.tpe().t_range.clone())
+        }
+        (SType::SOption(elem_tpe), "get") => Some(*elem_tpe.clone()),
+        (SType::SOption(_), "isDefined") => Some(SType::SBoolean),
+        (SType::STuple(tuple), _) => {
+            let index = tuple_field_index(field)?;
+            tuple.items.get(usize::from(index) - 1).cloned()
+        }
+        (t, _) if t.is_numeric() => numeric_cast_tpe(field),
+        _ => {
+            let method = specialized_method(obj_tpe, field, &[])?;
+            Some(*method.tpe().t_range.clone())
+        }
+    }
+}
+
+/// 1-based index of the tuple field (`_1`, `_2`, ...)
+pub(crate) fn tuple_field_index(field: &str) -> Option<u8> {
+    match field.strip_prefix('_')?.parse::<u8>() {
+        Ok(index) if index > 0 && !field.starts_with("_0") => Some(index),
+        _ => None,
+    }
+}
+
+/// Target type of the numeric conversion method, e.g. `toLong`
+pub(crate) fn numeric_cast_tpe(method: &str) -> Option<SType> {
+    match method {
+        "toByte" => Some(SType::SByte),
+        "toShort" => Some(SType::SShort),
+        "toInt" => Some(SType::SInt),
+        "toLong" => Some(SType::SLong),
+        "toBigInt" => Some(SType::SBigInt),
+        _ => None,
+    }
+}
+
+/// Position of the numeric type in the widening order (Byte < Short < Int < Long < BigInt)
+pub(crate) fn numeric_rank(tpe: &SType) -> Option<u8> {
+    match tpe {
+        SType::SByte => Some(0),
+        SType::SShort => Some(1),
+        SType::SInt => Some(2),
+        SType::SLong => Some(3),
+        SType::SBigInt => Some(4),
+        _ => None,
+    }
+}
+
+/// Type the numeric operands of a binary operation are upcast to (the widest of them)
+pub(crate) fn numeric_upcast_tpe(l: &SType, r: &SType) -> Option<SType> {
+    if numeric_rank(l)? >= numeric_rank(r)? {
+        Some(l.clone())
+    } else {
+        Some(r.clone())
+    }
+}
+
+fn type_companion(obj_tpe: &SType) -> Option<STypeCompanion> {
+    match obj_tpe {
+        SType::SContext => Some(STypeCompanion::Context),
+        SType::SBox => Some(STypeCompanion::Box),
+        SType::SColl(_) => Some(STypeCompanion::Coll),
+        SType::SGroupElement => Some(STypeCompanion::GroupElem),
+        SType::SHeader => Some(STypeCompanion::Header),
+        SType::SPreHeader => Some(STypeCompanion::PreHeader),
+        SType::SOption(_) => Some(STypeCompanion::Option),
+        SType::SAvlTree => Some(STypeCompanion::AvlTree),
+        SType::SSigmaProp => Some(STypeCompanion::SigmaProp),
+        SType::SGlobal => Some(STypeCompanion::Global),
+        _ => None,
+    }
+}
+
+fn method_by_name(obj_tpe: &SType, name: &str) -> Option<SMethod> {
+    type_companion(obj_tpe)?
+        .methods()
+        .into_iter()
+        .find(|method| method.name() == name)
+}
+
+/// Names of the properties and methods of the given type
+pub(crate) fn member_names(obj_tpe: &SType) -> Vec<String> {
+    let mut names: Vec<String> = match obj_tpe {
+        SType::SColl(_) => std::iter::once("size")
+            .chain(COLL_METHODS.iter().copied())
+            .map(str::to_string)
+            .collect(),
+        SType::SOption(_) => vec![
+            "get".to_string(),
+            "isDefined".to_string(),
+            "getOrElse".to_string(),
+        ],
+        SType::STuple(tuple) => (1..=tuple.items.len())
+            .map(|index| format!("_{0}", index))
+            .collect(),
+        SType::SContext => vec!["dataInputs".to_string()],
+        t if t.is_numeric() => ["toByte", "toShort", "toInt", "toLong", "toBigInt"]
+            .iter()
+            .map(|name| name.to_string())
+            .collect(),
+        _ => Vec::new(),
+    };
+    for method in type_companion(obj_tpe)
+        .map(|companion| companion.methods())
+        .unwrap_or_default()
+    {
+        if !names.iter().any(|name| name == method.name()) {
+            names.push(method.name().to_string());
+        }
+    }
+    names
+}
+
+/// Method with the signature specialized for the given object and argument types
+pub(crate) fn specialized_method(
+    obj_tpe: &SType,
+    name: &str,
+    arg_tpes: &[SType],
+) -> Option<SMethod> {
+    let method = method_by_name(obj_tpe, name)?;
+    if method.tpe().t_dom.len() != arg_tpes.len() + 1 {
+        return None;
+    }
+    method
+        .specialize_for(obj_tpe.clone(), arg_tpes.to_vec())
+        .ok()
+}
+
+fn binary_op_tpe(
+    op: &BinaryOp,
+    l: &SType,
+    r: &SType,
+    span: TextRange,
+) -> Result<SType, TypeInferenceError> {
+    let numeric = numeric_upcast_tpe(l, r);
+    let same_bool = l == r && *l == SType::SBoolean;
+    // at least one SigmaProp operand, the other one is a SigmaProp or Boolean
+    let sigma_prop = matches!(
+        (l, r),
+        (SType::SSigmaProp, SType::SSigmaProp | SType::SBoolean)
+            | (SType::SBoolean, SType::SSigmaProp)
+    );
+    match (op, numeric) {
+        (
+            BinaryOp::Plus
+            | BinaryOp::Minus
+            | BinaryOp::Multiply
+            | BinaryOp::Divide
+            | BinaryOp::Modulo,
+            Some(tpe),
+        ) => Ok(tpe),
+        (BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge, Some(_)) => Ok(SType::SBoolean),
+        (BinaryOp::Eq | BinaryOp::NEq, numeric) if l == r || numeric.is_some() => {
+            Ok(SType::SBoolean)
+        }
+        (BinaryOp::And | BinaryOp::Or, _) if same_bool => Ok(SType::SBoolean),
+        (BinaryOp::And | BinaryOp::Or, _) if sigma_prop => Ok(SType::SSigmaProp),
+        (BinaryOp::Xor, _) if same_bool || (l == r && l.is_numeric()) => Ok(l.clone()),
+        _ => Err(TypeInferenceError::new(
+            format!("Invalid operand types for {0:?}: {1} and {2}", op, l, r),
+            span,
+        )),
+    }
+}
+
+fn unary_op_tpe(op: &UnaryOp, t: &SType, span: TextRange) -> Result<SType, TypeInferenceError> {
+    match op {
+        UnaryOp::Neg if t.is_numeric() => Ok(t.clone()),
+        UnaryOp::Not if *t == SType::SBoolean => Ok(SType::SBoolean),
+        _ => Err(TypeInferenceError::new(
+            format!("Invalid operand type for {0:?}: {1}", op, t),
+            span,
+        )),
+    }
 }
 
 #[cfg(test)]
@@ -65,6 +647,31 @@ mod tests {
     use super::*;
     use expect_test::expect;
 
+    fn infer_tpe(input: &str) -> Result<SType, TypeInferenceError> {
+        let root = crate::ast::Root::cast(crate::parser::parse(input).syntax()).unwrap();
+        let binder = crate::binder::Binder::new(crate::script_env::ScriptEnv::new());
+        let bind = binder.bind(hir::lower(root).unwrap()).unwrap();
+        assign_type(bind).map(|expr| expr.tpe.unwrap())
+    }
+
+    #[test]
+    fn sigma_prop_logical_ops() {
+        assert_eq!(
+            infer_tpe("sigmaProp(HEIGHT > 1) && sigmaProp(HEIGHT < 5)"),
+            Ok(SType::SSigmaProp)
+        );
+        assert_eq!(
+            infer_tpe("sigmaProp(HEIGHT > 1) || HEIGHT < 5"),
+            Ok(SType::SSigmaProp)
+        );
+        assert_eq!(
+            infer_tpe("HEIGHT < 5 && sigmaProp(HEIGHT > 1)"),
+            Ok(SType::SSigmaProp)
+        );
+        assert_eq!(infer_tpe("HEIGHT < 5 && HEIGHT > 1"), Ok(SType::SBoolean));
+        assert!(infer_tpe("sigmaProp(HEIGHT > 1) ^ sigmaProp(HEIGHT < 5)").is_err());
+    }
+
     #[test]
     fn bin_smoke() {
         check(
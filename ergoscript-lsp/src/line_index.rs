@@ -0,0 +1,84 @@
+//! Conversion between byte offsets and LSP positions (line and UTF-16 column)
+
+use lsp_types::Position;
+
+/// Start offsets of the lines in the text
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index the lines of the text
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        LineIndex { text, line_starts }
+    }
+
+    /// Position of the byte offset (clamped to the text length)
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.text.len());
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(next_line) => next_line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let character = self.text[line_start..offset]
+            .chars()
+            .map(char::len_utf16)
+            .sum::<usize>();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Byte offset of the position (clamped to the end of the line)
+    pub fn offset(&self, position: Position) -> usize {
+        let line_start = match self.line_starts.get(position.line as usize) {
+            Some(start) => *start,
+            None => return self.text.len(),
+        };
+        let mut character = 0;
+        for (idx, c) in self.text[line_start..].char_indices() {
+            if c == '\n' || character >= position.character as usize {
+                return line_start + idx;
+            }
+            character += c.len_utf16();
+        }
+        self.text.len()
+    }
+
+    /// LSP range of the byte range
+    pub fn range(&self, range: std::ops::Range<usize>) -> lsp_types::Range {
+        lsp_types::Range::new(self.position(range.start), self.position(range.end))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let text = "val a = 1\nval ß = \"𝔼\"\nHEIGHT > a";
+        let index = LineIndex::new(text);
+        assert_eq!(index.position(0), Position::new(0, 0));
+        assert_eq!(index.position(10), Position::new(1, 0));
+        // `ß` is 2 bytes and 1 UTF-16 code unit, `𝔼` is 4 bytes and 2 UTF-16 code units
+        let after_str = text.find("\"\n").unwrap() + 1;
+        assert_eq!(index.position(after_str), Position::new(1, 12));
+        assert_eq!(index.position(text.len()), Position::new(2, 10));
+        for offset in text.char_indices().map(|(idx, _)| idx) {
+            assert_eq!(index.offset(index.position(offset)), offset);
+        }
+    }
+
+    #[test]
+    fn out_of_range() {
+        let index = LineIndex::new("ab\ncd");
+        assert_eq!(index.offset(Position::new(0, 10)), 2);
+        assert_eq!(index.offset(Position::new(5, 0)), 5);
+        assert_eq!(index.position(100), Position::new(1, 2));
+    }
+}
@@ -0,0 +1,270 @@
+//! ErgoScript language server (diagnostics, hover, go-to-definition and completion) over stdio
+
+// Coding conventions
+#![forbid(unsafe_code)]
+#![deny(non_upper_case_globals)]
+#![deny(non_camel_case_types)]
+#![deny(non_snake_case)]
+#![deny(unused_mut)]
+#![deny(dead_code)]
+#![deny(unused_imports)]
+#![deny(missing_docs)]
+#![deny(clippy::wildcard_enum_match_arm)]
+#![deny(clippy::unwrap_used)]
+#![deny(clippy::expect_used)]
+#![deny(clippy::todo)]
+#![deny(clippy::unimplemented)]
+#![deny(clippy::unreachable)]
+#![deny(clippy::panic)]
+
+mod line_index;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+use ergoscript_compiler::ide;
+use line_index::LineIndex;
+use lsp_server::Connection;
+use lsp_server::ErrorCode;
+use lsp_server::Message;
+use lsp_server::Notification;
+use lsp_server::Request;
+use lsp_server::Response;
+use lsp_types::notification::DidChangeTextDocument;
+use lsp_types::notification::DidCloseTextDocument;
+use lsp_types::notification::DidOpenTextDocument;
+use lsp_types::notification::Notification as _;
+use lsp_types::notification::PublishDiagnostics;
+use lsp_types::request::Completion;
+use lsp_types::request::GotoDefinition;
+use lsp_types::request::HoverRequest;
+use lsp_types::request::Request as _;
+use lsp_types::CompletionItemKind;
+use lsp_types::CompletionOptions;
+use lsp_types::CompletionResponse;
+use lsp_types::DiagnosticSeverity;
+use lsp_types::GotoDefinitionResponse;
+use lsp_types::Hover;
+use lsp_types::HoverContents;
+use lsp_types::HoverProviderCapability;
+use lsp_types::Location;
+use lsp_types::MarkupContent;
+use lsp_types::MarkupKind;
+use lsp_types::OneOf;
+use lsp_types::PublishDiagnosticsParams;
+use lsp_types::ServerCapabilities;
+use lsp_types::TextDocumentPositionParams;
+use lsp_types::TextDocumentSyncCapability;
+use lsp_types::TextDocumentSyncKind;
+use lsp_types::Url;
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+fn main() -> Result<(), BoxError> {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    connection.initialize(serde_json::to_value(capabilities)?)?;
+    Server::default().run(&connection)?;
+    io_threads.join()?;
+    Ok(())
+}
+
+/// Open documents by their URI
+#[derive(Default)]
+struct Server {
+    documents: HashMap<Url, String>,
+}
+
+impl Server {
+    fn run(&mut self, connection: &Connection) -> Result<(), BoxError> {
+        for msg in &connection.receiver {
+            match msg {
+                Message::Request(req) => {
+                    if connection.handle_shutdown(&req)? {
+                        return Ok(());
+                    }
+                    connection.sender.send(self.handle_request(req).into())?;
+                }
+                Message::Notification(not) => match self.handle_notification(not) {
+                    Ok(Some(params)) => {
+                        let not = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+                        connection.sender.send(not.into())?;
+                    }
+                    Ok(None) => (),
+                    // malformed notification is skipped, there is no response to report it
+                    Err(e) => eprintln!("invalid notification params: {}", e),
+                },
+                Message::Response(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, req: Request) -> Response {
+        let id = req.id.clone();
+        let res = match req.method.as_str() {
+            HoverRequest::METHOD => {
+                serde_json::from_value(req.params).map(|params: lsp_types::HoverParams| {
+                    serde_json::to_value(self.hover(params.text_document_position_params))
+                })
+            }
+            GotoDefinition::METHOD => {
+                serde_json::from_value(req.params).map(|params: lsp_types::GotoDefinitionParams| {
+                    serde_json::to_value(self.goto_definition(params.text_document_position_params))
+                })
+            }
+            Completion::METHOD => {
+                serde_json::from_value(req.params).map(|params: lsp_types::CompletionParams| {
+                    serde_json::to_value(self.completion(params.text_document_position))
+                })
+            }
+            _ => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request: {}", req.method),
+                )
+            }
+        };
+        match res.and_then(|value| value) {
+            Ok(value) => Response::new_ok(id, value),
+            Err(e) => Response::new_err(id, ErrorCode::InvalidParams as i32, e.to_string()),
+        }
+    }
+
+    /// Updates the documents, returns the diagnostics to publish
+    fn handle_notification(
+        &mut self,
+        not: Notification,
+    ) -> Result<Option<PublishDiagnosticsParams>, serde_json::Error> {
+        match not.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams =
+                    serde_json::from_value(not.params)?;
+                let doc = params.text_document;
+                self.documents.insert(doc.uri.clone(), doc.text);
+                Ok(Some(self.diagnostics(doc.uri, Some(doc.version))))
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams =
+                    serde_json::from_value(not.params)?;
+                let doc = params.text_document;
+                // full text sync, the last change is the whole document
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(doc.uri.clone(), change.text);
+                }
+                Ok(Some(self.diagnostics(doc.uri, Some(doc.version))))
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams =
+                    serde_json::from_value(not.params)?;
+                let uri = params.text_document.uri;
+                self.documents.remove(&uri);
+                Ok(Some(PublishDiagnosticsParams::new(uri, Vec::new(), None)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn diagnostics(&self, uri: Url, version: Option<i32>) -> PublishDiagnosticsParams {
+        let text = self.documents.get(&uri).map(String::as_str).unwrap_or("");
+        let index = LineIndex::new(text);
+        let diagnostics = ide::diagnostics(text)
+            .into_iter()
+            .map(|d| lsp_types::Diagnostic {
+                range: index.range(d.range),
+                severity: Some(DiagnosticSeverity::ERROR),
+                source: Some("ergoscript".to_string()),
+                message: d.message,
+                ..Default::default()
+            })
+            .collect();
+        PublishDiagnosticsParams::new(uri, diagnostics, version)
+    }
+
+    /// Document text and the byte offset of the position
+    fn document(&self, pos: &TextDocumentPositionParams) -> Option<(&str, usize)> {
+        let text = self.documents.get(&pos.text_document.uri)?;
+        let offset = LineIndex::new(text).offset(pos.position);
+        Some((text, offset))
+    }
+
+    fn hover(&self, pos: TextDocumentPositionParams) -> Option<Hover> {
+        let (text, offset) = self.document(&pos)?;
+        let info = ide::hover(text, offset)?;
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```ergoscript\n{}\n```", info.tpe),
+            }),
+            range: Some(LineIndex::new(text).range(info.range)),
+        })
+    }
+
+    fn goto_definition(&self, pos: TextDocumentPositionParams) -> Option<GotoDefinitionResponse> {
+        let (text, offset) = self.document(&pos)?;
+        let range = ide::goto_definition(text, offset)?;
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            pos.text_document.uri,
+            LineIndex::new(text).range(range),
+        )))
+    }
+
+    fn completion(&self, pos: TextDocumentPositionParams) -> Option<CompletionResponse> {
+        let (text, offset) = self.document(&pos)?;
+        let items = ide::completions(text, offset)
+            .into_iter()
+            .map(|item| lsp_types::CompletionItem {
+                label: item.label,
+                kind: Some(match item.kind {
+                    ide::CompletionKind::Function => CompletionItemKind::FUNCTION,
+                    ide::CompletionKind::Variable => CompletionItemKind::VARIABLE,
+                    ide::CompletionKind::Property => CompletionItemKind::PROPERTY,
+                }),
+                detail: Some(item.detail).filter(|detail| !detail.is_empty()),
+                ..Default::default()
+            })
+            .collect();
+        Some(CompletionResponse::Array(items))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_notification() {
+        let mut server = Server::default();
+        let not = Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            serde_json::json!({ "textDocument": 1 }),
+        );
+        assert!(server.handle_notification(not).is_err());
+        let uri = Url::parse("file:///contract.es").unwrap();
+        let not = Notification::new(
+            DidOpenTextDocument::METHOD.to_string(),
+            lsp_types::DidOpenTextDocumentParams {
+                text_document: lsp_types::TextDocumentItem::new(
+                    uri.clone(),
+                    "ergoscript".to_string(),
+                    1,
+                    "HEIGHT +".to_string(),
+                ),
+            },
+        );
+        let params = server.handle_notification(not).unwrap().unwrap();
+        assert_eq!(params.uri, uri);
+        assert!(!params.diagnostics.is_empty());
+    }
+}
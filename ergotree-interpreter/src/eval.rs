@@ -4,6 +4,8 @@ use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProp;
 use std::fmt::Display;
 use std::rc::Rc;
 
+use ergotree_ir::ergo_tree::ErgoTree;
+use ergotree_ir::ergo_tree::ErgoTreeVersion;
 use ergotree_ir::mir::expr::Expr;
 use ergotree_ir::mir::value::Value;
 use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
@@ -18,6 +20,8 @@ use self::env::Env;
 pub mod context;
 /// Environment for
 pub mod env;
+/// Soft-forkable validation rules
+pub mod validation_settings;
 
 pub(crate) mod and;
 pub(crate) mod apply;
@@ -37,6 +41,7 @@ pub(crate) mod coll_filter;
 pub(crate) mod coll_fold;
 pub(crate) mod coll_forall;
 pub(crate) mod coll_map;
+pub(crate) mod coll_shift;
 pub(crate) mod coll_size;
 pub(crate) mod coll_slice;
 pub(crate) mod collection;
@@ -66,11 +71,13 @@ pub(crate) mod if_op;
 pub(crate) mod logical_not;
 pub(crate) mod long_to_byte_array;
 pub(crate) mod method_call;
+pub(crate) mod mod_q;
 pub(crate) mod multiply_group;
 pub(crate) mod negation;
 pub(crate) mod option_get;
 pub(crate) mod option_get_or_else;
 pub(crate) mod option_is_defined;
+pub(crate) mod option_value;
 pub(crate) mod or;
 pub(crate) mod property_call;
 pub(crate) mod savltree;
@@ -84,8 +91,11 @@ pub(crate) mod sheader;
 pub(crate) mod sigma_and;
 pub(crate) mod sigma_or;
 pub(crate) mod sigma_prop_bytes;
+pub(crate) mod sigma_prop_is_proven;
+pub(crate) mod snumeric;
 pub(crate) mod soption;
 pub(crate) mod spreheader;
+pub(crate) mod ssigmaprop;
 pub(crate) mod subst_const;
 pub(crate) mod tree_lookup;
 pub(crate) mod tuple;
@@ -119,7 +129,7 @@ impl Display for ReductionDiagnosticInfo {
 pub struct ReductionResult {
     /// value of SigmaProp type which represents a statement verifiable via sigma protocol.
     pub sigma_prop: SigmaBoolean,
-    /// estimated cost of expression evaluation
+    /// cost of expression evaluation (in block cost units)
     pub cost: u64,
     /// Diagnostic information about the reduction (pretty printed expr and/or env)
     pub diag: ReductionDiagnosticInfo,
@@ -131,37 +141,64 @@ pub fn reduce_to_crypto(
     env: &Env,
     ctx: Rc<Context>,
 ) -> Result<ReductionResult, EvalError> {
+    reduce_to_crypto_jit(expr, env, ctx).map(|(reduction, _)| reduction)
+}
+
+/// Same as [`reduce_to_crypto`], but also returns the reduction cost in JIT cost units, so that
+/// further costs can be added before rounding down to block cost units
+pub(crate) fn reduce_to_crypto_jit(
+    expr: &Expr,
+    env: &Env,
+    ctx: Rc<Context>,
+) -> Result<(ReductionResult, u64), EvalError> {
     let ctx_clone = ctx.clone();
-    fn inner(expr: &Expr, env: &Env, ctx: Rc<Context>) -> Result<ReductionResult, EvalError> {
-        let cost_accum = CostAccumulator::new(0, None);
+    fn inner(
+        expr: &Expr,
+        env: &Env,
+        ctx: Rc<Context>,
+    ) -> Result<(ReductionResult, u64), EvalError> {
+        let cost_accum = CostAccumulator::new(0, ctx.cost_limit);
         let mut ectx = EvalContext::new(ctx, cost_accum);
         let mut env_mut = env.clone();
-        expr.eval(&mut env_mut, &mut ectx)
-            .and_then(|v| -> Result<ReductionResult, EvalError> {
+        expr.eval(&mut env_mut, &mut ectx).and_then(
+            |v| -> Result<(ReductionResult, u64), EvalError> {
+                let cost = ectx.cost_accum.total_cost();
+                let jit_cost = ectx.cost_accum.total_jit_cost();
                 match v {
-                    Value::Boolean(b) => Ok(ReductionResult {
-                        sigma_prop: SigmaBoolean::TrivialProp(b),
-                        cost: 0,
-                        diag: ReductionDiagnosticInfo {
-                            env: env_mut.clone(),
-                            pretty_printed_expr: None,
+                    Value::Boolean(b) => Ok((
+                        ReductionResult {
+                            sigma_prop: SigmaBoolean::TrivialProp(b),
+                            cost,
+                            diag: ReductionDiagnosticInfo {
+                                env: env_mut.clone(),
+                                pretty_printed_expr: None,
+                            },
                         },
-                    }),
-                    Value::SigmaProp(sp) => Ok(ReductionResult {
-                        sigma_prop: sp.value().clone(),
-                        cost: 0,
-                        diag: ReductionDiagnosticInfo {
-                            env: env_mut.clone(),
-                            pretty_printed_expr: None,
+                        jit_cost,
+                    )),
+                    Value::SigmaProp(sp) => Ok((
+                        ReductionResult {
+                            sigma_prop: sp.value().clone(),
+                            cost,
+                            diag: ReductionDiagnosticInfo {
+                                env: env_mut.clone(),
+                                pretty_printed_expr: None,
+                            },
                         },
-                    }),
+                        jit_cost,
+                    )),
                     _ => Err(EvalError::InvalidResultType),
                 }
-            })
+            },
+        )
     }
 
     let res = inner(expr, env, ctx);
-    if let Ok(reduction) = res {
+    if let Err(e @ EvalError::CostError(_)) = res {
+        // re-evaluating with source spans would only exceed the cost limit again
+        return Err(e);
+    }
+    if let Ok((reduction, jit_cost)) = res {
         if reduction.sigma_prop == SigmaBoolean::TrivialProp(false) {
             let (_, printed_expr_str) = expr
                 .pretty_print()
@@ -174,9 +211,9 @@ pub fn reduce_to_crypto(
                     pretty_printed_expr: Some(printed_expr_str),
                 },
             };
-            return Ok(new_reduction);
+            return Ok((new_reduction, jit_cost));
         } else {
-            return Ok(reduction);
+            return Ok((reduction, jit_cost));
         }
     }
     let (spanned_expr, printed_expr_str) = expr
@@ -186,6 +223,67 @@ pub fn reduce_to_crypto(
         .map_err(|e| e.wrap_spanned_with_src(printed_expr_str.to_string()))
 }
 
+/// Checks the soft-fork conditions for the given ErgoTree. Returns `true` if the tree should be
+/// accepted without evaluation, `false` if the tree should be evaluated, or an error if the tree
+/// can be neither evaluated nor accepted.
+///
+/// The tree version is checked against the activated script version (see
+/// [`Context::activated_script_version`]). When the activated script version is higher than the
+/// one supported by this interpreter, trees of unsupported versions are accepted. Trees that
+/// cannot be parsed are accepted if the parsing error is a soft-fork condition according to
+/// the context's validation settings (e.g. an op code added via soft-fork voting).
+pub fn check_soft_fork_condition(tree: &ErgoTree, ctx: &Context) -> Result<bool, EvalError> {
+    let activated_version = ctx.activated_script_version();
+    if let Ok(header) = tree.header() {
+        let tree_version = *header.version();
+        if activated_version > ErgoTreeVersion::MAX_SCRIPT_VERSION {
+            if tree_version > ErgoTreeVersion::MAX_SCRIPT_VERSION {
+                return Ok(true);
+            }
+        } else if tree_version > activated_version {
+            return Err(EvalError::ScriptVersionError {
+                tree_version,
+                activated_version,
+            });
+        }
+    }
+    match tree {
+        ErgoTree::Unparsed {
+            tree_bytes: _,
+            error,
+        } => {
+            if ctx.validation_settings.is_soft_fork(error) {
+                Ok(true)
+            } else {
+                Err(error.clone().into())
+            }
+        }
+        ErgoTree::Parsed(_) => Ok(false),
+    }
+}
+
+/// Evaluate the proposition of the given ErgoTree by reducing it to SigmaBoolean value.
+/// Trees accepted under the soft-fork rules (see [`check_soft_fork_condition`]) are reduced to
+/// `true` without evaluation.
+pub fn reduce_tree_to_crypto(
+    tree: &ErgoTree,
+    env: &Env,
+    ctx: Rc<Context>,
+) -> Result<ReductionResult, EvalError> {
+    if check_soft_fork_condition(tree, &ctx)? {
+        return Ok(ReductionResult {
+            sigma_prop: SigmaBoolean::TrivialProp(true),
+            cost: 0,
+            diag: ReductionDiagnosticInfo {
+                env: env.clone(),
+                pretty_printed_expr: None,
+            },
+        });
+    }
+    let expr = tree.proposition()?;
+    reduce_to_crypto(&expr, env, ctx)
+}
+
 /// Expects SigmaProp constant value and returns it's value. Otherwise, returns an error.
 pub fn extract_sigma_boolean(expr: &Expr) -> Result<SigmaBoolean, EvalError> {
     match expr {
@@ -260,10 +358,28 @@ fn smethod_eval_fn(method: &SMethod) -> Result<EvalFn, EvalError> {
         sbox::TYPE_CODE => match method.method_id() {
             sbox::VALUE_METHOD_ID => self::sbox::VALUE_EVAL_FN,
             sbox::GET_REG_METHOD_ID => self::sbox::GET_REG_EVAL_FN,
+            sbox::PROPOSITION_BYTES_METHOD_ID => self::sbox::PROPOSITION_BYTES_EVAL_FN,
+            sbox::BYTES_METHOD_ID => self::sbox::BYTES_EVAL_FN,
+            sbox::BYTES_WITHOUT_REF_METHOD_ID => self::sbox::BYTES_WITHOUT_REF_EVAL_FN,
+            sbox::ID_METHOD_ID => self::sbox::ID_EVAL_FN,
+            sbox::CREATION_INFO_METHOD_ID => self::sbox::CREATION_INFO_EVAL_FN,
             sbox::TOKENS_METHOD_ID => self::sbox::TOKENS_EVAL_FN,
+            method_id => match sbox::register_index(&method_id) {
+                Some(reg_index) => self::sbox::REGISTER_EVAL_FNS[reg_index as usize],
+                None => {
+                    return Err(EvalError::NotFound(format!(
+                        "Eval fn: unknown method id in SBox: {:?}",
+                        method_id
+                    )))
+                }
+            },
+        },
+        ssigmaprop::TYPE_CODE => match method.method_id() {
+            ssigmaprop::PROP_BYTES_METHOD_ID => self::ssigmaprop::PROP_BYTES_EVAL_FN,
+            ssigmaprop::IS_PROVEN_METHOD_ID => self::ssigmaprop::IS_PROVEN_EVAL_FN,
             method_id => {
                 return Err(EvalError::NotFound(format!(
-                    "Eval fn: unknown method id in SBox: {:?}",
+                    "Eval fn: unknown method id in SSigmaProp: {:?}",
                     method_id
                 )))
             }
@@ -286,6 +402,8 @@ fn smethod_eval_fn(method: &SMethod) -> Result<EvalFn, EvalError> {
         sgroup_elem::TYPE_CODE => match method.method_id() {
             sgroup_elem::GET_ENCODED_METHOD_ID => self::sgroup_elem::GET_ENCODED_EVAL_FN,
             sgroup_elem::NEGATE_METHOD_ID => self::sgroup_elem::NEGATE_EVAL_FN,
+            sgroup_elem::EXPONENTIATE_METHOD_ID => self::sgroup_elem::EXPONENTIATE_EVAL_FN,
+            sgroup_elem::MULTIPLY_METHOD_ID => self::sgroup_elem::MULTIPLY_EVAL_FN,
             method_id => {
                 return Err(EvalError::NotFound(format!(
                     "Eval fn: unknown method id in SGroupElement: {:?}",
@@ -293,6 +411,23 @@ fn smethod_eval_fn(method: &SMethod) -> Result<EvalFn, EvalError> {
                 )))
             }
         },
+        snumeric::BYTE_TYPE_CODE
+        | snumeric::SHORT_TYPE_CODE
+        | snumeric::INT_TYPE_CODE
+        | snumeric::LONG_TYPE_CODE
+        | snumeric::BIGINT_TYPE_CODE => match method.method_id() {
+            snumeric::TO_BYTE_METHOD_ID => self::snumeric::TO_BYTE_EVAL_FN,
+            snumeric::TO_SHORT_METHOD_ID => self::snumeric::TO_SHORT_EVAL_FN,
+            snumeric::TO_INT_METHOD_ID => self::snumeric::TO_INT_EVAL_FN,
+            snumeric::TO_LONG_METHOD_ID => self::snumeric::TO_LONG_EVAL_FN,
+            snumeric::TO_BIGINT_METHOD_ID => self::snumeric::TO_BIGINT_EVAL_FN,
+            method_id => {
+                return Err(EvalError::NotFound(format!(
+                    "Eval fn: unknown method id in numeric type: {:?}",
+                    method_id
+                )))
+            }
+        },
         soption::TYPE_CODE => match method.method_id() {
             soption::MAP_METHOD_ID => self::soption::MAP_EVAL_FN,
             soption::FILTER_METHOD_ID => self::soption::FILTER_EVAL_FN,
@@ -371,8 +506,13 @@ pub(crate) mod tests {
 
     #![allow(dead_code)]
 
+    use super::cost_accum::CostError;
     use super::env::Env;
+    use super::validation_settings::RuleId;
+    use super::validation_settings::RuleStatus;
+    use super::validation_settings::ValidationSettings;
     use super::*;
+    use ergotree_ir::ergo_tree::ErgoTreeHeader;
     use ergotree_ir::mir::bin_op::BinOp;
     use ergotree_ir::mir::bin_op::BinOpKind;
     use ergotree_ir::mir::bin_op::RelationOp;
@@ -381,6 +521,7 @@ pub(crate) mod tests {
     use ergotree_ir::mir::constant::TryExtractInto;
     use ergotree_ir::mir::val_def::ValDef;
     use ergotree_ir::mir::val_use::ValUse;
+    use ergotree_ir::serialization::SigmaSerializable;
     use ergotree_ir::types::stype::SType;
     use expect_test::expect;
     use sigma_test_util::force_any_val;
@@ -456,4 +597,102 @@ pub(crate) mod tests {
         "#]]
         .assert_eq(&res.diag.to_string());
     }
+
+    fn ctx_with_block_version(block_version: u8) -> Rc<Context> {
+        let mut ctx = force_any_val::<Context>();
+        ctx.pre_header.version = block_version;
+        Rc::new(ctx)
+    }
+
+    #[test]
+    fn tree_version_higher_than_activated() {
+        let tree = ErgoTree::new(
+            ErgoTreeHeader::v1(false),
+            &Expr::Const(SigmaProp::new(SigmaBoolean::TrivialProp(true)).into()),
+        )
+        .unwrap();
+        // block version 1 activates script version 0
+        let res = reduce_tree_to_crypto(&tree, &Env::empty(), ctx_with_block_version(1));
+        assert_eq!(
+            res,
+            Err(EvalError::ScriptVersionError {
+                tree_version: ErgoTreeVersion::V1,
+                activated_version: ErgoTreeVersion::V0,
+            })
+        );
+        let res = reduce_tree_to_crypto(&tree, &Env::empty(), ctx_with_block_version(2)).unwrap();
+        assert_eq!(res.sigma_prop, SigmaBoolean::TrivialProp(true));
+    }
+
+    #[test]
+    fn soft_fork_tree_version() {
+        let false_prop = Expr::Const(SigmaProp::new(SigmaBoolean::TrivialProp(false)).into());
+        let unsupported_version = ErgoTreeVersion::from(3u8);
+        let tree = ErgoTree::new(
+            ErgoTreeHeader::with_version(unsupported_version, false),
+            &false_prop,
+        )
+        .unwrap();
+        // the unsupported version is not activated yet
+        let ctx = ctx_with_block_version(3);
+        assert!(reduce_tree_to_crypto(&tree, &Env::empty(), ctx).is_err());
+        // the unsupported version is activated, the tree is accepted without evaluation
+        let ctx = ctx_with_block_version(4);
+        let res = reduce_tree_to_crypto(&tree, &Env::empty(), ctx.clone()).unwrap();
+        assert_eq!(res.sigma_prop, SigmaBoolean::TrivialProp(true));
+        // trees of the supported versions are still evaluated
+        let supported_tree = ErgoTree::new(
+            ErgoTreeHeader::with_version(ErgoTreeVersion::V2, false),
+            &false_prop,
+        )
+        .unwrap();
+        let res = reduce_tree_to_crypto(&supported_tree, &Env::empty(), ctx).unwrap();
+        assert_eq!(res.sigma_prop, SigmaBoolean::TrivialProp(false));
+    }
+
+    #[test]
+    fn soft_fork_unparsed_tree() {
+        // v2 tree with the root op code which is unknown to this interpreter
+        let tree = ErgoTree::sigma_parse_bytes(&[0x0a, 1, 0x75]).unwrap();
+        assert!(matches!(tree, ErgoTree::Unparsed { .. }));
+        assert!(reduce_tree_to_crypto(&tree, &Env::empty(), ctx_with_block_version(3)).is_err());
+        // the op code is added via soft-fork voting
+        let mut ctx = force_any_val::<Context>();
+        ctx.validation_settings = ValidationSettings::default()
+            .with_status(RuleId::CheckValidOpCode, RuleStatus::Changed(vec![0x75]));
+        let ctx = Rc::new(ctx);
+        assert!(check_soft_fork_condition(&tree, &ctx).unwrap());
+        let res = reduce_tree_to_crypto(&tree, &Env::empty(), ctx).unwrap();
+        assert_eq!(res.sigma_prop, SigmaBoolean::TrivialProp(true));
+    }
+
+    #[test]
+    fn reduction_cost() {
+        let expr: Expr = BinOp {
+            kind: BinOpKind::Relation(RelationOp::Eq),
+            left: Box::new(1i32.into()),
+            right: Box::new(1i32.into()),
+        }
+        .into();
+        let ctx = Rc::new(force_any_val::<Context>());
+        let res = reduce_to_crypto(&expr, &Env::empty(), ctx).unwrap();
+        assert_eq!(res.sigma_prop, SigmaBoolean::TrivialProp(true));
+        assert!(res.cost > 0);
+    }
+
+    #[test]
+    fn reduction_cost_limit_exceeded() {
+        let expr: Expr = BinOp {
+            kind: BinOpKind::Relation(RelationOp::Eq),
+            left: Box::new(1i32.into()),
+            right: Box::new(1i32.into()),
+        }
+        .into();
+        let ctx = Rc::new(force_any_val::<Context>().with_cost_limit(0));
+        let res = reduce_to_crypto(&expr, &Env::empty(), ctx);
+        assert!(matches!(
+            res,
+            Err(EvalError::CostError(CostError::LimitExceeded(0)))
+        ));
+    }
 }
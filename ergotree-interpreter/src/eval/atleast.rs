@@ -8,6 +8,7 @@ use ergotree_ir::sigma_protocol::sigma_boolean::cthreshold::Cthreshold;
 use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
 use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProp;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -26,6 +27,8 @@ impl Evaluable for Atleast {
             ))),
         }?;
 
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.atleast, normalized_input_vals.len())?;
         let bound = bound_v.try_extract_into::<i32>()?;
         let input = normalized_input_vals
             .into_iter()
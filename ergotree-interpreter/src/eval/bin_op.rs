@@ -15,6 +15,8 @@ use num_traits::CheckedMul;
 use num_traits::CheckedRem;
 use num_traits::CheckedSub;
 use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::ToPrimitive;
 
 use crate::eval;
 use crate::eval::env::Env;
@@ -98,6 +100,21 @@ where
     Ok(op(lv_raw, rv_raw).into())
 }
 
+/// Shift `lv_raw` by the number of bits given in `rv`, fails if the shift is negative or not
+/// less than the bit width of the type (bits shifted out of the primitive types are discarded)
+fn eval_bit_shift<T, F>(op: BitOp, lv_raw: T, rv: Value, shift: F) -> Result<Value, EvalError>
+where
+    T: TryExtractFrom<Value> + Into<Value> + ToPrimitive + std::fmt::Display,
+    F: FnOnce(&T, u32) -> Option<T>,
+{
+    let rv_raw = rv.try_extract_into::<T>()?;
+    rv_raw
+        .to_u32()
+        .and_then(|n| shift(&lv_raw, n))
+        .ok_or_else(|| arithmetic_err(&op.to_string(), lv_raw, rv_raw, "invalid shift"))
+        .map(|t| t.into()) // convert T to Value
+}
+
 fn eval_ge(lv: Value, rv: Value) -> Result<Value, EvalError> {
     match lv {
         Value::Byte(lv_raw) => Ok((lv_raw >= rv.try_extract_into::<i8>()?).into()),
@@ -170,9 +187,17 @@ where
     Ok((lv_raw.min(rv_raw)).into())
 }
 
+/// Equality of collections is charged per item (base cost is charged for the BinOp node)
+fn add_eq_items_cost(lv: &Value, ctx: &mut EvalContext) -> Result<(), EvalError> {
+    if let Value::Coll(coll) = lv {
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.eq_coll, coll.len())?;
+    }
+    Ok(())
+}
+
 impl Evaluable for BinOp {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
-        ctx.cost_accum.add(Costs::DEFAULT.eq_const_size)?;
         let lv = self.left.eval(env, ctx)?;
         // using closure to keep right value from evaluation (for lazy AND, OR, XOR)
         let mut rv = || self.right.eval(env, ctx);
@@ -193,8 +218,16 @@ impl Evaluable for BinOp {
                 )),
             },
             BinOpKind::Relation(op) => match op {
-                RelationOp::Eq => Ok(Value::Boolean(lv == rv()?)),
-                RelationOp::NEq => Ok(Value::Boolean(lv != rv()?)),
+                RelationOp::Eq => {
+                    let rv = rv()?;
+                    add_eq_items_cost(&lv, ctx)?;
+                    Ok(Value::Boolean(lv == rv))
+                }
+                RelationOp::NEq => {
+                    let rv = rv()?;
+                    add_eq_items_cost(&lv, ctx)?;
+                    Ok(Value::Boolean(lv != rv))
+                }
                 RelationOp::Gt => eval_gt(lv, rv()?),
                 RelationOp::Lt => eval_lt(lv, rv()?),
                 RelationOp::Ge => eval_ge(lv, rv()?),
@@ -314,6 +347,69 @@ impl Evaluable for BinOp {
                         lv
                     ))),
                 },
+                BitOp::BitShiftLeft => match lv {
+                    Value::Byte(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shl(n))
+                    }
+                    Value::Short(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shl(n))
+                    }
+                    Value::Int(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shl(n))
+                    }
+                    Value::Long(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shl(n))
+                    }
+                    Value::BigInt(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shl(n))
+                    }
+                    _ => Err(EvalError::UnexpectedValue(format!(
+                        "expected BinOp::left to be numeric value, got {0:?}",
+                        lv
+                    ))),
+                },
+                BitOp::BitShiftRight => match lv {
+                    Value::Byte(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr(n))
+                    }
+                    Value::Short(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr(n))
+                    }
+                    Value::Int(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr(n))
+                    }
+                    Value::Long(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr(n))
+                    }
+                    Value::BigInt(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr(n))
+                    }
+                    _ => Err(EvalError::UnexpectedValue(format!(
+                        "expected BinOp::left to be numeric value, got {0:?}",
+                        lv
+                    ))),
+                },
+                BitOp::BitShiftRightZeroed => match lv {
+                    Value::Byte(lv_raw) => eval_bit_shift(op, lv_raw, rv()?, |l, n| {
+                        l.checked_shr(n).map(|_| l.unsigned_shr(n))
+                    }),
+                    Value::Short(lv_raw) => eval_bit_shift(op, lv_raw, rv()?, |l, n| {
+                        l.checked_shr(n).map(|_| l.unsigned_shr(n))
+                    }),
+                    Value::Int(lv_raw) => eval_bit_shift(op, lv_raw, rv()?, |l, n| {
+                        l.checked_shr(n).map(|_| l.unsigned_shr(n))
+                    }),
+                    Value::Long(lv_raw) => eval_bit_shift(op, lv_raw, rv()?, |l, n| {
+                        l.checked_shr(n).map(|_| l.unsigned_shr(n))
+                    }),
+                    Value::BigInt(lv_raw) => {
+                        eval_bit_shift(op, lv_raw, rv()?, |l, n| l.checked_shr_zeroed(n))
+                    }
+                    _ => Err(EvalError::UnexpectedValue(format!(
+                        "expected BinOp::left to be numeric value, got {0:?}",
+                        lv
+                    ))),
+                },
             },
         }
     }
@@ -531,6 +627,39 @@ mod tests {
         assert_eq!(eval_bit_op(BitOp::BitAnd, max(), min()), Ok(b(0)));
         assert_eq!(eval_bit_op(BitOp::BitOr, max(), min()), Ok(b(-1)));
         assert_eq!(eval_bit_op(BitOp::BitXor, max(), min()), Ok(b(-1)));
+
+        assert_eq!(eval_bit_op(BitOp::BitShiftLeft, b(-1), b(255)), Ok(min()));
+        assert!(eval_bit_op(BitOp::BitShiftLeft, b(1), b(255)).is_err());
+        assert!(eval_bit_op(BitOp::BitShiftLeft, b(1), b(256)).is_err());
+        assert!(eval_bit_op(BitOp::BitShiftLeft, b(1), b(-1)).is_err());
+        assert_eq!(eval_bit_op(BitOp::BitShiftRight, min(), b(255)), Ok(b(-1)));
+        assert_eq!(
+            eval_bit_op(BitOp::BitShiftRightZeroed, min(), b(255)),
+            Ok(b(1))
+        );
+        assert_eq!(
+            eval_bit_op(BitOp::BitShiftRightZeroed, b(-1), b(1)),
+            Ok(max())
+        );
+    }
+
+    #[test]
+    fn test_bit_shifts() {
+        // bits shifted out are discarded
+        assert_eq!(eval_bit_op(BitOp::BitShiftLeft, 3i8, 7i8), Ok(i8::MIN));
+        assert!(eval_bit_op(BitOp::BitShiftLeft, 1i8, 8i8).is_err());
+        assert!(eval_bit_op(BitOp::BitShiftLeft, 1i32, -1i32).is_err());
+        assert_eq!(eval_bit_op(BitOp::BitShiftRight, -8i16, 2i16), Ok(-2i16));
+        assert!(eval_bit_op(BitOp::BitShiftRight, -8i16, 16i16).is_err());
+        assert_eq!(
+            eval_bit_op(BitOp::BitShiftRightZeroed, -1i32, 28i32),
+            Ok(15i32)
+        );
+        assert_eq!(
+            eval_bit_op(BitOp::BitShiftRightZeroed, -1i64, 63i64),
+            Ok(1i64)
+        );
+        assert_eq!(eval_bit_op(BitOp::BitShiftRightZeroed, -1i8, 0i8), Ok(-1i8));
     }
 
     proptest! {
@@ -555,6 +684,13 @@ mod tests {
             prop_assert_eq!(eval_bit_op(BitOp::BitAnd, l, r), Ok(l & r));
             prop_assert_eq!(eval_bit_op(BitOp::BitOr, l, r), Ok(l | r));
             prop_assert_eq!(eval_bit_op(BitOp::BitXor, l, r), Ok(l ^ r));
+            let shift = u32::try_from(r).ok();
+            prop_assert_eq!(eval_bit_op(BitOp::BitShiftLeft, l, r).ok(),
+                    shift.and_then(|n| l.checked_shl(n)));
+            prop_assert_eq!(eval_bit_op(BitOp::BitShiftRight, l, r).ok(),
+                    shift.and_then(|n| l.checked_shr(n)));
+            prop_assert_eq!(eval_bit_op(BitOp::BitShiftRightZeroed, l, r).ok(),
+                    shift.and_then(|n| (l as u64).checked_shr(n)).map(|v| v as i64));
 
             prop_assert_eq!(eval_relation_op(RelationOp::Gt, l, r), l > r);
             prop_assert_eq!(eval_relation_op(RelationOp::Lt, l, r), l < r);
@@ -1,28 +1,40 @@
 use ergotree_ir::mir::block::BlockValue;
-use ergotree_ir::mir::constant::TryExtractInto;
-use ergotree_ir::mir::val_def::ValDef;
+use ergotree_ir::mir::expr::Expr;
+use ergotree_ir::mir::val_def::ValId;
 use ergotree_ir::mir::value::Value;
-use ergotree_ir::source_span::Spanned;
 use hashbrown::HashMap;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
 use crate::eval::Evaluable;
 
+/// Variable id and the bound expression of the block item (`ValDef` or `FunDef`)
+fn block_item_binding(item: &Expr) -> Result<(ValId, &Expr), EvalError> {
+    match item {
+        Expr::ValDef(val_def) => Ok((val_def.expr().id, val_def.expr().rhs.as_ref())),
+        Expr::FunDef(fun_def) => Ok((fun_def.expr().id, fun_def.expr().rhs.as_ref())),
+        _ => Err(EvalError::UnexpectedExpr(format!(
+            "BlockValue: expected ValDef or FunDef item, got {0:?}",
+            item
+        ))),
+    }
+}
+
 impl Evaluable for BlockValue {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         // The start of the top-level block of statements does not contain any
         // pre-existing `ValDef`s.
         let is_top_level_block = env.is_empty();
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.block_value, self.items.len())?;
 
         if is_top_level_block {
             for i in &self.items {
-                // TODO: new try_extract_spanned_into?
-                let spanned_val_def = &i.clone().try_extract_into::<Spanned<ValDef>>()?;
-                let val_def = spanned_val_def.expr();
-                let v: Value = val_def.rhs.eval(env, ctx)?;
-                env.insert(val_def.id, v);
+                let (idx, rhs) = block_item_binding(i)?;
+                let v: Value = rhs.eval(env, ctx)?;
+                env.insert(idx, v);
             }
             // Keep all `ValDef`s introduced in this block
             self.result.eval(env, ctx)
@@ -31,11 +43,8 @@ impl Evaluable for BlockValue {
             let mut new_variables = vec![];
 
             for i in &self.items {
-                // TODO: new try_extract_spanned_into?
-                let spanned_val_def = &i.clone().try_extract_into::<Spanned<ValDef>>()?;
-                let val_def = spanned_val_def.expr();
-                let idx = val_def.id;
-                let v: Value = val_def.rhs.eval(env, ctx)?;
+                let (idx, rhs) = block_item_binding(i)?;
+                let v: Value = rhs.eval(env, ctx)?;
                 if let Some(old_val) = env.get(idx) {
                     existing_variables.insert(idx, old_val.clone());
                 } else {
@@ -62,9 +71,16 @@ impl Evaluable for BlockValue {
 mod tests {
     use ergotree_ir::mir::block::BlockValue;
     use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::mir::fun_def::FunDef;
+    use ergotree_ir::mir::val_def::ValId;
+    use ergotree_ir::mir::val_use::ValUse;
     use ergotree_ir::serialization::sigma_serialize_roundtrip;
+    use ergotree_ir::types::stype::SType;
+    use ergotree_ir::types::stype_param::STypeVar;
     use proptest::prelude::*;
 
+    use crate::eval::tests::eval_out_wo_ctx;
+
     proptest! {
 
         #[test]
@@ -73,4 +89,26 @@ mod tests {
             prop_assert_eq![sigma_serialize_roundtrip(&e), e];
         }
     }
+
+    #[test]
+    fn eval_fun_def_item() {
+        let fun_def: Expr = FunDef {
+            id: ValId(1),
+            tpe_args: vec![STypeVar::t()],
+            rhs: Box::new(2i32.into()),
+        }
+        .into();
+        let block: Expr = BlockValue {
+            items: vec![fun_def],
+            result: Box::new(
+                ValUse {
+                    val_id: ValId(1),
+                    tpe: SType::SInt,
+                }
+                .into(),
+            ),
+        }
+        .into();
+        assert_eq!(eval_out_wo_ctx::<i32>(&block), 2);
+    }
 }
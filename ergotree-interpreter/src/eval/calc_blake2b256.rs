@@ -5,6 +5,7 @@ use ergotree_ir::mir::value::Value;
 use sigma_util::hash::blake2b256_hash;
 use sigma_util::AsVecU8;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -15,6 +16,8 @@ impl Evaluable for CalcBlake2b256 {
         let input_v = self.input.eval(env, ctx)?;
         match input_v.clone() {
             Value::Coll(CollKind::NativeColl(NativeColl::CollByte(coll_byte))) => {
+                ctx.cost_accum
+                    .add_items_cost(Costs::DEFAULT.calc_blake2b256, coll_byte.len())?;
                 let expected_hash: Vec<u8> =
                     blake2b256_hash(coll_byte.as_vec_u8().as_slice()).to_vec();
                 Ok(expected_hash.into())
@@ -4,6 +4,7 @@ use ergotree_ir::mir::value::CollKind;
 use ergotree_ir::mir::value::Value;
 use ergotree_ir::types::stype::SType;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -49,6 +50,8 @@ impl Evaluable for Append {
         let input_vecval: Vec<Value> = extract_vecval(input_v)?;
         let col_2_vecval: Vec<Value> = extract_vecval(col2_v)?;
         let concat_vecval: Vec<Value> = concat(input_vecval, col_2_vecval);
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.append, concat_vecval.len())?;
         Ok(Value::Coll(CollKind::from_vec(
             input_elem_tpe,
             concat_vecval,
@@ -3,6 +3,7 @@ use ergotree_ir::mir::value::CollKind;
 use ergotree_ir::mir::value::NativeColl;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -13,6 +14,10 @@ impl Evaluable for Fold {
         let input_v = self.input.eval(env, ctx)?;
         let zero_v = self.zero.eval(env, ctx)?;
         let fold_op_v = self.fold_op.eval(env, ctx)?;
+        if let Value::Coll(coll) = &input_v {
+            ctx.cost_accum
+                .add_items_cost(Costs::DEFAULT.fold, coll.len())?;
+        }
         let input_v_clone = input_v.clone();
         let mut fold_op_call = |arg: Value| match &fold_op_v {
             Value::Lambda(func_value) => {
@@ -2,6 +2,7 @@ use ergotree_ir::mir::coll_forall::ForAll;
 use ergotree_ir::mir::constant::TryExtractInto;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -11,6 +12,10 @@ impl Evaluable for ForAll {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
         let condition_v = self.condition.eval(env, ctx)?;
+        if let Value::Coll(coll) = &input_v {
+            ctx.cost_accum
+                .add_items_cost(Costs::DEFAULT.forall, coll.len())?;
+        }
         let input_v_clone = input_v.clone();
         let mut condition_call = |arg: Value| match &condition_v {
             Value::Lambda(func_value) => {
@@ -2,6 +2,7 @@ use ergotree_ir::mir::coll_map::Map;
 use ergotree_ir::mir::value::CollKind;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -11,6 +12,10 @@ impl Evaluable for Map {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
         let mapper_v = self.mapper.eval(env, ctx)?;
+        if let Value::Coll(coll) = &input_v {
+            ctx.cost_accum
+                .add_items_cost(Costs::DEFAULT.map, coll.len())?;
+        }
         let input_v_clone = input_v.clone();
         let mut mapper_call = |arg: Value| match &mapper_v {
             Value::Lambda(func_value) => {
@@ -0,0 +1,181 @@
+use std::iter;
+
+use ergotree_ir::bigint256::BigInt256;
+use ergotree_ir::mir::coll_shift::CollShift;
+use ergotree_ir::mir::coll_shift::CollShiftOp;
+use ergotree_ir::mir::constant::TryExtractInto;
+use ergotree_ir::mir::value::CollKind;
+use ergotree_ir::mir::value::Value;
+use ergotree_ir::types::stype::SType;
+use num_traits::Zero;
+
+use crate::eval::costs::Costs;
+use crate::eval::env::Env;
+use crate::eval::EvalContext;
+use crate::eval::EvalError;
+use crate::eval::Evaluable;
+
+fn zero_of(tpe: &SType) -> Result<Value, EvalError> {
+    match tpe {
+        SType::SByte => Ok(Value::Byte(0)),
+        SType::SShort => Ok(Value::Short(0)),
+        SType::SInt => Ok(Value::Int(0)),
+        SType::SLong => Ok(Value::Long(0)),
+        SType::SBigInt => Ok(Value::BigInt(BigInt256::zero())),
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "CollShift: expected numeric element type, got {0:?}",
+            tpe
+        ))),
+    }
+}
+
+/// Move items towards the tail by `n` positions, filling the head with `fill`
+fn shift_right(items: Vec<Value>, n: usize, fill: Value) -> Vec<Value> {
+    let len = items.len();
+    let n = n.min(len);
+    iter::repeat(fill)
+        .take(n)
+        .chain(items.into_iter().take(len - n))
+        .collect()
+}
+
+impl Evaluable for CollShift {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let input_v = self.input.eval(env, ctx)?;
+        let n_v = self.n.eval(env, ctx)?;
+        let (mut items, elem_tpe) = match input_v {
+            Value::Coll(coll) => Ok((coll.as_vec(), coll.elem_tpe().clone())),
+            _ => Err(EvalError::UnexpectedValue(format!(
+                "CollShift: expected input to be Value::Coll, got: {0:?}",
+                input_v
+            ))),
+        }?;
+        let n = n_v.try_extract_into::<i32>()?;
+        if n < 0 {
+            return Err(EvalError::UnexpectedValue(format!(
+                "CollShift({0}): expected non-negative number of positions, got {1}",
+                self.op, n
+            )));
+        }
+        let n = n as usize;
+        let len = items.len();
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.coll_shift, len)?;
+        let res = match self.op {
+            CollShiftOp::ShiftLeft => {
+                let zero = zero_of(&elem_tpe)?;
+                items
+                    .into_iter()
+                    .skip(n)
+                    .chain(iter::repeat(zero))
+                    .take(len)
+                    .collect()
+            }
+            CollShiftOp::ShiftRight => match items.first().cloned() {
+                Some(head) => shift_right(items, n, head),
+                None => items,
+            },
+            CollShiftOp::ShiftRightZeroed => shift_right(items, n, zero_of(&elem_tpe)?),
+            CollShiftOp::RotateLeft => {
+                if len > 0 {
+                    items.rotate_left(n % len);
+                }
+                items
+            }
+            CollShiftOp::RotateRight => {
+                if len > 0 {
+                    items.rotate_right(n % len);
+                }
+                items
+            }
+        };
+        Ok(Value::Coll(CollKind::from_vec(elem_tpe, res)?))
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use ergotree_ir::mir::expr::Expr;
+
+    use super::*;
+    use crate::eval::tests::eval_out_wo_ctx;
+    use crate::eval::tests::try_eval_out_wo_ctx;
+
+    fn eval_shift(op: CollShiftOp, items: Vec<i32>, n: i32) -> Vec<i32> {
+        let expr: Expr = CollShift::new(op, Expr::Const(items.into()), n.into())
+            .unwrap()
+            .into();
+        eval_out_wo_ctx::<Vec<i32>>(&expr)
+    }
+
+    #[test]
+    fn shifts() {
+        let items = vec![-1i32, 2, 3, 4];
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftLeft, items.clone(), 1),
+            vec![2, 3, 4, 0]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftRight, items.clone(), 2),
+            vec![-1, -1, -1, 2]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftRightZeroed, items.clone(), 2),
+            vec![0, 0, -1, 2]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftLeft, items.clone(), 5),
+            vec![0, 0, 0, 0]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftRight, items, 0),
+            vec![-1, 2, 3, 4]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::ShiftRight, vec![], 2),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn rotations() {
+        let items = vec![1i32, 2, 3, 4];
+        assert_eq!(
+            eval_shift(CollShiftOp::RotateLeft, items.clone(), 1),
+            vec![2, 3, 4, 1]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::RotateRight, items.clone(), 5),
+            vec![4, 1, 2, 3]
+        );
+        assert_eq!(
+            eval_shift(CollShiftOp::RotateLeft, vec![], 3),
+            Vec::<i32>::new()
+        );
+    }
+
+    #[test]
+    fn rotate_bytes() {
+        let expr: Expr = CollShift::new(
+            CollShiftOp::RotateRight,
+            Expr::Const(vec![1i8, 2, 3].into()),
+            1i32.into(),
+        )
+        .unwrap()
+        .into();
+        assert_eq!(eval_out_wo_ctx::<Vec<i8>>(&expr), vec![3i8, 1, 2]);
+    }
+
+    #[test]
+    fn negative_shift() {
+        let expr: Expr = CollShift::new(
+            CollShiftOp::ShiftLeft,
+            Expr::Const(vec![1i64, 2].into()),
+            (-1i32).into(),
+        )
+        .unwrap()
+        .into();
+        assert!(try_eval_out_wo_ctx::<Vec<i64>>(&expr).is_err());
+    }
+}
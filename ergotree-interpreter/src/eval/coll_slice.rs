@@ -3,6 +3,7 @@ use ergotree_ir::mir::constant::TryExtractInto;
 use ergotree_ir::mir::value::CollKind;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -27,7 +28,11 @@ impl Evaluable for Slice {
         // see https://github.com/ergoplatform/sigma-rust/issues/724
         let range = from.max(0) as usize..until.min(input_vec.len() as i32) as usize;
         match input_vec.get(range) {
-            Some(slice) => Ok(Value::Coll(CollKind::from_vec(elem_tpe, slice.to_vec())?)),
+            Some(slice) => {
+                ctx.cost_accum
+                    .add_items_cost(Costs::DEFAULT.slice, slice.len())?;
+                Ok(Value::Coll(CollKind::from_vec(elem_tpe, slice.to_vec())?))
+            }
             // Scala version returns empty collection if the range is out of bounds
             None => Ok(Value::Coll(CollKind::from_vec(elem_tpe, vec![])?)),
         }
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use crate::eval::validation_settings::ValidationSettings;
 use crate::sigma_protocol::prover::ContextExtension;
 use bounded_vec::BoundedVec;
 use ergo_chain_types::{Header, PreHeader};
 use ergotree_ir::chain::ergo_box::ErgoBox;
+use ergotree_ir::ergo_tree::ErgoTreeVersion;
 
 /// BoundedVec type for Tx inputs, output_candidates and outputs
 pub type TxIoVec<T> = BoundedVec<T, 1, { u16::MAX as usize }>;
@@ -27,6 +29,10 @@ pub struct Context {
     pub headers: [Header; 10],
     /// prover-defined key-value pairs, that may be used inside a script
     pub extension: ContextExtension,
+    /// Maximum cost of the script evaluation (in block cost units), no limit if None
+    pub cost_limit: Option<u64>,
+    /// Validation rules settings (voted by miners) defining the soft-fork conditions
+    pub validation_settings: ValidationSettings,
 }
 
 impl Context {
@@ -37,6 +43,20 @@ impl Context {
             ..self
         }
     }
+
+    /// Activated script version (block version - 1), which defines the rules of the script
+    /// interpretation and is voted by miners
+    pub fn activated_script_version(&self) -> ErgoTreeVersion {
+        ErgoTreeVersion::from_block_version(self.pre_header.version)
+    }
+
+    /// Return a new Context with given cost limit (in block cost units)
+    pub fn with_cost_limit(self, cost_limit: u64) -> Self {
+        Context {
+            cost_limit: Some(cost_limit),
+            ..self
+        }
+    }
 }
 
 #[cfg(feature = "arbitrary")]
@@ -83,6 +103,8 @@ mod arbitrary {
                             pre_header,
                             extension,
                             headers,
+                            cost_limit: None,
+                            validation_settings: ValidationSettings::default(),
                         }
                     },
                 )
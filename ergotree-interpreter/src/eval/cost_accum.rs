@@ -1,7 +1,8 @@
-use super::costs::{Cost, Costs};
+use super::costs::{from_block_cost, to_block_cost, Cost, Costs, PerItemCost};
 use ergotree_ir::mir::expr::Expr;
 use thiserror::Error;
 
+/// Accumulates the cost of the evaluation (in JIT cost units) and checks it against the limit
 #[derive(Debug)]
 pub struct CostAccumulator {
     costs: Costs,
@@ -16,11 +17,12 @@ pub enum CostError {
 }
 
 impl CostAccumulator {
+    /// `initial_cost` and `cost_limit` are in block cost units
     pub fn new(initial_cost: u64, cost_limit: Option<u64>) -> CostAccumulator {
         CostAccumulator {
             costs: Costs::DEFAULT,
-            accum: initial_cost,
-            limit: cost_limit,
+            accum: from_block_cost(initial_cost),
+            limit: cost_limit.map(from_block_cost),
         }
     }
 
@@ -30,12 +32,36 @@ impl CostAccumulator {
     }
 
     pub fn add(&mut self, cost: Cost) -> Result<(), CostError> {
-        self.accum += u32::from(cost) as u64;
+        self.add_jit_cost(u32::from(cost) as u64)
+    }
+
+    /// Add the cost of processing the given number of items (the base cost is charged in
+    /// [`Self::add_cost_of`])
+    pub fn add_items_cost(
+        &mut self,
+        per_item_cost: PerItemCost,
+        n_items: usize,
+    ) -> Result<(), CostError> {
+        self.add_jit_cost(per_item_cost.items_cost(n_items))
+    }
+
+    fn add_jit_cost(&mut self, cost: u64) -> Result<(), CostError> {
+        self.accum = self.accum.saturating_add(cost);
         if let Some(limit) = self.limit {
             if self.accum > limit {
-                return Err(CostError::LimitExceeded(limit));
+                return Err(CostError::LimitExceeded(to_block_cost(limit)));
             }
         }
         Ok(())
     }
+
+    /// Accumulated cost in block cost units
+    pub fn total_cost(&self) -> u64 {
+        to_block_cost(self.accum)
+    }
+
+    /// Accumulated cost in JIT cost units
+    pub fn total_jit_cost(&self) -> u64 {
+        self.accum
+    }
 }
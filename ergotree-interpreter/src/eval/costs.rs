@@ -1,22 +1,454 @@
+use ergotree_ir::mir::bin_op::ArithOp;
+use ergotree_ir::mir::bin_op::BinOpKind;
+use ergotree_ir::mir::bin_op::RelationOp;
 use ergotree_ir::mir::expr::Expr;
+use ergotree_ir::mir::global_vars::GlobalVars;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaConjecture;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProofOfKnowledgeTree;
+use ergotree_ir::types::stype::SType;
 
 extern crate derive_more;
 use derive_more::{From, Into};
 
-#[derive(PartialEq, Eq, Debug, Clone, From, Into)]
+/// Number of JIT cost units in one block cost unit
+pub const JIT_COST_SCALE: u64 = 10;
+
+/// Cost of the operation in JIT cost units (1/10 of the block cost unit)
+#[derive(PartialEq, Eq, Debug, Clone, Copy, From, Into)]
 pub struct Cost(u32);
 
+/// Converts JIT cost units to the block cost units
+pub fn to_block_cost(jit_cost: u64) -> u64 {
+    jit_cost / JIT_COST_SCALE
+}
+
+/// Converts block cost units to the JIT cost units
+pub fn from_block_cost(block_cost: u64) -> u64 {
+    block_cost.saturating_mul(JIT_COST_SCALE)
+}
+
+/// Cost of the operation that depends on the number of processed items.
+/// Items are processed in chunks, i.e. `base + per_chunk * number_of_chunks`
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct PerItemCost {
+    /// Cost charged regardless of the number of items
+    pub base: Cost,
+    /// Cost of processing one chunk of items
+    pub per_chunk: Cost,
+    /// Number of items in one chunk
+    pub chunk_size: usize,
+}
+
+impl PerItemCost {
+    /// Create new per-item cost
+    pub const fn new(base: u32, per_chunk: u32, chunk_size: usize) -> PerItemCost {
+        PerItemCost {
+            base: Cost(base),
+            per_chunk: Cost(per_chunk),
+            chunk_size,
+        }
+    }
+
+    /// Number of chunks for the given number of items (at least one chunk is always charged)
+    pub fn chunks(&self, n_items: usize) -> usize {
+        if n_items == 0 {
+            1
+        } else {
+            (n_items - 1) / self.chunk_size + 1
+        }
+    }
+
+    /// Cost of the items processing (excluding the base cost)
+    pub fn items_cost(&self, n_items: usize) -> u64 {
+        self.chunks(n_items) as u64 * u32::from(self.per_chunk) as u64
+    }
+}
+
+/// Cost of the operation that depends on the type of the operands
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct TypeBasedCost {
+    /// Cost for the primitive numeric types (Byte, Short, Int, Long)
+    pub primitive: Cost,
+    /// Cost for the BigInt type
+    pub big_int: Cost,
+}
+
+impl TypeBasedCost {
+    /// Create new type-based cost
+    pub const fn new(primitive: u32, big_int: u32) -> TypeBasedCost {
+        TypeBasedCost {
+            primitive: Cost(primitive),
+            big_int: Cost(big_int),
+        }
+    }
+
+    /// Cost for the operands of the given type
+    pub fn cost(&self, tpe: &SType) -> Cost {
+        match tpe {
+            SType::SBigInt => self.big_int,
+            _ => self.primitive,
+        }
+    }
+}
+
+/// Cost table of the operations (JIT costing) as in the reference interpreter
 #[derive(Debug)]
 pub struct Costs {
-    pub eq_const_size: Cost,
+    pub constant: Cost,
+    pub constant_placeholder: Cost,
+    pub val_use: Cost,
+    pub func_value: Cost,
+    pub apply: Cost,
+    pub if_op: Cost,
+    pub select_field: Cost,
+    pub tuple: Cost,
+    pub context: Cost,
+    pub global: Cost,
+    pub height: Cost,
+    pub inputs: Cost,
+    pub outputs: Cost,
+    pub self_box: Cost,
+    pub miner_pubkey: Cost,
+    pub group_generator: Cost,
+    pub last_block_utxo_root_hash: Cost,
+    pub method_call: Cost,
+    pub property_call: Cost,
+    pub plus_minus: TypeBasedCost,
+    pub multiply: TypeBasedCost,
+    pub divide: TypeBasedCost,
+    pub modulo: TypeBasedCost,
+    pub min_max: TypeBasedCost,
+    pub compare: TypeBasedCost,
+    pub eq_primitive: Cost,
+    pub eq_big_int: Cost,
+    pub eq_group_element: Cost,
+    pub eq_coll: PerItemCost,
+    pub bin_logical: Cost,
+    pub bit_op: TypeBasedCost,
+    pub negation: TypeBasedCost,
+    pub bit_inversion: TypeBasedCost,
+    pub logical_not: Cost,
+    pub upcast: TypeBasedCost,
+    pub downcast: TypeBasedCost,
+    pub option_get: Cost,
+    pub option_is_defined: Cost,
+    pub option_get_or_else: Cost,
+    pub some_value: Cost,
+    pub none_value: Cost,
+    pub extract_amount: Cost,
+    pub extract_register_as: Cost,
+    pub extract_bytes: Cost,
+    pub extract_bytes_with_no_ref: Cost,
+    pub extract_script_bytes: Cost,
+    pub extract_creation_info: Cost,
+    pub extract_id: Cost,
+    pub by_index: Cost,
+    pub size_of: Cost,
+    pub bool_to_sigma_prop: Cost,
+    pub sigma_prop_is_proven: Cost,
+    pub create_prove_dlog: Cost,
+    pub create_prove_dh_tuple: Cost,
+    pub create_avl_tree: Cost,
+    pub tree_lookup: Cost,
+    pub decode_point: Cost,
+    pub multiply_group: Cost,
+    pub mod_q: Cost,
+    pub plus_minus_mod_q: Cost,
+    pub exponentiate: Cost,
+    pub get_var: Cost,
+    pub deserialize_context: Cost,
+    pub deserialize_register: Cost,
+    pub long_to_byte_array: Cost,
+    pub byte_array_to_long: Cost,
+    pub byte_array_to_bigint: Cost,
+    pub block_value: PerItemCost,
+    pub collection: PerItemCost,
+    pub and: PerItemCost,
+    pub or: PerItemCost,
+    pub xor: PerItemCost,
+    pub xor_of: PerItemCost,
+    pub atleast: PerItemCost,
+    pub sigma_and: PerItemCost,
+    pub sigma_or: PerItemCost,
+    pub sigma_prop_bytes: PerItemCost,
+    pub map: PerItemCost,
+    pub filter: PerItemCost,
+    pub exists: PerItemCost,
+    pub forall: PerItemCost,
+    pub fold: PerItemCost,
+    pub slice: PerItemCost,
+    pub coll_shift: PerItemCost,
+    pub append: PerItemCost,
+    pub calc_blake2b256: PerItemCost,
+    pub calc_sha256: PerItemCost,
+    pub subst_constants: PerItemCost,
+    pub prove_dlog_verify: Cost,
+    pub prove_dh_tuple_verify: Cost,
+    pub conjecture_verify: PerItemCost,
+    pub threshold_verify: PerItemCost,
 }
 
 impl Costs {
     pub const DEFAULT: Costs = Costs {
-        eq_const_size: Cost(1),
+        constant: Cost(5),
+        constant_placeholder: Cost(1),
+        val_use: Cost(5),
+        func_value: Cost(5),
+        apply: Cost(30),
+        if_op: Cost(10),
+        select_field: Cost(10),
+        tuple: Cost(15),
+        context: Cost(1),
+        global: Cost(10),
+        height: Cost(26),
+        inputs: Cost(10),
+        outputs: Cost(10),
+        self_box: Cost(10),
+        miner_pubkey: Cost(20),
+        group_generator: Cost(10),
+        last_block_utxo_root_hash: Cost(15),
+        method_call: Cost(4),
+        property_call: Cost(4),
+        plus_minus: TypeBasedCost::new(15, 20),
+        multiply: TypeBasedCost::new(15, 40),
+        divide: TypeBasedCost::new(15, 40),
+        modulo: TypeBasedCost::new(15, 40),
+        min_max: TypeBasedCost::new(5, 10),
+        compare: TypeBasedCost::new(20, 30),
+        eq_primitive: Cost(3),
+        eq_big_int: Cost(5),
+        eq_group_element: Cost(172),
+        eq_coll: PerItemCost::new(10, 2, 1),
+        bin_logical: Cost(20),
+        bit_op: TypeBasedCost::new(20, 40),
+        negation: TypeBasedCost::new(30, 40),
+        bit_inversion: TypeBasedCost::new(30, 40),
+        logical_not: Cost(15),
+        upcast: TypeBasedCost::new(10, 30),
+        downcast: TypeBasedCost::new(10, 30),
+        option_get: Cost(15),
+        option_is_defined: Cost(10),
+        option_get_or_else: Cost(20),
+        some_value: Cost(10),
+        none_value: Cost(5),
+        extract_amount: Cost(8),
+        extract_register_as: Cost(50),
+        extract_bytes: Cost(12),
+        extract_bytes_with_no_ref: Cost(12),
+        extract_script_bytes: Cost(12),
+        extract_creation_info: Cost(16),
+        extract_id: Cost(12),
+        by_index: Cost(30),
+        size_of: Cost(14),
+        bool_to_sigma_prop: Cost(15),
+        sigma_prop_is_proven: Cost(10),
+        create_prove_dlog: Cost(10),
+        create_prove_dh_tuple: Cost(20),
+        create_avl_tree: Cost(10),
+        tree_lookup: Cost(40),
+        decode_point: Cost(300),
+        multiply_group: Cost(40),
+        mod_q: Cost(40),
+        plus_minus_mod_q: Cost(50),
+        exponentiate: Cost(900),
+        get_var: Cost(10),
+        deserialize_context: Cost(1000),
+        deserialize_register: Cost(1000),
+        long_to_byte_array: Cost(17),
+        byte_array_to_long: Cost(16),
+        byte_array_to_bigint: Cost(30),
+        block_value: PerItemCost::new(1, 1, 10),
+        collection: PerItemCost::new(20, 1, 10),
+        and: PerItemCost::new(10, 5, 32),
+        or: PerItemCost::new(5, 5, 64),
+        xor: PerItemCost::new(10, 2, 128),
+        xor_of: PerItemCost::new(20, 5, 32),
+        atleast: PerItemCost::new(20, 3, 5),
+        sigma_and: PerItemCost::new(10, 2, 1),
+        sigma_or: PerItemCost::new(10, 2, 1),
+        sigma_prop_bytes: PerItemCost::new(35, 6, 1),
+        map: PerItemCost::new(20, 1, 10),
+        filter: PerItemCost::new(20, 1, 10),
+        exists: PerItemCost::new(3, 1, 10),
+        forall: PerItemCost::new(3, 1, 10),
+        fold: PerItemCost::new(3, 1, 10),
+        slice: PerItemCost::new(10, 2, 100),
+        coll_shift: PerItemCost::new(10, 2, 100),
+        append: PerItemCost::new(20, 2, 100),
+        calc_blake2b256: PerItemCost::new(20, 7, 128),
+        calc_sha256: PerItemCost::new(80, 8, 64),
+        subst_constants: PerItemCost::new(100, 100, 1),
+        // parse challenge + 2 exponentiations + group multiplication + commitment serialization
+        prove_dlog_verify: Cost(2420),
+        // parse challenge + 4 exponentiations + 2 group multiplications + commitment serialization
+        prove_dh_tuple_verify: Cost(4270),
+        conjecture_verify: PerItemCost::new(15, 5, 1),
+        threshold_verify: PerItemCost::new(30, 40, 1),
     };
 
-    pub fn cost_of(&self, _: &Expr) -> Cost {
-        Cost(1)
+    /// Cost of the expression node evaluation, excluding the cost of its children and the
+    /// per-item cost of the collection processing (charged during the evaluation)
+    pub fn cost_of(&self, expr: &Expr) -> Cost {
+        match expr {
+            Expr::Const(_) => self.constant,
+            Expr::ConstPlaceholder(_) => self.constant_placeholder,
+            Expr::SubstConstants(_) => self.subst_constants.base,
+            Expr::ByteArrayToLong(_) => self.byte_array_to_long,
+            Expr::ByteArrayToBigInt(_) => self.byte_array_to_bigint,
+            Expr::LongToByteArray(_) => self.long_to_byte_array,
+            Expr::Collection(_) => self.collection.base,
+            Expr::Tuple(_) => self.tuple,
+            Expr::CalcBlake2b256(_) => self.calc_blake2b256.base,
+            Expr::CalcSha256(_) => self.calc_sha256.base,
+            Expr::Context => self.context,
+            Expr::Global => self.global,
+            Expr::GlobalVars(v) => match v {
+                GlobalVars::Inputs => self.inputs,
+                GlobalVars::Outputs => self.outputs,
+                GlobalVars::Height => self.height,
+                GlobalVars::SelfBox => self.self_box,
+                GlobalVars::MinerPubKey => self.miner_pubkey,
+                GlobalVars::GroupGenerator => self.group_generator,
+                GlobalVars::LastBlockUtxoRootHash => self.last_block_utxo_root_hash,
+            },
+            Expr::FuncValue(_) => self.func_value,
+            Expr::Apply(_) => self.apply,
+            Expr::MethodCall(_) => self.method_call,
+            Expr::PropertyCall(_) => self.property_call,
+            Expr::BlockValue(_) => self.block_value.base,
+            Expr::ValDef(_) | Expr::FunDef(_) => Cost(0),
+            Expr::ValUse(_) => self.val_use,
+            Expr::If(_) => self.if_op,
+            Expr::BinOp(op) => {
+                let op = op.expr();
+                let arg_tpe = op.left.tpe();
+                match op.kind {
+                    BinOpKind::Arith(arith_op) => match arith_op {
+                        ArithOp::Plus | ArithOp::Minus => self.plus_minus.cost(&arg_tpe),
+                        ArithOp::Multiply => self.multiply.cost(&arg_tpe),
+                        ArithOp::Divide => self.divide.cost(&arg_tpe),
+                        ArithOp::Modulo => self.modulo.cost(&arg_tpe),
+                        ArithOp::Max | ArithOp::Min => self.min_max.cost(&arg_tpe),
+                    },
+                    BinOpKind::Relation(rel_op) => match rel_op {
+                        RelationOp::Eq | RelationOp::NEq => self.eq_cost(&arg_tpe),
+                        RelationOp::Ge | RelationOp::Gt | RelationOp::Le | RelationOp::Lt => {
+                            self.compare.cost(&arg_tpe)
+                        }
+                    },
+                    BinOpKind::Logical(_) => self.bin_logical,
+                    BinOpKind::Bit(_) => self.bit_op.cost(&arg_tpe),
+                }
+            }
+            Expr::And(_) => self.and.base,
+            Expr::Or(_) => self.or.base,
+            Expr::Xor(_) => self.xor.base,
+            Expr::Atleast(_) => self.atleast.base,
+            Expr::LogicalNot(_) => self.logical_not,
+            Expr::Negation(op) => self.negation.cost(&op.expr().tpe()),
+            Expr::BitInversion(op) => self.bit_inversion.cost(&op.tpe()),
+            Expr::OptionGet(_) => self.option_get,
+            Expr::OptionIsDefined(_) => self.option_is_defined,
+            Expr::OptionGetOrElse(_) => self.option_get_or_else,
+            Expr::SomeValue(_) => self.some_value,
+            Expr::NoneValue(_) => self.none_value,
+            Expr::ExtractAmount(_) => self.extract_amount,
+            Expr::ExtractRegisterAs(_) => self.extract_register_as,
+            Expr::ExtractBytes(_) => self.extract_bytes,
+            Expr::ExtractBytesWithNoRef(_) => self.extract_bytes_with_no_ref,
+            Expr::ExtractScriptBytes(_) => self.extract_script_bytes,
+            Expr::ExtractCreationInfo(_) => self.extract_creation_info,
+            Expr::ExtractId(_) => self.extract_id,
+            Expr::ByIndex(_) => self.by_index,
+            Expr::SizeOf(_) => self.size_of,
+            Expr::Slice(_) => self.slice.base,
+            Expr::CollShift(_) => self.coll_shift.base,
+            Expr::Fold(_) => self.fold.base,
+            Expr::Map(_) => self.map.base,
+            Expr::Filter(_) => self.filter.base,
+            Expr::Exists(_) => self.exists.base,
+            Expr::ForAll(_) => self.forall.base,
+            Expr::SelectField(_) | Expr::SelectFieldShort(_) => self.select_field,
+            Expr::BoolToSigmaProp(_) => self.bool_to_sigma_prop,
+            Expr::Upcast(op) => self.upcast.cost(&op.tpe()),
+            Expr::Downcast(op) => self.downcast.cost(&op.input.tpe()),
+            Expr::CreateProveDlog(_) => self.create_prove_dlog,
+            Expr::CreateProveDhTuple(_) => self.create_prove_dh_tuple,
+            Expr::SigmaPropBytes(_) => self.sigma_prop_bytes.base,
+            Expr::SigmaPropIsProven(_) => self.sigma_prop_is_proven,
+            Expr::DecodePoint(_) => self.decode_point,
+            Expr::SigmaAnd(_) => self.sigma_and.base,
+            Expr::SigmaOr(_) => self.sigma_or.base,
+            Expr::GetVar(_) => self.get_var,
+            Expr::DeserializeRegister(_) => self.deserialize_register,
+            Expr::DeserializeContext(_) => self.deserialize_context,
+            Expr::MultiplyGroup(_) => self.multiply_group,
+            Expr::ModQ(_) => self.mod_q,
+            Expr::PlusModQ(_) | Expr::MinusModQ(_) => self.plus_minus_mod_q,
+            Expr::Exponentiate(_) => self.exponentiate,
+            Expr::XorOf(_) => self.xor_of.base,
+            Expr::TreeLookup(_) => self.tree_lookup,
+            Expr::CreateAvlTree(_) => self.create_avl_tree,
+            Expr::Append(_) => self.append.base,
+        }
+    }
+
+    /// Cost of the equality check of the values of the given type
+    /// (for collections only the base cost, items are charged on comparison)
+    pub fn eq_cost(&self, tpe: &SType) -> Cost {
+        match tpe {
+            SType::SBigInt => self.eq_big_int,
+            SType::SGroupElement => self.eq_group_element,
+            SType::SColl(_) => self.eq_coll.base,
+            _ => self.eq_primitive,
+        }
+    }
+
+    /// Cost of the verification of the proof for the given sigma proposition
+    pub fn sigma_verify_cost(&self, sb: &SigmaBoolean) -> u64 {
+        match sb {
+            SigmaBoolean::TrivialProp(_) => 0,
+            SigmaBoolean::ProofOfKnowledge(SigmaProofOfKnowledgeTree::ProveDlog(_)) => {
+                u32::from(self.prove_dlog_verify) as u64
+            }
+            SigmaBoolean::ProofOfKnowledge(SigmaProofOfKnowledgeTree::ProveDhTuple(_)) => {
+                u32::from(self.prove_dh_tuple_verify) as u64
+            }
+            SigmaBoolean::SigmaConjecture(conj) => {
+                let (node_cost, children): (PerItemCost, &[SigmaBoolean]) = match conj {
+                    SigmaConjecture::Cand(cand) => (self.conjecture_verify, cand.items.as_slice()),
+                    SigmaConjecture::Cor(cor) => (self.conjecture_verify, cor.items.as_slice()),
+                    SigmaConjecture::Cthreshold(ct) => {
+                        (self.threshold_verify, ct.children.as_slice())
+                    }
+                };
+                let children_cost: u64 = children.iter().map(|c| self.sigma_verify_cost(c)).sum();
+                u32::from(node_cost.base) as u64
+                    + node_cost.items_cost(children.len())
+                    + children_cost
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn per_item_cost_chunks() {
+        let cost = PerItemCost::new(10, 2, 100);
+        assert_eq!(cost.chunks(0), 1);
+        assert_eq!(cost.chunks(1), 1);
+        assert_eq!(cost.chunks(100), 1);
+        assert_eq!(cost.chunks(101), 2);
+        assert_eq!(cost.items_cost(250), 6);
+    }
+
+    #[test]
+    fn block_cost_conversion() {
+        assert_eq!(to_block_cost(from_block_cost(12345)), 12345);
+        assert_eq!(to_block_cost(19), 1);
     }
 }
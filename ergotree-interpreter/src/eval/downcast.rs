@@ -7,6 +7,7 @@ use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
 use crate::eval::Evaluable;
+use num_traits::ToPrimitive;
 use std::convert::TryFrom;
 
 fn downcast_to_bigint(in_v: Value) -> Result<Value, EvalError> {
@@ -15,6 +16,7 @@ fn downcast_to_bigint(in_v: Value) -> Result<Value, EvalError> {
         Value::Short(v) => Ok(BigInt256::from(v).into()),
         Value::Int(v) => Ok(BigInt256::from(v).into()),
         Value::Long(v) => Ok(BigInt256::from(v).into()),
+        Value::BigInt(_) => Ok(in_v),
         _ => Err(EvalError::UnexpectedValue(format!(
             "Downcast: cannot downcast {0:?} to BigInt",
             in_v
@@ -28,6 +30,12 @@ fn downcast_to_long(in_v: Value) -> Result<Value, EvalError> {
         Value::Short(v) => Ok((v as i64).into()),
         Value::Int(v) => Ok((v as i64).into()),
         Value::Long(_) => Ok(in_v),
+        Value::BigInt(b) => match b.to_i64() {
+            Some(v) => Ok(v.into()),
+            _ => Err(EvalError::UnexpectedValue(
+                "Downcast: Long overflow".to_string(),
+            )),
+        },
         _ => Err(EvalError::UnexpectedValue(format!(
             "Downcast: cannot downcast {0:?} to Long",
             in_v
@@ -46,6 +54,12 @@ fn downcast_to_int(in_v: Value) -> Result<Value, EvalError> {
                 "Downcast: Int overflow".to_string(),
             )),
         },
+        Value::BigInt(b) => match b.to_i32() {
+            Some(v) => Ok(v.into()),
+            _ => Err(EvalError::UnexpectedValue(
+                "Downcast: Int overflow".to_string(),
+            )),
+        },
         _ => Err(EvalError::UnexpectedValue(format!(
             "Downcast: cannot downcast {0:?} to Int",
             in_v
@@ -68,6 +82,12 @@ fn downcast_to_short(in_v: Value) -> Result<Value, EvalError> {
                 "Downcast: Short overflow".to_string(),
             )),
         },
+        Value::BigInt(b) => match b.to_i16() {
+            Some(v) => Ok(v.into()),
+            _ => Err(EvalError::UnexpectedValue(
+                "Downcast: Short overflow".to_string(),
+            )),
+        },
         _ => Err(EvalError::UnexpectedValue(format!(
             "Downcast: cannot downcast {0:?} to Short",
             in_v
@@ -96,6 +116,12 @@ fn downcast_to_byte(in_v: Value) -> Result<Value, EvalError> {
                 "Downcast: Byte overflow".to_string(),
             )),
         },
+        Value::BigInt(b) => match b.to_i8() {
+            Some(v) => Ok(v.into()),
+            _ => Err(EvalError::UnexpectedValue(
+                "Downcast: Byte overflow".to_string(),
+            )),
+        },
         _ => Err(EvalError::UnexpectedValue(format!(
             "Downcast: cannot downcast {0:?} to Byte",
             in_v
@@ -103,20 +129,25 @@ fn downcast_to_byte(in_v: Value) -> Result<Value, EvalError> {
     }
 }
 
+/// Downcast the numeric value to the given (narrower or the same) numeric type, fails on overflow
+pub(crate) fn downcast(in_v: Value, tpe: &SType) -> Result<Value, EvalError> {
+    match tpe {
+        SType::SBigInt => downcast_to_bigint(in_v),
+        SType::SLong => downcast_to_long(in_v),
+        SType::SInt => downcast_to_int(in_v),
+        SType::SShort => downcast_to_short(in_v),
+        SType::SByte => downcast_to_byte(in_v),
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "Downcast: expected numeric value, got {0:?}",
+            in_v
+        ))),
+    }
+}
+
 impl Evaluable for Downcast {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
-        match self.tpe {
-            SType::SBigInt => downcast_to_bigint(input_v),
-            SType::SLong => downcast_to_long(input_v),
-            SType::SInt => downcast_to_int(input_v),
-            SType::SShort => downcast_to_short(input_v),
-            SType::SByte => downcast_to_byte(input_v),
-            _ => Err(EvalError::UnexpectedValue(format!(
-                "Downcast: expected numeric value, got {0:?}",
-                input_v
-            ))),
-        }
+        downcast(input_v, &self.tpe)
     }
 }
 
@@ -6,6 +6,7 @@ use std::fmt::Display;
 use bounded_vec::BoundedVecOutOfBounds;
 use derive_more::TryInto;
 use ergotree_ir::ergo_tree::ErgoTreeError;
+use ergotree_ir::ergo_tree::ErgoTreeVersion;
 use ergotree_ir::mir::constant::TryExtractFromError;
 use ergotree_ir::serialization::SigmaParsingError;
 use ergotree_ir::serialization::SigmaSerializationError;
@@ -59,6 +60,16 @@ pub enum EvalError {
     /// ErgoTree error
     #[error("ErgoTree error: {0}")]
     ErgoTreeError(#[from] ErgoTreeError),
+    /// ErgoTree version is higher than the activated script version
+    #[error(
+        "ErgoTree version {tree_version} is higher than activated script version {activated_version}"
+    )]
+    ScriptVersionError {
+        /// ErgoTree version
+        tree_version: ErgoTreeVersion,
+        /// Activated script version
+        activated_version: ErgoTreeVersion,
+    },
     /// Invalid item quantity for BoundedVec
     #[error("Invalid item quantity for BoundedVec: {0}")]
     BoundedVecError(#[from] BoundedVecOutOfBounds),
@@ -165,7 +176,6 @@ impl EvalError {
 
     /// Wrap eval error with source code
     pub fn wrap_spanned_with_src(self, source: String) -> Self {
-        #[allow(clippy::panic)]
         match self {
             EvalError::Spanned(e) => EvalError::SpannedWithSource(SpannedWithSourceEvalError {
                 error: e.error,
@@ -173,7 +183,8 @@ impl EvalError {
                 env: e.env,
                 source,
             }),
-            e => panic!("Expected Spanned, got {:?}", e),
+            // errors without a source span (e.g. cost limit) are returned as is
+            e => e,
         }
     }
 }
@@ -8,25 +8,29 @@ use crate::eval::EvalContext;
 use crate::eval::EvalError;
 use crate::eval::Evaluable;
 
+/// Exponentiate the GroupElement by the BigInt (positive, <= 256 bit) exponent
+pub(crate) fn exponentiate(left_v: Value, right_v: Value) -> Result<Value, EvalError> {
+    let exp_scalar: Option<Scalar> = match right_v.clone() {
+        Value::BigInt(bi) => dlog_group::bigint256_to_scalar(bi),
+        _ => None,
+    };
+
+    match (left_v.clone(), exp_scalar) {
+        (Value::GroupElement(group), Some(exp)) => {
+            Ok(ergo_chain_types::ec_point::exponentiate(&group, &exp).into())
+        }
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "Exponentiate input should be GroupElement, BigInt (positive, <= 256 bit). Received: {0:?}",
+            (left_v, right_v)
+        ))),
+    }
+}
+
 impl Evaluable for Exponentiate {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let left_v = self.left.eval(env, ctx)?;
         let right_v = self.right.eval(env, ctx)?;
-
-        let exp_scalar: Option<Scalar> = match right_v.clone() {
-            Value::BigInt(bi) => dlog_group::bigint256_to_scalar(bi),
-            _ => None,
-        };
-
-        match (left_v.clone(), exp_scalar) {
-            (Value::GroupElement(group), Some(exp)) => {
-                Ok(ergo_chain_types::ec_point::exponentiate(&group, &exp).into())
-            }
-            _ => Err(EvalError::UnexpectedValue(format!(
-                "Exponentiate input should be GroupElement, BigInt (positive, <= 256 bit). Received: {0:?}",
-                (left_v, right_v)
-            ))),
-        }
+        exponentiate(left_v, right_v)
     }
 }
 
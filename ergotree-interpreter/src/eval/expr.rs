@@ -35,6 +35,7 @@ impl Evaluable for Expr {
             Expr::ValUse(op) => op.eval(env, ctx),
             Expr::BlockValue(op) => op.expr().eval(env, ctx),
             Expr::SelectField(op) => op.eval(env, ctx),
+            Expr::SelectFieldShort(op) => op.eval(env, ctx),
             Expr::ExtractAmount(op) => op.eval(env, ctx),
             Expr::ConstPlaceholder(_) => Err(EvalError::UnexpectedExpr(
                 ("ConstPlaceholder is not supported").to_string(),
@@ -43,6 +44,9 @@ impl Evaluable for Expr {
             Expr::ValDef(_) => Err(EvalError::UnexpectedExpr(
                 ("ValDef should be evaluated in BlockValue").to_string(),
             )),
+            Expr::FunDef(_) => Err(EvalError::UnexpectedExpr(
+                ("FunDef should be evaluated in BlockValue").to_string(),
+            )),
             Expr::And(op) => op.eval(env, ctx),
             Expr::Or(op) => op.eval(env, ctx),
             Expr::Xor(op) => op.eval(env, ctx),
@@ -59,14 +63,18 @@ impl Evaluable for Expr {
             Expr::ExtractScriptBytes(op) => op.eval(env, ctx),
             Expr::SizeOf(op) => op.eval(env, ctx),
             Expr::Slice(op) => op.eval(env, ctx),
+            Expr::CollShift(op) => op.eval(env, ctx),
             Expr::CreateProveDlog(op) => op.eval(env, ctx),
             Expr::CreateProveDhTuple(op) => op.eval(env, ctx),
             Expr::ExtractCreationInfo(op) => op.eval(env, ctx),
             Expr::Exists(op) => op.eval(env, ctx),
             Expr::ExtractId(op) => op.eval(env, ctx),
             Expr::SigmaPropBytes(op) => op.eval(env, ctx),
+            Expr::SigmaPropIsProven(op) => op.eval(env, ctx),
             Expr::OptionIsDefined(op) => op.expr().eval(env, ctx),
             Expr::OptionGetOrElse(op) => op.expr().eval(env, ctx),
+            Expr::SomeValue(op) => op.eval(env, ctx),
+            Expr::NoneValue(op) => op.eval(env, ctx),
             Expr::Negation(op) => op.expr().eval(env, ctx),
             Expr::BitInversion(op) => op.eval(env, ctx),
             Expr::ForAll(op) => op.eval(env, ctx),
@@ -78,6 +86,9 @@ impl Evaluable for Expr {
             Expr::DeserializeContext(op) => op.eval(env, ctx),
             Expr::GetVar(op) => op.eval(env, ctx),
             Expr::MultiplyGroup(op) => op.eval(env, ctx),
+            Expr::ModQ(op) => op.eval(env, ctx),
+            Expr::PlusModQ(op) => op.eval(env, ctx),
+            Expr::MinusModQ(op) => op.eval(env, ctx),
             Expr::Exponentiate(op) => op.eval(env, ctx),
             Expr::XorOf(op) => op.eval(env, ctx),
             Expr::ExtractBytes(op) => op.eval(env, ctx),
@@ -3,6 +3,7 @@ use ergotree_ir::mir::global_vars::GlobalVars;
 use ergotree_ir::mir::value::Value;
 use ergotree_ir::serialization::SigmaSerializable;
 
+use super::scontext::last_block_utxo_root_hash;
 use super::EvalContext;
 use super::EvalError;
 use super::Evaluable;
@@ -18,6 +19,7 @@ impl Evaluable for GlobalVars {
                 Ok(ectx.ctx.pre_header.miner_pk.sigma_serialize_bytes()?.into())
             }
             GlobalVars::GroupGenerator => Ok(ergo_chain_types::ec_point::generator().into()),
+            GlobalVars::LastBlockUtxoRootHash => Ok(last_block_utxo_root_hash(&ectx.ctx)),
         }
     }
 }
@@ -34,6 +36,7 @@ mod tests {
     use ergoscript_compiler::compiler::compile_expr;
     use ergoscript_compiler::script_env::ScriptEnv;
     use ergotree_ir::chain::ergo_box::ErgoBox;
+    use ergotree_ir::mir::avl_tree_data::AvlTreeData;
     use sigma_test_util::force_any_val;
 
     use super::*;
@@ -80,4 +83,12 @@ mod tests {
             ergo_chain_types::ec_point::generator()
         );
     }
+
+    #[test]
+    fn eval_last_block_utxo_root_hash() {
+        let ctx = Rc::new(force_any_val::<Context>());
+        let tree = eval_out::<AvlTreeData>(&GlobalVars::LastBlockUtxoRootHash.into(), ctx.clone());
+        assert_eq!(tree.digest, ctx.headers[0].state_root);
+        assert_eq!(tree.key_length, 32);
+    }
 }
@@ -0,0 +1,106 @@
+use ergotree_ir::bigint256::BigInt256;
+use ergotree_ir::mir::constant::TryExtractInto;
+use ergotree_ir::mir::mod_q::MinusModQ;
+use ergotree_ir::mir::mod_q::ModQ;
+use ergotree_ir::mir::mod_q::PlusModQ;
+use ergotree_ir::mir::value::Value;
+use num_bigint::BigInt;
+
+use crate::eval::env::Env;
+use crate::eval::EvalContext;
+use crate::eval::EvalError;
+use crate::eval::Evaluable;
+use crate::sigma_protocol::wscalar::Wscalar;
+
+/// Non-negative remainder of the division by the group order, i.e. a value in `[0, q)`.
+/// Fails if the remainder does not fit into the 256-bit signed BigInt (i.e. is above 2^255 - 1).
+fn mod_q(v: BigInt) -> Result<Value, EvalError> {
+    let q = Wscalar::group_order();
+    let rem = v % &q;
+    let rem = if rem < BigInt::from(0) { rem + q } else { rem };
+    BigInt256::try_from(rem)
+        .map(Value::BigInt)
+        .map_err(EvalError::ArithmeticException)
+}
+
+fn eval_bigint(v: Value) -> Result<BigInt, EvalError> {
+    Ok(v.try_extract_into::<BigInt256>()?.into())
+}
+
+impl Evaluable for ModQ {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let input_v = self.input.eval(env, ctx)?;
+        mod_q(eval_bigint(input_v)?)
+    }
+}
+
+impl Evaluable for PlusModQ {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let left_v = self.left.eval(env, ctx)?;
+        let right_v = self.right.eval(env, ctx)?;
+        mod_q(eval_bigint(left_v)? + eval_bigint(right_v)?)
+    }
+}
+
+impl Evaluable for MinusModQ {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let left_v = self.left.eval(env, ctx)?;
+        let right_v = self.right.eval(env, ctx)?;
+        mod_q(eval_bigint(left_v)? - eval_bigint(right_v)?)
+    }
+}
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::mir::unary_op::OneArgOpTryBuild;
+    use num_traits::Num;
+
+    use super::*;
+    use crate::eval::tests::eval_out_wo_ctx;
+    use crate::eval::tests::try_eval_out_wo_ctx;
+
+    fn b(v: i64) -> BigInt256 {
+        BigInt256::from(v)
+    }
+
+    #[test]
+    fn group_order() {
+        let q = BigInt::from_str_radix(
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        assert_eq!(Wscalar::group_order(), q);
+    }
+
+    #[test]
+    fn eval_mod_q() {
+        let expr: Expr = ModQ::try_build(b(5).into()).unwrap().into();
+        assert_eq!(eval_out_wo_ctx::<BigInt256>(&expr), b(5));
+        // negative values wrap around to q - x, which does not fit into BigInt
+        let expr: Expr = ModQ::try_build(b(-1).into()).unwrap().into();
+        assert!(try_eval_out_wo_ctx::<BigInt256>(&expr).is_err());
+        let expr: Expr = PlusModQ::new(expr, b(1).into()).unwrap().into();
+        assert!(try_eval_out_wo_ctx::<BigInt256>(&expr).is_err());
+    }
+
+    #[test]
+    fn eval_plus_minus_mod_q() {
+        let expr: Expr = PlusModQ::new(b(2).into(), b(3).into()).unwrap().into();
+        assert_eq!(eval_out_wo_ctx::<BigInt256>(&expr), b(5));
+        let expr: Expr = MinusModQ::new(b(7).into(), b(3).into()).unwrap().into();
+        assert_eq!(eval_out_wo_ctx::<BigInt256>(&expr), b(4));
+        let expr: Expr = MinusModQ::new(b(3).into(), b(7).into()).unwrap().into();
+        assert!(try_eval_out_wo_ctx::<BigInt256>(&expr).is_err());
+        // no overflow on the intermediate sum
+        let max = BigInt256::try_from(BigInt::from(2).pow(255) - 1).unwrap();
+        let expr: Expr = PlusModQ::new(max.clone().into(), max.into())
+            .unwrap()
+            .into();
+        let q = Wscalar::group_order();
+        let expected = BigInt::from(2).pow(256) - 2 - q;
+        assert_eq!(BigInt::from(eval_out_wo_ctx::<BigInt256>(&expr)), expected);
+    }
+}
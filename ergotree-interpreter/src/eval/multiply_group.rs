@@ -6,18 +6,22 @@ use crate::eval::EvalContext;
 use crate::eval::EvalError;
 use crate::eval::Evaluable;
 
+/// Multiply two GroupElement values
+pub(crate) fn multiply_group(left_v: Value, right_v: Value) -> Result<Value, EvalError> {
+    match (left_v.clone(), right_v.clone()) {
+        (Value::GroupElement(left), Value::GroupElement(right)) => Ok((*left * &*right).into()),
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "Expected MultiplyGroup input to be GroupElement, got: {0:?}",
+            (left_v, right_v)
+        ))),
+    }
+}
+
 impl Evaluable for MultiplyGroup {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let left_v = self.left.eval(env, ctx)?;
         let right_v = self.right.eval(env, ctx)?;
-
-        match (left_v.clone(), right_v.clone()) {
-            (Value::GroupElement(left), Value::GroupElement(right)) => Ok((*left * &*right).into()),
-            _ => Err(EvalError::UnexpectedValue(format!(
-                "Expected MultiplyGroup input to be GroupElement, got: {0:?}",
-                (left_v, right_v)
-            ))),
-        }
+        multiply_group(left_v, right_v)
     }
 }
 
@@ -0,0 +1,48 @@
+use ergotree_ir::mir::option_value::NoneValue;
+use ergotree_ir::mir::option_value::SomeValue;
+use ergotree_ir::mir::value::Value;
+
+use crate::eval::env::Env;
+use crate::eval::EvalContext;
+use crate::eval::EvalError;
+use crate::eval::Evaluable;
+
+impl Evaluable for SomeValue {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let input_v = self.input.eval(env, ctx)?;
+        Ok(Value::Opt(Box::new(Some(input_v))))
+    }
+}
+
+impl Evaluable for NoneValue {
+    fn eval(&self, _env: &mut Env, _ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        Ok(Value::Opt(Box::new(None)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::types::stype::SType;
+
+    use super::*;
+    use crate::eval::tests::eval_out_wo_ctx;
+
+    #[test]
+    fn eval_some() {
+        let expr: Expr = SomeValue {
+            input: Box::new(1i64.into()),
+        }
+        .into();
+        assert_eq!(eval_out_wo_ctx::<Option<i64>>(&expr), Some(1i64));
+    }
+
+    #[test]
+    fn eval_none() {
+        let expr: Expr = NoneValue {
+            elem_tpe: SType::SLong,
+        }
+        .into();
+        assert_eq!(eval_out_wo_ctx::<Option<i64>>(&expr), None);
+    }
+}
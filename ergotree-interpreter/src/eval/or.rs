@@ -2,6 +2,7 @@ use ergotree_ir::mir::constant::TryExtractInto;
 use ergotree_ir::mir::or::Or;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -11,6 +12,8 @@ impl Evaluable for Or {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
         let input_v_bools = input_v.try_extract_into::<Vec<bool>>()?;
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.or, input_v_bools.len())?;
         Ok(input_v_bools.iter().any(|b| *b).into())
     }
 }
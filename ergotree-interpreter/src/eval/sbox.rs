@@ -6,6 +6,7 @@ use crate::eval::EvalError;
 use ergotree_ir::chain::ergo_box::ErgoBox;
 use ergotree_ir::mir::constant::TryExtractInto;
 use ergotree_ir::mir::value::Value;
+use ergotree_ir::serialization::SigmaSerializable;
 
 use super::EvalFn;
 
@@ -15,12 +16,63 @@ pub(crate) static VALUE_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
     ))
 };
 
+pub(crate) static PROPOSITION_BYTES_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
+    Ok(obj
+        .try_extract_into::<Arc<ErgoBox>>()?
+        .script_bytes()?
+        .into())
+};
+
+pub(crate) static BYTES_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
+    Ok(obj
+        .try_extract_into::<Arc<ErgoBox>>()?
+        .sigma_serialize_bytes()?
+        .into())
+};
+
+pub(crate) static BYTES_WITHOUT_REF_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
+    Ok(obj
+        .try_extract_into::<Arc<ErgoBox>>()?
+        .bytes_without_ref()?
+        .into())
+};
+
+pub(crate) static ID_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
+    let bytes: Vec<i8> = obj.try_extract_into::<Arc<ErgoBox>>()?.box_id().into();
+    Ok(bytes.into())
+};
+
+pub(crate) static CREATION_INFO_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
+    Ok(obj
+        .try_extract_into::<Arc<ErgoBox>>()?
+        .creation_info()
+        .into())
+};
+
 pub(crate) static GET_REG_EVAL_FN: EvalFn = |_env, _ctx, obj, args| {
     let reg_id = args
         .get(0)
         .cloned()
         .ok_or_else(|| EvalError::NotFound("register index is missing".to_string()))?
         .try_extract_into::<i8>()?;
+    get_reg(obj, reg_id)
+};
+
+/// `Box.R0..Box.R9` properties, indexed by the register index
+pub(crate) static REGISTER_EVAL_FNS: [EvalFn; 10] = [
+    |_env, _ctx, obj, _args| get_reg(obj, 0),
+    |_env, _ctx, obj, _args| get_reg(obj, 1),
+    |_env, _ctx, obj, _args| get_reg(obj, 2),
+    |_env, _ctx, obj, _args| get_reg(obj, 3),
+    |_env, _ctx, obj, _args| get_reg(obj, 4),
+    |_env, _ctx, obj, _args| get_reg(obj, 5),
+    |_env, _ctx, obj, _args| get_reg(obj, 6),
+    |_env, _ctx, obj, _args| get_reg(obj, 7),
+    |_env, _ctx, obj, _args| get_reg(obj, 8),
+    |_env, _ctx, obj, _args| get_reg(obj, 9),
+];
+
+fn get_reg(obj: Value, reg_id: i8) -> Result<Value, EvalError> {
     let reg_id = reg_id.try_into().map_err(|e| {
         EvalError::RegisterIdOutOfBounds(format!(
             "register index {reg_id} is out of bounds: {:?} ",
@@ -38,7 +90,7 @@ pub(crate) static GET_REG_EVAL_FN: EvalFn = |_env, _ctx, obj, args| {
             })?
             .map(|c| Value::from(c.v)),
     )))
-};
+}
 
 pub(crate) static TOKENS_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
     let res: Value = obj.try_extract_into::<Arc<ErgoBox>>()?.tokens_raw().into();
@@ -52,8 +104,13 @@ mod tests {
     use ergotree_ir::mir::expr::Expr;
     use ergotree_ir::mir::global_vars::GlobalVars;
     use ergotree_ir::mir::property_call::PropertyCall;
+    use ergotree_ir::mir::value::Value;
+    use ergotree_ir::serialization::SigmaSerializable;
     use ergotree_ir::types::sbox;
+    use ergotree_ir::types::smethod::SMethod;
     use sigma_test_util::force_any_val;
+    use sigma_util::AsVecI8;
+    use std::convert::TryInto;
 
     use crate::eval::context::Context;
     use crate::eval::tests::eval_out;
@@ -71,6 +128,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn eval_box_bytes_properties() {
+        let ctx = Rc::new(force_any_val::<Context>());
+        let eval_prop = |method: &SMethod| {
+            let expr: Expr = PropertyCall::new(GlobalVars::SelfBox.into(), method.clone())
+                .unwrap()
+                .into();
+            eval_out::<Vec<i8>>(&expr, ctx.clone())
+        };
+        assert_eq!(
+            eval_prop(&sbox::PROPOSITION_BYTES_METHOD),
+            ctx.self_box.script_bytes().unwrap()
+        );
+        assert_eq!(
+            eval_prop(&sbox::BYTES_METHOD),
+            ctx.self_box.sigma_serialize_bytes().unwrap().as_vec_i8()
+        );
+        assert_eq!(
+            eval_prop(&sbox::BYTES_WITHOUT_REF_METHOD),
+            ctx.self_box.bytes_without_ref().unwrap()
+        );
+        let id: Vec<i8> = ctx.self_box.box_id().into();
+        assert_eq!(eval_prop(&sbox::ID_METHOD), id);
+    }
+
+    #[test]
+    fn eval_box_creation_info() {
+        let expr: Expr = PropertyCall::new(
+            GlobalVars::SelfBox.into(),
+            sbox::CREATION_INFO_METHOD.clone(),
+        )
+        .unwrap()
+        .into();
+        let ctx = Rc::new(force_any_val::<Context>());
+        assert_eq!(
+            eval_out::<(i32, Vec<i8>)>(&expr, ctx.clone()),
+            ctx.self_box.creation_info()
+        );
+    }
+
+    #[test]
+    fn eval_box_registers() {
+        let ctx = Rc::new(force_any_val::<Context>());
+        for reg_index in 0..=9u8 {
+            let expr: Expr = PropertyCall::new(
+                GlobalVars::SelfBox.into(),
+                sbox::REGISTER_METHODS[reg_index as usize].clone(),
+            )
+            .unwrap()
+            .into();
+            let expected = ctx
+                .self_box
+                .get_register((reg_index as i8).try_into().unwrap())
+                .unwrap()
+                .map(|c| Value::from(c.v));
+            assert_eq!(
+                eval_out::<Value>(&expr, ctx.clone()),
+                Value::Opt(Box::new(expected))
+            );
+        }
+    }
+
     #[test]
     fn eval_box_tokens() {
         let expr: Expr = PropertyCall::new(GlobalVars::SelfBox.into(), sbox::TOKENS_METHOD.clone())
@@ -4,6 +4,7 @@ use ergotree_ir::mir::value::CollKind;
 use ergotree_ir::mir::value::Value;
 use ergotree_ir::types::stype::SType;
 
+use super::context::Context;
 use super::EvalError;
 use super::EvalFn;
 
@@ -77,15 +78,20 @@ pub(crate) static LAST_BLOCK_UTXO_ROOT_HASH_EVAL_FN: EvalFn = |_env, ctx, obj, _
             obj
         )));
     }
-    let digest = ctx.ctx.headers[0].state_root;
+    Ok(last_block_utxo_root_hash(ctx.ctx.as_ref()))
+};
+
+/// UTXO set digest (as AVL tree) after the last block (`Context.LastBlockUtxoRootHash`)
+pub(crate) fn last_block_utxo_root_hash(ctx: &Context) -> Value {
+    let digest = ctx.headers[0].state_root;
     let tree_flags = AvlTreeFlags::new(true, true, true);
-    Ok(Value::AvlTree(Box::from(AvlTreeData {
+    Value::AvlTree(Box::from(AvlTreeData {
         digest,
         tree_flags,
         key_length: 32,
         value_length_opt: None,
-    })))
-};
+    }))
+}
 
 #[cfg(test)]
 #[cfg(feature = "arbitrary")]
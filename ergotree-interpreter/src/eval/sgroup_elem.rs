@@ -4,6 +4,8 @@ use ergo_chain_types::EcPoint;
 use ergotree_ir::mir::value::Value;
 use ergotree_ir::serialization::SigmaSerializable;
 
+use super::exponentiate::exponentiate;
+use super::multiply_group::multiply_group;
 use super::EvalFn;
 
 pub(crate) static GET_ENCODED_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
@@ -29,6 +31,22 @@ pub(crate) static NEGATE_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| {
     Ok(Value::GroupElement(Box::new(negated)))
 };
 
+pub(crate) static EXPONENTIATE_EVAL_FN: EvalFn = |_env, _ctx, obj, args| {
+    let exponent = args
+        .get(0)
+        .cloned()
+        .ok_or_else(|| EvalError::NotFound("exp: missing first arg".to_string()))?;
+    exponentiate(obj, exponent)
+};
+
+pub(crate) static MULTIPLY_EVAL_FN: EvalFn = |_env, _ctx, obj, args| {
+    let other = args
+        .get(0)
+        .cloned()
+        .ok_or_else(|| EvalError::NotFound("multiply: missing first arg".to_string()))?;
+    multiply_group(obj, other)
+};
+
 #[allow(clippy::unwrap_used)]
 #[cfg(test)]
 #[cfg(feature = "arbitrary")]
@@ -39,6 +57,7 @@ mod tests {
 
     use crate::eval::tests::eval_out_wo_ctx;
     use ergo_chain_types::EcPoint;
+    use ergotree_ir::bigint256::BigInt256;
     use ergotree_ir::serialization::SigmaSerializable;
     use sigma_test_util::force_any_val;
 
@@ -72,4 +91,27 @@ mod tests {
         .into();
         assert_eq!(-input, eval_out_wo_ctx::<EcPoint>(&expr))
     }
+
+    #[test]
+    fn eval_exp_and_multiply() {
+        let input = force_any_val::<EcPoint>();
+        let exp_expr: Expr = MethodCall::new(
+            input.clone().into(),
+            sgroup_elem::EXPONENTIATE_METHOD.clone(),
+            vec![BigInt256::from(2i64).into()],
+        )
+        .unwrap()
+        .into();
+        let multiply_expr: Expr = MethodCall::new(
+            input.clone().into(),
+            sgroup_elem::MULTIPLY_METHOD.clone(),
+            vec![input.into()],
+        )
+        .unwrap()
+        .into();
+        assert_eq!(
+            eval_out_wo_ctx::<EcPoint>(&exp_expr),
+            eval_out_wo_ctx::<EcPoint>(&multiply_expr)
+        );
+    }
 }
@@ -4,6 +4,7 @@ use ergotree_ir::mir::value::Value;
 use ergotree_ir::sigma_protocol::sigma_boolean::cand::Cand;
 use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProp;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -11,6 +12,8 @@ use crate::eval::Evaluable;
 
 impl Evaluable for SigmaAnd {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.sigma_and, self.items.len())?;
         let items_v_res = self.items.try_mapped_ref(|it| it.eval(env, ctx));
         let items_sigmabool = items_v_res?
             .try_mapped(|it| it.try_extract_into::<SigmaProp>())?
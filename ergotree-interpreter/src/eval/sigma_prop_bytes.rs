@@ -1,6 +1,7 @@
 use ergotree_ir::mir::sigma_prop_bytes::SigmaPropBytes;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -10,7 +11,12 @@ impl Evaluable for SigmaPropBytes {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
         match input_v {
-            Value::SigmaProp(sigma_prop) => Ok(sigma_prop.prop_bytes()?.into()),
+            Value::SigmaProp(sigma_prop) => {
+                let bytes = sigma_prop.prop_bytes()?;
+                ctx.cost_accum
+                    .add_items_cost(Costs::DEFAULT.sigma_prop_bytes, bytes.len())?;
+                Ok(bytes.into())
+            }
             _ => Err(EvalError::UnexpectedValue(format!(
                 "Expected SigmaPropBytes input to be Value::SigmaProp, got {0:?}",
                 input_v
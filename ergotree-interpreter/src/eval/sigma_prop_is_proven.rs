@@ -0,0 +1,70 @@
+use std::convert::TryFrom;
+
+use ergotree_ir::mir::sigma_prop_is_proven::SigmaPropIsProven;
+use ergotree_ir::mir::value::Value;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProp;
+
+use crate::eval::env::Env;
+use crate::eval::EvalContext;
+use crate::eval::EvalError;
+use crate::eval::Evaluable;
+
+/// Only trivial propositions can be decided without a proof
+pub(crate) fn is_proven(sigma_prop: SigmaProp) -> Result<Value, EvalError> {
+    bool::try_from(sigma_prop.clone())
+        .map(Value::Boolean)
+        .map_err(|_| {
+            EvalError::UnexpectedValue(format!(
+                "SigmaPropIsProven: expected trivial proposition, got {0:?}",
+                sigma_prop
+            ))
+        })
+}
+
+impl Evaluable for SigmaPropIsProven {
+    fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
+        let input_v = self.input.eval(env, ctx)?;
+        match input_v {
+            Value::SigmaProp(sigma_prop) => is_proven(*sigma_prop),
+            _ => Err(EvalError::UnexpectedValue(format!(
+                "Expected SigmaPropIsProven input to be Value::SigmaProp, got {0:?}",
+                input_v
+            ))),
+        }
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::mir::unary_op::OneArgOpTryBuild;
+    use ergotree_ir::sigma_protocol::sigma_boolean::ProveDlog;
+    use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+    use sigma_test_util::force_any_val;
+
+    use super::*;
+    use crate::eval::tests::eval_out_wo_ctx;
+    use crate::eval::tests::try_eval_out_wo_ctx;
+
+    fn is_proven(sigma_prop: SigmaProp) -> Expr {
+        SigmaPropIsProven::try_build(sigma_prop.into())
+            .unwrap()
+            .into()
+    }
+
+    #[test]
+    fn eval_trivial() {
+        let expr = is_proven(SigmaProp::new(SigmaBoolean::TrivialProp(true)));
+        assert!(eval_out_wo_ctx::<bool>(&expr));
+        let expr = is_proven(SigmaProp::new(SigmaBoolean::TrivialProp(false)));
+        assert!(!eval_out_wo_ctx::<bool>(&expr));
+    }
+
+    #[test]
+    fn eval_non_trivial() {
+        let expr = is_proven(force_any_val::<ProveDlog>().into());
+        assert!(try_eval_out_wo_ctx::<bool>(&expr).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use ergotree_ir::mir::value::Value;
+use ergotree_ir::types::stype::SType;
+
+use super::downcast::downcast;
+use super::upcast::upcast;
+use super::EvalFn;
+use crate::eval::EvalError;
+
+/// Position of the numeric type in the widening order (Byte < Short < Int < Long < BigInt)
+fn numeric_rank(v: &Value) -> Result<u8, EvalError> {
+    match v {
+        Value::Byte(_) => Ok(0),
+        Value::Short(_) => Ok(1),
+        Value::Int(_) => Ok(2),
+        Value::Long(_) => Ok(3),
+        Value::BigInt(_) => Ok(4),
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "expected obj to be a numeric value, got: {0:?}",
+            v
+        ))),
+    }
+}
+
+fn type_rank(tpe: &SType) -> u8 {
+    match tpe {
+        SType::SByte => 0,
+        SType::SShort => 1,
+        SType::SInt => 2,
+        SType::SLong => 3,
+        _ => 4,
+    }
+}
+
+/// Convert the numeric value to the given numeric type, fails on overflow
+fn cast(obj: Value, tpe: &SType) -> Result<Value, EvalError> {
+    if type_rank(tpe) >= numeric_rank(&obj)? {
+        upcast(obj, tpe)
+    } else {
+        downcast(obj, tpe)
+    }
+}
+
+pub(crate) static TO_BYTE_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| cast(obj, &SType::SByte);
+
+pub(crate) static TO_SHORT_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| cast(obj, &SType::SShort);
+
+pub(crate) static TO_INT_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| cast(obj, &SType::SInt);
+
+pub(crate) static TO_LONG_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| cast(obj, &SType::SLong);
+
+pub(crate) static TO_BIGINT_EVAL_FN: EvalFn = |_env, _ctx, obj, _args| cast(obj, &SType::SBigInt);
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+mod tests {
+    use ergotree_ir::bigint256::BigInt256;
+    use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::mir::property_call::PropertyCall;
+    use ergotree_ir::types::smethod::MethodId;
+    use ergotree_ir::types::snumeric;
+
+    use crate::eval::tests::eval_out_wo_ctx;
+    use crate::eval::tests::try_eval_out_wo_ctx;
+
+    fn cast_expr(input: Expr, method_id: MethodId) -> Expr {
+        let method = snumeric::cast_method(&input.tpe(), &method_id).unwrap();
+        PropertyCall::new(input, method).unwrap().into()
+    }
+
+    #[test]
+    fn eval_upcast() {
+        assert_eq!(
+            eval_out_wo_ctx::<i64>(&cast_expr(7i8.into(), snumeric::TO_LONG_METHOD_ID)),
+            7i64
+        );
+        assert_eq!(
+            eval_out_wo_ctx::<BigInt256>(&cast_expr((-3i32).into(), snumeric::TO_BIGINT_METHOD_ID)),
+            BigInt256::from(-3i64)
+        );
+        assert_eq!(
+            eval_out_wo_ctx::<i32>(&cast_expr(5i32.into(), snumeric::TO_INT_METHOD_ID)),
+            5i32
+        );
+    }
+
+    #[test]
+    fn eval_downcast() {
+        assert_eq!(
+            eval_out_wo_ctx::<i16>(&cast_expr(300i64.into(), snumeric::TO_SHORT_METHOD_ID)),
+            300i16
+        );
+        assert_eq!(
+            eval_out_wo_ctx::<i8>(&cast_expr(
+                BigInt256::from(-100i64).into(),
+                snumeric::TO_BYTE_METHOD_ID
+            )),
+            -100i8
+        );
+    }
+
+    #[test]
+    fn eval_downcast_overflow() {
+        assert!(
+            try_eval_out_wo_ctx::<i8>(&cast_expr(300i32.into(), snumeric::TO_BYTE_METHOD_ID))
+                .is_err()
+        );
+        assert!(try_eval_out_wo_ctx::<i64>(&cast_expr(
+            (BigInt256::from(i64::MAX) * BigInt256::from(4i64)).into(),
+            snumeric::TO_LONG_METHOD_ID
+        ))
+        .is_err());
+    }
+}
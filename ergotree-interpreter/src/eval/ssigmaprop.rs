@@ -0,0 +1,60 @@
+use ergotree_ir::mir::constant::TryExtractInto;
+use ergotree_ir::sigma_protocol::sigma_boolean::SigmaProp;
+
+use super::costs::Costs;
+use super::sigma_prop_is_proven::is_proven;
+use super::EvalFn;
+
+pub(crate) static PROP_BYTES_EVAL_FN: EvalFn = |_env, ctx, obj, _args| {
+    let bytes = obj.try_extract_into::<SigmaProp>()?.prop_bytes()?;
+    ctx.cost_accum
+        .add_items_cost(Costs::DEFAULT.sigma_prop_bytes, bytes.len())?;
+    Ok(bytes.into())
+};
+
+pub(crate) static IS_PROVEN_EVAL_FN: EvalFn =
+    |_env, _ctx, obj, _args| is_proven(obj.try_extract_into::<SigmaProp>()?);
+
+#[allow(clippy::unwrap_used)]
+#[cfg(test)]
+#[cfg(feature = "arbitrary")]
+mod tests {
+    use ergotree_ir::mir::expr::Expr;
+    use ergotree_ir::mir::property_call::PropertyCall;
+    use ergotree_ir::sigma_protocol::sigma_boolean::ProveDlog;
+    use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+    use ergotree_ir::types::ssigmaprop;
+    use sigma_test_util::force_any_val;
+
+    use super::*;
+    use crate::eval::tests::eval_out_wo_ctx;
+    use crate::eval::tests::try_eval_out_wo_ctx;
+
+    #[test]
+    fn eval_prop_bytes() {
+        let sigma_prop: SigmaProp = force_any_val::<ProveDlog>().into();
+        let expr: Expr = PropertyCall::new(
+            sigma_prop.clone().into(),
+            ssigmaprop::PROP_BYTES_METHOD.clone(),
+        )
+        .unwrap()
+        .into();
+        assert_eq!(
+            eval_out_wo_ctx::<Vec<u8>>(&expr),
+            sigma_prop.prop_bytes().unwrap()
+        );
+    }
+
+    #[test]
+    fn eval_is_proven() {
+        let is_proven_expr = |sigma_prop: SigmaProp| -> Expr {
+            PropertyCall::new(sigma_prop.into(), ssigmaprop::IS_PROVEN_METHOD.clone())
+                .unwrap()
+                .into()
+        };
+        let expr = is_proven_expr(SigmaProp::new(SigmaBoolean::TrivialProp(true)));
+        assert!(eval_out_wo_ctx::<bool>(&expr));
+        let expr = is_proven_expr(force_any_val::<ProveDlog>().into());
+        assert!(try_eval_out_wo_ctx::<bool>(&expr).is_err());
+    }
+}
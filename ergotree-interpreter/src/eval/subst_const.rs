@@ -1,3 +1,4 @@
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -25,6 +26,8 @@ impl Evaluable for SubstConstants {
             .into_iter()
             .map(|i| i as usize)
             .collect();
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.subst_constants, positions.len())?;
 
         let new_constants = if let Value::Coll(CollKind::WrappedColl { items, .. }) = new_values_v {
             let mut items_const = vec![];
@@ -68,20 +68,25 @@ fn upcast_to_byte(in_v: Value) -> Result<Value, EvalError> {
     }
 }
 
+/// Upcast the numeric value to the given (wider or the same) numeric type
+pub(crate) fn upcast(in_v: Value, tpe: &SType) -> Result<Value, EvalError> {
+    match tpe {
+        SType::SBigInt => upcast_to_bigint(in_v),
+        SType::SLong => upcast_to_long(in_v),
+        SType::SInt => upcast_to_int(in_v),
+        SType::SShort => upcast_to_short(in_v),
+        SType::SByte => upcast_to_byte(in_v),
+        _ => Err(EvalError::UnexpectedValue(format!(
+            "Upcast: expected numeric value, got {0:?}",
+            in_v
+        ))),
+    }
+}
+
 impl Evaluable for Upcast {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
-        match self.tpe {
-            SType::SBigInt => upcast_to_bigint(input_v),
-            SType::SLong => upcast_to_long(input_v),
-            SType::SInt => upcast_to_int(input_v),
-            SType::SShort => upcast_to_short(input_v),
-            SType::SByte => upcast_to_byte(input_v),
-            _ => Err(EvalError::UnexpectedValue(format!(
-                "Upcast: expected numeric value, got {0:?}",
-                input_v
-            ))),
-        }
+        upcast(input_v, &self.tpe)
     }
 }
 
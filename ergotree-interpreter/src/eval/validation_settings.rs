@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+
+use ergotree_ir::ergo_tree::ErgoTreeConstantError;
+use ergotree_ir::ergo_tree::ErgoTreeError;
+use ergotree_ir::ergo_tree::ErgoTreeRootParsingError;
+use ergotree_ir::serialization::SigmaParsingError;
+
+/// Ids of the soft-forkable validation rules which are checked on ErgoTree deserialization
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy)]
+#[repr(u16)]
+pub enum RuleId {
+    /// Op code should be supported by the serializer
+    CheckValidOpCode = 1002,
+    /// Type code should be supported by the serializer
+    CheckTypeCode = 1008,
+    /// Method should be found in the type's method table
+    CheckAndGetMethod = 1011,
+    /// Size flag should be set in the ErgoTree header for the versions above 0
+    CheckHeaderSizeBit = 1012,
+}
+
+/// Status of the validation rule
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum RuleStatus {
+    /// Rule is checked as is (default)
+    Enabled,
+    /// Rule is replaced with a new rule (with the given id)
+    Replaced(u16),
+    /// Rule parameters are changed, i.e. new op codes, type codes or (type code, method id)
+    /// pairs are added
+    Changed(Vec<u8>),
+}
+
+/// Validation rules settings, which define what errors are soft-fork conditions, i.e. when the
+/// script that cannot be parsed should be accepted without evaluation (as other nodes that voted
+/// for the change can parse and evaluate it)
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct ValidationSettings {
+    statuses: HashMap<RuleId, RuleStatus>,
+}
+
+impl ValidationSettings {
+    /// Return new settings with the given rule status
+    pub fn with_status(self, rule_id: RuleId, status: RuleStatus) -> Self {
+        let mut statuses = self.statuses;
+        statuses.insert(rule_id, status);
+        ValidationSettings { statuses }
+    }
+
+    /// Rule status (enabled if it was not changed)
+    pub fn status(&self, rule_id: RuleId) -> RuleStatus {
+        self.statuses
+            .get(&rule_id)
+            .cloned()
+            .unwrap_or(RuleStatus::Enabled)
+    }
+
+    /// Returns true if the given ErgoTree parsing error is a soft-fork condition
+    pub fn is_soft_fork(&self, error: &ErgoTreeError) -> bool {
+        match error {
+            ErgoTreeError::HeaderError(_) => self.is_replaced(RuleId::CheckHeaderSizeBit),
+            ErgoTreeError::SigmaParsingError(e)
+            | ErgoTreeError::RootParsingError(ErgoTreeRootParsingError::SigmaParsingError(e))
+            | ErgoTreeError::ConstantsError(ErgoTreeConstantError::ParsingError(e)) => {
+                self.is_soft_fork_parsing_error(e)
+            }
+            ErgoTreeError::RootParsingError(ErgoTreeRootParsingError::NonConsumedBytes)
+            | ErgoTreeError::ConstantsError(ErgoTreeConstantError::SetConstantError(_))
+            | ErgoTreeError::RootSerializationError(_)
+            | ErgoTreeError::IoError(_) => false,
+        }
+    }
+
+    fn is_soft_fork_parsing_error(&self, error: &SigmaParsingError) -> bool {
+        match error {
+            SigmaParsingError::InvalidOpCode(op_code) => {
+                self.is_code_added(RuleId::CheckValidOpCode, &[*op_code])
+            }
+            SigmaParsingError::InvalidTypeCode(type_code) => {
+                self.is_code_added(RuleId::CheckTypeCode, &[*type_code])
+            }
+            SigmaParsingError::UnknownMethodId(method_id, type_code) => {
+                self.is_code_added(RuleId::CheckAndGetMethod, &[*type_code, method_id.0])
+            }
+            _ => false,
+        }
+    }
+
+    fn is_replaced(&self, rule_id: RuleId) -> bool {
+        matches!(self.status(rule_id), RuleStatus::Replaced(_))
+    }
+
+    fn is_code_added(&self, rule_id: RuleId, code: &[u8]) -> bool {
+        match self.status(rule_id) {
+            RuleStatus::Replaced(_) => true,
+            RuleStatus::Changed(new_codes) => new_codes.chunks(code.len()).any(|c| c == code),
+            RuleStatus::Enabled => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ergotree_ir::ergo_tree::ErgoTreeHeaderError;
+    use ergotree_ir::ergo_tree::ErgoTreeVersion;
+    use ergotree_ir::types::smethod::MethodId;
+
+    fn root_parsing_error(e: SigmaParsingError) -> ErgoTreeError {
+        ErgoTreeError::RootParsingError(ErgoTreeRootParsingError::SigmaParsingError(e))
+    }
+
+    #[test]
+    fn default_settings_no_soft_fork() {
+        let settings = ValidationSettings::default();
+        assert!(!settings.is_soft_fork(&root_parsing_error(SigmaParsingError::InvalidOpCode(0xfe))));
+        assert!(!settings.is_soft_fork(&ErgoTreeError::HeaderError(
+            ErgoTreeHeaderError::SizeFlagNotSet(ErgoTreeVersion::V1)
+        )));
+    }
+
+    #[test]
+    fn op_code_added() {
+        let settings = ValidationSettings::default()
+            .with_status(RuleId::CheckValidOpCode, RuleStatus::Changed(vec![0xfe]));
+        assert!(settings.is_soft_fork(&root_parsing_error(SigmaParsingError::InvalidOpCode(0xfe))));
+        assert!(!settings.is_soft_fork(&root_parsing_error(SigmaParsingError::InvalidOpCode(0xfd))));
+        assert!(settings.is_soft_fork(&ErgoTreeError::SigmaParsingError(
+            SigmaParsingError::InvalidOpCode(0xfe)
+        )));
+    }
+
+    #[test]
+    fn method_added() {
+        let settings = ValidationSettings::default().with_status(
+            RuleId::CheckAndGetMethod,
+            RuleStatus::Changed(vec![99, 20, 12, 5]),
+        );
+        assert!(
+            settings.is_soft_fork(&root_parsing_error(SigmaParsingError::UnknownMethodId(
+                MethodId(5),
+                12
+            )))
+        );
+        assert!(
+            !settings.is_soft_fork(&root_parsing_error(SigmaParsingError::UnknownMethodId(
+                MethodId(12),
+                20
+            )))
+        );
+    }
+
+    #[test]
+    fn rule_replaced() {
+        let settings = ValidationSettings::default()
+            .with_status(RuleId::CheckHeaderSizeBit, RuleStatus::Replaced(1016))
+            .with_status(RuleId::CheckTypeCode, RuleStatus::Replaced(1017));
+        assert!(settings.is_soft_fork(&ErgoTreeError::HeaderError(
+            ErgoTreeHeaderError::SizeFlagNotSet(ErgoTreeVersion::V1)
+        )));
+        assert!(settings.is_soft_fork(&ErgoTreeError::ConstantsError(
+            ErgoTreeConstantError::ParsingError(SigmaParsingError::InvalidTypeCode(110))
+        )));
+    }
+}
@@ -1,6 +1,7 @@
 use ergotree_ir::mir::constant::TryExtractInto;
 use ergotree_ir::mir::value::Value;
 
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalContext;
 use crate::eval::EvalError;
@@ -11,6 +12,8 @@ impl Evaluable for XorOf {
     fn eval(&self, env: &mut Env, ctx: &mut EvalContext) -> Result<Value, EvalError> {
         let input_v = self.input.eval(env, ctx)?;
         let input_v_bools = input_v.try_extract_into::<Vec<bool>>()?;
+        ctx.cost_accum
+            .add_items_cost(Costs::DEFAULT.xor_of, input_v_bools.len())?;
         Ok(input_v_bools.into_iter().fold(false, |a, b| a ^ b).into())
     }
 }
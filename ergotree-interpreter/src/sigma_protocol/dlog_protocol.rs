@@ -91,7 +91,7 @@ pub mod interactive_prover {
     /// the challenge "e", and witness w.
     pub(crate) fn second_message(
         private_input: &DlogProverInput,
-        rnd: Wscalar,
+        rnd: &Wscalar,
         challenge: &Challenge,
     ) -> SecondDlogProverMessage {
         let e: Scalar = challenge.clone().into();
@@ -140,7 +140,7 @@ mod tests {
         fn test_compute_commitment(secret in any::<DlogProverInput>(), challenge in any::<Challenge>()) {
             let pk = secret.public_image();
             let (r, commitment) = interactive_prover::first_message();
-            let second_message = interactive_prover::second_message(&secret, r, &challenge);
+            let second_message = interactive_prover::second_message(&secret, &r, &challenge);
             let a = interactive_prover::compute_commitment(&pk, &challenge, &second_message);
             prop_assert_eq!(a, *commitment.a);
         }
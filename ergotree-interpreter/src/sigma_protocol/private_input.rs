@@ -15,6 +15,8 @@ use derive_more::From;
 use k256::elliptic_curve::PrimeField;
 use num_bigint::BigUint;
 use num_traits::ToPrimitive;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
 
 use super::crypto_utils;
 use super::wscalar::Wscalar;
@@ -35,6 +37,20 @@ impl std::fmt::Debug for DlogProverInput {
     }
 }
 
+impl Zeroize for DlogProverInput {
+    fn zeroize(&mut self) {
+        self.w.zeroize();
+    }
+}
+
+impl Drop for DlogProverInput {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for DlogProverInput {}
+
 impl DlogProverInput {
     /// Scalar(secret key) size in bytes
     pub const SIZE_BYTES: usize = 32;
@@ -123,6 +139,20 @@ impl std::fmt::Debug for DhTupleProverInput {
     }
 }
 
+impl Zeroize for DhTupleProverInput {
+    fn zeroize(&mut self) {
+        self.w.zeroize();
+    }
+}
+
+impl Drop for DhTupleProverInput {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for DhTupleProverInput {}
+
 impl DhTupleProverInput {
     /// Size in bytes: 32(secret)+33(g)+33(h)+33(u)+33(v)=164 bytes
     pub const SIZE_BYTES: usize = DlogProverInput::SIZE_BYTES + EcPoint::GROUP_SIZE * 4;
@@ -286,5 +316,17 @@ pub(crate) mod arbitrary {
 }
 
 #[cfg(test)]
-#[cfg(feature = "arbitrary")]
-mod tests {}
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zeroize() {
+        let mut dlog = DlogProverInput::random();
+        assert!(!dlog.is_zero());
+        dlog.zeroize();
+        assert!(dlog.is_zero());
+        let mut dht = DhTupleProverInput::random();
+        dht.zeroize();
+        assert!(bool::from(dht.w.as_scalar_ref().is_zero()));
+    }
+}
@@ -5,7 +5,7 @@ mod prover_result;
 
 pub mod hint;
 
-use crate::eval::reduce_to_crypto;
+use crate::eval::reduce_tree_to_crypto;
 use crate::eval::ReductionDiagnosticInfo;
 use crate::sigma_protocol::crypto_utils::secure_random_bytes;
 use crate::sigma_protocol::fiat_shamir::fiat_shamir_hash_fn;
@@ -146,9 +146,9 @@ pub trait Prover {
         message: &[u8],
         hints_bag: &HintsBag,
     ) -> Result<ProverResult, ProverError> {
-        let expr = tree.proposition()?;
         let ctx_ext = ctx.extension.clone();
-        let reduction_result = reduce_to_crypto(&expr, env, ctx).map_err(ProverError::EvalError)?;
+        let reduction_result =
+            reduce_tree_to_crypto(tree, env, ctx).map_err(ProverError::EvalError)?;
         self.generate_proof(reduction_result.sigma_prop, message, hints_bag)
             .map(|p| ProverResult {
                 proof: p,
@@ -883,26 +883,24 @@ fn step9_real_schnorr<P: Prover + ?Sized>(
             .iter()
             .find(|s| s.public_image() == us.proposition.clone().into());
         let z = match priv_key_opt {
-            Some(PrivateInput::DlogProverInput(priv_key)) => match hints_bag
-                .own_commitments()
-                .iter()
-                .find(|c| c.position == us.position)
-            {
-                Some(oc) => dlog_protocol::interactive_prover::second_message(
-                    priv_key,
-                    oc.secret_randomness.clone(),
-                    &challenge,
-                ),
-                None => dlog_protocol::interactive_prover::second_message(
-                    priv_key,
-                    us.randomness_opt.clone().ok_or({
-                        ProverError::Unexpected(
-                            "step9_real_schnorr: empty randomness in UnprovenSchnorr",
-                        )
-                    })?,
-                    &challenge,
-                ),
-            },
+            Some(PrivateInput::DlogProverInput(priv_key)) => {
+                match hints_bag.own_commitment(&us.position) {
+                    Some(oc) => dlog_protocol::interactive_prover::second_message(
+                        priv_key,
+                        &oc.secret_randomness,
+                        &challenge,
+                    ),
+                    None => dlog_protocol::interactive_prover::second_message(
+                        priv_key,
+                        us.randomness_opt.as_ref().ok_or({
+                            ProverError::Unexpected(
+                                "step9_real_schnorr: empty randomness in UnprovenSchnorr",
+                            )
+                        })?,
+                        &challenge,
+                    ),
+                }
+            }
             Some(PrivateInput::DhTupleProverInput(_)) => {
                 return Err(ProverError::Unexpected(
                     "step9_real_schnorr: Expected DLOG prover input in prover secrets, got DhTupleProverInput",
@@ -960,28 +958,26 @@ fn step9_real_dh_tuple<P: Prover + ?Sized>(
             .iter()
             .find(|s| s.public_image() == dhu.proposition.clone().into());
         let z = match priv_key_opt {
-            Some(PrivateInput::DhTupleProverInput(priv_key)) => match hints_bag
-                .own_commitments()
-                .iter()
-                .find(|c| c.position == dhu.position)
-            {
-                Some(commitment_from_hints_bag) => {
-                    dht_protocol::interactive_prover::second_message(
+            Some(PrivateInput::DhTupleProverInput(priv_key)) => {
+                match hints_bag.own_commitment(&dhu.position) {
+                    Some(commitment_from_hints_bag) => {
+                        dht_protocol::interactive_prover::second_message(
+                            priv_key,
+                            &commitment_from_hints_bag.secret_randomness,
+                            &dhu_challenge,
+                        )
+                    }
+                    None => dht_protocol::interactive_prover::second_message(
                         priv_key,
-                        &commitment_from_hints_bag.secret_randomness,
+                        dhu.randomness_opt.as_ref().ok_or({
+                            ProverError::Unexpected(
+                                "step9_real_dh_tuple: empty randomness in UnprovenDhTuple",
+                            )
+                        })?,
                         &dhu_challenge,
-                    )
+                    ),
                 }
-                None => dht_protocol::interactive_prover::second_message(
-                    priv_key,
-                    &dhu.randomness_opt.clone().ok_or({
-                        ProverError::Unexpected(
-                            "step9_real_dh_tuple: empty randomness in UnprovenDhTuple",
-                        )
-                    })?,
-                    &dhu_challenge,
-                ),
-            },
+            }
             Some(PrivateInput::DlogProverInput(_)) => {
                 return Err(ProverError::Unexpected("step9_real_dh_tuple: Expected DhTupleProverInput  in prover secrets, got DlogProverInput"));
             }
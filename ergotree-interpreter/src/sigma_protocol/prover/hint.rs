@@ -2,6 +2,8 @@
 
 use derive_more::From;
 use ergotree_ir::sigma_protocol::sigma_boolean::SigmaBoolean;
+use zeroize::Zeroize;
+use zeroize::ZeroizeOnDrop;
 
 use crate::sigma_protocol::challenge::Challenge;
 use crate::sigma_protocol::unchecked_tree::UncheckedTree;
@@ -125,7 +127,7 @@ pub struct RealCommitment {
 /// Please note that this randomness should be kept in secret by the prover.
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
-#[derive(PartialEq, Eq, Debug, Clone)]
+#[derive(PartialEq, Eq, Clone)]
 pub struct OwnCommitment {
     ///  image of a secret
     #[cfg_attr(feature = "json", serde(rename = "pubkey"))]
@@ -141,6 +143,32 @@ pub struct OwnCommitment {
     pub position: NodePosition,
 }
 
+impl std::fmt::Debug for OwnCommitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // to avoid leaking the randomness in error messages, logs, etc.
+        f.debug_struct("OwnCommitment")
+            .field("image", &self.image)
+            .field("secret_randomness", &"***")
+            .field("commitment", &self.commitment)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+impl Zeroize for OwnCommitment {
+    fn zeroize(&mut self) {
+        self.secret_randomness.zeroize();
+    }
+}
+
+impl Drop for OwnCommitment {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for OwnCommitment {}
+
 ///A hint which contains a commitment to randomness associated with a public image of a secret.
 #[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "arbitrary", derive(proptest_derive::Arbitrary))]
@@ -250,11 +278,10 @@ impl HintsBag {
     /// Commitments from all CommitmentHints in the bag
     pub fn commitments(&self) -> Vec<CommitmentHint> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::CommitmentHint(v) = hint {
-                    Some(v)
+                    Some(v.clone())
                 } else {
                     None
                 }
@@ -265,11 +292,10 @@ impl HintsBag {
     /// RealSecretProof hints only
     pub fn real_proofs(&self) -> Vec<RealSecretProof> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::SecretProven(SecretProven::RealSecretProof(v)) = hint {
-                    Some(v)
+                    Some(v.clone())
                 } else {
                     None
                 }
@@ -280,11 +306,10 @@ impl HintsBag {
     /// RealCommitment hints only
     pub fn real_commitments(&self) -> Vec<RealCommitment> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::CommitmentHint(CommitmentHint::RealCommitment(v)) = hint {
-                    Some(v)
+                    Some(v.clone())
                 } else {
                     None
                 }
@@ -295,11 +320,10 @@ impl HintsBag {
     /// OwnCommitment hints only
     pub fn own_commitments(&self) -> Vec<OwnCommitment> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::CommitmentHint(CommitmentHint::OwnCommitment(v)) = hint {
-                    Some(v)
+                    Some(v.clone())
                 } else {
                     None
                 }
@@ -307,6 +331,16 @@ impl HintsBag {
             .collect()
     }
 
+    /// OwnCommitment hint for the given position in the tree
+    pub fn own_commitment(&self, position: &NodePosition) -> Option<&OwnCommitment> {
+        self.hints.iter().find_map(|hint| match hint {
+            Hint::CommitmentHint(CommitmentHint::OwnCommitment(c)) if &c.position == position => {
+                Some(c)
+            }
+            _ => None,
+        })
+    }
+
     /// Images of real secrets + real commitments in the bag
     pub fn real_images(&self) -> Vec<SigmaBoolean> {
         let mut from_proofs: Vec<SigmaBoolean> =
@@ -323,11 +357,10 @@ impl HintsBag {
     /// SimulatedSecretProof proofs only
     pub fn simulated_proofs(&self) -> Vec<SimulatedSecretProof> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::SecretProven(SecretProven::SimulatedSecretProof(v)) = hint {
-                    Some(v)
+                    Some(v.clone())
                 } else {
                     None
                 }
@@ -338,11 +371,10 @@ impl HintsBag {
     /// All proofs from SecretProven variants
     pub fn proofs(&self) -> Vec<SecretProven> {
         self.hints
-            .clone()
-            .into_iter()
+            .iter()
             .filter_map(|hint| {
                 if let Hint::SecretProven(sp) = hint {
-                    Some(sp)
+                    Some(sp.clone())
                 } else {
                     None
                 }
@@ -16,9 +16,12 @@ use super::{
     SigmaBoolean, UncheckedTree,
 };
 use crate::eval::context::Context;
+use crate::eval::cost_accum::CostError;
+use crate::eval::costs::to_block_cost;
+use crate::eval::costs::Costs;
 use crate::eval::env::Env;
 use crate::eval::EvalError;
-use crate::eval::{reduce_to_crypto, ReductionDiagnosticInfo};
+use crate::eval::{check_soft_fork_condition, reduce_to_crypto_jit, ReductionDiagnosticInfo};
 use dlog_protocol::FirstDlogProverMessage;
 use ergotree_ir::ergo_tree::ErgoTree;
 use ergotree_ir::ergo_tree::ErgoTreeError;
@@ -47,10 +50,14 @@ pub enum VerifierError {
 pub struct VerificationResult {
     /// result of SigmaProp condition verification via sigma protocol
     pub result: bool,
-    /// estimated cost of contract execution
+    /// cost of contract execution (script reduction and sigma protocol verification) in block
+    /// cost units
     pub cost: u64,
     /// Diagnostic information about the reduction (pretty printed expr and/or env)
     pub diag: ReductionDiagnosticInfo,
+    /// true if the script was accepted without evaluation under the soft-fork rules (see
+    /// [`check_soft_fork_condition`])
+    pub soft_fork: bool,
 }
 
 /// Verifier for the proofs generater by [`super::prover::Prover`]
@@ -59,6 +66,9 @@ pub trait Verifier {
     /// Step 1: Deserialize context variables
     /// Step 2: Evaluate expression and produce SigmaProp value, which is zero-knowledge statement (see also `SigmaBoolean`).
     /// Step 3: Verify that the proof is presented to satisfy SigmaProp conditions.
+    /// The total cost is checked against the context's cost limit (if any).
+    /// Scripts which cannot be evaluated by this interpreter are accepted under the soft-fork
+    /// rules (see [`check_soft_fork_condition`]).
     fn verify(
         &self,
         tree: &ErgoTree,
@@ -67,11 +77,33 @@ pub trait Verifier {
         proof: ProofBytes,
         message: &[u8],
     ) -> Result<VerificationResult, VerifierError> {
+        if check_soft_fork_condition(tree, &ctx)? {
+            return Ok(VerificationResult {
+                result: true,
+                cost: 0,
+                diag: ReductionDiagnosticInfo {
+                    env: env.clone(),
+                    pretty_printed_expr: None,
+                },
+                soft_fork: true,
+            });
+        }
         let expr = tree.proposition()?;
-        let reduction_result = reduce_to_crypto(&expr, env, ctx)?;
+        let cost_limit = ctx.cost_limit;
+        let (reduction_result, reduction_jit_cost) = reduce_to_crypto_jit(&expr, env, ctx)?;
+        let mut cost = reduction_result.cost;
         let res: bool = match reduction_result.sigma_prop {
             SigmaBoolean::TrivialProp(b) => b,
             sb => {
+                // both costs are in JIT units, rounded down to block units only once
+                cost = to_block_cost(
+                    reduction_jit_cost.saturating_add(Costs::DEFAULT.sigma_verify_cost(&sb)),
+                );
+                if let Some(limit) = cost_limit {
+                    if cost > limit {
+                        return Err(EvalError::CostError(CostError::LimitExceeded(limit)).into());
+                    }
+                }
                 match proof {
                     ProofBytes::Empty => false,
                     ProofBytes::Some(proof_bytes) => {
@@ -85,8 +117,9 @@ pub trait Verifier {
         };
         Ok(VerificationResult {
             result: res,
-            cost: 0,
+            cost,
             diag: reduction_result.diag,
+            soft_fork: false,
         })
     }
 }
@@ -463,5 +496,38 @@ mod tests {
                                             message.as_slice());
             prop_assert_eq!(ver_res.unwrap().result, true)
         }
+
+        #[test]
+        fn test_verifier_cost_limit(secret in any::<DlogProverInput>(), message in vec(any::<u8>(), 100..200)) {
+            let pk = secret.public_image();
+            let tree = ErgoTree::try_from(Expr::Const(pk.into())).unwrap();
+            let prover = TestProver {
+                secrets: vec![PrivateInput::DlogProverInput(secret)],
+            };
+            let proof = prover.prove(&tree,
+                &Env::empty(),
+                Rc::new(force_any_val::<Context>()),
+                message.as_slice(),
+                &HintsBag::empty()).unwrap().proof;
+            let verifier = TestVerifier;
+            let ver_res = verifier.verify(&tree,
+                                            &Env::empty(),
+                                            Rc::new(force_any_val::<Context>()),
+                                            proof.clone(),
+                                            message.as_slice()).unwrap();
+            prop_assert!(ver_res.result);
+            // reduction cost (a single constant) plus the cost of the signature verification
+            let reduction_cost = u32::from(Costs::DEFAULT.constant) as u64;
+            let sig_verify_cost = u32::from(Costs::DEFAULT.prove_dlog_verify) as u64;
+            prop_assert_eq!(ver_res.cost, to_block_cost(reduction_cost + sig_verify_cost));
+
+            let ctx = force_any_val::<Context>().with_cost_limit(ver_res.cost - 1);
+            let limited_res = verifier.verify(&tree,
+                                            &Env::empty(),
+                                            Rc::new(ctx),
+                                            proof,
+                                            message.as_slice());
+            prop_assert!(limited_res.is_err());
+        }
     }
 }
@@ -13,6 +13,9 @@ use k256::elliptic_curve::generic_array::GenericArray;
 use k256::elliptic_curve::ops::Reduce;
 use k256::Scalar;
 use k256::U256;
+use num_bigint::BigInt;
+use num_bigint::Sign;
+use zeroize::Zeroize;
 
 use super::challenge::Challenge;
 use super::GroupSizedBytes;
@@ -35,6 +38,19 @@ impl Wscalar {
     pub fn as_scalar_ref(&self) -> &Scalar {
         &self.0
     }
+
+    /// Order of the secp256k1 group (q), i.e. the modulus of the scalars
+    pub fn group_order() -> BigInt {
+        // -1 is the largest scalar (q - 1)
+        let max_scalar = -Scalar::ONE;
+        BigInt::from_bytes_be(Sign::Plus, &max_scalar.to_bytes()) + 1
+    }
+}
+
+impl Zeroize for Wscalar {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
 }
 
 impl From<GroupSizedBytes> for Wscalar {
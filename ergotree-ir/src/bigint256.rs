@@ -11,7 +11,8 @@ use num_bigint::ToBigInt;
 use num_derive::{One, Zero};
 use num_integer::Integer;
 use num_traits::{
-    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Num, Zero,
+    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub, Num,
+    ToPrimitive, Zero,
 };
 
 /// 256-bit signed integer type
@@ -222,6 +223,54 @@ impl CheckedNeg for BigInt256 {
     }
 }
 
+impl ToPrimitive for BigInt256 {
+    fn to_i64(&self) -> Option<i64> {
+        self.0 .0.to_i64()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.0 .0.to_u64()
+    }
+}
+
+impl BigInt256 {
+    /// Number of bits in the two's complement representation
+    pub const BITS: u32 = 256;
+
+    /// Shift left by `n` bits, returns None if `n` is not less than 256 or the result does not
+    /// fit into 256 bits
+    pub fn checked_shl(&self, n: u32) -> Option<Self> {
+        if n >= Self::BITS {
+            return None;
+        }
+        Self::try_from(&self.0 .0 << n as usize).ok()
+    }
+
+    /// Arithmetic (sign-preserving) shift right by `n` bits, returns None if `n` is not less
+    /// than 256
+    pub fn checked_shr(&self, n: u32) -> Option<Self> {
+        if n >= Self::BITS {
+            return None;
+        }
+        // BigInt shift right is rounding towards negative infinity, i.e. arithmetic shift
+        Some(BigInt256(Int256(&self.0 .0 >> n as usize)))
+    }
+
+    /// Logical (zero-filling) shift right by `n` bits of the two's complement representation,
+    /// returns None if `n` is not less than 256
+    pub fn checked_shr_zeroed(&self, n: u32) -> Option<Self> {
+        if n >= Self::BITS {
+            return None;
+        }
+        if n == 0 || self.0 .0 >= BigInt::zero() {
+            return self.checked_shr(n);
+        }
+        let unsigned = &self.0 .0 + (BigInt::from(1) << Self::BITS as usize);
+        // the highest bit is zeroed, so the result always fits
+        Self::try_from(unsigned >> n as usize).ok()
+    }
+}
+
 impl BitAnd for BigInt256 {
     type Output = Self;
 
@@ -349,4 +398,24 @@ mod tests {
         let bigint_from_bytes = BigInt256::try_from(&bytes[..]);
         assert!(bigint_from_bytes.is_err());
     }
+
+    #[test]
+    fn shifts() {
+        let b = BigInt256::from;
+        assert_eq!(
+            b(1).checked_shl(254),
+            Some(BigInt256::max_value() / b(2) + b(1))
+        );
+        assert_eq!(b(1).checked_shl(255), None);
+        assert_eq!(b(-1).checked_shl(255), Some(BigInt256::min_value()));
+        assert_eq!(b(1).checked_shl(256), None);
+        assert_eq!(b(-5).checked_shr(1), Some(b(-3)));
+        assert_eq!(b(5).checked_shr(1), Some(b(2)));
+        assert_eq!(b(-1).checked_shr(255), Some(b(-1)));
+        assert_eq!(b(-1).checked_shr_zeroed(1), Some(BigInt256::max_value()));
+        assert_eq!(b(-1).checked_shr_zeroed(255), Some(b(1)));
+        assert_eq!(b(-1).checked_shr_zeroed(0), Some(b(-1)));
+        assert_eq!(b(4).checked_shr_zeroed(2), Some(b(1)));
+        assert_eq!(b(4).checked_shr_zeroed(256), None);
+    }
 }
@@ -84,6 +84,12 @@ impl ErgoBox {
     // let's set to 121 + 1 to be safe
     pub const MAX_TOKENS_COUNT: usize = 122;
 
+    /// Maximum size of the serialized box (in bytes)
+    pub const MAX_BOX_SIZE: usize = 4096;
+
+    /// Maximum size of the serialized box guarding script (in bytes)
+    pub const MAX_SCRIPT_SIZE: usize = 4096;
+
     /// Crate new box
     pub fn new(
         value: BoxValue,
@@ -139,6 +139,7 @@ impl ErgoTree {
         r: &mut R,
         header: ErgoTreeHeader,
     ) -> Result<ParsedErgoTree, ErgoTreeError> {
+        r.set_tree_version(*header.version());
         let constants = if header.is_constant_segregation() {
             ErgoTree::sigma_parse_constants(r)?
         } else {
@@ -153,6 +154,22 @@ impl ErgoTree {
         })
     }
 
+    /// Parse the tree body of the known size, all bytes of the body should be consumed
+    fn sigma_parse_sized_body(
+        body: &[u8],
+        header: ErgoTreeHeader,
+    ) -> Result<ParsedErgoTree, ErgoTreeError> {
+        let mut r = SigmaByteReader::new(Cursor::new(body), ConstantStore::empty());
+        let parsed_tree = ErgoTree::sigma_parse_sized(&mut r, header)?;
+        let mut rest = Vec::new();
+        r.read_to_end(&mut rest)?;
+        if rest.is_empty() {
+            Ok(parsed_tree)
+        } else {
+            Err(ErgoTreeRootParsingError::NonConsumedBytes.into())
+        }
+    }
+
     fn sigma_parse_constants<R: SigmaByteRead>(
         r: &mut R,
     ) -> Result<Vec<Constant>, SigmaParsingError> {
@@ -175,22 +192,7 @@ impl ErgoTree {
     /// Creates a tree using provided header and root expression
     pub fn new(header: ErgoTreeHeader, expr: &Expr) -> Result<Self, ErgoTreeError> {
         Ok(if header.is_constant_segregation() {
-            let mut data = Vec::new();
-            let cs = ConstantStore::empty();
-            let mut w = SigmaByteWriter::new(&mut data, Some(cs));
-            expr.sigma_serialize(&mut w)?;
-            #[allow(clippy::unwrap_used)]
-            // We set constant store earlier
-            let constants = w.constant_store_mut_ref().unwrap().get_all();
-            let cursor = Cursor::new(&mut data[..]);
-            let new_cs = ConstantStore::new(constants.clone());
-            let mut sr = SigmaByteReader::new(cursor, new_cs);
-            let parsed_expr = Expr::sigma_parse(&mut sr)?;
-            ErgoTree::Parsed(ParsedErgoTree {
-                header,
-                constants,
-                root: parsed_expr,
-            })
+            Self::segregate(header, expr, Vec::new())?
         } else {
             ErgoTree::Parsed(ParsedErgoTree {
                 header,
@@ -200,9 +202,60 @@ impl ErgoTree {
         })
     }
 
+    /// Creates a constant-segregated tree where the root expression may already refer to the
+    /// given `constants` via [`crate::mir::constant::ConstantPlaceholder`] (by their index in
+    /// the list). Any other constants found in the expression are appended after them.
+    pub fn with_segregated_constants(
+        version: ErgoTreeVersion,
+        expr: &Expr,
+        constants: Vec<Constant>,
+    ) -> Result<Self, ErgoTreeError> {
+        Self::segregate(ErgoTreeHeader::with_version(version, true), expr, constants)
+    }
+
+    fn segregate(
+        header: ErgoTreeHeader,
+        expr: &Expr,
+        constants: Vec<Constant>,
+    ) -> Result<Self, ErgoTreeError> {
+        let mut data = Vec::new();
+        let cs = ConstantStore::new(constants);
+        let mut w = SigmaByteWriter::new(&mut data, Some(cs));
+        expr.sigma_serialize(&mut w)?;
+        #[allow(clippy::unwrap_used)]
+        // We set constant store earlier
+        let constants = w.constant_store_mut_ref().unwrap().get_all();
+        let cursor = Cursor::new(&mut data[..]);
+        let new_cs = ConstantStore::new(constants.clone());
+        let mut sr = SigmaByteReader::new(cursor, new_cs);
+        sr.set_tree_version(*header.version());
+        let parsed_expr = Expr::sigma_parse(&mut sr)?;
+        Ok(ErgoTree::Parsed(ParsedErgoTree {
+            header,
+            constants,
+            root: parsed_expr,
+        }))
+    }
+
     /// Reasonable limit for the number of constants allowed in the ErgoTree
     pub const MAX_CONSTANTS_COUNT: usize = 4096;
 
+    /// Returns the tree header (for the unparsed tree it's parsed from the original bytes)
+    pub fn header(&self) -> Result<ErgoTreeHeader, ErgoTreeError> {
+        match self {
+            ErgoTree::Unparsed {
+                tree_bytes,
+                error: _,
+            } => {
+                let header_byte = tree_bytes
+                    .first()
+                    .ok_or_else(|| ErgoTreeError::IoError("empty tree bytes".to_string()))?;
+                Ok(ErgoTreeHeader::new(*header_byte)?)
+            }
+            ErgoTree::Parsed(parsed) => Ok(parsed.header.clone()),
+        }
+    }
+
     /// get Expr out of ErgoTree
     pub fn proposition(&self) -> Result<Expr, ErgoTreeError> {
         let tree = self.parsed_tree()?.clone();
@@ -220,6 +273,7 @@ impl ErgoTree {
                 cursor,
                 ConstantStore::new(tree.constants),
             );
+            sr.set_tree_version(*tree.header.version());
             let parsed_expr = Expr::sigma_parse(&mut sr)?;
             Ok(parsed_expr)
         } else {
@@ -349,16 +403,13 @@ impl SigmaSerializable for ErgoTree {
             let tree_size_bytes = r.get_u32()?;
             let mut buf = vec![0u8; tree_size_bytes as usize];
             r.read_exact(buf.as_mut_slice())?;
-            let buf_copy = buf.clone();
-            let mut inner_r =
-                SigmaByteReader::new(Cursor::new(&mut buf[..]), ConstantStore::empty());
-            match ErgoTree::sigma_parse_sized(&mut inner_r, header.clone()) {
+            match ErgoTree::sigma_parse_sized_body(&buf, header.clone()) {
                 Ok(parsed_tree) => Ok(parsed_tree.into()),
                 Err(error) => {
                     let mut bytes = vec![header.serialized()];
                     #[allow(clippy::unwrap_used)]
                     bytes.put_u32(tree_size_bytes).unwrap();
-                    bytes.extend_from_slice(&buf_copy);
+                    bytes.extend_from_slice(&buf);
                     Ok(ErgoTree::Unparsed {
                         tree_bytes: bytes,
                         error,
@@ -366,6 +417,8 @@ impl SigmaSerializable for ErgoTree {
                 }
             }
         } else {
+            let outer_tree_version = r.tree_version();
+            r.set_tree_version(*header.version());
             let constants = if header.is_constant_segregation() {
                 ErgoTree::sigma_parse_constants(r)?
             } else {
@@ -373,6 +426,7 @@ impl SigmaSerializable for ErgoTree {
             };
             r.set_constant_store(ConstantStore::new(constants.clone()));
             let root = Expr::sigma_parse(r)?;
+            r.set_tree_version(outer_tree_version);
             Ok(ErgoTree::Parsed(ParsedErgoTree {
                 header,
                 constants,
@@ -398,11 +452,8 @@ impl SigmaSerializable for ErgoTree {
                     let tree_size_bytes = r.get_u32()?;
                     let mut buf = vec![0u8; tree_size_bytes as usize];
                     r.read_exact(buf.as_mut_slice())?;
-                    let mut inner_r =
-                        SigmaByteReader::new(Cursor::new(&mut buf[..]), ConstantStore::empty());
-                    Ok(wrap_in_ergotree(ErgoTree::sigma_parse_sized(
-                        &mut inner_r,
-                        header,
+                    Ok(wrap_in_ergotree(ErgoTree::sigma_parse_sized_body(
+                        &buf, header,
                     )))
                 } else {
                     Ok(wrap_in_ergotree(ErgoTree::sigma_parse_sized(
@@ -504,7 +555,12 @@ mod tests {
     use super::*;
     use crate::chain::address::AddressEncoder;
     use crate::chain::address::NetworkPrefix;
+    use crate::mir::bool_to_sigma::BoolToSigmaProp;
     use crate::mir::constant::Literal;
+    use crate::mir::property_call::PropertyCall;
+    use crate::sigma_protocol::sigma_boolean::SigmaBoolean;
+    use crate::sigma_protocol::sigma_boolean::SigmaProp;
+    use crate::types::ssigmaprop;
     use proptest::prelude::*;
 
     proptest! {
@@ -683,6 +739,60 @@ mod tests {
         assert_eq!(new_ergo_tree.get_constant(0).unwrap().unwrap(), true.into());
     }
 
+    #[test]
+    fn parse_tree_without_size_flag_v1() {
+        // version 1 without size flag
+        assert_eq!(
+            ErgoTreeHeader::new(1),
+            Err(ErgoTreeHeaderError::SizeFlagNotSet(ErgoTreeVersion::V1))
+        );
+        let bytes = [1u8, 0, 1];
+        let tree = ErgoTree::sigma_parse_bytes(&bytes).unwrap();
+        assert!(tree.parsed_tree().is_err());
+        assert!(tree.header().is_err());
+        assert_eq!(tree.sigma_serialize_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn parse_v0_tree_with_is_proven() {
+        // op codes and methods are not gated on the tree version
+        let is_proven: Expr = PropertyCall::new(
+            Expr::Const(SigmaProp::new(SigmaBoolean::TrivialProp(true)).into()),
+            ssigmaprop::IS_PROVEN_METHOD.clone(),
+        )
+        .unwrap()
+        .into();
+        let root: Expr = BoolToSigmaProp {
+            input: Box::new(is_proven),
+        }
+        .into();
+        let tree = ErgoTree::new(ErgoTreeHeader::v0(false), &root).unwrap();
+        let bytes = tree.sigma_serialize_bytes().unwrap();
+        let parsed_tree = ErgoTree::sigma_parse_bytes(&bytes).unwrap();
+        assert_eq!(parsed_tree, tree);
+    }
+
+    #[test]
+    fn parse_tree_unknown_version() {
+        let version = ErgoTreeVersion::from(5u8);
+        assert!(version > ErgoTreeVersion::MAX_SCRIPT_VERSION);
+        let tree = ErgoTree::new(
+            ErgoTreeHeader::with_version(version, false),
+            &Expr::Const(true.into()),
+        )
+        .unwrap();
+        let bytes = tree.sigma_serialize_bytes().unwrap();
+        let parsed_tree = ErgoTree::sigma_parse_bytes(&bytes).unwrap();
+        assert_eq!(parsed_tree, tree);
+        assert_eq!(parsed_tree.header().unwrap().version(), &version);
+        // header of the unparsed tree
+        let mut invalid_bytes = bytes;
+        invalid_bytes.push(0);
+        let unparsed_tree = ErgoTree::sigma_parse_bytes(&invalid_bytes).unwrap();
+        assert!(unparsed_tree.parsed_tree().is_err());
+        assert_eq!(unparsed_tree.header().unwrap().version(), &version);
+    }
+
     #[test]
     fn dex_t2tpool_parse() {
         let base16_str = "19a3030f0400040204020404040404060406058080a0f6f4acdbe01b058080a0f6f4acdbe01b050004d00f0400040005000500d81ad601b2a5730000d602e4c6a70405d603db63087201d604db6308a7d605b27203730100d606b27204730200d607b27203730300d608b27204730400d609b27203730500d60ab27204730600d60b9973078c720602d60c999973088c720502720bd60d8c720802d60e998c720702720dd60f91720e7309d6108c720a02d6117e721006d6127e720e06d613998c7209027210d6147e720d06d615730ad6167e721306d6177e720c06d6187e720b06d6199c72127218d61a9c72167218d1edededededed93c27201c2a793e4c672010405720292c17201c1a793b27203730b00b27204730c00938c7205018c720601ed938c7207018c720801938c7209018c720a019593720c730d95720f929c9c721172127e7202069c7ef07213069a9c72147e7215067e9c720e720206929c9c721472167e7202069c7ef0720e069a9c72117e7215067e9c721372020695ed720f917213730e907217a19d721972149d721a7211ed9272199c7217721492721a9c72177211";
@@ -17,7 +17,7 @@ use crate::serialization::sigma_byte_writer::SigmaByteWrite;
 ///  Bit 4 == 1 if constant segregation is used for this ErgoTree (default = 0)
 ///  (see <https://github.com/ScorexFoundation/sigmastate-interpreter/issues/264>)
 ///  Bit 3 == 1 if size of the whole tree is serialized after the header byte (default = 0)
-///  Bits 2-0 - language version (current version == 0)
+///  Bits 2-0 - language version (see [`ErgoTreeVersion`])
 ///
 ///  Currently we don't specify interpretation for the second and other bytes of the header.
 ///  We reserve the possibility to extend header by using Bit 7 == 1 and chain additional bytes as in VLQ.
@@ -51,9 +51,13 @@ impl ErgoTreeHeader {
 
     /// Parse from byte
     pub fn new(header_byte: u8) -> Result<Self, ErgoTreeHeaderError> {
-        let version = ErgoTreeVersion::parse_version(header_byte)?;
+        let version = ErgoTreeVersion::parse_version(header_byte);
         let has_size = header_byte & Self::HAS_SIZE_FLAG != 0;
         let is_constant_segregation = header_byte & Self::CONSTANT_SEGREGATION_FLAG != 0;
+        // the size flag is mandatory for version 1 and above (CheckHeaderSizeBit rule)
+        if version > ErgoTreeVersion::V0 && !has_size {
+            return Err(ErgoTreeHeaderError::SizeFlagNotSet(version));
+        }
         Ok(ErgoTreeHeader {
             version,
             is_constant_segregation,
@@ -84,10 +88,16 @@ impl ErgoTreeHeader {
 
     /// Return a header with version set to 1 (with size flag set) and constant segregation flag set to the given value
     pub fn v1(constant_segregation: bool) -> Self {
+        Self::with_version(ErgoTreeVersion::V1, constant_segregation)
+    }
+
+    /// Return a header with the given version and constant segregation flag set to the given
+    /// value (size flag is set for versions above 0)
+    pub fn with_version(version: ErgoTreeVersion, constant_segregation: bool) -> Self {
         ErgoTreeHeader {
-            version: ErgoTreeVersion::V1,
+            version,
             is_constant_segregation: constant_segregation,
-            has_size: true,
+            has_size: version > ErgoTreeVersion::V0,
         }
     }
 
@@ -108,43 +118,55 @@ impl ErgoTreeHeader {
 }
 
 /// Header parsing error
-#[derive(Error, PartialEq, Eq, Debug, Clone, From)]
+#[derive(Error, PartialEq, Eq, Debug, Clone)]
 pub enum ErgoTreeHeaderError {
-    /// Invalid version
-    #[error("Invalid version: {0}")]
-    VersionError(ErgoTreeVersionError),
+    /// Size flag is not set for the ErgoTree version above 0
+    #[error("Size flag is not set for ErgoTree version {0}")]
+    SizeFlagNotSet(ErgoTreeVersion),
     /// IO error
     #[error("IO error: {0}")]
     IoError(String),
 }
 
-/// ErgoTree version 0..=7, should fit in 3 bits
-#[derive(PartialEq, Eq, Debug, Clone)]
+/// ErgoTree version 0..=7, should fit in 3 bits.
+/// Also used for the activated script version (block version - 1) which is voted by miners.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Clone, Copy, From)]
 pub struct ErgoTreeVersion(u8);
 
 impl ErgoTreeVersion {
     /// Header mask to extract version bits.
     pub const VERSION_MASK: u8 = 0x07;
-    /// Version 0
+    /// Version 0 (v3.x protocol, AOT costing)
     pub const V0: Self = ErgoTreeVersion(0);
-    /// Version 1 (size flag is mandatory)
+    /// Version 1 (v4.x protocol, size flag is mandatory)
     pub const V1: Self = ErgoTreeVersion(1);
+    /// Version 2 (v5.x protocol, JIT costing)
+    pub const V2: Self = ErgoTreeVersion(2);
+    /// Maximum script version supported by this interpreter. Trees of the higher versions are
+    /// accepted without evaluation (soft-fork) once the higher version is activated on the network
+    pub const MAX_SCRIPT_VERSION: Self = ErgoTreeVersion::V2;
 
     /// Returns a value of the version bits from the given header byte.
-    pub fn parse_version(header_byte: u8) -> Result<Self, ErgoTreeVersionError> {
-        let version = header_byte & ErgoTreeVersion::VERSION_MASK;
-        if version <= 1 {
-            Ok(ErgoTreeVersion(version))
-        } else {
-            Err(ErgoTreeVersionError::InvalidVersion(version))
-        }
+    /// Any version is parsed, the check against the activated script version is made by the
+    /// interpreter
+    pub fn parse_version(header_byte: u8) -> Self {
+        ErgoTreeVersion(header_byte & ErgoTreeVersion::VERSION_MASK)
+    }
+
+    /// Activated script version for the given block version
+    pub fn from_block_version(block_version: u8) -> Self {
+        ErgoTreeVersion(block_version.saturating_sub(1))
+    }
+}
+
+impl From<ErgoTreeVersion> for u8 {
+    fn from(v: ErgoTreeVersion) -> Self {
+        v.0
     }
 }
 
-/// Version parsing error
-#[derive(Error, PartialEq, Eq, Debug, Clone, From)]
-pub enum ErgoTreeVersionError {
-    /// Invalid version
-    #[error("Invalid version: {0}")]
-    InvalidVersion(u8),
+impl std::fmt::Display for ErgoTreeVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
 }
@@ -30,6 +30,8 @@ pub mod coll_fold;
 pub mod coll_forall;
 /// Collection.map
 pub mod coll_map;
+/// Collection shifts and rotations
+pub mod coll_shift;
 /// Collection.size
 pub mod coll_size;
 /// Collection.slice
@@ -65,6 +67,8 @@ pub mod extract_id;
 pub mod extract_reg_as;
 /// Box.scriptBytes
 pub mod extract_script_bytes;
+/// Let-bound (possibly generic) function definition
+pub mod fun_def;
 /// User-defined function
 pub mod func_value;
 pub mod get_var;
@@ -76,6 +80,8 @@ pub mod logical_not;
 pub mod long_to_byte_array;
 /// Object method call
 pub mod method_call;
+/// Arithmetic operations modulo the secp256k1 group order
+pub mod mod_q;
 /// Multiply op for GroupElement
 pub mod multiply_group;
 /// Negation operation on numeric type.
@@ -86,6 +92,8 @@ pub mod option_get;
 pub mod option_get_or_else;
 /// Returns false if the option is None, true otherwise.
 pub mod option_is_defined;
+/// Option value constructors (Some and None)
+pub mod option_value;
 /// Logical OR op
 pub mod or;
 /// Object property call
@@ -96,6 +104,8 @@ pub mod sigma_and;
 pub mod sigma_or;
 /// Extract serialized bytes of a SigmaProp value
 pub mod sigma_prop_bytes;
+/// Converts SigmaProp value to Boolean
+pub mod sigma_prop_is_proven;
 pub mod subst_const;
 /// Perform a lookup of key in a tree
 pub mod tree_lookup;
@@ -0,0 +1,198 @@
+use std::fmt::Display;
+
+use crate::has_opcode::HasOpCode;
+use crate::serialization::op_code::OpCode;
+use crate::serialization::sigma_byte_reader::SigmaByteRead;
+use crate::serialization::sigma_byte_writer::SigmaByteWrite;
+use crate::serialization::SigmaParsingError;
+use crate::serialization::SigmaSerializable;
+use crate::serialization::SigmaSerializeResult;
+use crate::types::stype::SType;
+
+use super::expr::Expr;
+use super::expr::InvalidArgumentError;
+
+#[cfg(feature = "arbitrary")]
+use proptest_derive::Arbitrary;
+
+/// Shift and rotation operations on collections
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+#[cfg_attr(feature = "arbitrary", derive(Arbitrary))]
+pub enum CollShiftOp {
+    /// Move elements towards the head, filling the vacated tail positions with zeros
+    ShiftLeft,
+    /// Move elements towards the tail, filling the vacated head positions with the first element
+    /// (the same way as the arithmetic shift preserves the sign bit)
+    ShiftRight,
+    /// Move elements towards the tail, filling the vacated head positions with zeros
+    ShiftRightZeroed,
+    /// Cyclic move of elements towards the head
+    RotateLeft,
+    /// Cyclic move of elements towards the tail
+    RotateRight,
+}
+
+impl CollShiftOp {
+    /// Returns true if vacated positions are filled (i.e. for shifts but not rotations)
+    pub fn is_shift(&self) -> bool {
+        matches!(
+            self,
+            CollShiftOp::ShiftLeft | CollShiftOp::ShiftRight | CollShiftOp::ShiftRightZeroed
+        )
+    }
+}
+
+impl From<CollShiftOp> for OpCode {
+    fn from(op: CollShiftOp) -> Self {
+        match op {
+            CollShiftOp::ShiftLeft => OpCode::COLL_SHIFT_LEFT,
+            CollShiftOp::ShiftRight => OpCode::COLL_SHIFT_RIGHT,
+            CollShiftOp::ShiftRightZeroed => OpCode::COLL_SHIFT_RIGHT_ZEROED,
+            CollShiftOp::RotateLeft => OpCode::COLL_ROTATE_LEFT,
+            CollShiftOp::RotateRight => OpCode::COLL_ROTATE_RIGHT,
+        }
+    }
+}
+
+impl Display for CollShiftOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CollShiftOp::ShiftLeft => write!(f, "shiftLeft"),
+            CollShiftOp::ShiftRight => write!(f, "shiftRight"),
+            CollShiftOp::ShiftRightZeroed => write!(f, "shiftRightZeroed"),
+            CollShiftOp::RotateLeft => write!(f, "rotateLeft"),
+            CollShiftOp::RotateRight => write!(f, "rotateRight"),
+        }
+    }
+}
+
+/// Shift or rotate the collection elements by the given number of positions
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct CollShift {
+    /// Operation kind
+    pub op: CollShiftOp,
+    /// Collection (of numeric type elements for shifts)
+    pub input: Box<Expr>,
+    /// Number of positions to shift (non-negative `SInt`)
+    pub n: Box<Expr>,
+}
+
+impl CollShift {
+    /// Create new object, returns an error if any of the requirements failed
+    pub fn new(op: CollShiftOp, input: Expr, n: Expr) -> Result<Self, InvalidArgumentError> {
+        match input.post_eval_tpe() {
+            SType::SColl(elem_tpe) => {
+                if op.is_shift() && !elem_tpe.is_numeric() {
+                    return Err(InvalidArgumentError(format!(
+                        "CollShift({0}): expected input element type to be numeric, got {1:?}",
+                        op, elem_tpe
+                    )));
+                }
+            }
+            _ => {
+                return Err(InvalidArgumentError(format!(
+                    "CollShift({0}): expected input to be SColl, got {1:?}",
+                    op,
+                    input.tpe()
+                )))
+            }
+        };
+        if n.post_eval_tpe() != SType::SInt {
+            return Err(InvalidArgumentError(format!(
+                "CollShift({0}): expected n type to be SInt, got {1:?}",
+                op,
+                n.tpe()
+            )));
+        }
+        Ok(Self {
+            op,
+            input: input.into(),
+            n: n.into(),
+        })
+    }
+
+    /// Type
+    pub fn tpe(&self) -> SType {
+        self.input.tpe()
+    }
+}
+
+impl HasOpCode for CollShift {
+    fn op_code(&self) -> OpCode {
+        self.op.into()
+    }
+}
+
+pub(crate) fn coll_shift_sigma_serialize<W: SigmaByteWrite>(
+    coll_shift: &CollShift,
+    w: &mut W,
+) -> SigmaSerializeResult {
+    coll_shift.input.sigma_serialize(w)?;
+    coll_shift.n.sigma_serialize(w)
+}
+
+pub(crate) fn coll_shift_sigma_parse<R: SigmaByteRead>(
+    op: CollShiftOp,
+    r: &mut R,
+) -> Result<CollShift, SigmaParsingError> {
+    let input = Expr::sigma_parse(r)?;
+    let n = Expr::sigma_parse(r)?;
+    Ok(CollShift::new(op, input, n)?)
+}
+
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::unwrap_used)]
+mod arbitrary {
+    use super::*;
+    use crate::mir::expr::arbitrary::ArbExprParams;
+    use proptest::prelude::*;
+
+    impl Arbitrary for CollShift {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            (
+                any::<CollShiftOp>(),
+                any_with::<Expr>(ArbExprParams {
+                    tpe: SType::SColl(SType::SInt.into()),
+                    depth: 1,
+                }),
+                any_with::<Expr>(ArbExprParams {
+                    tpe: SType::SInt,
+                    depth: 0,
+                }),
+            )
+                .prop_map(|(op, input, n)| Self::new(op, input, n).unwrap())
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::mir::expr::Expr;
+    use crate::serialization::sigma_serialize_roundtrip;
+    use proptest::prelude::*;
+
+    proptest! {
+
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn ser_roundtrip(v in any::<CollShift>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+    }
+
+    #[test]
+    fn shift_non_numeric_coll() {
+        let input = Expr::Const(vec![true, false].into());
+        assert!(CollShift::new(CollShiftOp::ShiftLeft, input.clone(), 1i32.into()).is_err());
+        assert!(CollShift::new(CollShiftOp::RotateLeft, input, 1i32.into()).is_ok());
+    }
+}
@@ -25,6 +25,7 @@ use super::coll_filter::Filter;
 use super::coll_fold::Fold;
 use super::coll_forall::ForAll;
 use super::coll_map::Map;
+use super::coll_shift::CollShift;
 use super::coll_size::SizeOf;
 use super::coll_slice::Slice;
 use super::collection::Collection;
@@ -44,23 +45,30 @@ use super::extract_creation_info::ExtractCreationInfo;
 use super::extract_id::ExtractId;
 use super::extract_reg_as::ExtractRegisterAs;
 use super::extract_script_bytes::ExtractScriptBytes;
+use super::fun_def::FunDef;
 use super::func_value::FuncValue;
 use super::global_vars::GlobalVars;
 use super::if_op::If;
 use super::logical_not::LogicalNot;
 use super::long_to_byte_array::LongToByteArray;
 use super::method_call::MethodCall;
+use super::mod_q::MinusModQ;
+use super::mod_q::ModQ;
+use super::mod_q::PlusModQ;
 use super::multiply_group::MultiplyGroup;
 use super::negation::Negation;
 use super::option_get::OptionGet;
 use super::option_get_or_else::OptionGetOrElse;
 use super::option_is_defined::OptionIsDefined;
+use super::option_value::NoneValue;
+use super::option_value::SomeValue;
 use super::or::Or;
 use super::property_call::PropertyCall;
 use super::select_field::SelectField;
 use super::sigma_and::SigmaAnd;
 use super::sigma_or::SigmaOr;
 use super::sigma_prop_bytes::SigmaPropBytes;
+use super::sigma_prop_is_proven::SigmaPropIsProven;
 use super::subst_const::SubstConstants;
 use super::tree_lookup::TreeLookup;
 use super::tuple::Tuple;
@@ -127,6 +135,8 @@ pub enum Expr {
     BlockValue(Spanned<BlockValue>),
     /// let-bound expression
     ValDef(Spanned<ValDef>),
+    /// let-bound (possibly generic) function
+    FunDef(Spanned<FunDef>),
     /// Reference to ValDef
     ValUse(ValUse),
     /// If, non-lazy - evaluate both branches
@@ -153,6 +163,10 @@ pub enum Expr {
     OptionIsDefined(Spanned<OptionIsDefined>),
     /// Returns the option's value if the option is nonempty, otherwise return the result of evaluating `default`.
     OptionGetOrElse(Spanned<OptionGetOrElse>),
+    /// Option with a value
+    SomeValue(SomeValue),
+    /// Empty option
+    NoneValue(NoneValue),
     /// Box monetary value
     ExtractAmount(ExtractAmount),
     /// Extract register's value (box.RX properties)
@@ -174,6 +188,8 @@ pub enum Expr {
     SizeOf(SizeOf),
     /// Collection slice
     Slice(Spanned<Slice>),
+    /// Collection shift or rotation
+    CollShift(Spanned<CollShift>),
     /// Collection fold op
     Fold(Spanned<Fold>),
     /// Collection map op
@@ -186,6 +202,9 @@ pub enum Expr {
     ForAll(Spanned<ForAll>),
     /// Tuple field access
     SelectField(Spanned<SelectField>),
+    /// Tuple field access in the short form (`SELECT_1..SELECT_5` op codes)
+    #[from(ignore)]
+    SelectFieldShort(SelectField),
     /// Bool to SigmaProp
     BoolToSigmaProp(BoolToSigmaProp),
     /// Upcast numeric value
@@ -198,6 +217,8 @@ pub enum Expr {
     CreateProveDhTuple(CreateProveDhTuple),
     /// Extract serialized bytes of a SigmaProp value
     SigmaPropBytes(SigmaPropBytes),
+    /// Convert SigmaProp to Boolean (true if the proposition is proven)
+    SigmaPropIsProven(SigmaPropIsProven),
     /// Decode byte array to EC point
     DecodePoint(DecodePoint),
     /// AND conjunction for sigma propositions
@@ -216,6 +237,12 @@ pub enum Expr {
     DeserializeContext(DeserializeContext),
     /// MultiplyGroup op for GroupElement
     MultiplyGroup(MultiplyGroup),
+    /// BigInt modulo the secp256k1 group order
+    ModQ(ModQ),
+    /// Addition of BigInt values modulo the secp256k1 group order
+    PlusModQ(PlusModQ),
+    /// Subtraction of BigInt values modulo the secp256k1 group order
+    MinusModQ(MinusModQ),
     /// Exponentiate op for GroupElement
     Exponentiate(Exponentiate),
     /// XOR for collection of booleans
@@ -249,12 +276,14 @@ impl Expr {
             Expr::PropertyCall(v) => v.expr().tpe(),
             Expr::BlockValue(v) => v.expr().tpe(),
             Expr::ValDef(v) => v.expr().tpe(),
+            Expr::FunDef(v) => v.expr().tpe(),
             Expr::ValUse(v) => v.tpe.clone(),
             Expr::BinOp(v) => v.expr().tpe(),
             Expr::OptionGet(v) => v.expr().tpe(),
             Expr::ExtractRegisterAs(v) => v.expr().tpe(),
             Expr::Fold(v) => v.expr().tpe(),
             Expr::SelectField(v) => v.expr().tpe(),
+            Expr::SelectFieldShort(v) => v.tpe(),
             Expr::ExtractAmount(v) => v.tpe(),
             Expr::And(v) => v.expr().tpe(),
             Expr::Or(v) => v.expr().tpe(),
@@ -271,14 +300,18 @@ impl Expr {
             Expr::ExtractScriptBytes(v) => v.tpe(),
             Expr::SizeOf(v) => v.tpe(),
             Expr::Slice(v) => v.expr().tpe(),
+            Expr::CollShift(v) => v.expr().tpe(),
             Expr::CreateProveDlog(v) => v.tpe(),
             Expr::CreateProveDhTuple(v) => v.tpe(),
             Expr::ExtractCreationInfo(v) => v.tpe(),
             Expr::Exists(v) => v.expr().tpe(),
             Expr::ExtractId(v) => v.tpe(),
             Expr::SigmaPropBytes(v) => v.tpe(),
+            Expr::SigmaPropIsProven(v) => v.tpe(),
             Expr::OptionIsDefined(v) => v.expr().tpe(),
             Expr::OptionGetOrElse(v) => v.expr().tpe(),
+            Expr::SomeValue(v) => v.tpe(),
+            Expr::NoneValue(v) => v.tpe(),
             Expr::Negation(v) => v.expr().tpe(),
             Expr::BitInversion(v) => v.tpe(),
             Expr::ForAll(v) => v.expr().tpe(),
@@ -290,6 +323,9 @@ impl Expr {
             Expr::DeserializeContext(v) => v.tpe(),
             Expr::GetVar(v) => v.expr().tpe(),
             Expr::MultiplyGroup(v) => v.tpe(),
+            Expr::ModQ(v) => v.tpe(),
+            Expr::PlusModQ(v) => v.tpe(),
+            Expr::MinusModQ(v) => v.tpe(),
             Expr::Exponentiate(v) => v.tpe(),
             Expr::XorOf(v) => v.tpe(),
             Expr::ExtractBytes(v) => v.tpe(),
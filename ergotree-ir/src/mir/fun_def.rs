@@ -0,0 +1,135 @@
+use crate::serialization::op_code::OpCode;
+use crate::serialization::sigma_byte_reader::SigmaByteRead;
+use crate::serialization::sigma_byte_writer::SigmaByteWrite;
+use crate::serialization::SigmaParsingError;
+use crate::serialization::SigmaSerializable;
+use crate::serialization::SigmaSerializationError;
+use crate::serialization::SigmaSerializeResult;
+use crate::types::stype::SType;
+use crate::types::stype_param::STypeVar;
+
+use super::expr::Expr;
+use super::val_def::ValId;
+
+use crate::has_opcode::HasStaticOpCode;
+
+/** IR node for let-bound (possibly generic) functions `def f[T](...) = rhs`.
+ * The same as ValDef but with the (optional) list of type arguments.
+ * @param id unique identifier of the variable in the current scope. */
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct FunDef {
+    /// Variable id
+    pub id: ValId,
+    /// Type arguments
+    pub tpe_args: Vec<STypeVar>,
+    /// Expr, bound to the variable
+    pub rhs: Box<Expr>,
+}
+
+impl FunDef {
+    /// Type
+    pub fn tpe(&self) -> SType {
+        self.rhs.tpe()
+    }
+}
+
+impl HasStaticOpCode for FunDef {
+    const OP_CODE: OpCode = OpCode::FUN_DEF;
+}
+
+impl SigmaSerializable for FunDef {
+    fn sigma_serialize<W: SigmaByteWrite>(&self, w: &mut W) -> SigmaSerializeResult {
+        self.id.sigma_serialize(w)?;
+        let tpe_args_count = u8::try_from(self.tpe_args.len()).map_err(|_| {
+            SigmaSerializationError::NotSupported(format!(
+                "FunDef: too many type arguments ({}), at most 255 are supported",
+                self.tpe_args.len()
+            ))
+        })?;
+        w.put_u8(tpe_args_count)?;
+        self.tpe_args
+            .iter()
+            .try_for_each(|tpe_arg| SType::STypeVar(tpe_arg.clone()).sigma_serialize(w))?;
+        self.rhs.sigma_serialize(w)
+    }
+
+    fn sigma_parse<R: SigmaByteRead>(r: &mut R) -> Result<Self, SigmaParsingError> {
+        let id = ValId::sigma_parse(r)?;
+        let tpe_args_count = r.get_u8()?;
+        let mut tpe_args = Vec::with_capacity(tpe_args_count as usize);
+        for _ in 0..tpe_args_count {
+            match SType::sigma_parse(r)? {
+                SType::STypeVar(tpe_arg) => tpe_args.push(tpe_arg),
+                tpe => {
+                    return Err(SigmaParsingError::Misc(format!(
+                        "FunDef: expected type argument to be STypeVar, got {0:?}",
+                        tpe
+                    )))
+                }
+            }
+        }
+        let rhs = Expr::sigma_parse(r)?;
+        r.val_def_type_store().insert(id, rhs.tpe());
+        Ok(FunDef {
+            id,
+            tpe_args,
+            rhs: Box::new(rhs),
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::panic)]
+mod tests {
+    use crate::serialization::sigma_serialize_roundtrip;
+
+    use super::*;
+
+    use proptest::prelude::*;
+
+    proptest! {
+
+        #[test]
+        fn ser_roundtrip(id in any::<ValId>(), rhs in any::<Expr>()) {
+            let e = Expr::FunDef(
+                FunDef {
+                    id,
+                    tpe_args: vec![STypeVar::t(), STypeVar::iv()],
+                    rhs: rhs.into(),
+                }
+                .into(),
+            );
+            prop_assert_eq![sigma_serialize_roundtrip(&e), e];
+        }
+    }
+
+    #[test]
+    fn ser_roundtrip_no_tpe_args() {
+        let e = Expr::FunDef(
+            FunDef {
+                id: ValId(1),
+                tpe_args: vec![],
+                rhs: Box::new(1i32.into()),
+            }
+            .into(),
+        );
+        assert_eq![sigma_serialize_roundtrip(&e), e];
+    }
+
+    #[test]
+    fn ser_too_many_tpe_args() {
+        let e = Expr::FunDef(
+            FunDef {
+                id: ValId(1),
+                tpe_args: vec![STypeVar::t(); 256],
+                rhs: Box::new(1i32.into()),
+            }
+            .into(),
+        );
+        assert!(matches!(
+            e.sigma_serialize_bytes(),
+            Err(SigmaSerializationError::NotSupported(_))
+        ));
+    }
+}
@@ -21,6 +21,8 @@ pub enum GlobalVars {
     MinerPubKey,
     /// GroupElement (EcPoint) generator
     GroupGenerator,
+    /// Authenticated dynamic dictionary digest representing Utxo state before current state
+    LastBlockUtxoRootHash,
 }
 
 impl GlobalVars {
@@ -33,6 +35,7 @@ impl GlobalVars {
             GlobalVars::SelfBox => SType::SBox,
             GlobalVars::MinerPubKey => SType::SColl(Box::new(SType::SByte)),
             GlobalVars::GroupGenerator => SType::SGroupElement,
+            GlobalVars::LastBlockUtxoRootHash => SType::SAvlTree,
         }
     }
 }
@@ -47,6 +50,7 @@ impl HasOpCode for GlobalVars {
             GlobalVars::Height => OpCode::HEIGHT,
             GlobalVars::MinerPubKey => OpCode::MINER_PUBKEY,
             GlobalVars::GroupGenerator => OpCode::GROUP_GENERATOR,
+            GlobalVars::LastBlockUtxoRootHash => OpCode::LAST_BLOCK_UTXO_ROOT_HASH,
         }
     }
 }
@@ -60,6 +64,7 @@ impl Display for GlobalVars {
             GlobalVars::Height => write!(f, "HEIGHT"),
             GlobalVars::MinerPubKey => write!(f, "MINER_PUBKEY"),
             GlobalVars::GroupGenerator => write!(f, "GROUP_GENERATOR"),
+            GlobalVars::LastBlockUtxoRootHash => write!(f, "LastBlockUtxoRootHash"),
         }
     }
 }
@@ -82,7 +87,8 @@ mod tests {
                 Just(Height),
                 Just(SelfBox),
                 Just(MinerPubKey),
-                Just(GroupGenerator)
+                Just(GroupGenerator),
+                Just(LastBlockUtxoRootHash)
             ]
             .boxed()
         }
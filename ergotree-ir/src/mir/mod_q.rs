@@ -0,0 +1,228 @@
+use crate::serialization::op_code::OpCode;
+use crate::serialization::sigma_byte_reader::SigmaByteRead;
+use crate::serialization::sigma_byte_writer::SigmaByteWrite;
+use crate::serialization::SigmaParsingError;
+use crate::serialization::SigmaSerializable;
+use crate::serialization::SigmaSerializeResult;
+use crate::types::stype::SType;
+
+use super::expr::Expr;
+use super::expr::InvalidArgumentError;
+use super::unary_op::OneArgOp;
+use super::unary_op::OneArgOpTryBuild;
+use crate::has_opcode::HasStaticOpCode;
+
+/// Returns a BigInt value modulo the secp256k1 group order (q)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct ModQ {
+    /// Input value of SBigInt type
+    pub input: Box<Expr>,
+}
+
+impl ModQ {
+    /// Type
+    pub fn tpe(&self) -> SType {
+        SType::SBigInt
+    }
+}
+
+impl HasStaticOpCode for ModQ {
+    const OP_CODE: OpCode = OpCode::MOD_Q;
+}
+
+impl OneArgOp for ModQ {
+    fn input(&self) -> &Expr {
+        &self.input
+    }
+}
+
+impl OneArgOpTryBuild for ModQ {
+    fn try_build(input: Expr) -> Result<Self, InvalidArgumentError> {
+        input.check_post_eval_tpe(&SType::SBigInt)?;
+        Ok(Self {
+            input: input.into(),
+        })
+    }
+}
+
+fn check_mod_q_args(op_name: &str, left: &Expr, right: &Expr) -> Result<(), InvalidArgumentError> {
+    match (left.post_eval_tpe(), right.post_eval_tpe()) {
+        (SType::SBigInt, SType::SBigInt) => Ok(()),
+        (_, _) => Err(InvalidArgumentError(format!(
+            "{0} Expected: (SBigInt, SBigInt), Actual: {1:?}",
+            op_name,
+            (left.tpe(), right.tpe())
+        ))),
+    }
+}
+
+/// Adds two BigInt values modulo the secp256k1 group order (q)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct PlusModQ {
+    /// BigInt
+    pub left: Box<Expr>,
+    /// BigInt
+    pub right: Box<Expr>,
+}
+
+impl PlusModQ {
+    /// Create new object, returns an error if any of the requirements failed
+    pub fn new(left: Expr, right: Expr) -> Result<Self, InvalidArgumentError> {
+        check_mod_q_args("PlusModQ", &left, &right)?;
+        Ok(PlusModQ {
+            left: left.into(),
+            right: right.into(),
+        })
+    }
+
+    /// Type
+    pub fn tpe(&self) -> SType {
+        SType::SBigInt
+    }
+}
+
+impl HasStaticOpCode for PlusModQ {
+    const OP_CODE: OpCode = OpCode::PLUS_MOD_Q;
+}
+
+impl SigmaSerializable for PlusModQ {
+    fn sigma_serialize<W: SigmaByteWrite>(&self, w: &mut W) -> SigmaSerializeResult {
+        self.left.sigma_serialize(w)?;
+        self.right.sigma_serialize(w)
+    }
+
+    fn sigma_parse<R: SigmaByteRead>(r: &mut R) -> Result<Self, SigmaParsingError> {
+        let left = Expr::sigma_parse(r)?;
+        let right = Expr::sigma_parse(r)?;
+        Ok(PlusModQ::new(left, right)?)
+    }
+}
+
+/// Subtracts two BigInt values modulo the secp256k1 group order (q)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct MinusModQ {
+    /// BigInt
+    pub left: Box<Expr>,
+    /// BigInt
+    pub right: Box<Expr>,
+}
+
+impl MinusModQ {
+    /// Create new object, returns an error if any of the requirements failed
+    pub fn new(left: Expr, right: Expr) -> Result<Self, InvalidArgumentError> {
+        check_mod_q_args("MinusModQ", &left, &right)?;
+        Ok(MinusModQ {
+            left: left.into(),
+            right: right.into(),
+        })
+    }
+
+    /// Type
+    pub fn tpe(&self) -> SType {
+        SType::SBigInt
+    }
+}
+
+impl HasStaticOpCode for MinusModQ {
+    const OP_CODE: OpCode = OpCode::MINUS_MOD_Q;
+}
+
+impl SigmaSerializable for MinusModQ {
+    fn sigma_serialize<W: SigmaByteWrite>(&self, w: &mut W) -> SigmaSerializeResult {
+        self.left.sigma_serialize(w)?;
+        self.right.sigma_serialize(w)
+    }
+
+    fn sigma_parse<R: SigmaByteRead>(r: &mut R) -> Result<Self, SigmaParsingError> {
+        let left = Expr::sigma_parse(r)?;
+        let right = Expr::sigma_parse(r)?;
+        Ok(MinusModQ::new(left, right)?)
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::unwrap_used)]
+mod arbitrary {
+    use super::*;
+    use crate::mir::expr::arbitrary::ArbExprParams;
+    use proptest::prelude::*;
+
+    fn arb_bigint_expr() -> impl Strategy<Value = Expr> {
+        any_with::<Expr>(ArbExprParams {
+            tpe: SType::SBigInt,
+            depth: 0,
+        })
+    }
+
+    impl Arbitrary for ModQ {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            arb_bigint_expr()
+                .prop_map(|input| ModQ::try_build(input).unwrap())
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for PlusModQ {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            (arb_bigint_expr(), arb_bigint_expr())
+                .prop_map(|(left, right)| PlusModQ::new(left, right).unwrap())
+                .boxed()
+        }
+    }
+
+    impl Arbitrary for MinusModQ {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            (arb_bigint_expr(), arb_bigint_expr())
+                .prop_map(|(left, right)| MinusModQ::new(left, right).unwrap())
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::mir::expr::Expr;
+    use crate::serialization::sigma_serialize_roundtrip;
+    use proptest::prelude::*;
+
+    proptest! {
+
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn ser_roundtrip_mod_q(v in any::<ModQ>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+
+        #[test]
+        fn ser_roundtrip_plus_mod_q(v in any::<PlusModQ>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+
+        #[test]
+        fn ser_roundtrip_minus_mod_q(v in any::<MinusModQ>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+    }
+
+    #[test]
+    fn non_bigint_args() {
+        assert!(ModQ::try_build(1i64.into()).is_err());
+        assert!(PlusModQ::new(1i64.into(), 1i64.into()).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use crate::serialization::op_code::OpCode;
+use crate::serialization::sigma_byte_reader::SigmaByteRead;
+use crate::serialization::sigma_byte_writer::SigmaByteWrite;
+use crate::serialization::SigmaParsingError;
+use crate::serialization::SigmaSerializable;
+use crate::serialization::SigmaSerializeResult;
+use crate::types::stype::SType;
+
+use super::expr::Expr;
+use crate::has_opcode::HasStaticOpCode;
+
+/// Option with the given value (`Some(input)`)
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct SomeValue {
+    /// Option's value
+    pub input: Box<Expr>,
+}
+
+impl SomeValue {
+    /// Type
+    pub fn tpe(&self) -> SType {
+        SType::SOption(self.input.tpe().into())
+    }
+}
+
+impl HasStaticOpCode for SomeValue {
+    const OP_CODE: OpCode = OpCode::SOME_VALUE;
+}
+
+impl SigmaSerializable for SomeValue {
+    fn sigma_serialize<W: SigmaByteWrite>(&self, w: &mut W) -> SigmaSerializeResult {
+        self.input.sigma_serialize(w)
+    }
+
+    fn sigma_parse<R: SigmaByteRead>(r: &mut R) -> Result<Self, SigmaParsingError> {
+        let input = Expr::sigma_parse(r)?;
+        Ok(SomeValue {
+            input: input.into(),
+        })
+    }
+}
+
+/// Empty option (`None`) of the given element type
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct NoneValue {
+    /// Option's element type
+    pub elem_tpe: SType,
+}
+
+impl NoneValue {
+    /// Type
+    pub fn tpe(&self) -> SType {
+        SType::SOption(self.elem_tpe.clone().into())
+    }
+}
+
+impl HasStaticOpCode for NoneValue {
+    const OP_CODE: OpCode = OpCode::NONE_VALUE;
+}
+
+impl SigmaSerializable for NoneValue {
+    fn sigma_serialize<W: SigmaByteWrite>(&self, w: &mut W) -> SigmaSerializeResult {
+        self.elem_tpe.sigma_serialize(w)
+    }
+
+    fn sigma_parse<R: SigmaByteRead>(r: &mut R) -> Result<Self, SigmaParsingError> {
+        let elem_tpe = SType::sigma_parse(r)?;
+        Ok(NoneValue { elem_tpe })
+    }
+}
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary {
+    use super::*;
+    use crate::mir::expr::arbitrary::ArbExprParams;
+    use proptest::prelude::*;
+
+    impl Arbitrary for SomeValue {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            any_with::<Expr>(ArbExprParams {
+                tpe: SType::SInt,
+                depth: 1,
+            })
+            .prop_map(|input| SomeValue {
+                input: input.into(),
+            })
+            .boxed()
+        }
+    }
+
+    impl Arbitrary for NoneValue {
+        type Strategy = BoxedStrategy<Self>;
+        type Parameters = ();
+
+        fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+            any::<SType>()
+                .prop_map(|elem_tpe| NoneValue { elem_tpe })
+                .boxed()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "arbitrary")]
+#[allow(clippy::panic)]
+mod tests {
+    use super::*;
+    use crate::serialization::sigma_serialize_roundtrip;
+    use proptest::prelude::*;
+
+    proptest! {
+
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn ser_roundtrip_some(v in any::<SomeValue>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+
+        #[test]
+        fn ser_roundtrip_none(v in any::<NoneValue>()) {
+            let expr: Expr = v.into();
+            prop_assert_eq![sigma_serialize_roundtrip(&expr), expr];
+        }
+    }
+}
@@ -104,6 +104,18 @@ impl SelectField {
     pub fn tpe(&self) -> SType {
         self.field_tpe.clone()
     }
+
+    /// Op code of the short form (`SELECT_1..SELECT_5`) if there is one for the field index
+    pub fn short_form_op_code(&self) -> Option<OpCode> {
+        match self.field_index.0 {
+            1 => Some(OpCode::SELECT_1),
+            2 => Some(OpCode::SELECT_2),
+            3 => Some(OpCode::SELECT_3),
+            4 => Some(OpCode::SELECT_4),
+            5 => Some(OpCode::SELECT_5),
+            _ => None,
+        }
+    }
 }
 
 impl SigmaSerializable for SelectField {
@@ -119,6 +131,37 @@ impl SigmaSerializable for SelectField {
     }
 }
 
+/// Serialize in the short form (op code `SELECT_N` followed by the input) if there is one for
+/// the field index, otherwise falls back to the `SELECT_FIELD` form
+pub(crate) fn select_field_short_sigma_serialize<W: SigmaByteWrite>(
+    select_field: &SelectField,
+    w: &mut W,
+) -> SigmaSerializeResult {
+    match select_field.short_form_op_code() {
+        Some(op_code) => {
+            op_code.sigma_serialize(w)?;
+            select_field.input.sigma_serialize(w)
+        }
+        None => {
+            SelectField::OP_CODE.sigma_serialize(w)?;
+            select_field.sigma_serialize(w)
+        }
+    }
+}
+
+/// Parse the short form (`SELECT_N` op code is already read) of the selection of the field
+/// with the given 1-based index
+pub(crate) fn select_field_short_sigma_parse<R: SigmaByteRead>(
+    field_index: u8,
+    r: &mut R,
+) -> Result<SelectField, SigmaParsingError> {
+    let input = Expr::sigma_parse(r)?;
+    let field_index = TupleFieldIndex::try_from(field_index).map_err(|_| {
+        SigmaParsingError::ValueOutOfBounds(format!("invalid tuple field index: {0}", field_index))
+    })?;
+    Ok(SelectField::new(input, field_index)?)
+}
+
 #[cfg(test)]
 #[cfg(feature = "arbitrary")]
 #[allow(clippy::unwrap_used)]
@@ -136,4 +179,17 @@ mod tests {
             .into();
         assert_eq![sigma_serialize_roundtrip(&e), e];
     }
+
+    #[test]
+    fn ser_roundtrip_short_form() {
+        let input = Expr::Const((1i64, true, 2i32).into());
+        for i in 1..=3u8 {
+            let e = Expr::SelectFieldShort(
+                SelectField::new(input.clone(), i.try_into().unwrap()).unwrap(),
+            );
+            let bytes = e.sigma_serialize_bytes().unwrap();
+            assert_eq!(bytes[0], OpCode::SELECT_1.value() + i - 1);
+            assert_eq![sigma_serialize_roundtrip(&e), e];
+        }
+    }
 }
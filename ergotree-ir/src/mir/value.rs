@@ -122,6 +122,19 @@ where
             } => v.clone(),
         }
     }
+
+    /// Number of items in the collection
+    pub fn len(&self) -> usize {
+        match self {
+            CollKind::NativeColl(NativeColl::CollByte(coll_byte)) => coll_byte.len(),
+            CollKind::WrappedColl { items, .. } => items.len(),
+        }
+    }
+
+    /// Returns true if the collection has no items
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// Lambda
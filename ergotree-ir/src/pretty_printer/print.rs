@@ -18,6 +18,7 @@ use crate::mir::coll_filter::Filter;
 use crate::mir::coll_fold::Fold;
 use crate::mir::coll_forall::ForAll;
 use crate::mir::coll_map::Map;
+use crate::mir::coll_shift::CollShift;
 use crate::mir::coll_size::SizeOf;
 use crate::mir::coll_slice::Slice;
 use crate::mir::collection::Collection;
@@ -38,6 +39,7 @@ use crate::mir::extract_creation_info::ExtractCreationInfo;
 use crate::mir::extract_id::ExtractId;
 use crate::mir::extract_reg_as::ExtractRegisterAs;
 use crate::mir::extract_script_bytes::ExtractScriptBytes;
+use crate::mir::fun_def::FunDef;
 use crate::mir::func_value::FuncValue;
 use crate::mir::get_var::GetVar;
 use crate::mir::global_vars::GlobalVars;
@@ -45,17 +47,23 @@ use crate::mir::if_op::If;
 use crate::mir::logical_not::LogicalNot;
 use crate::mir::long_to_byte_array::LongToByteArray;
 use crate::mir::method_call::MethodCall;
+use crate::mir::mod_q::MinusModQ;
+use crate::mir::mod_q::ModQ;
+use crate::mir::mod_q::PlusModQ;
 use crate::mir::multiply_group::MultiplyGroup;
 use crate::mir::negation::Negation;
 use crate::mir::option_get::OptionGet;
 use crate::mir::option_get_or_else::OptionGetOrElse;
 use crate::mir::option_is_defined::OptionIsDefined;
+use crate::mir::option_value::NoneValue;
+use crate::mir::option_value::SomeValue;
 use crate::mir::or::Or;
 use crate::mir::property_call::PropertyCall;
 use crate::mir::select_field::SelectField;
 use crate::mir::sigma_and::SigmaAnd;
 use crate::mir::sigma_or::SigmaOr;
 use crate::mir::sigma_prop_bytes::SigmaPropBytes;
+use crate::mir::sigma_prop_is_proven::SigmaPropIsProven;
 use crate::mir::subst_const::SubstConstants;
 use crate::mir::tree_lookup::TreeLookup;
 use crate::mir::tuple::Tuple;
@@ -102,6 +110,7 @@ impl Print for Expr {
             Expr::Append(v) => v.expr().print(w),
             Expr::BlockValue(v) => v.expr().print(w),
             Expr::ValDef(v) => v.expr().print(w),
+            Expr::FunDef(v) => v.expr().print(w),
             Expr::ValUse(v) => v.print(w),
             Expr::Const(v) => v.print(w),
             Expr::BinOp(v) => v.expr().print(w),
@@ -139,6 +148,8 @@ impl Print for Expr {
             Expr::OptionGet(v) => v.expr().print(w),
             Expr::OptionIsDefined(v) => v.expr().print(w),
             Expr::OptionGetOrElse(v) => v.expr().print(w),
+            Expr::SomeValue(v) => v.print(w),
+            Expr::NoneValue(v) => v.print(w),
             Expr::ExtractAmount(v) => v.print(w),
             Expr::ExtractRegisterAs(v) => v.expr().print(w),
             Expr::ExtractBytes(v) => v.print(w),
@@ -148,18 +159,24 @@ impl Print for Expr {
             Expr::ExtractId(v) => v.print(w),
             Expr::SizeOf(v) => v.print(w),
             Expr::Slice(v) => v.expr().print(w),
+            Expr::CollShift(v) => v.expr().print(w),
             Expr::Fold(v) => v.expr().print(w),
             Expr::Map(v) => v.expr().print(w),
             Expr::Filter(v) => v.expr().print(w),
             Expr::Exists(v) => v.expr().print(w),
             Expr::ForAll(v) => v.expr().print(w),
             Expr::SelectField(v) => v.expr().print(w),
+            Expr::SelectFieldShort(v) => match v.print(w)? {
+                Expr::SelectField(printed) => Ok(Expr::SelectFieldShort(printed.expr)),
+                printed => Ok(printed),
+            },
             Expr::BoolToSigmaProp(v) => v.print(w),
             Expr::Upcast(v) => v.print(w),
             Expr::Downcast(v) => v.print(w),
             Expr::CreateProveDlog(v) => v.print(w),
             Expr::CreateProveDhTuple(v) => v.print(w),
             Expr::SigmaPropBytes(v) => v.print(w),
+            Expr::SigmaPropIsProven(v) => v.print(w),
             Expr::DecodePoint(v) => v.print(w),
             Expr::SigmaAnd(v) => v.print(w),
             Expr::SigmaOr(v) => v.print(w),
@@ -167,6 +184,9 @@ impl Print for Expr {
             Expr::DeserializeRegister(v) => v.print(w),
             Expr::DeserializeContext(v) => v.print(w),
             Expr::MultiplyGroup(v) => v.print(w),
+            Expr::ModQ(v) => v.print(w),
+            Expr::PlusModQ(v) => v.print(w),
+            Expr::MinusModQ(v) => v.print(w),
             Expr::Exponentiate(v) => v.print(w),
             Expr::XorOf(v) => v.print(w),
             Expr::TreeLookup(v) => v.expr().print(w),
@@ -222,6 +242,29 @@ impl Print for ValDef {
     }
 }
 
+impl Print for FunDef {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let offset = w.current_pos();
+        write!(w, "def v{}", self.id)?;
+        if !self.tpe_args.is_empty() {
+            let tpe_args: Vec<String> = self.tpe_args.iter().map(|t| t.as_string()).collect();
+            write!(w, "[{}]", tpe_args.join(", "))?;
+        }
+        write!(w, " = ")?;
+        let rhs = self.rhs.print(w)?;
+        let length = w.current_pos() - offset;
+        Ok(Spanned {
+            source_span: SourceSpan { offset, length },
+            expr: FunDef {
+                id: self.id,
+                tpe_args: self.tpe_args.clone(),
+                rhs: Box::new(rhs),
+            },
+        }
+        .into())
+    }
+}
+
 impl Print for Constant {
     fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
         write!(w, "{:?}", self.v)?;
@@ -918,6 +961,25 @@ impl Print for OptionGetOrElse {
     }
 }
 
+impl Print for SomeValue {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        write!(w, "Some(")?;
+        let input = self.input.print(w)?;
+        write!(w, ")")?;
+        Ok(SomeValue {
+            input: Box::new(input),
+        }
+        .into())
+    }
+}
+
+impl Print for NoneValue {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        write!(w, "None[{}]", self.elem_tpe)?;
+        Ok(self.clone().into())
+    }
+}
+
 impl Print for ExtractBytesWithNoRef {
     fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
         let input = self.input.print(w)?;
@@ -951,6 +1013,26 @@ impl Print for Slice {
     }
 }
 
+impl Print for CollShift {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let offset = w.current_pos();
+        let input = self.input.print(w)?;
+        write!(w, ".{}(", self.op)?;
+        let n = self.n.print(w)?;
+        write!(w, ")")?;
+        let length = w.current_pos() - offset;
+        Ok(Spanned {
+            expr: CollShift {
+                op: self.op,
+                input: Box::new(input),
+                n: Box::new(n),
+            },
+            source_span: SourceSpan { offset, length },
+        }
+        .into())
+    }
+}
+
 impl Print for ForAll {
     fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
         let offset = w.current_pos();
@@ -1014,6 +1096,17 @@ impl Print for SigmaPropBytes {
     }
 }
 
+impl Print for SigmaPropIsProven {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let input = self.input.print(w)?;
+        write!(w, ".isProven")?;
+        Ok(SigmaPropIsProven {
+            input: Box::new(input),
+        }
+        .into())
+    }
+}
+
 impl Print for DecodePoint {
     fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
         write!(w, "decodePoint(")?;
@@ -1055,6 +1148,45 @@ impl Print for MultiplyGroup {
     }
 }
 
+impl Print for ModQ {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let input = self.input.print(w)?;
+        write!(w, ".modQ")?;
+        Ok(ModQ {
+            input: Box::new(input),
+        }
+        .into())
+    }
+}
+
+impl Print for PlusModQ {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let left = self.left.print(w)?;
+        write!(w, ".plusModQ(")?;
+        let right = self.right.print(w)?;
+        write!(w, ")")?;
+        Ok(PlusModQ {
+            left: left.into(),
+            right: right.into(),
+        }
+        .into())
+    }
+}
+
+impl Print for MinusModQ {
+    fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
+        let left = self.left.print(w)?;
+        write!(w, ".minusModQ(")?;
+        let right = self.right.print(w)?;
+        write!(w, ")")?;
+        Ok(MinusModQ {
+            left: left.into(),
+            right: right.into(),
+        }
+        .into())
+    }
+}
+
 impl Print for Exponentiate {
     fn print(&self, w: &mut dyn Printer) -> Result<Expr, PrintError> {
         write!(w, "exponentiate(")?;
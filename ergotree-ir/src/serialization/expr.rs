@@ -24,6 +24,9 @@ use crate::mir::coll_filter::Filter;
 use crate::mir::coll_fold::Fold;
 use crate::mir::coll_forall::ForAll;
 use crate::mir::coll_map::Map;
+use crate::mir::coll_shift::coll_shift_sigma_parse;
+use crate::mir::coll_shift::coll_shift_sigma_serialize;
+use crate::mir::coll_shift::CollShiftOp;
 use crate::mir::coll_size::SizeOf;
 use crate::mir::coll_slice::Slice;
 use crate::mir::collection::bool_const_coll_sigma_parse;
@@ -47,6 +50,7 @@ use crate::mir::extract_creation_info::ExtractCreationInfo;
 use crate::mir::extract_id::ExtractId;
 use crate::mir::extract_reg_as::ExtractRegisterAs;
 use crate::mir::extract_script_bytes::ExtractScriptBytes;
+use crate::mir::fun_def::FunDef;
 use crate::mir::func_value::FuncValue;
 use crate::mir::get_var::GetVar;
 use crate::mir::global_vars::GlobalVars;
@@ -54,17 +58,25 @@ use crate::mir::if_op::If;
 use crate::mir::logical_not::LogicalNot;
 use crate::mir::long_to_byte_array::LongToByteArray;
 use crate::mir::method_call::MethodCall;
+use crate::mir::mod_q::MinusModQ;
+use crate::mir::mod_q::ModQ;
+use crate::mir::mod_q::PlusModQ;
 use crate::mir::multiply_group::MultiplyGroup;
 use crate::mir::negation::Negation;
 use crate::mir::option_get::OptionGet;
 use crate::mir::option_get_or_else::OptionGetOrElse;
 use crate::mir::option_is_defined::OptionIsDefined;
+use crate::mir::option_value::NoneValue;
+use crate::mir::option_value::SomeValue;
 use crate::mir::or::Or;
 use crate::mir::property_call::PropertyCall;
+use crate::mir::select_field::select_field_short_sigma_parse;
+use crate::mir::select_field::select_field_short_sigma_serialize;
 use crate::mir::select_field::SelectField;
 use crate::mir::sigma_and::SigmaAnd;
 use crate::mir::sigma_or::SigmaOr;
 use crate::mir::sigma_prop_bytes::SigmaPropBytes;
+use crate::mir::sigma_prop_is_proven::SigmaPropIsProven;
 use crate::mir::subst_const::SubstConstants;
 use crate::mir::tree_lookup::TreeLookup;
 use crate::mir::tuple::Tuple;
@@ -112,6 +124,9 @@ impl Expr {
                 OpCode::OUTPUTS => Ok(Expr::GlobalVars(GlobalVars::Outputs)),
                 OpCode::MINER_PUBKEY => Ok(Expr::GlobalVars(GlobalVars::MinerPubKey)),
                 OpCode::GROUP_GENERATOR => Ok(Expr::GlobalVars(GlobalVars::GroupGenerator)),
+                OpCode::LAST_BLOCK_UTXO_ROOT_HASH => {
+                    Ok(Expr::GlobalVars(GlobalVars::LastBlockUtxoRootHash))
+                }
                 OpCode::GLOBAL => Ok(Expr::Global),
                 OpCode::PROPERTY_CALL => {
                     Ok(Expr::PropertyCall(PropertyCall::sigma_parse(r)?.into()))
@@ -149,13 +164,34 @@ impl Expr {
                 OpCode::BIT_OR => Ok(bin_op_sigma_parse(BitOp::BitOr.into(), r)?),
                 OpCode::BIT_AND => Ok(bin_op_sigma_parse(BitOp::BitAnd.into(), r)?),
                 OpCode::BIT_XOR => Ok(bin_op_sigma_parse(BitOp::BitXor.into(), r)?),
+                OpCode::BIT_SHIFT_RIGHT => Ok(bin_op_sigma_parse(BitOp::BitShiftRight.into(), r)?),
+                OpCode::BIT_SHIFT_LEFT => Ok(bin_op_sigma_parse(BitOp::BitShiftLeft.into(), r)?),
+                OpCode::BIT_SHIFT_RIGHT_ZEROED => {
+                    Ok(bin_op_sigma_parse(BitOp::BitShiftRightZeroed.into(), r)?)
+                }
                 OpCode::BLOCK_VALUE => Ok(Expr::BlockValue(BlockValue::sigma_parse(r)?.into())),
                 OpCode::FUNC_VALUE => Ok(Expr::FuncValue(FuncValue::sigma_parse(r)?)),
                 OpCode::APPLY => Ok(Expr::Apply(Apply::sigma_parse(r)?)),
                 OpCode::VAL_DEF => Ok(Expr::ValDef(ValDef::sigma_parse(r)?.into())),
+                FunDef::OP_CODE => Ok(Expr::FunDef(FunDef::sigma_parse(r)?.into())),
                 OpCode::VAL_USE => Ok(Expr::ValUse(ValUse::sigma_parse(r)?)),
                 ExtractAmount::OP_CODE => Ok(Expr::ExtractAmount(ExtractAmount::sigma_parse(r)?)),
                 OpCode::SELECT_FIELD => Ok(Expr::SelectField(SelectField::sigma_parse(r)?.into())),
+                OpCode::SELECT_1 => Ok(Expr::SelectFieldShort(select_field_short_sigma_parse(
+                    1, r,
+                )?)),
+                OpCode::SELECT_2 => Ok(Expr::SelectFieldShort(select_field_short_sigma_parse(
+                    2, r,
+                )?)),
+                OpCode::SELECT_3 => Ok(Expr::SelectFieldShort(select_field_short_sigma_parse(
+                    3, r,
+                )?)),
+                OpCode::SELECT_4 => Ok(Expr::SelectFieldShort(select_field_short_sigma_parse(
+                    4, r,
+                )?)),
+                OpCode::SELECT_5 => Ok(Expr::SelectFieldShort(select_field_short_sigma_parse(
+                    5, r,
+                )?)),
                 OpCode::CALC_BLAKE2B256 => Ok(CalcBlake2b256::sigma_parse(r)?.into()),
                 CalcSha256::OP_CODE => Ok(CalcSha256::sigma_parse(r)?.into()),
                 And::OP_CODE => Ok(And::sigma_parse(r)?.into()),
@@ -175,9 +211,27 @@ impl Expr {
                 ByIndex::OP_CODE => Ok(ByIndex::sigma_parse(r)?.into()),
                 SizeOf::OP_CODE => Ok(SizeOf::sigma_parse(r)?.into()),
                 Slice::OP_CODE => Ok(Slice::sigma_parse(r)?.into()),
+                OpCode::COLL_SHIFT_LEFT => {
+                    Ok(coll_shift_sigma_parse(CollShiftOp::ShiftLeft, r)?.into())
+                }
+                OpCode::COLL_SHIFT_RIGHT => {
+                    Ok(coll_shift_sigma_parse(CollShiftOp::ShiftRight, r)?.into())
+                }
+                OpCode::COLL_SHIFT_RIGHT_ZEROED => {
+                    Ok(coll_shift_sigma_parse(CollShiftOp::ShiftRightZeroed, r)?.into())
+                }
+                OpCode::COLL_ROTATE_LEFT => {
+                    Ok(coll_shift_sigma_parse(CollShiftOp::RotateLeft, r)?.into())
+                }
+                OpCode::COLL_ROTATE_RIGHT => {
+                    Ok(coll_shift_sigma_parse(CollShiftOp::RotateRight, r)?.into())
+                }
                 CreateProveDlog::OP_CODE => Ok(CreateProveDlog::sigma_parse(r)?.into()),
                 CreateProveDhTuple::OP_CODE => Ok(CreateProveDhTuple::sigma_parse(r)?.into()),
                 SigmaPropBytes::OP_CODE => Ok(SigmaPropBytes::sigma_parse(r)?.into()),
+                SigmaPropIsProven::OP_CODE => Ok(SigmaPropIsProven::sigma_parse(r)?.into()),
+                SomeValue::OP_CODE => Ok(SomeValue::sigma_parse(r)?.into()),
+                NoneValue::OP_CODE => Ok(NoneValue::sigma_parse(r)?.into()),
                 Tuple::OP_CODE => Ok(Tuple::sigma_parse(r)?.into()),
                 DecodePoint::OP_CODE => Ok(DecodePoint::sigma_parse(r)?.into()),
                 SubstConstants::OP_CODE => Ok(SubstConstants::sigma_parse(r)?.into()),
@@ -190,15 +244,14 @@ impl Expr {
                 DeserializeRegister::OP_CODE => Ok(DeserializeRegister::sigma_parse(r)?.into()),
                 DeserializeContext::OP_CODE => Ok(DeserializeContext::sigma_parse(r)?.into()),
                 MultiplyGroup::OP_CODE => Ok(MultiplyGroup::sigma_parse(r)?.into()),
+                ModQ::OP_CODE => Ok(ModQ::sigma_parse(r)?.into()),
+                PlusModQ::OP_CODE => Ok(PlusModQ::sigma_parse(r)?.into()),
+                MinusModQ::OP_CODE => Ok(MinusModQ::sigma_parse(r)?.into()),
                 Exponentiate::OP_CODE => Ok(Exponentiate::sigma_parse(r)?.into()),
                 XorOf::OP_CODE => Ok(XorOf::sigma_parse(r)?.into()),
                 TreeLookup::OP_CODE => Ok(TreeLookup::sigma_parse(r)?.into()),
                 CreateAvlTree::OP_CODE => Ok(CreateAvlTree::sigma_parse(r)?.into()),
-                o => Err(SigmaParsingError::NotImplementedOpCode(format!(
-                    "{0}(shift {1})",
-                    o.value(),
-                    o.shift()
-                ))),
+                o => Err(SigmaParsingError::InvalidOpCode(o.value())),
             }
         };
         res
@@ -246,10 +299,12 @@ impl SigmaSerializable for Expr {
             Expr::BlockValue(op) => op.expr().sigma_serialize_w_opcode(w),
             Expr::ValUse(op) => op.sigma_serialize_w_opcode(w),
             Expr::ValDef(op) => op.expr().sigma_serialize_w_opcode(w),
+            Expr::FunDef(op) => op.expr().sigma_serialize_w_opcode(w),
             Expr::FuncValue(op) => op.sigma_serialize_w_opcode(w),
             Expr::Apply(op) => op.sigma_serialize_w_opcode(w),
             Expr::ExtractAmount(op) => op.sigma_serialize_w_opcode(w),
             Expr::SelectField(op) => op.sigma_serialize_w_opcode(w),
+            Expr::SelectFieldShort(op) => select_field_short_sigma_serialize(op, w),
             Expr::CalcBlake2b256(op) => op.sigma_serialize_w_opcode(w),
             Expr::CalcSha256(op) => op.sigma_serialize_w_opcode(w),
             Expr::Collection(op) => {
@@ -271,12 +326,19 @@ impl SigmaSerializable for Expr {
             Expr::ExtractScriptBytes(op) => op.sigma_serialize_w_opcode(w),
             Expr::SizeOf(op) => op.sigma_serialize_w_opcode(w),
             Expr::Slice(op) => op.expr().sigma_serialize_w_opcode(w),
+            Expr::CollShift(op) => {
+                op.expr().op_code().sigma_serialize(w)?;
+                coll_shift_sigma_serialize(op.expr(), w)
+            }
             Expr::CreateProveDlog(op) => op.sigma_serialize_w_opcode(w),
             Expr::CreateProveDhTuple(op) => op.sigma_serialize_w_opcode(w),
             Expr::ExtractCreationInfo(op) => op.sigma_serialize_w_opcode(w),
             Expr::Exists(op) => op.sigma_serialize_w_opcode(w),
             Expr::ExtractId(op) => op.sigma_serialize_w_opcode(w),
             Expr::SigmaPropBytes(op) => op.sigma_serialize_w_opcode(w),
+            Expr::SigmaPropIsProven(op) => op.sigma_serialize_w_opcode(w),
+            Expr::SomeValue(op) => op.sigma_serialize_w_opcode(w),
+            Expr::NoneValue(op) => op.sigma_serialize_w_opcode(w),
             Expr::OptionIsDefined(op) => op.expr().sigma_serialize_w_opcode(w),
             Expr::OptionGetOrElse(op) => op.expr().sigma_serialize_w_opcode(w),
             Expr::Negation(op) => op.expr().sigma_serialize_w_opcode(w),
@@ -290,6 +352,9 @@ impl SigmaSerializable for Expr {
             Expr::DeserializeRegister(op) => op.sigma_serialize_w_opcode(w),
             Expr::DeserializeContext(op) => op.sigma_serialize_w_opcode(w),
             Expr::MultiplyGroup(op) => op.sigma_serialize_w_opcode(w),
+            Expr::ModQ(op) => op.sigma_serialize_w_opcode(w),
+            Expr::PlusModQ(op) => op.sigma_serialize_w_opcode(w),
+            Expr::MinusModQ(op) => op.sigma_serialize_w_opcode(w),
             Expr::Exponentiate(op) => op.sigma_serialize_w_opcode(w),
             Expr::XorOf(op) => op.sigma_serialize_w_opcode(w),
             Expr::ExtractBytes(op) => op.sigma_serialize_w_opcode(w),
@@ -34,12 +34,16 @@ impl SigmaSerializable for MethodCall {
 #[cfg(feature = "arbitrary")]
 #[allow(clippy::unwrap_used)]
 mod tests {
+    use crate::bigint256::BigInt256;
     use crate::mir::expr::Expr;
     use crate::mir::method_call::MethodCall;
     use crate::serialization::sigma_serialize_roundtrip;
     use crate::types::scoll;
+    use crate::types::sgroup_elem;
     use crate::types::stype::SType;
     use crate::types::stype_param::STypeVar;
+    use ergo_chain_types::EcPoint;
+    use sigma_test_util::force_any_val;
 
     #[test]
     fn ser_roundtrip() {
@@ -54,4 +58,24 @@ mod tests {
         .into();
         assert_eq![sigma_serialize_roundtrip(&mc), mc];
     }
+
+    #[test]
+    fn ser_roundtrip_group_elem_methods() {
+        let exp: Expr = MethodCall::new(
+            force_any_val::<EcPoint>().into(),
+            sgroup_elem::EXPONENTIATE_METHOD.clone(),
+            vec![BigInt256::from(3i64).into()],
+        )
+        .unwrap()
+        .into();
+        assert_eq![sigma_serialize_roundtrip(&exp), exp];
+        let multiply: Expr = MethodCall::new(
+            force_any_val::<EcPoint>().into(),
+            sgroup_elem::MULTIPLY_METHOD.clone(),
+            vec![force_any_val::<EcPoint>().into()],
+        )
+        .unwrap()
+        .into();
+        assert_eq![sigma_serialize_roundtrip(&multiply), multiply];
+    }
 }
@@ -36,6 +36,8 @@ mod tests {
     use crate::mir::property_call::PropertyCall;
     use crate::serialization::sigma_serialize_roundtrip;
     use crate::types::scontext;
+    use crate::types::snumeric;
+    use crate::types::stype::SType;
 
     #[test]
     fn ser_roundtrip_property() {
@@ -43,4 +45,26 @@ mod tests {
         let expr = Expr::PropertyCall(mc.into());
         assert_eq![sigma_serialize_roundtrip(&expr), expr];
     }
+
+    #[test]
+    fn ser_roundtrip_numeric_cast() {
+        for (input, tpe) in [
+            (Expr::from(1i8), SType::SByte),
+            (Expr::from(1i16), SType::SShort),
+            (Expr::from(1i32), SType::SInt),
+            (Expr::from(1i64), SType::SLong),
+        ] {
+            for method_id in [
+                snumeric::TO_BYTE_METHOD_ID,
+                snumeric::TO_SHORT_METHOD_ID,
+                snumeric::TO_INT_METHOD_ID,
+                snumeric::TO_LONG_METHOD_ID,
+                snumeric::TO_BIGINT_METHOD_ID,
+            ] {
+                let method = snumeric::cast_method(&tpe, &method_id).unwrap();
+                let expr: Expr = PropertyCall::new(input.clone(), method).unwrap().into();
+                assert_eq![sigma_serialize_roundtrip(&expr), expr];
+            }
+        }
+    }
 }
@@ -12,9 +12,11 @@ use crate::mir::coll_filter::Filter;
 use crate::mir::coll_fold::Fold;
 use crate::mir::coll_forall::ForAll;
 use crate::mir::coll_map::Map;
+use crate::mir::coll_shift::CollShift;
 use crate::mir::coll_slice::Slice;
 use crate::mir::expr::Expr;
 use crate::mir::extract_reg_as::ExtractRegisterAs;
+use crate::mir::fun_def::FunDef;
 use crate::mir::get_var::GetVar;
 use crate::mir::logical_not::LogicalNot;
 use crate::mir::method_call::MethodCall;
@@ -95,6 +97,7 @@ macro_rules! into_expr {
 into_expr!(Append);
 into_expr!(BlockValue);
 into_expr!(ValDef);
+into_expr!(FunDef);
 into_expr!(BinOp);
 into_expr!(ByIndex);
 into_expr!(SubstConstants);
@@ -108,6 +111,7 @@ into_expr!(OptionIsDefined);
 into_expr!(OptionGetOrElse);
 into_expr!(ExtractRegisterAs);
 into_expr!(Slice);
+into_expr!(CollShift);
 into_expr!(Fold);
 into_expr!(Map);
 into_expr!(Filter);
@@ -153,6 +157,7 @@ impl Expr {
             Expr::PropertyCall(op) => op.source_span,
             Expr::BlockValue(op) => op.source_span,
             Expr::ValDef(op) => op.source_span,
+            Expr::FunDef(op) => op.source_span,
             Expr::ValUse(_) => SourceSpan::empty(),
             Expr::If(_) => SourceSpan::empty(),
             Expr::BinOp(op) => op.source_span,
@@ -176,18 +181,23 @@ impl Expr {
             Expr::ByIndex(op) => op.source_span,
             Expr::SizeOf(_) => SourceSpan::empty(),
             Expr::Slice(op) => op.source_span,
+            Expr::CollShift(op) => op.source_span,
             Expr::Fold(op) => op.source_span,
             Expr::Map(op) => op.source_span,
             Expr::Filter(op) => op.source_span,
             Expr::Exists(op) => op.source_span,
             Expr::ForAll(op) => op.source_span,
             Expr::SelectField(op) => op.source_span,
+            Expr::SelectFieldShort(_) => SourceSpan::empty(),
             Expr::BoolToSigmaProp(_) => SourceSpan::empty(),
             Expr::Upcast(_) => SourceSpan::empty(),
             Expr::Downcast(_) => SourceSpan::empty(),
             Expr::CreateProveDlog(_) => SourceSpan::empty(),
             Expr::CreateProveDhTuple(_) => SourceSpan::empty(),
             Expr::SigmaPropBytes(_) => SourceSpan::empty(),
+            Expr::SigmaPropIsProven(_) => SourceSpan::empty(),
+            Expr::SomeValue(_) => SourceSpan::empty(),
+            Expr::NoneValue(_) => SourceSpan::empty(),
             Expr::DecodePoint(_) => SourceSpan::empty(),
             Expr::SigmaAnd(_) => SourceSpan::empty(),
             Expr::SigmaOr(_) => SourceSpan::empty(),
@@ -195,6 +205,9 @@ impl Expr {
             Expr::DeserializeRegister(_) => SourceSpan::empty(),
             Expr::DeserializeContext(_) => SourceSpan::empty(),
             Expr::MultiplyGroup(_) => SourceSpan::empty(),
+            Expr::ModQ(_) => SourceSpan::empty(),
+            Expr::PlusModQ(_) => SourceSpan::empty(),
+            Expr::MinusModQ(_) => SourceSpan::empty(),
             Expr::Exponentiate(_) => SourceSpan::empty(),
             Expr::XorOf(_) => SourceSpan::empty(),
             Expr::TreeLookup(op) => op.source_span,
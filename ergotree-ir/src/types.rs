@@ -19,10 +19,14 @@ pub mod sgroup_elem;
 pub mod sheader;
 /// Object method(property) signature type
 pub mod smethod;
+/// Numeric types (Byte, Short, Int, Long, BigInt) methods
+pub mod snumeric;
 /// Option object type companion
 pub mod soption;
 /// PreHeader's methods
 pub mod spreheader;
+/// SigmaProp methods
+pub mod ssigmaprop;
 /// Tuple type
 pub mod stuple;
 /// Type companion for an object
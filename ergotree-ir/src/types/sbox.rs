@@ -16,20 +16,52 @@ pub const TYPE_CODE: TypeCode = TypeCode::SBOX;
 pub static TYPE_NAME: &str = "Box";
 /// Box.value property
 pub const VALUE_METHOD_ID: MethodId = MethodId(1);
+/// Box.propositionBytes property
+pub const PROPOSITION_BYTES_METHOD_ID: MethodId = MethodId(2);
+/// Box.bytes property
+pub const BYTES_METHOD_ID: MethodId = MethodId(3);
+/// Box.bytesWithoutRef property
+pub const BYTES_WITHOUT_REF_METHOD_ID: MethodId = MethodId(4);
+/// Box.id property
+pub const ID_METHOD_ID: MethodId = MethodId(5);
+/// Box.creationInfo property
+pub const CREATION_INFO_METHOD_ID: MethodId = MethodId(6);
 /// Box.Rx property
 pub const GET_REG_METHOD_ID: MethodId = MethodId(7);
 /// Box.tokens property
 pub const TOKENS_METHOD_ID: MethodId = MethodId(8);
+/// Box.R0 property (method ids of R1..R9 follow in order)
+pub const R0_METHOD_ID: MethodId = MethodId(9);
+
+/// Method id of the `Box.Rn` property for the given register index (0..=9)
+pub const fn register_method_id(reg_index: u8) -> MethodId {
+    MethodId(R0_METHOD_ID.0 + reg_index)
+}
+
+/// Register index (0..=9) for the `Box.Rn` property method id
+pub fn register_index(method_id: &MethodId) -> Option<u8> {
+    method_id
+        .0
+        .checked_sub(R0_METHOD_ID.0)
+        .filter(|idx| *idx <= 9)
+}
 
 lazy_static! {
     /// Box method descriptors
-    pub(crate) static ref METHOD_DESC: Vec<&'static SMethodDesc> =
-        vec![
-            &GET_REG_METHOD_DESC,
+    pub(crate) static ref METHOD_DESC: Vec<&'static SMethodDesc> = {
+        let mut methods: Vec<&'static SMethodDesc> = vec![
             &VALUE_METHOD_DESC,
-            &TOKENS_METHOD_DESC
-        ]
-    ;
+            &PROPOSITION_BYTES_METHOD_DESC,
+            &BYTES_METHOD_DESC,
+            &BYTES_WITHOUT_REF_METHOD_DESC,
+            &ID_METHOD_DESC,
+            &CREATION_INFO_METHOD_DESC,
+            &GET_REG_METHOD_DESC,
+            &TOKENS_METHOD_DESC,
+        ];
+        methods.extend(REGISTER_METHOD_DESC.iter());
+        methods
+    };
 }
 
 lazy_static! {
@@ -46,6 +78,49 @@ lazy_static! {
     pub static ref VALUE_METHOD: SMethod = SMethod::new(STypeCompanion::Box, VALUE_METHOD_DESC.clone(),);
 }
 
+lazy_static! {
+    static ref PROPOSITION_BYTES_METHOD_DESC: SMethodDesc =
+        property("propositionBytes", SType::SColl(SType::SByte.into()), PROPOSITION_BYTES_METHOD_ID);
+    /// Box.propositionBytes
+    pub static ref PROPOSITION_BYTES_METHOD: SMethod =
+        SMethod::new(STypeCompanion::Box, PROPOSITION_BYTES_METHOD_DESC.clone(),);
+}
+
+lazy_static! {
+    static ref BYTES_METHOD_DESC: SMethodDesc =
+        property("bytes", SType::SColl(SType::SByte.into()), BYTES_METHOD_ID);
+    /// Box.bytes
+    pub static ref BYTES_METHOD: SMethod =
+        SMethod::new(STypeCompanion::Box, BYTES_METHOD_DESC.clone(),);
+}
+
+lazy_static! {
+    static ref BYTES_WITHOUT_REF_METHOD_DESC: SMethodDesc =
+        property("bytesWithoutRef", SType::SColl(SType::SByte.into()), BYTES_WITHOUT_REF_METHOD_ID);
+    /// Box.bytesWithoutRef
+    pub static ref BYTES_WITHOUT_REF_METHOD: SMethod =
+        SMethod::new(STypeCompanion::Box, BYTES_WITHOUT_REF_METHOD_DESC.clone(),);
+}
+
+lazy_static! {
+    static ref ID_METHOD_DESC: SMethodDesc =
+        property("id", SType::SColl(SType::SByte.into()), ID_METHOD_ID);
+    /// Box.id
+    pub static ref ID_METHOD: SMethod =
+        SMethod::new(STypeCompanion::Box, ID_METHOD_DESC.clone(),);
+}
+
+lazy_static! {
+    static ref CREATION_INFO_METHOD_DESC: SMethodDesc = property(
+        "creationInfo",
+        STuple::pair(SType::SInt, SType::SColl(SType::SByte.into())).into(),
+        CREATION_INFO_METHOD_ID
+    );
+    /// Box.creationInfo
+    pub static ref CREATION_INFO_METHOD: SMethod =
+        SMethod::new(STypeCompanion::Box, CREATION_INFO_METHOD_DESC.clone(),);
+}
+
 lazy_static! {
     static ref GET_REG_METHOD_DESC: SMethodDesc = SMethodDesc {
         method_id: GET_REG_METHOD_ID,
@@ -80,6 +155,32 @@ lazy_static! {
         SMethod::new( STypeCompanion::Box,TOKENS_METHOD_DESC.clone(),);
 }
 
+lazy_static! {
+    static ref REGISTER_METHOD_DESC: Vec<SMethodDesc> = (0..=9u8)
+        .map(|reg_index| SMethodDesc {
+            method_id: register_method_id(reg_index),
+            name: REGISTER_METHOD_NAMES[reg_index as usize],
+            tpe: SFunc {
+                t_dom: vec![SType::SBox],
+                t_range: SType::SOption(Box::new(STypeVar::t().into())).into(),
+                tpe_params: vec![],
+            },
+        })
+        .collect();
+    /// Box.R0..Box.R9 (indexed by the register index)
+    pub static ref REGISTER_METHODS: Vec<SMethod> = REGISTER_METHOD_DESC
+        .iter()
+        .map(|desc| SMethod::new(STypeCompanion::Box, desc.clone()))
+        .collect();
+}
+
+static REGISTER_METHOD_NAMES: [&str; 10] =
+    ["R0", "R1", "R2", "R3", "R4", "R5", "R6", "R7", "R8", "R9"];
+
+fn property(name: &'static str, res_tpe: SType, id: MethodId) -> SMethodDesc {
+    SMethodDesc::property(SType::SBox, name, res_tpe, id)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,5 +190,30 @@ mod tests {
         assert!(SMethod::from_ids(TYPE_CODE, VALUE_METHOD_ID).map(|e| e.name()) == Ok("value"));
         assert!(SMethod::from_ids(TYPE_CODE, GET_REG_METHOD_ID).map(|e| e.name()) == Ok("getReg"));
         assert!(SMethod::from_ids(TYPE_CODE, TOKENS_METHOD_ID).map(|e| e.name()) == Ok("tokens"));
+        assert!(
+            SMethod::from_ids(TYPE_CODE, PROPOSITION_BYTES_METHOD_ID).map(|e| e.name())
+                == Ok("propositionBytes")
+        );
+        assert!(SMethod::from_ids(TYPE_CODE, BYTES_METHOD_ID).map(|e| e.name()) == Ok("bytes"));
+        assert!(
+            SMethod::from_ids(TYPE_CODE, BYTES_WITHOUT_REF_METHOD_ID).map(|e| e.name())
+                == Ok("bytesWithoutRef")
+        );
+        assert!(SMethod::from_ids(TYPE_CODE, ID_METHOD_ID).map(|e| e.name()) == Ok("id"));
+        assert!(
+            SMethod::from_ids(TYPE_CODE, CREATION_INFO_METHOD_ID).map(|e| e.name())
+                == Ok("creationInfo")
+        );
+        assert!(SMethod::from_ids(TYPE_CODE, MethodId(9)).map(|e| e.name()) == Ok("R0"));
+        assert!(SMethod::from_ids(TYPE_CODE, MethodId(13)).map(|e| e.name()) == Ok("R4"));
+        assert!(SMethod::from_ids(TYPE_CODE, MethodId(18)).map(|e| e.name()) == Ok("R9"));
+        assert!(SMethod::from_ids(TYPE_CODE, MethodId(19)).is_err());
+    }
+
+    #[test]
+    fn test_register_index() {
+        assert_eq!(register_index(&register_method_id(4)), Some(4));
+        assert_eq!(register_index(&TOKENS_METHOD_ID), None);
+        assert_eq!(register_index(&MethodId(19)), None);
     }
 }
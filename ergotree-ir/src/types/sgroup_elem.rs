@@ -16,13 +16,19 @@ pub static TYPE_NAME: &str = "GroupElement";
 pub const GET_ENCODED_METHOD_ID: MethodId = MethodId(2);
 /// GroupElement.negate
 pub const NEGATE_METHOD_ID: MethodId = MethodId(5);
+/// GroupElement.exp
+pub const EXPONENTIATE_METHOD_ID: MethodId = MethodId(3);
+/// GroupElement.multiply
+pub const MULTIPLY_METHOD_ID: MethodId = MethodId(4);
 
 lazy_static! {
     /// GroupElement method descriptors
     pub(crate) static ref METHOD_DESC: Vec<&'static SMethodDesc> =
         vec![
             &GET_ENCODED_METHOD_DESC,
-            &NEGATE_METHOD_DESC
+            &NEGATE_METHOD_DESC,
+            &EXPONENTIATE_METHOD_DESC,
+            &MULTIPLY_METHOD_DESC,
         ]
     ;
 }
@@ -53,6 +59,32 @@ lazy_static! {
     pub static ref NEGATE_METHOD: SMethod = SMethod::new(STypeCompanion::GroupElem, NEGATE_METHOD_DESC.clone(),);
 }
 
+lazy_static! {
+    static ref EXPONENTIATE_METHOD_DESC: SMethodDesc = SMethodDesc {
+        method_id: EXPONENTIATE_METHOD_ID,
+        name: "exp",
+        tpe: SFunc::new(
+            vec![SType::SGroupElement, SType::SBigInt],
+            SType::SGroupElement,
+        )
+    };
+    /// GroupElement.exp
+    pub static ref EXPONENTIATE_METHOD: SMethod = SMethod::new(STypeCompanion::GroupElem, EXPONENTIATE_METHOD_DESC.clone(),);
+}
+
+lazy_static! {
+    static ref MULTIPLY_METHOD_DESC: SMethodDesc = SMethodDesc {
+        method_id: MULTIPLY_METHOD_ID,
+        name: "multiply",
+        tpe: SFunc::new(
+            vec![SType::SGroupElement, SType::SGroupElement],
+            SType::SGroupElement,
+        )
+    };
+    /// GroupElement.multiply
+    pub static ref MULTIPLY_METHOD: SMethod = SMethod::new(STypeCompanion::GroupElem, MULTIPLY_METHOD_DESC.clone(),);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,5 +96,11 @@ mod tests {
                 == Ok("getEncoded")
         );
         assert!(SMethod::from_ids(TYPE_CODE, NEGATE_METHOD_ID).map(|e| e.name()) == Ok("negate"));
+        assert!(
+            SMethod::from_ids(TYPE_CODE, EXPONENTIATE_METHOD_ID).map(|e| e.name()) == Ok("exp")
+        );
+        assert!(
+            SMethod::from_ids(TYPE_CODE, MULTIPLY_METHOD_ID).map(|e| e.name()) == Ok("multiply")
+        );
     }
 }
@@ -0,0 +1,133 @@
+use crate::serialization::types::TypeCode;
+
+use super::smethod::MethodId;
+use super::smethod::SMethod;
+use super::smethod::SMethodDesc;
+use super::stype::SType;
+use super::stype_companion::STypeCompanion;
+use lazy_static::lazy_static;
+
+/// SByte type code
+pub const BYTE_TYPE_CODE: TypeCode = TypeCode::SBYTE;
+/// SShort type code
+pub const SHORT_TYPE_CODE: TypeCode = TypeCode::SSHORT;
+/// SInt type code
+pub const INT_TYPE_CODE: TypeCode = TypeCode::SINT;
+/// SLong type code
+pub const LONG_TYPE_CODE: TypeCode = TypeCode::SLONG;
+/// SBigInt type code
+pub const BIGINT_TYPE_CODE: TypeCode = TypeCode::SBIGINT;
+
+/// SByte type name
+pub static BYTE_TYPE_NAME: &str = "Byte";
+/// SShort type name
+pub static SHORT_TYPE_NAME: &str = "Short";
+/// SInt type name
+pub static INT_TYPE_NAME: &str = "Int";
+/// SLong type name
+pub static LONG_TYPE_NAME: &str = "Long";
+/// SBigInt type name
+pub static BIGINT_TYPE_NAME: &str = "BigInt";
+
+/// Numeric.toByte
+pub const TO_BYTE_METHOD_ID: MethodId = MethodId(1);
+/// Numeric.toShort
+pub const TO_SHORT_METHOD_ID: MethodId = MethodId(2);
+/// Numeric.toInt
+pub const TO_INT_METHOD_ID: MethodId = MethodId(3);
+/// Numeric.toLong
+pub const TO_LONG_METHOD_ID: MethodId = MethodId(4);
+/// Numeric.toBigInt
+pub const TO_BIGINT_METHOD_ID: MethodId = MethodId(5);
+
+/// Numeric cast method (`toByte`, `toShort`, `toInt`, `toLong`, `toBigInt`) of the given
+/// numeric type, returns None if the type is not numeric or there is no such method
+pub fn cast_method(obj_tpe: &SType, method_id: &MethodId) -> Option<SMethod> {
+    let type_companion = match obj_tpe {
+        SType::SByte => STypeCompanion::Byte,
+        SType::SShort => STypeCompanion::Short,
+        SType::SInt => STypeCompanion::Int,
+        SType::SLong => STypeCompanion::Long,
+        SType::SBigInt => STypeCompanion::BigInt,
+        _ => return None,
+    };
+    type_companion.method_by_id(method_id)
+}
+
+fn cast_method_descs(obj_tpe: SType) -> Vec<SMethodDesc> {
+    vec![
+        SMethodDesc::property(obj_tpe.clone(), "toByte", SType::SByte, TO_BYTE_METHOD_ID),
+        SMethodDesc::property(
+            obj_tpe.clone(),
+            "toShort",
+            SType::SShort,
+            TO_SHORT_METHOD_ID,
+        ),
+        SMethodDesc::property(obj_tpe.clone(), "toInt", SType::SInt, TO_INT_METHOD_ID),
+        SMethodDesc::property(obj_tpe.clone(), "toLong", SType::SLong, TO_LONG_METHOD_ID),
+        SMethodDesc::property(obj_tpe, "toBigInt", SType::SBigInt, TO_BIGINT_METHOD_ID),
+    ]
+}
+
+lazy_static! {
+    static ref BYTE_METHODS: Vec<SMethodDesc> = cast_method_descs(SType::SByte);
+    static ref SHORT_METHODS: Vec<SMethodDesc> = cast_method_descs(SType::SShort);
+    static ref INT_METHODS: Vec<SMethodDesc> = cast_method_descs(SType::SInt);
+    static ref LONG_METHODS: Vec<SMethodDesc> = cast_method_descs(SType::SLong);
+    static ref BIGINT_METHODS: Vec<SMethodDesc> = cast_method_descs(SType::SBigInt);
+}
+
+lazy_static! {
+    /// Byte method descriptors
+    pub(crate) static ref BYTE_METHOD_DESC: Vec<&'static SMethodDesc> = BYTE_METHODS.iter().collect();
+    /// Short method descriptors
+    pub(crate) static ref SHORT_METHOD_DESC: Vec<&'static SMethodDesc> = SHORT_METHODS.iter().collect();
+    /// Int method descriptors
+    pub(crate) static ref INT_METHOD_DESC: Vec<&'static SMethodDesc> = INT_METHODS.iter().collect();
+    /// Long method descriptors
+    pub(crate) static ref LONG_METHOD_DESC: Vec<&'static SMethodDesc> = LONG_METHODS.iter().collect();
+    /// BigInt method descriptors
+    pub(crate) static ref BIGINT_METHOD_DESC: Vec<&'static SMethodDesc> = BIGINT_METHODS.iter().collect();
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_ids() {
+        for type_code in [
+            BYTE_TYPE_CODE,
+            SHORT_TYPE_CODE,
+            INT_TYPE_CODE,
+            LONG_TYPE_CODE,
+            BIGINT_TYPE_CODE,
+        ] {
+            assert!(
+                SMethod::from_ids(type_code, TO_BYTE_METHOD_ID).map(|e| e.name()) == Ok("toByte")
+            );
+            assert!(
+                SMethod::from_ids(type_code, TO_SHORT_METHOD_ID).map(|e| e.name()) == Ok("toShort")
+            );
+            assert!(
+                SMethod::from_ids(type_code, TO_INT_METHOD_ID).map(|e| e.name()) == Ok("toInt")
+            );
+            assert!(
+                SMethod::from_ids(type_code, TO_LONG_METHOD_ID).map(|e| e.name()) == Ok("toLong")
+            );
+            assert!(
+                SMethod::from_ids(type_code, TO_BIGINT_METHOD_ID).map(|e| e.name())
+                    == Ok("toBigInt")
+            );
+        }
+    }
+
+    #[test]
+    fn test_cast_method() {
+        let m = cast_method(&SType::SInt, &TO_LONG_METHOD_ID).unwrap();
+        assert_eq!(m.tpe().t_dom, vec![SType::SInt]);
+        assert_eq!(*m.tpe().t_range, SType::SLong);
+        assert!(cast_method(&SType::SBoolean, &TO_LONG_METHOD_ID).is_none());
+    }
+}
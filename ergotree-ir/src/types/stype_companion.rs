@@ -14,8 +14,10 @@ use super::sheader;
 use super::smethod::MethodId;
 use super::smethod::SMethod;
 use super::smethod::SMethodDesc;
+use super::snumeric;
 use super::soption;
 use super::spreheader;
+use super::ssigmaprop;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
@@ -40,6 +42,18 @@ pub enum STypeCompanion {
     Option,
     /// AVL tree
     AvlTree,
+    /// SigmaProp
+    SigmaProp,
+    /// Byte
+    Byte,
+    /// Short
+    Short,
+    /// Int
+    Int,
+    /// Long
+    Long,
+    /// BigInt
+    BigInt,
 }
 
 impl STypeCompanion {
@@ -54,6 +68,12 @@ impl STypeCompanion {
             STypeCompanion::PreHeader => &spreheader::METHOD_DESC,
             STypeCompanion::Option => &soption::METHOD_DESC,
             STypeCompanion::AvlTree => &savltree::METHOD_DESC,
+            STypeCompanion::SigmaProp => &ssigmaprop::METHOD_DESC,
+            STypeCompanion::Byte => &snumeric::BYTE_METHOD_DESC,
+            STypeCompanion::Short => &snumeric::SHORT_METHOD_DESC,
+            STypeCompanion::Int => &snumeric::INT_METHOD_DESC,
+            STypeCompanion::Long => &snumeric::LONG_METHOD_DESC,
+            STypeCompanion::BigInt => &snumeric::BIGINT_METHOD_DESC,
         }
     }
 
@@ -85,6 +105,12 @@ impl STypeCompanion {
             STypeCompanion::PreHeader => spreheader::TYPE_CODE,
             STypeCompanion::Option => soption::TYPE_CODE,
             STypeCompanion::AvlTree => savltree::TYPE_CODE,
+            STypeCompanion::SigmaProp => ssigmaprop::TYPE_CODE,
+            STypeCompanion::Byte => snumeric::BYTE_TYPE_CODE,
+            STypeCompanion::Short => snumeric::SHORT_TYPE_CODE,
+            STypeCompanion::Int => snumeric::INT_TYPE_CODE,
+            STypeCompanion::Long => snumeric::LONG_TYPE_CODE,
+            STypeCompanion::BigInt => snumeric::BIGINT_TYPE_CODE,
         }
     }
 
@@ -100,6 +126,12 @@ impl STypeCompanion {
             STypeCompanion::PreHeader => spreheader::TYPE_NAME,
             STypeCompanion::Option => soption::TYPE_NAME,
             STypeCompanion::AvlTree => savltree::TYPE_NAME,
+            STypeCompanion::SigmaProp => ssigmaprop::TYPE_NAME,
+            STypeCompanion::Byte => snumeric::BYTE_TYPE_NAME,
+            STypeCompanion::Short => snumeric::SHORT_TYPE_NAME,
+            STypeCompanion::Int => snumeric::INT_TYPE_NAME,
+            STypeCompanion::Long => snumeric::LONG_TYPE_NAME,
+            STypeCompanion::BigInt => snumeric::BIGINT_TYPE_NAME,
         }
     }
 }